@@ -1 +1,3 @@
-head /dev/urandom | tr -dc A-Za-z0-9 | head -c128 > configuration/server/jwt_secret.txt
\ No newline at end of file
+mkdir -p configuration/server/jwt_keys
+openssl genpkey -algorithm ed25519 -out configuration/server/jwt_keys/jwt-key-1.pem
+openssl pkey -in configuration/server/jwt_keys/jwt-key-1.pem -pubout -out configuration/server/jwt_keys/jwt-key-1.pub.pem
@@ -1,25 +1,20 @@
-const socket = new WebSocket('ws://localhost:3000/ws');
-
-socket.addEventListener('open', function (event) {
-    socket.send('Hello Server!');
-});
+// Requires a valid authorization_token cookie, see docs/gateway.md
+const socket = new WebSocket(`ws://${window.location.host}/gateway`);
+let heartbeat = null;
 
 socket.addEventListener('message', function (event) {
-    console.log('Message from server ', event.data);
-});
+    const payload = JSON.parse(event.data);
+    console.log('Message from server ', payload);
 
+    if (payload.op === 'HELLO') {
+        socket.send(JSON.stringify({ op: 'IDENTIFY', d: { channel_ids: [1] } }));
+        heartbeat = setInterval(() => {
+            socket.send(JSON.stringify({ op: 'HEARTBEAT' }));
+        }, payload.d.heartbeat_interval_ms);
+    }
+});
 
-setTimeout(() => {
-    const obj = { hello: "world" };
-    const blob = new Blob([JSON.stringify(obj, null, 2)], {
-      type: "application/json",
-    });
-    console.log("Sending blob over websocket");
-    socket.send(blob);
-}, 1000);
-
-setTimeout(() => {
-    socket.send('About done here...');
-    console.log("Sending close over websocket");
-    socket.close(3000, "Crash and Burn!");
-}, 3000);
\ No newline at end of file
+socket.addEventListener('close', function (event) {
+    clearInterval(heartbeat);
+    console.log(`Gateway closed: ${event.code} ${event.reason}`);
+});
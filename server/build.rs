@@ -0,0 +1,4 @@
+fn main() {
+    // sqlx::migrate! embeds the migrations at compile time, new files have to trigger a rebuild
+    println!("cargo:rerun-if-changed=migrations");
+}
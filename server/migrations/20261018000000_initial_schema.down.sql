@@ -0,0 +1,23 @@
+-- Drops everything the baseline created, dependents first
+-- Takes the append-only trigger with it
+DROP TABLE IF EXISTS audit_events;
+DROP FUNCTION IF EXISTS audit_events_append_only();
+DROP TABLE IF EXISTS admin_audit_log;
+DROP TABLE IF EXISTS user_bans;
+DROP TABLE IF EXISTS passkeys;
+DROP TABLE IF EXISTS user_recovery_codes;
+DROP TABLE IF EXISTS user_mfa;
+DROP TABLE IF EXISTS refresh_tokens;
+DROP TABLE IF EXISTS sessions;
+DROP TABLE IF EXISTS user_blocks;
+DROP TABLE IF EXISTS dm_recipients;
+DROP TABLE IF EXISTS dm_channels;
+DROP TABLE IF EXISTS channel_overwrites;
+DROP TABLE IF EXISTS guild_member_roles;
+DROP TABLE IF EXISTS roles;
+DROP TABLE IF EXISTS invites;
+DROP TABLE IF EXISTS guild_members;
+DROP TABLE IF EXISTS channels;
+DROP TABLE IF EXISTS guilds;
+DROP TABLE IF EXISTS messages;
+DROP TABLE IF EXISTS users;
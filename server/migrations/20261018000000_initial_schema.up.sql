@@ -0,0 +1,284 @@
+-- Baseline of the schema that used to be applied from sql/ on every boot.
+-- Everything is idempotent so databases created by those files can adopt it.
+
+CREATE TABLE
+    IF NOT EXISTS users (
+        id BIGINT PRIMARY KEY UNIQUE NOT NULL,
+        username VARCHAR(48) NOT NULL,
+        password_hash VARCHAR(256) NOT NULL,
+        salt VARCHAR(64) NOT NULL,
+        email VARCHAR(64) NOT NULL UNIQUE,
+        created_at BIGINT NOT NULL,
+        verified BOOLEAN NOT NULL,
+        banned BOOLEAN NOT NULL,
+        date_of_birth DATE NOT NULL
+    );
+
+CREATE TABLE
+    IF NOT EXISTS messages (
+        id BIGINT PRIMARY KEY NOT NULL UNIQUE,
+        content TEXT NOT NULL,
+        author_id BIGINT NOT NULL,
+        channel_id BIGINT NOT NULL,
+        created_at BIGINT NOT NULL,
+        updated_at BIGINT
+    );
+
+CREATE INDEX
+    IF NOT EXISTS messages_channel_id_id_idx
+    ON messages (channel_id, id);
+
+CREATE TABLE
+    IF NOT EXISTS guilds (
+        id BIGINT PRIMARY KEY NOT NULL UNIQUE,
+        name VARCHAR(100) NOT NULL,
+        owner_id BIGINT NOT NULL,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE TABLE
+    IF NOT EXISTS channels (
+        id BIGINT PRIMARY KEY NOT NULL UNIQUE,
+        guild_id BIGINT NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+        name VARCHAR(100) NOT NULL,
+        kind SMALLINT NOT NULL,
+        position INTEGER NOT NULL,
+        parent_id BIGINT,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS channels_guild_id_idx
+    ON channels (guild_id);
+
+CREATE TABLE
+    IF NOT EXISTS guild_members (
+        guild_id BIGINT NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+        user_id BIGINT NOT NULL,
+        nickname VARCHAR(32),
+        joined_at BIGINT NOT NULL,
+        PRIMARY KEY (guild_id, user_id)
+    );
+
+CREATE INDEX
+    IF NOT EXISTS guild_members_user_id_idx
+    ON guild_members (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS invites (
+        code VARCHAR(16) PRIMARY KEY NOT NULL UNIQUE,
+        guild_id BIGINT NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+        creator_id BIGINT NOT NULL,
+        created_at BIGINT NOT NULL,
+        expires_at BIGINT,
+        max_uses INTEGER,
+        uses INTEGER NOT NULL DEFAULT 0
+    );
+
+CREATE TABLE
+    IF NOT EXISTS roles (
+        id BIGINT PRIMARY KEY NOT NULL UNIQUE,
+        guild_id BIGINT NOT NULL REFERENCES guilds (id) ON DELETE CASCADE,
+        name VARCHAR(100) NOT NULL,
+        permissions BIGINT NOT NULL,
+        position INTEGER NOT NULL,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS roles_guild_id_idx
+    ON roles (guild_id);
+
+CREATE TABLE
+    IF NOT EXISTS guild_member_roles (
+        guild_id BIGINT NOT NULL,
+        user_id BIGINT NOT NULL,
+        role_id BIGINT NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+        PRIMARY KEY (guild_id, user_id, role_id),
+        FOREIGN KEY (guild_id, user_id) REFERENCES guild_members (guild_id, user_id) ON DELETE CASCADE
+    );
+
+CREATE TABLE
+    IF NOT EXISTS channel_overwrites (
+        channel_id BIGINT NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
+        target_id BIGINT NOT NULL,
+        kind SMALLINT NOT NULL,
+        allow BIGINT NOT NULL,
+        deny BIGINT NOT NULL,
+        PRIMARY KEY (channel_id, target_id)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS dm_channels (
+        id BIGINT PRIMARY KEY NOT NULL UNIQUE,
+        kind SMALLINT NOT NULL,
+        -- Only group DMs have an owner
+        owner_id BIGINT,
+        -- Only set for direct DMs, lowest user id first so every pair of users has at most one
+        user_low_id BIGINT,
+        user_high_id BIGINT,
+        created_at BIGINT NOT NULL,
+        UNIQUE (user_low_id, user_high_id)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS dm_recipients (
+        channel_id BIGINT NOT NULL REFERENCES dm_channels (id) ON DELETE CASCADE,
+        user_id BIGINT NOT NULL,
+        joined_at BIGINT NOT NULL,
+        PRIMARY KEY (channel_id, user_id)
+    );
+
+CREATE INDEX
+    IF NOT EXISTS dm_recipients_user_id_idx
+    ON dm_recipients (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS user_blocks (
+        user_id BIGINT NOT NULL,
+        blocked_id BIGINT NOT NULL,
+        created_at BIGINT NOT NULL,
+        PRIMARY KEY (user_id, blocked_id)
+    );
+
+-- Refresh tokens moved to the refresh_tokens table
+ALTER TABLE users
+    DROP COLUMN IF EXISTS valid_refresh_token;
+
+CREATE TABLE
+    IF NOT EXISTS sessions (
+        -- Shared with the refresh token family of the login
+        id UUID PRIMARY KEY NOT NULL,
+        user_id BIGINT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        device_label TEXT NOT NULL,
+        ip TEXT,
+        user_agent TEXT,
+        created_at BIGINT NOT NULL,
+        last_used_at BIGINT NOT NULL,
+        -- Moves forward with every refresh, the session ends together with its newest refresh token
+        expires_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS sessions_user_id_idx
+    ON sessions (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS refresh_tokens (
+        jti UUID PRIMARY KEY NOT NULL,
+        -- Every token rotated out of the same login shares the family, it is the id of the login's session
+        family_id UUID NOT NULL REFERENCES sessions (id) ON DELETE CASCADE,
+        user_id BIGINT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        issued_at BIGINT NOT NULL,
+        expires_at BIGINT NOT NULL,
+        -- Set once the token was exchanged for a new one, presenting it again revokes the family
+        rotated_at BIGINT
+    );
+
+CREATE INDEX
+    IF NOT EXISTS refresh_tokens_family_id_idx
+    ON refresh_tokens (family_id);
+
+CREATE INDEX
+    IF NOT EXISTS refresh_tokens_user_id_idx
+    ON refresh_tokens (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS user_mfa (
+        user_id BIGINT PRIMARY KEY NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        totp_secret BYTEA NOT NULL,
+        -- Set once the user proved the authenticator app works, until then the secret is only an enrollment
+        confirmed_at BIGINT,
+        -- Time step of the last accepted code, codes of that step and older ones can't be replayed
+        last_used_step BIGINT,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE TABLE
+    IF NOT EXISTS user_recovery_codes (
+        user_id BIGINT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        -- SHA-256 of the normalized code, the codes themselves are only shown once
+        code_hash TEXT NOT NULL,
+        used_at BIGINT,
+        PRIMARY KEY (user_id, code_hash)
+    );
+
+CREATE TABLE
+    IF NOT EXISTS passkeys (
+        -- Credential id picked by the authenticator
+        credential_id BYTEA PRIMARY KEY NOT NULL,
+        user_id BIGINT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        -- COSE_Key from the attested credential data of the registration
+        public_key BYTEA NOT NULL,
+        -- Signature counter of the authenticator, authenticators that don't count always report 0
+        sign_count BIGINT NOT NULL,
+        label TEXT NOT NULL,
+        created_at BIGINT NOT NULL,
+        last_used_at BIGINT
+    );
+
+CREATE INDEX
+    IF NOT EXISTS passkeys_user_id_idx
+    ON passkeys (user_id);
+
+CREATE TABLE
+    IF NOT EXISTS user_bans (
+        user_id BIGINT PRIMARY KEY NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+        reason TEXT NOT NULL,
+        banned_at BIGINT NOT NULL,
+        -- NULL for permanent bans
+        expires_at BIGINT
+    );
+
+-- Admins get the admin claim in their access tokens, there is no route to grant it
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE
+    IF NOT EXISTS admin_audit_log (
+        id BIGSERIAL PRIMARY KEY NOT NULL,
+        -- No foreign keys, the log has to outlive deleted admins and users
+        admin_id BIGINT NOT NULL,
+        action TEXT NOT NULL,
+        target_user_id BIGINT,
+        -- Action specific, e.g. the reason and expiry of a ban
+        details JSONB NOT NULL,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS admin_audit_log_target_user_id_idx
+    ON admin_audit_log (target_user_id, id);
+
+CREATE TABLE
+    IF NOT EXISTS audit_events (
+        id BIGSERIAL PRIMARY KEY NOT NULL,
+        event_type TEXT NOT NULL,
+        -- No foreign keys, events have to outlive deleted users
+        -- NULL when nobody was logged in, e.g. a failed login
+        actor_user_id BIGINT,
+        -- NULL when the event can't be tied to a user, e.g. a login with an unknown email
+        target_user_id BIGINT,
+        ip TEXT,
+        user_agent TEXT,
+        metadata JSONB NOT NULL,
+        created_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS audit_events_target_user_id_idx
+    ON audit_events (target_user_id, id);
+
+CREATE OR REPLACE FUNCTION audit_events_append_only()
+    RETURNS TRIGGER
+    LANGUAGE plpgsql
+AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$;
+
+CREATE OR REPLACE TRIGGER audit_events_append_only
+    BEFORE UPDATE OR DELETE OR TRUNCATE ON audit_events
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION audit_events_append_only();
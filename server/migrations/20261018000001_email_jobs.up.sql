@@ -0,0 +1,24 @@
+-- Outbound emails, written by the routes and sent by the email workers
+CREATE TABLE
+    IF NOT EXISTS email_jobs (
+        id BIGSERIAL PRIMARY KEY NOT NULL,
+        -- A job enqueued twice with the same key is only sent once
+        idempotency_key TEXT NOT NULL UNIQUE,
+        sender TEXT,
+        recipients TEXT[] NOT NULL,
+        -- The whole email as it goes over SMTP
+        message TEXT NOT NULL,
+        -- pending, sending, sent or dead
+        status TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        last_error TEXT,
+        run_at BIGINT NOT NULL,
+        -- A sending job whose worker died is picked up again after this
+        locked_until BIGINT,
+        created_at BIGINT NOT NULL,
+        updated_at BIGINT NOT NULL
+    );
+
+CREATE INDEX
+    IF NOT EXISTS email_jobs_status_run_at_idx
+    ON email_jobs (status, run_at);
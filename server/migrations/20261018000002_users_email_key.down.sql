@@ -0,0 +1,3 @@
+-- The constraint may come from the UNIQUE of the initial schema instead of the up migration, dropping
+-- it here would leave emails without a uniqueness check
+SELECT 1;
@@ -0,0 +1,18 @@
+-- CREATE TABLE IF NOT EXISTS leaves users tables from before the UNIQUE without the constraint. Registering
+-- relies on it to turn a second account for an email into a conflict, so refuse to migrate
+-- until duplicates are merged by hand instead of dropping accounts here
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conname = 'users_email_key' AND conrelid = 'users'::regclass
+    ) THEN
+        IF EXISTS (
+            SELECT 1 FROM users GROUP BY email HAVING COUNT(*) > 1
+        ) THEN
+            RAISE EXCEPTION 'users has duplicate emails, merge them before adding users_email_key';
+        END IF;
+        ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+    END IF;
+END;
+$$;
@@ -0,0 +1,2 @@
+-- The backfilled users can't be told apart from the ones verified later, there is nothing to undo
+SELECT 1;
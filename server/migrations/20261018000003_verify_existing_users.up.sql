@@ -0,0 +1,6 @@
+-- Users only get created once they open their verification link, but the boot-time scripts stored
+-- them with verified = FALSE anyway. Every row that exists when this runs came in through that
+-- flow, users created later carry the real state
+UPDATE users
+    SET verified = TRUE
+    WHERE verified = FALSE;
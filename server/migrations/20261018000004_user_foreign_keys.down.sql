@@ -0,0 +1,6 @@
+ALTER TABLE guilds DROP CONSTRAINT IF EXISTS guilds_owner_id_fkey;
+ALTER TABLE dm_channels DROP CONSTRAINT IF EXISTS dm_channels_owner_id_fkey;
+ALTER TABLE user_blocks DROP CONSTRAINT IF EXISTS user_blocks_blocked_id_fkey;
+ALTER TABLE user_blocks DROP CONSTRAINT IF EXISTS user_blocks_user_id_fkey;
+ALTER TABLE dm_recipients DROP CONSTRAINT IF EXISTS dm_recipients_user_id_fkey;
+ALTER TABLE guild_members DROP CONSTRAINT IF EXISTS guild_members_user_id_fkey;
@@ -0,0 +1,36 @@
+-- Guild members, DM recipients and blocks had no foreign key to users, deleting a user left them behind.
+-- Rows of users that are already gone are dropped before the constraints are added
+DELETE FROM guild_members
+    WHERE NOT EXISTS (SELECT 1 FROM users WHERE users.id = guild_members.user_id);
+DELETE FROM dm_recipients
+    WHERE NOT EXISTS (SELECT 1 FROM users WHERE users.id = dm_recipients.user_id);
+DELETE FROM user_blocks
+    WHERE NOT EXISTS (SELECT 1 FROM users WHERE users.id = user_blocks.user_id)
+        OR NOT EXISTS (SELECT 1 FROM users WHERE users.id = user_blocks.blocked_id);
+UPDATE dm_channels
+    SET owner_id = NULL
+    WHERE owner_id IS NOT NULL
+        AND NOT EXISTS (SELECT 1 FROM users WHERE users.id = dm_channels.owner_id);
+
+ALTER TABLE guild_members
+    ADD CONSTRAINT guild_members_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE;
+ALTER TABLE dm_recipients
+    ADD CONSTRAINT dm_recipients_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE;
+ALTER TABLE user_blocks
+    ADD CONSTRAINT user_blocks_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE;
+ALTER TABLE user_blocks
+    ADD CONSTRAINT user_blocks_blocked_id_fkey
+    FOREIGN KEY (blocked_id) REFERENCES users (id) ON DELETE CASCADE;
+-- Users leave their DM channels before they are deleted, which hands group DMs on
+ALTER TABLE dm_channels
+    ADD CONSTRAINT dm_channels_owner_id_fkey
+    FOREIGN KEY (owner_id) REFERENCES users (id) ON DELETE SET NULL;
+-- Owners have to hand their guilds over or delete them first. Guilds whose owner is already gone
+-- can't be fixed here, NOT VALID only checks new and changed rows
+ALTER TABLE guilds
+    ADD CONSTRAINT guilds_owner_id_fkey
+    FOREIGN KEY (owner_id) REFERENCES users (id) ON DELETE RESTRICT
+    NOT VALID;
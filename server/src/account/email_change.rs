@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PendingEmailChange,
+    auth::{
+        AuthClaims,
+        ClaimType,
+        JWTKeys
+    },
+    database::{
+        DatabaseClient,
+        EmailChangeStore
+    }
+};
+
+use super::AccountError;
+
+
+/// Signs the token of the confirmation link, the change itself waits in Redis until the link is used
+pub async fn create_email_change_token(
+    db_client: &DatabaseClient,
+    jwt_keys: &JWTKeys,
+    lifetime_s: i64,
+    email_change: &PendingEmailChange
+) -> Result<String, AccountError> {
+    let jti = Uuid::new_v4();
+    let claims = AuthClaims::new_email_change(lifetime_s, email_change.user_id, jti);
+    let token = jwt_keys.encode(&claims)?;
+    db_client.create_email_change(jti, email_change, lifetime_s.max(1) as u64).await?;
+    Ok(token)
+}
+
+/// Checks the token of a confirmation link and marks it as used
+pub async fn take_email_change_token(
+    db_client: &DatabaseClient,
+    jwt_keys: &JWTKeys,
+    token: &str
+) -> Result<PendingEmailChange, AccountError> {
+    let claims: AuthClaims = jwt_keys.verify_token_and_return_claims(token).await?;
+    if claims.claim_type != ClaimType::EmailChange {
+        return Err(AccountError::InvalidToken);
+    }
+    let jti = claims.jti.ok_or(AccountError::InvalidToken)?;
+
+    match db_client.take_email_change(jti).await? {
+        Some(email_change) if email_change.user_id == claims.user_id => Ok(email_change),
+        _ => Err(AccountError::InvalidToken),
+    }
+}
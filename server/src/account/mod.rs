@@ -0,0 +1,101 @@
+mod payload;
+mod password;
+mod email_change;
+
+mod tests;
+
+pub use payload::{
+    ChangeEmailPayload,
+    ChangePasswordPayload,
+    ConfirmEmailChangePayload
+};
+pub use password::check_current_password;
+pub use email_change::{
+    create_email_change_token,
+    take_email_change_token
+};
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    auth::{
+        AuthError,
+        VerificationError
+    },
+    credentials::PasswordError,
+    database::DatabaseError,
+    email::EmailHandlerError
+};
+
+#[derive(Debug, Error)]
+pub enum AccountError {
+    #[error("The current password is wrong")]
+    WrongPassword,
+    #[error("The email address is already used by another account")]
+    EmailAlreadyExists,
+    #[error("Invalid email address")]
+    InvalidEmail,
+    #[error("The email change link is invalid, expired or was already used")]
+    InvalidToken,
+    #[error(transparent)]
+    PasswordError(#[from] PasswordError),
+    #[error(transparent)]
+    DatabaseError(DatabaseError),
+    #[error(transparent)]
+    EmailError(#[from] EmailHandlerError),
+    #[error("Failed to encode the email change token: {0}")]
+    EncodingError(#[from] jsonwebtoken::errors::Error),
+    // Wrong current passwords count like failed logins, e.g. the caller is locked out
+    #[error("Login protection rejected the request: {0:?}")]
+    LoginProtection(AuthError),
+}
+
+impl From<DatabaseError> for AccountError {
+    fn from(e: DatabaseError) -> Self {
+        match e {
+            DatabaseError::EmailAlreadyExists(_) => AccountError::EmailAlreadyExists,
+            e => AccountError::DatabaseError(e),
+        }
+    }
+}
+
+impl From<VerificationError> for AccountError {
+    fn from(_: VerificationError) -> Self {
+        AccountError::InvalidToken
+    }
+}
+
+impl IntoResponse for AccountError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AccountError::WrongPassword => (StatusCode::FORBIDDEN, "2500"),
+            AccountError::EmailAlreadyExists => (StatusCode::CONFLICT, "2501"),
+            AccountError::InvalidEmail => (StatusCode::BAD_REQUEST, "2502"),
+            AccountError::InvalidToken => (StatusCode::BAD_REQUEST, "2503"),
+            AccountError::PasswordError(e) => {
+                let status = match e {
+                    PasswordError::HashError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::BAD_REQUEST,
+                };
+                (status, e.into_internal_error_code())
+            },
+            AccountError::DatabaseError(e) => return e.into_response(),
+            AccountError::EmailError(e) => return e.into_response(),
+            AccountError::EncodingError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "2504"),
+            AccountError::LoginProtection(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    credentials::{
+        Password,
+        PasswordRequirements
+    },
+    database::{
+        CredentialStore,
+        DatabaseClient
+    }
+};
+
+use super::AccountError;
+
+
+/// Changes to the credentials need the current password, an access token alone isn't enough
+pub async fn check_current_password(
+    db_client: &DatabaseClient,
+    password_requirements: &PasswordRequirements,
+    user_id: i64,
+    password: &str
+) -> Result<(), AccountError> {
+    let (password_hash, salt) = db_client.get_password_hash_and_salt_by_user_id(user_id).await?;
+    let matches = Password::new(password, password_requirements).check_if_password_matches_hash(
+        &salt,
+        &password_hash
+    ).await?;
+    if !matches {
+        return Err(AccountError::WrongPassword);
+    }
+    Ok(())
+}
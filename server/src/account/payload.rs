@@ -0,0 +1,24 @@
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ChangePasswordPayload {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ChangeEmailPayload {
+    // Someone with a stolen access token can't move the account to their own address
+    pub password: String,
+    pub new_email: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ConfirmEmailChangePayload {
+    // Token from the link sent to the new address
+    pub token: String,
+}
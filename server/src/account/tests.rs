@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use crate::account::{
+        create_email_change_token,
+        take_email_change_token,
+        AccountError
+    };
+    use crate::app_objects::PendingEmailChange;
+    use crate::auth::{
+        AuthClaims,
+        JWTKeys
+    };
+    use crate::database::PasswordResetStore;
+    use crate::routes::tests::preparation::{
+        get_config,
+        TestContext
+    };
+
+    #[tokio::test]
+    async fn test_email_change_token_works_once() {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let db_client = TestContext::new().db_client();
+
+        let email_change = PendingEmailChange::new(420, "test_email".to_string(), "new_test_email".to_string());
+        let token = create_email_change_token(&db_client, &jwt_keys, 60, &email_change).await.unwrap();
+        assert_eq!(take_email_change_token(&db_client, &jwt_keys, &token).await.unwrap(), email_change);
+
+        let res = take_email_change_token(&db_client, &jwt_keys, &token).await;
+        assert!(matches!(res, Err(AccountError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_other_tokens_are_not_email_change_tokens() {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let db_client = TestContext::new().db_client();
+
+        let jti = uuid::Uuid::new_v4();
+        let password_reset_token = jwt_keys.encode(&AuthClaims::new_password_reset(60, 420, jti)).unwrap();
+        db_client.create_password_reset(jti, 420, 60).await.unwrap();
+        let res = take_email_change_token(&db_client, &jwt_keys, &password_reset_token).await;
+        assert!(matches!(res, Err(AccountError::InvalidToken)));
+    }
+}
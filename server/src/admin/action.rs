@@ -0,0 +1,27 @@
+/// What an entry of the admin audit log records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    SearchUsers,
+    ViewUser,
+    ViewSessions,
+    BanUser,
+    UnbanUser,
+    VerifyUser,
+    ResetPassword,
+    DeleteUser,
+}
+
+impl AdminAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminAction::SearchUsers => "search_users",
+            AdminAction::ViewUser => "view_user",
+            AdminAction::ViewSessions => "view_sessions",
+            AdminAction::BanUser => "ban_user",
+            AdminAction::UnbanUser => "unban_user",
+            AdminAction::VerifyUser => "verify_user",
+            AdminAction::ResetPassword => "reset_password",
+            AdminAction::DeleteUser => "delete_user",
+        }
+    }
+}
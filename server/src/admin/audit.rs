@@ -0,0 +1,34 @@
+use tracing::error;
+
+use crate::database::{
+    AdminAuditStore,
+    DatabaseClient
+};
+
+use super::{
+    AdminAction,
+    AdminError
+};
+
+
+/// Writes the audit log entry of an action, a request whose action can't be logged fails
+pub async fn record_admin_action(
+    db_client: &DatabaseClient,
+    admin_id: i64,
+    action: AdminAction,
+    target_user_id: Option<i64>,
+    details: serde_json::Value
+) -> Result<(), AdminError> {
+    db_client.insert_admin_audit_entry(
+        admin_id,
+        action.as_str(),
+        target_user_id,
+        &details
+    ).await.map_err(
+        |e| {
+            error!("Error writing the audit log entry of {} by admin {}: {:?}", action.as_str(), admin_id, e);
+            e
+        }
+    )?;
+    Ok(())
+}
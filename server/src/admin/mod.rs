@@ -0,0 +1,75 @@
+mod action;
+mod audit;
+mod payload;
+mod user_body;
+
+mod tests;
+
+pub use action::AdminAction;
+pub use audit::record_admin_action;
+pub use payload::{
+    AuditLogQuery,
+    BanPayload,
+    SearchUsersQuery
+};
+pub use user_body::AdminUserBody;
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    credentials::PasswordError,
+    database::DatabaseError,
+    email::EmailHandlerError,
+    password_reset::PasswordResetError
+};
+
+#[derive(Debug, Error)]
+pub enum AdminError {
+    #[error("A search needs an id, an email or a username")]
+    EmptySearch,
+    #[error("The limit has to be between 1 and {0}")]
+    InvalidLimit(i64),
+    #[error("The ban reason has to be between 1 and {0} characters")]
+    InvalidBanReason(usize),
+    #[error("The ban duration has to be positive")]
+    InvalidBanDuration,
+    #[error("Admins can't moderate their own account")]
+    CannotModerateSelf,
+    #[error(transparent)]
+    PasswordError(#[from] PasswordError),
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    EmailError(#[from] EmailHandlerError),
+    #[error(transparent)]
+    PasswordResetError(#[from] PasswordResetError),
+}
+
+impl IntoResponse for AdminError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AdminError::EmptySearch => (StatusCode::BAD_REQUEST, "2700"),
+            AdminError::InvalidLimit(_) => (StatusCode::BAD_REQUEST, "2701"),
+            AdminError::InvalidBanReason(_) => (StatusCode::BAD_REQUEST, "2702"),
+            AdminError::InvalidBanDuration => (StatusCode::BAD_REQUEST, "2703"),
+            AdminError::CannotModerateSelf => (StatusCode::FORBIDDEN, "2704"),
+            AdminError::PasswordError(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.into_internal_error_code()),
+            AdminError::DatabaseError(e) => return e.into_response(),
+            AdminError::EmailError(e) => return e.into_response(),
+            AdminError::PasswordResetError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,109 @@
+use serde::Deserialize;
+
+use super::AdminError;
+
+
+/// Makes `%`, `_` and `\` match themselves and lets the pattern match anything starting with `prefix`
+pub fn escape_like_prefix(prefix: &str) -> String {
+    let mut pattern = String::with_capacity(prefix.len() + 1);
+    for c in prefix.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+fn validated_limit(
+    limit: Option<i64>,
+    default_page_size: i64,
+    max_page_size: i64
+) -> Result<i64, AdminError> {
+    let limit = limit.unwrap_or(default_page_size);
+    if limit < 1 || limit > max_page_size {
+        return Err(AdminError::InvalidLimit(max_page_size));
+    }
+    Ok(limit)
+}
+
+/// Email and username are case-insensitive prefixes, every given filter has to match
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchUsersQuery {
+    pub id: Option<i64>,
+    pub email: Option<String>,
+    pub username: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl SearchUsersQuery {
+    /// Returns the LIKE patterns of the email and the username
+    pub fn validated_patterns(&self) -> Result<(Option<String>, Option<String>), AdminError> {
+        let pattern = |prefix: &Option<String>| {
+            prefix.as_deref()
+                .map(str::trim)
+                .filter(|prefix| !prefix.is_empty())
+                .map(escape_like_prefix)
+        };
+        let email_pattern = pattern(&self.email);
+        let username_pattern = pattern(&self.username);
+        if self.id.is_none() && email_pattern.is_none() && username_pattern.is_none() {
+            return Err(AdminError::EmptySearch);
+        }
+        Ok((email_pattern, username_pattern))
+    }
+
+    pub fn validated_limit(
+        &self,
+        default_page_size: i64,
+        max_page_size: i64
+    ) -> Result<i64, AdminError> {
+        validated_limit(self.limit, default_page_size, max_page_size)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditLogQuery {
+    pub target_user_id: Option<i64>,
+    // Id of the oldest entry of the previous page
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl AuditLogQuery {
+    pub fn validated_limit(
+        &self,
+        default_page_size: i64,
+        max_page_size: i64
+    ) -> Result<i64, AdminError> {
+        validated_limit(self.limit, default_page_size, max_page_size)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BanPayload {
+    pub reason: String,
+    // Permanent without a duration
+    pub duration_s: Option<i64>,
+}
+
+impl BanPayload {
+    /// Returns the trimmed reason and when the ban expires
+    pub fn validated(
+        &self,
+        now: i64,
+        max_reason_length: usize
+    ) -> Result<(String, Option<i64>), AdminError> {
+        let reason = self.reason.trim();
+        if reason.is_empty() || reason.chars().count() > max_reason_length {
+            return Err(AdminError::InvalidBanReason(max_reason_length));
+        }
+        let expires_at = match self.duration_s {
+            Some(duration_s) if duration_s < 1 => return Err(AdminError::InvalidBanDuration),
+            Some(duration_s) => Some(now.saturating_add(duration_s)),
+            None => None,
+        };
+        Ok((reason.to_string(), expires_at))
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use crate::admin::{
+        payload::escape_like_prefix,
+        AdminError,
+        BanPayload,
+        SearchUsersQuery
+    };
+
+    fn search_query(
+        id: Option<i64>,
+        email: Option<&str>,
+        username: Option<&str>
+    ) -> SearchUsersQuery {
+        SearchUsersQuery {
+            id,
+            email: email.map(str::to_string),
+            username: username.map(str::to_string),
+            limit: None,
+        }
+    }
+
+    #[test]
+    fn test_escape_like_prefix() {
+        assert_eq!(escape_like_prefix("alice"), "alice%");
+        assert_eq!(escape_like_prefix("100%_a\\b"), "100\\%\\_a\\\\b%");
+    }
+
+    #[test]
+    fn test_search_needs_a_filter() {
+        assert!(matches!(search_query(None, None, None).validated_patterns(), Err(AdminError::EmptySearch)));
+        assert!(matches!(search_query(None, Some("  "), None).validated_patterns(), Err(AdminError::EmptySearch)));
+        assert_eq!(search_query(Some(1), None, None).validated_patterns().unwrap(), (None, None));
+        assert_eq!(
+            search_query(None, Some(" alice@"), Some("al_")).validated_patterns().unwrap(),
+            (Some("alice@%".to_string()), Some("al\\_%".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_search_limit() {
+        let mut query = search_query(Some(1), None, None);
+        assert_eq!(query.validated_limit(25, 100).unwrap(), 25);
+        query.limit = Some(100);
+        assert_eq!(query.validated_limit(25, 100).unwrap(), 100);
+        for limit in [0, 101] {
+            query.limit = Some(limit);
+            assert!(matches!(query.validated_limit(25, 100), Err(AdminError::InvalidLimit(100))));
+        }
+    }
+
+    #[test]
+    fn test_ban_payload() {
+        let payload = BanPayload { reason: " spam ".to_string(), duration_s: Some(60) };
+        assert_eq!(payload.validated(1000, 10).unwrap(), ("spam".to_string(), Some(1060)));
+
+        let payload = BanPayload { reason: "spam".to_string(), duration_s: None };
+        assert_eq!(payload.validated(1000, 10).unwrap(), ("spam".to_string(), None));
+
+        for reason in ["", "   ", "way too long reason"] {
+            let payload = BanPayload { reason: reason.to_string(), duration_s: None };
+            assert!(matches!(payload.validated(1000, 10), Err(AdminError::InvalidBanReason(10))));
+        }
+        for duration_s in [0, -5] {
+            let payload = BanPayload { reason: "spam".to_string(), duration_s: Some(duration_s) };
+            assert!(matches!(payload.validated(1000, 10), Err(AdminError::InvalidBanDuration)));
+        }
+    }
+}
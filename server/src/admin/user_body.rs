@@ -0,0 +1,40 @@
+use serde::Serialize;
+
+use crate::app_objects::{
+    User,
+    UserBan
+};
+
+
+/// What admins get to see of a user, the credentials stay out
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AdminUserBody {
+    pub id: i64,
+    pub username: String,
+    pub email: String,
+    pub created_at: i64,
+    pub verified: bool,
+    pub banned: bool,
+    pub admin: bool,
+    // Only looked up for a single user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ban: Option<UserBan>,
+}
+
+impl AdminUserBody {
+    pub fn new(
+        user: User,
+        ban: Option<UserBan>
+    ) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at,
+            verified: user.verified,
+            banned: user.banned,
+            admin: user.admin,
+            ban,
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use serde::{Serialize, Deserialize};
+
+/// One moderation action taken through the admin API
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AdminAuditEntry {
+    pub id: i64,
+    pub admin_id: i64,
+    pub action: String,
+    // None for actions that don't target a single user, e.g. a search
+    pub target_user_id: Option<i64>,
+    pub details: serde_json::Value,
+    pub created_at: i64,
+}
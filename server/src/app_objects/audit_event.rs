@@ -0,0 +1,98 @@
+use serde::{Serialize, Deserialize};
+
+/// Kinds of security relevant events, stored as their `as_str` name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEventType {
+    LoginSuccess,
+    LoginFailure,
+    TokenRefresh,
+    RefreshTokenReused,
+    PasswordChange,
+    PasswordReset,
+    EmailChange,
+    EmailVerified,
+    UserBanned,
+    UserUnbanned,
+    MfaEnabled,
+    MfaDisabled,
+    RecoveryCodesRegenerated,
+    PasskeyAdded,
+    PasskeyRemoved,
+}
+
+impl AuditEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventType::LoginSuccess => "login_success",
+            AuditEventType::LoginFailure => "login_failure",
+            AuditEventType::TokenRefresh => "token_refresh",
+            AuditEventType::RefreshTokenReused => "refresh_token_reused",
+            AuditEventType::PasswordChange => "password_change",
+            AuditEventType::PasswordReset => "password_reset",
+            AuditEventType::EmailChange => "email_change",
+            AuditEventType::EmailVerified => "email_verified",
+            AuditEventType::UserBanned => "user_banned",
+            AuditEventType::UserUnbanned => "user_unbanned",
+            AuditEventType::MfaEnabled => "mfa_enabled",
+            AuditEventType::MfaDisabled => "mfa_disabled",
+            AuditEventType::RecoveryCodesRegenerated => "recovery_codes_regenerated",
+            AuditEventType::PasskeyAdded => "passkey_added",
+            AuditEventType::PasskeyRemoved => "passkey_removed",
+        }
+    }
+}
+
+/// A row of the append-only `audit_events` table. The id is given by Postgres,
+/// events that weren't written yet have id 0.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub event_type: String,
+    // Who caused the event, e.g. the admin that banned the target
+    pub actor_user_id: Option<i64>,
+    // Whose security log the event shows up in
+    pub target_user_id: Option<i64>,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub metadata: serde_json::Value,
+    pub created_at: i64,
+}
+
+impl AuditEvent {
+    /// An event the target caused themselves
+    pub fn new(
+        event_type: AuditEventType,
+        target_user_id: Option<i64>
+    ) -> Self {
+        Self {
+            id: 0,
+            event_type: event_type.as_str().to_string(),
+            actor_user_id: target_user_id,
+            target_user_id,
+            ip: None,
+            user_agent: None,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+
+    pub fn with_actor(mut self, actor_user_id: Option<i64>) -> Self {
+        self.actor_user_id = actor_user_id;
+        self
+    }
+
+    pub fn with_client(
+        mut self,
+        ip: Option<String>,
+        user_agent: Option<String>
+    ) -> Self {
+        self.ip = ip;
+        self.user_agent = user_agent;
+        self
+    }
+
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Serialize, Deserialize};
+
+/// Stored as SMALLINT, don't reorder the discriminants
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum ChannelKind {
+    Text = 0,
+    // Voice isn't implemented yet, the channel only exists so it can be placed in the list
+    Voice = 1,
+    // Groups other channels through their `parent_id`
+    Category = 2,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Channel {
+    pub id: i64,
+    pub guild_id: i64,
+    pub name: String,
+    pub kind: ChannelKind,
+    pub position: i32,
+    pub parent_id: Option<i64>,
+    pub created_at: i64,
+}
+
+impl Channel {
+    pub fn new(
+        id: i64,
+        guild_id: i64,
+        name: String,
+        kind: ChannelKind,
+        position: i32,
+        parent_id: Option<i64>,
+        created_at: i64
+    ) -> Self {
+        Self {
+            id,
+            guild_id,
+            name,
+            kind,
+            position,
+            parent_id,
+            created_at,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn accepts_messages(&self) -> bool {
+        self.kind == ChannelKind::Text
+    }
+}
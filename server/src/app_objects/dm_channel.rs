@@ -0,0 +1,78 @@
+use serde::{Serialize, Deserialize};
+
+/// Stored as SMALLINT, don't reorder the discriminants
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum DmKind {
+    // Between exactly two users, there is at most one per pair
+    Direct = 0,
+    Group = 1,
+}
+
+/// Private channel outside of any guild. Messages use the same storage as guild channels.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DmChannel {
+    pub id: i64,
+    pub kind: DmKind,
+    // Only group DMs have an owner, who can remove other recipients
+    pub owner_id: Option<i64>,
+    // Ordered by the time the recipients joined
+    pub recipient_ids: Vec<i64>,
+    pub created_at: i64,
+}
+
+impl DmChannel {
+    pub fn new_direct(
+        id: i64,
+        user_id: i64,
+        recipient_id: i64,
+        created_at: i64
+    ) -> Self {
+        Self {
+            id,
+            kind: DmKind::Direct,
+            owner_id: None,
+            recipient_ids: vec![user_id, recipient_id],
+            created_at,
+        }
+    }
+
+    /// The owner is the first recipient
+    pub fn new_group(
+        id: i64,
+        owner_id: i64,
+        recipient_ids: &[i64],
+        created_at: i64
+    ) -> Self {
+        let mut all_recipient_ids = vec![owner_id];
+        all_recipient_ids.extend_from_slice(recipient_ids);
+        Self {
+            id,
+            kind: DmKind::Group,
+            owner_id: Some(owner_id),
+            recipient_ids: all_recipient_ids,
+            created_at,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn is_recipient(&self, user_id: i64) -> bool {
+        self.recipient_ids.contains(&user_id)
+    }
+
+    /// The other user of a direct DM
+    pub fn other_recipient(&self, user_id: i64) -> Option<i64> {
+        if self.kind != DmKind::Direct {
+            return None;
+        }
+        self.recipient_ids.iter().copied().find(|recipient_id| *recipient_id != user_id)
+    }
+}
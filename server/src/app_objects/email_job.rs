@@ -0,0 +1,69 @@
+use serde::{Serialize, Deserialize};
+
+/// Where an email job is, stored as its `as_str` name
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailJobStatus {
+    // Waiting for `run_at`
+    Pending,
+    // Claimed by a worker until `locked_until`
+    Sending,
+    Sent,
+    // Gave up after the last attempt
+    Dead,
+}
+
+impl EmailJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailJobStatus::Pending => "pending",
+            EmailJobStatus::Sending => "sending",
+            EmailJobStatus::Sent => "sent",
+            EmailJobStatus::Dead => "dead",
+        }
+    }
+}
+
+/// A row of the `email_jobs` table. The id is given by Postgres, jobs that weren't enqueued yet have id 0.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EmailJob {
+    pub id: i64,
+    pub idempotency_key: String,
+    // The envelope the email is sent with
+    pub sender: Option<String>,
+    pub recipients: Vec<String>,
+    // The formatted email
+    pub message: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub run_at: i64,
+    pub locked_until: Option<i64>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl EmailJob {
+    /// Due right away
+    pub fn new(
+        idempotency_key: String,
+        sender: Option<String>,
+        recipients: Vec<String>,
+        message: String
+    ) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            id: 0,
+            idempotency_key,
+            sender,
+            recipients,
+            message,
+            status: EmailJobStatus::Pending.as_str().to_string(),
+            attempts: 0,
+            last_error: None,
+            run_at: now,
+            locked_until: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Guild {
+    pub id: i64,
+    pub name: String,
+    pub owner_id: i64,
+    pub created_at: i64,
+}
+
+impl Guild {
+    pub fn new(
+        id: i64,
+        name: String,
+        owner_id: i64,
+        created_at: i64
+    ) -> Self {
+        Self {
+            id,
+            name,
+            owner_id,
+            created_at,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GuildMember {
+    pub guild_id: i64,
+    pub user_id: i64,
+    pub nickname: Option<String>,
+    pub joined_at: i64,
+    // Without the @everyone role
+    #[serde(default)]
+    pub role_ids: Vec<i64>,
+}
+
+impl GuildMember {
+    pub fn new(
+        guild_id: i64,
+        user_id: i64,
+        joined_at: i64
+    ) -> Self {
+        Self {
+            guild_id,
+            user_id,
+            nickname: None,
+            joined_at,
+            role_ids: Vec::new(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
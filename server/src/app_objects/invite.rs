@@ -0,0 +1,26 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Invite {
+    pub code: String,
+    pub guild_id: i64,
+    pub creator_id: i64,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    pub max_uses: Option<i32>,
+    pub uses: i32,
+}
+
+impl Invite {
+    pub fn is_usable(&self, now: i64) -> bool {
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= now {
+                return false;
+            }
+        }
+        match self.max_uses {
+            Some(max_uses) => self.uses < max_uses,
+            None => true,
+        }
+    }
+}
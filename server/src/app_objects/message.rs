@@ -3,30 +3,30 @@ use std::fmt::Display;
 use serde::{Serialize, Deserialize};
 use chrono::DateTime;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Message {
     #[serde(rename = "i")]
     pub id: i64,
     #[serde(rename = "c")]
     pub content: String,
     #[serde(rename = "a")]
-    pub author_id: i32,
+    pub author_id: i64,
     #[serde(rename = "ca")]
     pub created_at: i64,
     #[serde(rename = "ua")]
     pub updated_at: Option<i64>,
     #[serde(rename = "ch")]
-    pub channel_id: i32,
+    pub channel_id: i64,
 }
 
 impl Message {
     pub fn new(
         id: i64,
         content: String,
-        author_id: i32,
+        author_id: i64,
         created_at: i64,
         updated_at: Option<i64>,
-        channel_id: i32
+        channel_id: i64
     ) -> Self {
         Self {
             id,
@@ -54,7 +54,7 @@ impl Message {
         &self.content
     }
 
-    pub fn get_author_id(&self) -> i32 {
+    pub fn get_author_id(&self) -> i64 {
         self.author_id
     }
 
@@ -62,7 +62,7 @@ impl Message {
         self.created_at
     }
 
-    pub fn get_channel_id(&self) -> i32 {
+    pub fn get_channel_id(&self) -> i64 {
         self.channel_id
     }
     
@@ -1,5 +1,62 @@
 mod message;
 mod users;
+mod guild;
+mod channel;
+mod guild_member;
+mod invite;
+mod role;
+mod permission_overwrite;
+mod dm_channel;
+mod refresh_token;
+mod session;
+mod user_mfa;
+mod passkey;
+mod pending_email_change;
+mod pending_registration;
+mod user_ban;
+mod admin_audit_entry;
+mod audit_event;
+mod email_job;
 
 pub use message::Message;
-pub use users::User;
\ No newline at end of file
+pub use users::User;
+pub use guild::Guild;
+pub use channel::{
+    Channel,
+    ChannelKind
+};
+pub use guild_member::GuildMember;
+pub use invite::Invite;
+pub use role::Role;
+pub use permission_overwrite::{
+    OverwriteKind,
+    PermissionOverwrite
+};
+pub use dm_channel::{
+    DmChannel,
+    DmKind
+};
+pub use refresh_token::{
+    RefreshToken,
+    RefreshTokenRotation
+};
+pub use session::Session;
+
+pub use user_mfa::UserMfa;
+pub use passkey::{
+    Passkey,
+    PasskeyCeremony,
+    PasskeyChallenge
+};
+pub use pending_email_change::PendingEmailChange;
+pub use pending_registration::PendingRegistration;
+pub use user_ban::UserBan;
+pub use admin_audit_entry::AdminAuditEntry;
+pub use audit_event::{
+    AuditEvent,
+    AuditEventType
+};
+pub use email_job::{
+    EmailJob,
+    EmailJobStatus
+};
@@ -0,0 +1,80 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+/// WebAuthn credential a user registered to log in without a password
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passkey {
+    pub credential_id: Vec<u8>,
+    pub user_id: i64,
+    // COSE_Key the assertions are verified with
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub label: String,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+}
+
+impl Passkey {
+    pub fn new(
+        credential_id: Vec<u8>,
+        user_id: i64,
+        public_key: Vec<u8>,
+        sign_count: i64,
+        label: String,
+        created_at: i64
+    ) -> Self {
+        Self {
+            credential_id,
+            user_id,
+            public_key,
+            sign_count,
+            label,
+            created_at,
+            last_used_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PasskeyCeremony {
+    Registration,
+    Authentication,
+}
+
+/// Challenge of a registration or login that is waiting for the authenticator's response.
+/// It is kept in Redis until it expires or is used once.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PasskeyChallenge {
+    pub id: Uuid,
+    pub ceremony: PasskeyCeremony,
+    pub challenge: Vec<u8>,
+    // The user that registers a passkey, logins don't know the user until the assertion arrives
+    pub user_id: Option<i64>,
+    pub created_at: i64,
+}
+
+impl PasskeyChallenge {
+    pub fn new(
+        ceremony: PasskeyCeremony,
+        challenge: Vec<u8>,
+        user_id: Option<i64>,
+        created_at: i64
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            ceremony,
+            challenge,
+            user_id,
+            created_at,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
@@ -0,0 +1,32 @@
+use serde::{Serialize, Deserialize};
+
+/// Email change waiting for the confirmation link sent to the new address
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PendingEmailChange {
+    pub user_id: i64,
+    // The change is dropped if the email changed again in the meantime
+    pub old_email: String,
+    pub new_email: String,
+}
+
+impl PendingEmailChange {
+    pub fn new(
+        user_id: i64,
+        old_email: String,
+        new_email: String
+    ) -> Self {
+        Self {
+            user_id,
+            old_email,
+            new_email,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+use super::User;
+
+/// Registration waiting for its email to be verified, the verification links only carry its id
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PendingRegistration {
+    pub id: Uuid,
+    pub email: String,
+    pub username: String,
+    pub password_hash: String,
+    pub password_salt: String,
+    pub date_of_birth: NaiveDate,
+}
+
+impl PendingRegistration {
+    pub fn new(
+        email: String,
+        username: String,
+        password_hash: String,
+        password_salt: String,
+        date_of_birth: NaiveDate
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            email,
+            username,
+            password_hash,
+            password_salt,
+            date_of_birth,
+        }
+    }
+
+    pub fn into_user(
+        self,
+        id: i64
+    ) -> User {
+        User {
+            email: self.email,
+            username: self.username,
+            password_hash: self.password_hash,
+            salt: self.password_salt,
+            date_of_birth: self.date_of_birth,
+            // The user is only created once the verification link was used
+            verified: true,
+            banned: false,
+            admin: false,
+            created_at: chrono::Utc::now().timestamp(),
+            id,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Serialize, Deserialize};
+
+use crate::auth::Permissions;
+
+/// Stored as SMALLINT, don't reorder the discriminants
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[repr(i16)]
+pub enum OverwriteKind {
+    Role = 0,
+    Member = 1,
+}
+
+/// Changes the permissions of a role or a member in a single channel.
+/// Role and user ids are both snowflakes, so a channel has at most one overwrite per target.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PermissionOverwrite {
+    pub channel_id: i64,
+    pub target_id: i64,
+    pub kind: OverwriteKind,
+    pub allow: Permissions,
+    pub deny: Permissions,
+}
+
+impl PermissionOverwrite {
+    pub fn apply(&self, permissions: Permissions) -> Permissions {
+        (permissions - self.deny) | self.allow
+    }
+}
@@ -0,0 +1,44 @@
+use uuid::Uuid;
+
+/// Refresh tokens are single use, every refresh exchanges the token for a new one of the same family.
+/// A family belongs to the session of a login and ends with it, or when one of its rotated tokens is presented again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefreshToken {
+    pub jti: Uuid,
+    pub family_id: Uuid,
+    pub user_id: i64,
+    pub issued_at: i64,
+    pub expires_at: i64,
+    // Set once the token was exchanged for a new one
+    pub rotated_at: Option<i64>,
+}
+
+impl RefreshToken {
+    /// First token of the family of a new session, issued on login
+    pub fn new_family(
+        jti: Uuid,
+        session_id: Uuid,
+        user_id: i64,
+        issued_at: i64,
+        expires_at: i64
+    ) -> Self {
+        Self {
+            jti,
+            family_id: session_id,
+            user_id,
+            issued_at,
+            expires_at,
+            rotated_at: None,
+        }
+    }
+}
+
+/// Outcome of exchanging a refresh token for a new one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefreshTokenRotation {
+    Rotated,
+    // The token was already exchanged before, so it was most likely stolen and its session got revoked
+    Reused,
+    // Unknown, expired or from a revoked session
+    Invalid,
+}
@@ -0,0 +1,61 @@
+use serde::{Serialize, Deserialize};
+
+use crate::auth::Permissions;
+
+/// The @everyone role shares its id with the guild and every member implicitly has it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Role {
+    pub id: i64,
+    pub guild_id: i64,
+    pub name: String,
+    pub permissions: Permissions,
+    // Higher roles can manage lower ones, @everyone is always at 0
+    pub position: i32,
+    pub created_at: i64,
+}
+
+impl Role {
+    pub fn new(
+        id: i64,
+        guild_id: i64,
+        name: String,
+        permissions: Permissions,
+        position: i32,
+        created_at: i64
+    ) -> Self {
+        Self {
+            id,
+            guild_id,
+            name,
+            permissions,
+            position,
+            created_at,
+        }
+    }
+
+    pub fn everyone(
+        guild_id: i64,
+        created_at: i64
+    ) -> Self {
+        Self::new(
+            guild_id,
+            guild_id,
+            "@everyone".to_string(),
+            Permissions::DEFAULT_EVERYONE,
+            0,
+            created_at
+        )
+    }
+
+    pub fn is_everyone(&self) -> bool {
+        self.id == self.guild_id
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
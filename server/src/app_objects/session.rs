@@ -0,0 +1,62 @@
+use serde::{Serialize, Deserialize};
+use uuid::Uuid;
+
+/// A login of a user on one device. The id is shared with the refresh token family of the login,
+/// deleting the session revokes its refresh tokens.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: i64,
+    pub device_label: String,
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: i64,
+    // Updated on every refresh
+    pub last_used_at: i64,
+    pub expires_at: i64,
+}
+
+impl Session {
+    pub fn new(
+        id: Uuid,
+        user_id: i64,
+        device_label: String,
+        ip: Option<String>,
+        user_agent: Option<String>,
+        created_at: i64,
+        expires_at: i64
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            device_label,
+            ip,
+            user_agent,
+            created_at,
+            last_used_at: created_at,
+            expires_at,
+        }
+    }
+
+    /// Uses the label picked by the client, falls back to the user agent
+    pub fn device_label(
+        label: Option<&str>,
+        user_agent: Option<&str>,
+        max_length: usize
+    ) -> String {
+        let label = label
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .or(user_agent)
+            .unwrap_or("Unknown device");
+        label.chars().take(max_length).collect()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
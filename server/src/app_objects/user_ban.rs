@@ -0,0 +1,39 @@
+use serde::{Serialize, Deserialize};
+
+/// Why and until when a user is banned, `users.banned` is only the flag
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserBan {
+    pub user_id: i64,
+    pub reason: String,
+    pub banned_at: i64,
+    // None for permanent bans
+    pub expires_at: Option<i64>,
+}
+
+impl UserBan {
+    pub fn new(
+        user_id: i64,
+        reason: String,
+        banned_at: i64,
+        expires_at: Option<i64>
+    ) -> Self {
+        Self {
+            user_id,
+            reason,
+            banned_at,
+            expires_at,
+        }
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
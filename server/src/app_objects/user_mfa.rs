@@ -0,0 +1,31 @@
+/// TOTP second factor of a user. A secret without `confirmed_at` is an enrollment the user
+/// didn't finish yet and isn't asked for on login.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserMfa {
+    pub user_id: i64,
+    pub totp_secret: Vec<u8>,
+    pub confirmed_at: Option<i64>,
+    // Time step of the last accepted code
+    pub last_used_step: Option<i64>,
+    pub created_at: i64,
+}
+
+impl UserMfa {
+    pub fn new(
+        user_id: i64,
+        totp_secret: Vec<u8>,
+        created_at: i64
+    ) -> Self {
+        Self {
+            user_id,
+            totp_secret,
+            confirmed_at: None,
+            last_used_step: None,
+            created_at,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+}
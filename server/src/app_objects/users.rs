@@ -2,7 +2,7 @@ use chrono::{
     NaiveDate
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct User {
     pub id: i64,
     pub username: String,
@@ -10,10 +10,10 @@ pub struct User {
     pub salt: String,
     pub email: String,
     pub created_at: i64,
-    pub valid_refresh_token: Option<String>,
     pub verified: bool,
     pub banned: bool,
-    pub date_of_birth: NaiveDate
+    pub date_of_birth: NaiveDate,
+    pub admin: bool,
 }
 
 
@@ -26,10 +26,10 @@ impl Default for User {
             salt: "".to_string(),
             email: "".to_string(),
             created_at: 0,
-            valid_refresh_token: None,
             verified: true,
             banned: false,
-            date_of_birth: NaiveDate::default()
+            date_of_birth: NaiveDate::default(),
+            admin: false,
         }
     }
     
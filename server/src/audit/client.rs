@@ -0,0 +1,48 @@
+use std::convert::Infallible;
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts
+};
+use axum_client_ip::SecureClientIp;
+use axum_extra::TypedHeader;
+use headers::UserAgent;
+
+
+/// Where a request came from, both parts are optional so extracting it never fails
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditClient {
+    pub ip: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl AuditClient {
+    /// For handlers that already extracted the IP and the user agent
+    pub fn new(
+        client_ip: Option<&SecureClientIp>,
+        user_agent: Option<&TypedHeader<UserAgent>>
+    ) -> Self {
+        Self {
+            ip: client_ip.map(|SecureClientIp(ip)| ip.to_string()),
+            user_agent: user_agent.map(|TypedHeader(user_agent)| user_agent.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuditClient
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection> {
+        let client_ip = Option::<SecureClientIp>::from_request_parts(parts, state).await?;
+        let user_agent = Option::<TypedHeader<UserAgent>>::from_request_parts(parts, state).await?;
+        Ok(AuditClient::new(client_ip.as_ref(), user_agent.as_ref()))
+    }
+}
@@ -0,0 +1,43 @@
+mod client;
+mod payload;
+mod writer;
+
+mod tests;
+
+pub use client::AuditClient;
+pub use payload::SecurityLogQuery;
+pub use writer::AuditLog;
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::database::DatabaseError;
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("The limit has to be between 1 and {0}")]
+    InvalidLimit(i64),
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+}
+
+impl IntoResponse for AuditError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            AuditError::InvalidLimit(_) => (StatusCode::BAD_REQUEST, "2800"),
+            AuditError::DatabaseError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+use super::AuditError;
+
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityLogQuery {
+    // Id of the oldest event of the previous page
+    pub before: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl SecurityLogQuery {
+    pub fn validated_limit(
+        &self,
+        default_page_size: i64,
+        max_page_size: i64
+    ) -> Result<i64, AuditError> {
+        let limit = self.limit.unwrap_or(default_page_size);
+        if limit < 1 || limit > max_page_size {
+            return Err(AuditError::InvalidLimit(max_page_size));
+        }
+        Ok(limit)
+    }
+}
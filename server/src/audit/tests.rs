@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use crate::app_objects::{
+        AuditEvent,
+        AuditEventType
+    };
+    use crate::audit::{
+        AuditError,
+        AuditLog,
+        SecurityLogQuery
+    };
+
+    #[tokio::test]
+    async fn test_audit_log_drops_events_when_full() {
+        let (audit_log, mut receiver) = AuditLog::new(1);
+        audit_log.record(AuditEvent::new(AuditEventType::LoginSuccess, Some(1)));
+        audit_log.record(AuditEvent::new(AuditEventType::LoginFailure, Some(1)));
+
+        let audit_event = receiver.recv().await.unwrap();
+        assert_eq!(audit_event.event_type, "login_success");
+        assert!(receiver.try_recv().is_err());
+
+        // Recording doesn't fail once the writer is gone
+        drop(receiver);
+        audit_log.record(AuditEvent::new(AuditEventType::LoginSuccess, Some(1)));
+    }
+
+    #[test]
+    fn test_audit_event_actor() {
+        let audit_event = AuditEvent::new(AuditEventType::PasswordChange, Some(1));
+        assert_eq!(audit_event.actor_user_id, Some(1));
+        let audit_event = AuditEvent::new(AuditEventType::UserBanned, Some(1)).with_actor(Some(2));
+        assert_eq!((audit_event.actor_user_id, audit_event.target_user_id), (Some(2), Some(1)));
+    }
+
+    #[test]
+    fn test_security_log_limit() {
+        let query = SecurityLogQuery { before: None, limit: None };
+        assert_eq!(query.validated_limit(25, 100).unwrap(), 25);
+        for limit in [0, 101] {
+            let query = SecurityLogQuery { before: None, limit: Some(limit) };
+            assert!(matches!(query.validated_limit(25, 100), Err(AuditError::InvalidLimit(100))));
+        }
+    }
+}
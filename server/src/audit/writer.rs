@@ -0,0 +1,65 @@
+use tokio::sync::mpsc::{
+    self,
+    error::TrySendError
+};
+use tracing::{
+    error,
+    warn
+};
+
+use crate::{
+    app_objects::AuditEvent,
+    database::{
+        AuditEventStore,
+        DatabaseClient
+    }
+};
+
+
+/// Hands audit events to a background task that writes them to Postgres, so recording one never
+/// waits for the database. When the queue is full the event is dropped and only logged.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    sender: mpsc::Sender<AuditEvent>,
+}
+
+impl AuditLog {
+    /// A log whose events end up in the returned receiver
+    pub fn new(capacity: usize) -> (Self, mpsc::Receiver<AuditEvent>) {
+        let (sender, receiver) = mpsc::channel(capacity.max(1));
+        (Self { sender }, receiver)
+    }
+
+    /// A log written to Postgres by a task that runs until every clone of the log is dropped
+    pub fn spawn(
+        db_client: DatabaseClient,
+        capacity: usize
+    ) -> Self {
+        let (audit_log, receiver) = Self::new(capacity);
+        tokio::spawn(write_audit_events(db_client, receiver));
+        audit_log
+    }
+
+    pub fn record(&self, audit_event: AuditEvent) {
+        match self.sender.try_send(audit_event) {
+            Ok(()) => {},
+            Err(TrySendError::Full(audit_event)) => {
+                warn!("Audit log queue is full, dropped {:?}", audit_event);
+            },
+            Err(TrySendError::Closed(audit_event)) => {
+                error!("Audit log writer stopped, dropped {:?}", audit_event);
+            },
+        }
+    }
+}
+
+async fn write_audit_events(
+    db_client: DatabaseClient,
+    mut receiver: mpsc::Receiver<AuditEvent>
+) {
+    while let Some(audit_event) = receiver.recv().await {
+        if let Err(e) = db_client.insert_audit_event(&audit_event).await {
+            error!("Error writing audit event {:?}: {:?}", audit_event, e);
+        }
+    }
+}
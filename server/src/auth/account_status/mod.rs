@@ -0,0 +1,92 @@
+mod tests;
+
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    app_objects::User,
+    database::{
+        BanStore,
+        DatabaseClient,
+        UserStore
+    }
+};
+
+use super::AuthError;
+
+/// Checked before a login hands out a session and before every refresh.
+/// Bans that ran out are lifted here, nothing else looks at them once they expired.
+pub async fn check_account_status(
+    db_client: &DatabaseClient,
+    user_id: i64
+) -> Result<User, AuthError> {
+    let user = db_client.get_user_by_id(user_id).await.map_err(
+        |e| {
+            error!("db_error: {:?}", e);
+            e.to_auth_error()
+        }
+    )?;
+    let Some(user) = user else {
+        return Err(AuthError::InvalidToken);
+    };
+    if !user.verified {
+        return Err(AuthError::UnverifiedEmail);
+    }
+    if !user.banned {
+        return Ok(user);
+    }
+
+    let user_ban = db_client.get_user_ban(user_id).await.map_err(
+        |e| {
+            error!("db_error: {:?}", e);
+            e.to_auth_error()
+        }
+    )?;
+    let now = chrono::Utc::now().timestamp();
+    match user_ban {
+        Some(user_ban) if !user_ban.is_active(now) => {
+            info!("ban of user {} expired, lifting it", user_id);
+            db_client.unban_user(user_id).await.map_err(
+                |e| {
+                    error!("db_error: {:?}", e);
+                    e.to_auth_error()
+                }
+            )?;
+            Ok(User {
+                banned: false,
+                ..user
+            })
+        },
+        Some(user_ban) => Err(AuthError::Banned {
+            reason: Some(user_ban.reason),
+            expires_at: user_ban.expires_at,
+        }),
+        // Flag set without going through a ban
+        None => Err(AuthError::Banned {
+            reason: None,
+            expires_at: None,
+        }),
+    }
+}
+
+/// Checked by the access token extractor, only looks at the Redis denylist so it stays cheap
+pub async fn check_denylist(
+    db_client: &DatabaseClient,
+    user_id: i64
+) -> Result<(), AuthError> {
+    let user_ban = db_client.get_denylisted_user(user_id).await.map_err(
+        |e| {
+            error!("db_error: {:?}", e);
+            e.to_auth_error()
+        }
+    )?;
+    match user_ban {
+        Some(user_ban) if user_ban.is_active(chrono::Utc::now().timestamp()) => Err(AuthError::Banned {
+            reason: Some(user_ban.reason),
+            expires_at: user_ban.expires_at,
+        }),
+        _ => Ok(()),
+    }
+}
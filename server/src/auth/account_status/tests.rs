@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app_objects::{
+            User,
+            UserBan
+        },
+        auth::{
+            check_account_status,
+            check_denylist,
+            AuthError
+        },
+        database::{
+            BanStore,
+            DatabaseClient,
+            DatabaseError,
+            UserStore
+        },
+        routes::tests::preparation::TestContext
+    };
+
+    /// Recreates user 452
+    async fn create_test_user(
+        db_client: &DatabaseClient,
+        verified: bool
+    ) {
+        let user = User {
+            id: 452,
+            email: "status@example.com".to_string(),
+            verified,
+            created_at: chrono::Utc::now().timestamp(),
+            ..User::default()
+        };
+        match db_client.delete_user(452).await {
+            Ok(_) | Err(DatabaseError::UserNotFound(_)) => {},
+            Err(e) => panic!("Error deleting user: {:?}", e),
+        }
+        db_client.insert_user(&user).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_account_status() {
+        let db_client = TestContext::new().db_client();
+        create_test_user(&db_client, false).await;
+        assert!(matches!(check_account_status(&db_client, 452).await, Err(AuthError::UnverifiedEmail)));
+
+        create_test_user(&db_client, true).await;
+        assert!(check_account_status(&db_client, 452).await.is_ok());
+        assert!(check_denylist(&db_client, 452).await.is_ok());
+
+        let now = chrono::Utc::now().timestamp();
+        db_client.ban_user(&UserBan::new(452, "spam".to_string(), now, Some(now + 60))).await.unwrap();
+        let expected_expires_at = Some(now + 60);
+        assert!(matches!(
+            check_account_status(&db_client, 452).await,
+            Err(AuthError::Banned { reason: Some(reason), expires_at }) if reason == "spam" && expires_at == expected_expires_at
+        ));
+        assert!(matches!(check_denylist(&db_client, 452).await, Err(AuthError::Banned { .. })));
+
+        assert!(matches!(check_account_status(&db_client, 453).await, Err(AuthError::InvalidToken)));
+
+        db_client.delete_user(452).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_expired_ban_is_lifted() {
+        let db_client = TestContext::new().db_client();
+        create_test_user(&db_client, true).await;
+
+        let now = chrono::Utc::now().timestamp();
+        db_client.ban_user(&UserBan::new(452, "spam".to_string(), now - 120, Some(now - 60))).await.unwrap();
+        assert!(check_account_status(&db_client, 452).await.is_ok());
+        assert!(!db_client.get_user_by_id(452).await.unwrap().unwrap().banned);
+        assert_eq!(db_client.get_user_ban(452).await.unwrap(), None);
+
+        db_client.delete_user(452).await.unwrap();
+    }
+}
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-use crate::auth::ClaimType;
+use crate::{
+    auth::ClaimType,
+    cloudflare::GetTurnstileCode,
+    mfa::MfaCodePayload
+};
 
 #[derive(Debug, Serialize)]
 pub struct AuthenticationBody {
@@ -16,9 +20,44 @@ impl AuthenticationBody {
     }
 }
 
+/// Returned by /authenticate instead of the session when the user has a second factor
+#[derive(Debug, Serialize)]
+pub struct MfaChallengeBody {
+    pub mfa_token: String,
+    pub token_type: String,
+}
+impl MfaChallengeBody {
+    pub fn new(mfa_token: String) -> Self {
+        Self {
+            mfa_token,
+            token_type: ClaimType::MfaPending.as_str().to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct AuthenticationPayload {
     pub email: String,
     pub password: String,
+    // Shown in the session list, the user agent is used when it's missing
+    #[serde(default)]
+    pub device_label: Option<String>,
+    // Only needed after repeated failed logins
+    #[serde(default, rename = "cf-turnstile-response")]
+    pub cf_turnstile_response: Option<String>,
+}
+
+impl GetTurnstileCode for AuthenticationPayload {
+    fn get_turnstile_code(&self) -> String {
+        self.cf_turnstile_response.clone().unwrap_or_default()
+    }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct MfaAuthenticationPayload {
+    pub mfa_token: String,
+    #[serde(flatten)]
+    pub second_factor: MfaCodePayload,
+    #[serde(default)]
+    pub device_label: Option<String>,
+}
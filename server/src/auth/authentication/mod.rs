@@ -7,6 +7,8 @@ pub use super::jwt::{
 
 pub use authentication::{
     AuthenticationBody,
-    AuthenticationPayload
+    AuthenticationPayload,
+    MfaAuthenticationPayload,
+    MfaChallengeBody
 };
 
@@ -0,0 +1,43 @@
+use axum::{
+    async_trait,
+    extract::{
+        FromRef,
+        FromRequestParts
+    },
+    http::request::Parts
+};
+
+use crate::{
+    auth::{
+        AuthClaims,
+        AuthError,
+        JWTKeys
+    },
+    database::DatabaseClient
+};
+
+/// Claims of an access token carrying the admin claim, rejects everyone else.
+/// The claim is taken from the user on every refresh, so revoking it takes up to one access token lifetime.
+#[derive(Debug)]
+pub struct AdminClaims(pub AuthClaims);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminClaims
+where
+    JWTKeys: FromRef<S>,
+    DatabaseClient: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection> {
+        let claims = AuthClaims::from_request_parts(parts, state).await?;
+        if !claims.admin {
+            return Err(AuthError::NotAdmin);
+        }
+        Ok(AdminClaims(claims))
+    }
+}
@@ -1,2 +1,169 @@
+use std::sync::Arc;
 
+use axum::{
+    async_trait,
+    extract::{
+        FromRef,
+        FromRequestParts,
+        Path
+    },
+    http::request::Parts,
+    RequestPartsExt
+};
+use serde::Deserialize;
 
+use crate::{
+    app_objects::{
+        Guild,
+        GuildMember,
+        Role
+    },
+    auth::{
+        AuthClaims,
+        AuthError,
+        JWTKeys
+    },
+    guilds::{
+        get_channel_permissions,
+        get_guild_permissions,
+        GuildError
+    },
+    database::DatabaseClient,
+    state::GuildsState
+};
+
+use super::permissions::{
+    can_manage_role,
+    Permissions
+};
+
+#[derive(Debug, Deserialize)]
+struct GuildPath {
+    guild_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChannelPath {
+    channel_id: i64,
+    // Set for the channel routes nested under a guild
+    guild_id: Option<i64>,
+}
+
+fn require_permissions(
+    permissions: Permissions,
+    required: Permissions
+) -> Result<(), GuildError> {
+    let missing = required - permissions;
+    if !missing.is_empty() {
+        return Err(AuthError::MissingPermissions(missing).into());
+    }
+    Ok(())
+}
+
+/// Guild wide permissions of the caller in the guild from the `guild_id` path parameter.
+/// Rejects users that aren't members of the guild.
+#[derive(Debug, Clone)]
+pub struct GuildPermissions {
+    pub guild: Guild,
+    pub member: GuildMember,
+    pub roles: Vec<Role>,
+    pub permissions: Permissions,
+}
+
+impl GuildPermissions {
+    pub fn require(
+        &self,
+        required: Permissions
+    ) -> Result<(), GuildError> {
+        require_permissions(self.permissions, required)
+    }
+
+    pub fn can_manage_role(
+        &self,
+        role_position: i32
+    ) -> bool {
+        can_manage_role(&self.guild, &self.member, &self.roles, role_position)
+    }
+}
+
+/// Permissions of the caller in the channel from the `channel_id` path parameter, with the channel overwrites applied.
+/// Rejects users that can't view the channel, and channels outside of the `guild_id` path parameter if there is one.
+/// Without a `guild_id` path parameter the channel can also be a DM channel.
+#[derive(Debug, Clone)]
+pub struct ChannelPermissions {
+    pub channel_id: i64,
+    pub user_id: i64,
+    pub accepts_messages: bool,
+    pub permissions: Permissions,
+}
+
+impl ChannelPermissions {
+    pub fn require(
+        &self,
+        required: Permissions
+    ) -> Result<(), GuildError> {
+        require_permissions(self.permissions, required)
+    }
+
+    pub fn require_text_channel(&self) -> Result<(), GuildError> {
+        if !self.accepts_messages {
+            return Err(GuildError::NotTextChannel(self.channel_id));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for GuildPermissions
+where
+    Arc<GuildsState>: FromRef<S>,
+    JWTKeys: FromRef<S>,
+    DatabaseClient: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = GuildError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection> {
+        let claims = AuthClaims::from_request_parts(parts, state).await?;
+        let Path(path) = parts.extract::<Path<GuildPath>>().await?;
+        let guilds_state = Arc::<GuildsState>::from_ref(state);
+
+        get_guild_permissions(
+            &guilds_state.db_client,
+            path.guild_id,
+            claims.user_id,
+            guilds_state.guilds_config.cache_ttl_s
+        ).await
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ChannelPermissions
+where
+    Arc<GuildsState>: FromRef<S>,
+    JWTKeys: FromRef<S>,
+    DatabaseClient: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = GuildError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection> {
+        let claims = AuthClaims::from_request_parts(parts, state).await?;
+        let Path(path) = parts.extract::<Path<ChannelPath>>().await?;
+        let guilds_state = Arc::<GuildsState>::from_ref(state);
+
+        get_channel_permissions(
+            &guilds_state.db_client,
+            path.guild_id,
+            path.channel_id,
+            claims.user_id,
+            guilds_state.guilds_config.cache_ttl_s
+        ).await
+    }
+}
@@ -1 +1,17 @@
-mod authorization;
\ No newline at end of file
+mod authorization;
+mod permissions;
+mod admin;
+
+mod tests;
+
+pub use admin::AdminClaims;
+pub use authorization::{
+    ChannelPermissions,
+    GuildPermissions
+};
+pub use permissions::{
+    compute_base_permissions,
+    compute_channel_permissions,
+    compute_dm_permissions,
+    Permissions
+};
@@ -0,0 +1,200 @@
+use bitflags::bitflags;
+use serde::{
+    de::Error as _,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer
+};
+use sqlx::{
+    error::BoxDynError,
+    postgres::{
+        PgTypeInfo,
+        PgValueRef
+    },
+    Decode,
+    Postgres,
+    Type
+};
+
+use crate::app_objects::{
+    DmChannel,
+    Guild,
+    GuildMember,
+    OverwriteKind,
+    PermissionOverwrite,
+    Role
+};
+
+bitflags! {
+    /// Stored as BIGINT and sent as a number, don't reuse the bits of removed flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Permissions: u64 {
+        const VIEW_CHANNEL = 1 << 0;
+        const SEND_MESSAGES = 1 << 1;
+        // Delete messages of other users
+        const MANAGE_MESSAGES = 1 << 2;
+        const MANAGE_CHANNELS = 1 << 3;
+        // Manage roles below the member's highest role and channel overwrites
+        const MANAGE_ROLES = 1 << 4;
+        const CREATE_INVITE = 1 << 5;
+        const KICK_MEMBERS = 1 << 6;
+        const BAN_MEMBERS = 1 << 7;
+        // Grants every permission and ignores channel overwrites
+        const ADMINISTRATOR = 1 << 8;
+    }
+}
+
+impl Permissions {
+    /// Given to the @everyone role of new guilds
+    pub const DEFAULT_EVERYONE: Permissions = Permissions::VIEW_CHANNEL
+        .union(Permissions::SEND_MESSAGES)
+        .union(Permissions::CREATE_INVITE);
+}
+
+impl Serialize for Permissions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.bits())
+    }
+}
+
+impl<'de> Deserialize<'de> for Permissions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bits = u64::deserialize(deserializer)?;
+        Permissions::from_bits(bits).ok_or_else(
+            || D::Error::custom(format!("unknown permission bits in {}", bits))
+        )
+    }
+}
+
+impl Type<Postgres> for Permissions {
+    fn type_info() -> PgTypeInfo {
+        <i64 as Type<Postgres>>::type_info()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Permissions {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let bits = <i64 as Decode<Postgres>>::decode(value)?;
+        // Bits of flags that no longer exist are dropped
+        Ok(Permissions::from_bits_truncate(bits as u64))
+    }
+}
+
+/// Guild wide permissions of the member: the @everyone role combined with every role the member has.
+/// The owner and administrators get every permission.
+pub fn compute_base_permissions(
+    guild: &Guild,
+    member: &GuildMember,
+    roles: &[Role]
+) -> Permissions {
+    if member.user_id == guild.owner_id {
+        return Permissions::all();
+    }
+
+    // Guilds created before roles existed don't have an @everyone role
+    let mut permissions = roles.iter()
+        .find(|role| role.guild_id == guild.id && role.is_everyone())
+        .map(|role| role.permissions)
+        .unwrap_or(Permissions::DEFAULT_EVERYONE);
+    for role in roles {
+        if role.guild_id == guild.id && member.role_ids.contains(&role.id) {
+            permissions |= role.permissions;
+        }
+    }
+
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+    permissions
+}
+
+/// Applies the overwrites of a channel to the base permissions, in order: the @everyone overwrite,
+/// the overwrites of the member's roles combined, then the overwrite of the member itself.
+/// A member that can't view the channel has no permissions in it.
+pub fn compute_channel_permissions(
+    base_permissions: Permissions,
+    guild_id: i64,
+    member: &GuildMember,
+    overwrites: &[PermissionOverwrite]
+) -> Permissions {
+    if base_permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::all();
+    }
+
+    let mut permissions = base_permissions;
+    let everyone_overwrite = overwrites.iter().find(
+        |overwrite| overwrite.kind == OverwriteKind::Role && overwrite.target_id == guild_id
+    );
+    if let Some(overwrite) = everyone_overwrite {
+        permissions = overwrite.apply(permissions);
+    }
+
+    // Role overwrites don't depend on each other's order, an allow on any role wins over a deny
+    let mut allow = Permissions::empty();
+    let mut deny = Permissions::empty();
+    for overwrite in overwrites {
+        if overwrite.kind == OverwriteKind::Role
+            && overwrite.target_id != guild_id
+            && member.role_ids.contains(&overwrite.target_id) {
+            allow |= overwrite.allow;
+            deny |= overwrite.deny;
+        }
+    }
+    permissions = (permissions - deny) | allow;
+
+    let member_overwrite = overwrites.iter().find(
+        |overwrite| overwrite.kind == OverwriteKind::Member && overwrite.target_id == member.user_id
+    );
+    if let Some(overwrite) = member_overwrite {
+        permissions = overwrite.apply(permissions);
+    }
+
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Permissions::empty();
+    }
+    permissions
+}
+
+/// Recipients can view and send messages in a DM, but a block between the two users
+/// of a direct DM takes away sending. Nobody can delete the messages of others.
+pub fn compute_dm_permissions(
+    channel: &DmChannel,
+    user_id: i64,
+    blocked: bool
+) -> Permissions {
+    if !channel.is_recipient(user_id) {
+        return Permissions::empty();
+    }
+    let permissions = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+    if blocked {
+        return permissions - Permissions::SEND_MESSAGES;
+    }
+    permissions
+}
+
+/// Position of the member's highest role, the @everyone role is always at 0
+pub fn highest_role_position(
+    member: &GuildMember,
+    roles: &[Role]
+) -> i32 {
+    roles.iter()
+        .filter(|role| member.role_ids.contains(&role.id))
+        .map(|role| role.position)
+        .max()
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Members can only edit, delete, assign and remove roles below their highest role, the owner can manage all of them
+pub fn can_manage_role(
+    guild: &Guild,
+    member: &GuildMember,
+    roles: &[Role],
+    role_position: i32
+) -> bool {
+    if member.user_id == guild.owner_id {
+        return true;
+    }
+    role_position < highest_role_position(member, roles)
+}
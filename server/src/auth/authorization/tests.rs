@@ -0,0 +1,357 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        app_objects::{
+            DmChannel,
+            Guild,
+            GuildMember,
+            OverwriteKind,
+            PermissionOverwrite,
+            Role
+        },
+        auth::authorization::permissions::{
+            can_manage_role,
+            compute_base_permissions,
+            compute_channel_permissions,
+            compute_dm_permissions,
+            highest_role_position,
+            Permissions
+        }
+    };
+
+    const GUILD_ID: i64 = 1;
+    const OWNER_ID: i64 = 10;
+    const USER_ID: i64 = 20;
+    const CHANNEL_ID: i64 = 100;
+    const MOD_ROLE_ID: i64 = 2;
+    const MUTED_ROLE_ID: i64 = 3;
+    const ADMIN_ROLE_ID: i64 = 4;
+
+    fn get_guild() -> Guild {
+        Guild::new(GUILD_ID, "guild".to_string(), OWNER_ID, 0)
+    }
+
+    fn get_member(user_id: i64, role_ids: &[i64]) -> GuildMember {
+        let mut member = GuildMember::new(GUILD_ID, user_id, 0);
+        member.role_ids = role_ids.to_vec();
+        member
+    }
+
+    /// @everyone with the default permissions, a moderator role, a muted role without permissions and an admin role
+    fn get_roles() -> Vec<Role> {
+        vec![
+            Role::everyone(GUILD_ID, 0),
+            Role::new(MUTED_ROLE_ID, GUILD_ID, "muted".to_string(), Permissions::empty(), 1, 0),
+            Role::new(
+                MOD_ROLE_ID,
+                GUILD_ID,
+                "mod".to_string(),
+                Permissions::MANAGE_MESSAGES | Permissions::KICK_MEMBERS | Permissions::MANAGE_ROLES,
+                5,
+                0
+            ),
+            Role::new(ADMIN_ROLE_ID, GUILD_ID, "admin".to_string(), Permissions::ADMINISTRATOR, 10, 0),
+        ]
+    }
+
+    fn get_overwrite(kind: OverwriteKind, target_id: i64, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite {
+            channel_id: CHANNEL_ID,
+            target_id,
+            kind,
+            allow,
+            deny,
+        }
+    }
+
+    fn channel_permissions(member: &GuildMember, overwrites: &[PermissionOverwrite]) -> Permissions {
+        let base_permissions = compute_base_permissions(&get_guild(), member, &get_roles());
+        compute_channel_permissions(base_permissions, GUILD_ID, member, overwrites)
+    }
+
+    #[test]
+    fn test_permissions_serialization() {
+        let permissions = Permissions::VIEW_CHANNEL | Permissions::BAN_MEMBERS;
+        let json = serde_json::to_string(&permissions).unwrap();
+        assert_eq!(json, permissions.bits().to_string());
+        assert_eq!(serde_json::from_str::<Permissions>(&json).unwrap(), permissions);
+
+        // Unknown bits are rejected instead of silently dropped
+        assert!(serde_json::from_str::<Permissions>(&(1u64 << 63).to_string()).is_err());
+        assert!(serde_json::from_str::<Permissions>("-1").is_err());
+    }
+
+    #[test]
+    fn test_default_everyone_permissions() {
+        assert_eq!(
+            Permissions::DEFAULT_EVERYONE,
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::CREATE_INVITE
+        );
+        assert!(!Permissions::DEFAULT_EVERYONE.contains(Permissions::MANAGE_MESSAGES));
+    }
+
+    #[test]
+    fn test_owner_has_all_permissions() {
+        let owner = get_member(OWNER_ID, &[]);
+        assert_eq!(compute_base_permissions(&get_guild(), &owner, &get_roles()), Permissions::all());
+
+        // Overwrites don't apply to the owner either
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, OWNER_ID, Permissions::empty(), Permissions::all()),
+        ];
+        assert_eq!(channel_permissions(&owner, &overwrites), Permissions::all());
+    }
+
+    #[test]
+    fn test_base_permissions_from_everyone_role() {
+        let member = get_member(USER_ID, &[]);
+        assert_eq!(
+            compute_base_permissions(&get_guild(), &member, &get_roles()),
+            Permissions::DEFAULT_EVERYONE
+        );
+
+        let mut roles = get_roles();
+        roles[0].permissions = Permissions::VIEW_CHANNEL;
+        assert_eq!(compute_base_permissions(&get_guild(), &member, &roles), Permissions::VIEW_CHANNEL);
+    }
+
+    #[test]
+    fn test_base_permissions_without_everyone_role() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        let roles: Vec<Role> = get_roles().into_iter().filter(|role| !role.is_everyone()).collect();
+        assert_eq!(
+            compute_base_permissions(&get_guild(), &member, &roles),
+            Permissions::DEFAULT_EVERYONE | roles[1].permissions
+        );
+    }
+
+    #[test]
+    fn test_base_permissions_combine_member_roles() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID, MUTED_ROLE_ID]);
+        assert_eq!(
+            compute_base_permissions(&get_guild(), &member, &get_roles()),
+            Permissions::DEFAULT_EVERYONE
+                | Permissions::MANAGE_MESSAGES
+                | Permissions::KICK_MEMBERS
+                | Permissions::MANAGE_ROLES
+        );
+    }
+
+    #[test]
+    fn test_base_permissions_ignore_unknown_roles() {
+        // A role the member had that was since deleted, and a role of another guild
+        let mut roles = get_roles();
+        roles.push(Role::new(50, 2, "other guild".to_string(), Permissions::ADMINISTRATOR, 1, 0));
+        let member = get_member(USER_ID, &[49, 50]);
+        assert_eq!(
+            compute_base_permissions(&get_guild(), &member, &roles),
+            Permissions::DEFAULT_EVERYONE
+        );
+    }
+
+    #[test]
+    fn test_administrator_has_all_permissions() {
+        let admin = get_member(USER_ID, &[ADMIN_ROLE_ID]);
+        assert_eq!(compute_base_permissions(&get_guild(), &admin, &get_roles()), Permissions::all());
+
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::empty(), Permissions::all()),
+            get_overwrite(OverwriteKind::Role, ADMIN_ROLE_ID, Permissions::empty(), Permissions::VIEW_CHANNEL),
+            get_overwrite(OverwriteKind::Member, USER_ID, Permissions::empty(), Permissions::all()),
+        ];
+        assert_eq!(channel_permissions(&admin, &overwrites), Permissions::all());
+    }
+
+    #[test]
+    fn test_everyone_role_with_administrator() {
+        let mut roles = get_roles();
+        roles[0].permissions |= Permissions::ADMINISTRATOR;
+        let member = get_member(USER_ID, &[]);
+        assert_eq!(compute_base_permissions(&get_guild(), &member, &roles), Permissions::all());
+    }
+
+    #[test]
+    fn test_no_overwrites_keep_base_permissions() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        let base_permissions = compute_base_permissions(&get_guild(), &member, &get_roles());
+        assert_eq!(channel_permissions(&member, &[]), base_permissions);
+    }
+
+    #[test]
+    fn test_everyone_overwrite() {
+        let member = get_member(USER_ID, &[]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::MANAGE_MESSAGES, Permissions::SEND_MESSAGES),
+        ];
+        assert_eq!(
+            channel_permissions(&member, &overwrites),
+            Permissions::VIEW_CHANNEL | Permissions::CREATE_INVITE | Permissions::MANAGE_MESSAGES
+        );
+    }
+
+    #[test]
+    fn test_role_overwrite_applies_after_everyone_overwrite() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        // Read only channel that moderators can still write in
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, MOD_ROLE_ID, Permissions::SEND_MESSAGES, Permissions::empty()),
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+
+        let member = get_member(USER_ID, &[]);
+        assert!(!channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_role_overwrites_of_other_roles_are_ignored() {
+        let member = get_member(USER_ID, &[MUTED_ROLE_ID]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, MOD_ROLE_ID, Permissions::empty(), Permissions::all()),
+        ];
+        assert_eq!(channel_permissions(&member, &overwrites), Permissions::DEFAULT_EVERYONE);
+    }
+
+    #[test]
+    fn test_role_overwrite_allow_wins_over_other_role_deny() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID, MUTED_ROLE_ID]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, MUTED_ROLE_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+            get_overwrite(OverwriteKind::Role, MOD_ROLE_ID, Permissions::SEND_MESSAGES, Permissions::empty()),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+
+        // The order of the overwrites doesn't matter
+        let reversed: Vec<PermissionOverwrite> = overwrites.into_iter().rev().collect();
+        assert!(channel_permissions(&member, &reversed).contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_member_overwrite_applies_last() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, USER_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+            get_overwrite(OverwriteKind::Role, MOD_ROLE_ID, Permissions::SEND_MESSAGES, Permissions::empty()),
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::SEND_MESSAGES, Permissions::empty()),
+        ];
+        assert!(!channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+            get_overwrite(OverwriteKind::Member, USER_ID, Permissions::SEND_MESSAGES, Permissions::empty()),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_member_overwrite_of_other_member_is_ignored() {
+        let member = get_member(USER_ID, &[]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, USER_ID + 1, Permissions::empty(), Permissions::all()),
+        ];
+        assert_eq!(channel_permissions(&member, &overwrites), Permissions::DEFAULT_EVERYONE);
+    }
+
+    #[test]
+    fn test_overwrite_kind_must_match_target() {
+        // A member overwrite whose target happens to be one of the member's role ids isn't a role overwrite
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, MOD_ROLE_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+            get_overwrite(OverwriteKind::Member, GUILD_ID, Permissions::empty(), Permissions::SEND_MESSAGES),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_hidden_channel_has_no_permissions() {
+        let member = get_member(USER_ID, &[MOD_ROLE_ID]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::empty(), Permissions::VIEW_CHANNEL),
+        ];
+        assert_eq!(channel_permissions(&member, &overwrites), Permissions::empty());
+
+        // Private channel visible to moderators only
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Role, GUILD_ID, Permissions::empty(), Permissions::VIEW_CHANNEL),
+            get_overwrite(OverwriteKind::Role, MOD_ROLE_ID, Permissions::VIEW_CHANNEL, Permissions::empty()),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES
+        ));
+        assert_eq!(channel_permissions(&get_member(USER_ID, &[]), &overwrites), Permissions::empty());
+    }
+
+    #[test]
+    fn test_channel_permissions_without_view_in_base() {
+        let mut roles = get_roles();
+        roles[0].permissions = Permissions::SEND_MESSAGES;
+        let member = get_member(USER_ID, &[]);
+        let base_permissions = compute_base_permissions(&get_guild(), &member, &roles);
+        assert_eq!(compute_channel_permissions(base_permissions, GUILD_ID, &member, &[]), Permissions::empty());
+
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, USER_ID, Permissions::VIEW_CHANNEL, Permissions::empty()),
+        ];
+        assert_eq!(
+            compute_channel_permissions(base_permissions, GUILD_ID, &member, &overwrites),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+    }
+
+    #[test]
+    fn test_overwrite_allow_wins_over_deny_of_the_same_overwrite() {
+        let member = get_member(USER_ID, &[]);
+        let overwrites = vec![
+            get_overwrite(OverwriteKind::Member, USER_ID, Permissions::SEND_MESSAGES, Permissions::SEND_MESSAGES),
+        ];
+        assert!(channel_permissions(&member, &overwrites).contains(Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_highest_role_position() {
+        let roles = get_roles();
+        assert_eq!(highest_role_position(&get_member(USER_ID, &[]), &roles), 0);
+        assert_eq!(highest_role_position(&get_member(USER_ID, &[MUTED_ROLE_ID]), &roles), 1);
+        assert_eq!(highest_role_position(&get_member(USER_ID, &[MUTED_ROLE_ID, MOD_ROLE_ID]), &roles), 5);
+        // Deleted roles don't count
+        assert_eq!(highest_role_position(&get_member(USER_ID, &[49]), &roles), 0);
+    }
+
+    #[test]
+    fn test_can_manage_role() {
+        let guild = get_guild();
+        let roles = get_roles();
+        let moderator = get_member(USER_ID, &[MOD_ROLE_ID]);
+        assert!(can_manage_role(&guild, &moderator, &roles, 0));
+        assert!(can_manage_role(&guild, &moderator, &roles, 4));
+        // Not their own role or anything above it
+        assert!(!can_manage_role(&guild, &moderator, &roles, 5));
+        assert!(!can_manage_role(&guild, &moderator, &roles, 10));
+
+        // Without roles not even @everyone can be managed
+        assert!(!can_manage_role(&guild, &get_member(USER_ID, &[]), &roles, 0));
+
+        let owner = get_member(OWNER_ID, &[]);
+        assert!(can_manage_role(&guild, &owner, &roles, 10));
+        assert!(can_manage_role(&guild, &owner, &roles, i32::MAX));
+    }
+
+    #[test]
+    fn test_dm_permissions() {
+        let direct = DmChannel::new_direct(CHANNEL_ID, OWNER_ID, USER_ID, 0);
+        let group = DmChannel::new_group(CHANNEL_ID, OWNER_ID, &[USER_ID], 0);
+        for channel in [&direct, &group] {
+            assert_eq!(
+                compute_dm_permissions(channel, USER_ID, false),
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+            );
+            assert_eq!(compute_dm_permissions(channel, 30, false), Permissions::empty());
+        }
+        // Blocked users can still read the history
+        assert_eq!(compute_dm_permissions(&direct, USER_ID, true), Permissions::VIEW_CHANNEL);
+        assert_eq!(compute_dm_permissions(&direct, 30, true), Permissions::empty());
+    }
+}
@@ -1,7 +1,10 @@
 use axum_extra::TypedHeader;
 use axum::{
     async_trait,
-    extract::FromRequestParts,
+    extract::{
+        FromRef,
+        FromRequestParts
+    },
     http::request::Parts,
     RequestPartsExt,
 };
@@ -9,8 +12,13 @@ use axum::{
 
 use anyhow::Result;
 
+use crate::database::DatabaseClient;
+
 use super::{
-    super::AuthError,
+    super::{
+        check_denylist,
+        AuthError
+    },
     extractors::extract_token_from_cookie,
     ClaimType,
     AuthClaims,
@@ -19,14 +27,20 @@ use super::{
 
 
 #[async_trait]
-impl FromRequestParts<JWTKeys> for AuthClaims
+impl<S> FromRequestParts<S> for AuthClaims
+where
+    JWTKeys: FromRef<S>,
+    DatabaseClient: FromRef<S>,
+    S: Send + Sync,
 {
     type Rejection = AuthError;
 
     async fn from_request_parts(
         parts: &mut Parts,
-        jwt_keys: &JWTKeys
+        state: &S
     ) -> Result<Self, Self::Rejection> {
+        let jwt_keys = JWTKeys::from_ref(state);
+
         let cookies = parts
             .extract::<TypedHeader<headers::Cookie>>().await;
 
@@ -48,6 +62,10 @@ impl FromRequestParts<JWTKeys> for AuthClaims
             return Err(AuthError::InvalidToken);
         }
 
+        // Access tokens outlive a ban by up to their lifetime without this
+        let db_client = DatabaseClient::from_ref(state);
+        check_denylist(&db_client, claims.user_id).await?;
+
         // if claims.exp < chrono::Utc::now().timestamp() {
         //     return Err(AuthError::ExpiredToken);
         // }
@@ -2,19 +2,29 @@ use serde::{
     Deserialize,
     Serialize
 };
+use uuid::Uuid;
 
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
 pub enum ClaimType {
     Access,
     Refresh,
+    // Proves the password step of a login with a second factor, only /authenticate/mfa accepts it
+    MfaPending,
+    // Emailed in a password reset link, only /password_reset/confirm accepts it
+    PasswordReset,
+    // Emailed to the new address of an email change, only /email_change/confirm accepts it
+    EmailChange,
 }
 
 impl ClaimType {
     pub fn as_str(&self) -> &'static str {
         match self {
             ClaimType::Access => "authorization_token",
-            ClaimType::Refresh => "refresh_token"
+            ClaimType::Refresh => "refresh_token",
+            ClaimType::MfaPending => "mfa_token",
+            ClaimType::PasswordReset => "password_reset_token",
+            ClaimType::EmailChange => "email_change_token"
         }
     }
 }
@@ -29,6 +39,15 @@ pub struct AuthClaims {
     pub claim_type: ClaimType,
     // User id
     pub user_id: i64,
+    // Token id, access tokens don't have one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<Uuid>,
+    // Session the token was issued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    // Only set on access tokens of admins, taken from the user on every refresh
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub admin: bool,
 }
 
 impl AuthClaims {
@@ -39,7 +58,8 @@ impl AuthClaims {
 
     pub fn new_access(
         lifetime: i64,
-        user_id: i64
+        user_id: i64,
+        session_id: Option<Uuid>
     ) -> Self {
         let now = chrono::Utc::now().timestamp();
         Self {
@@ -47,12 +67,22 @@ impl AuthClaims {
             exp: (now + lifetime),
             claim_type: ClaimType::Access,
             user_id,
+            jti: None,
+            sid: session_id,
+            admin: false,
         }
     }
 
+    pub fn with_admin(mut self, admin: bool) -> Self {
+        self.admin = admin;
+        self
+    }
+
     pub fn new_refresh(
         lifetime: i64,
-        user_id: i64
+        user_id: i64,
+        jti: Uuid,
+        session_id: Uuid
     ) -> Self {
         let now = chrono::Utc::now().timestamp();
         Self {
@@ -60,6 +90,60 @@ impl AuthClaims {
             exp: (now + lifetime),
             claim_type: ClaimType::Refresh,
             user_id,
+            jti: Some(jti),
+            sid: Some(session_id),
+            admin: false,
         }
     }
-}
\ No newline at end of file
+
+    pub fn new_mfa_pending(
+        lifetime: i64,
+        user_id: i64,
+        jti: Uuid
+    ) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            iat: now,
+            exp: (now + lifetime),
+            claim_type: ClaimType::MfaPending,
+            user_id,
+            jti: Some(jti),
+            sid: None,
+            admin: false,
+        }
+    }
+
+    pub fn new_password_reset(
+        lifetime: i64,
+        user_id: i64,
+        jti: Uuid
+    ) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            iat: now,
+            exp: (now + lifetime),
+            claim_type: ClaimType::PasswordReset,
+            user_id,
+            jti: Some(jti),
+            sid: None,
+            admin: false,
+        }
+    }
+
+    pub fn new_email_change(
+        lifetime: i64,
+        user_id: i64,
+        jti: Uuid
+    ) -> Self {
+        let now = chrono::Utc::now().timestamp();
+        Self {
+            iat: now,
+            exp: (now + lifetime),
+            claim_type: ClaimType::EmailChange,
+            user_id,
+            jti: Some(jti),
+            sid: None,
+            admin: false,
+        }
+    }
+}
@@ -1,50 +1,210 @@
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    sync::Arc
+};
 
-use jsonwebtoken::{DecodingKey, EncodingKey};
-use anyhow::Result;
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::{
+    jwk::{
+        AlgorithmParameters,
+        CommonParameters,
+        EllipticCurve,
+        Jwk,
+        JwkSet,
+        KeyAlgorithm,
+        OctetKeyPairParameters,
+        OctetKeyPairType,
+        PublicKeyUse,
+        RSAKeyParameters,
+        RSAKeyType
+    },
+    Algorithm,
+    DecodingKey,
+    EncodingKey,
+    Header
+};
+use anyhow::{
+    anyhow,
+    Result
+};
+use rsa::{
+    pkcs8::DecodePublicKey,
+    traits::PublicKeyParts,
+    RsaPublicKey
+};
+use serde::Serialize;
 
+use crate::configuration::{
+    Config,
+    JWTAlgorithm,
+    JWTSigningKeyConfig,
+    JWTVerificationKeyConfig
+};
 
-use crate::configuration::Config;
+// DER encoding of an Ed25519 SubjectPublicKeyInfo up to the 32 bytes of the key
+const ED25519_SPKI_PREFIX: [u8; 12] = [0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00];
 
+/// Public key that verifies tokens carrying its `kid`
 #[derive(Clone)]
-pub struct JWTKeys {
-    pub encoding: EncodingKey,
+pub struct VerificationKey {
+    pub algorithm: Algorithm,
     pub decoding: DecodingKey,
 }
 
+/// Keyring of the server. New tokens are signed with the current key, tokens signed with
+/// keys rotated out of the config are accepted until they expire.
+#[derive(Clone)]
+pub struct JWTKeys {
+    header: Header,
+    encoding: EncodingKey,
+    verification_keys: Arc<Vec<(String, VerificationKey)>>,
+    jwks: Arc<JwkSet>,
+}
+
 impl Debug for JWTKeys {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("JWTKeys")
+            .field("kid", &self.header.kid)
+            .field("algorithm", &self.header.alg)
             .field("encoding", &"EncodingKey")
-            .field("decoding", &"DecodingKey")
+            .field("verification_keys", &self.verification_keys.len())
             .finish()
     }
 }
 
+impl JWTAlgorithm {
+    pub fn to_algorithm(self) -> Algorithm {
+        match self {
+            JWTAlgorithm::EdDSA => Algorithm::EdDSA,
+            JWTAlgorithm::RS256 => Algorithm::RS256,
+        }
+    }
+}
+
+impl JWTSigningKeyConfig {
+    pub fn public_key(&self) -> JWTVerificationKeyConfig {
+        JWTVerificationKeyConfig {
+            kid: self.kid.clone(),
+            algorithm: self.algorithm,
+            public_key_path: self.public_key_path.clone(),
+        }
+    }
+}
+
 impl JWTKeys {
     pub fn new(config: &Config) -> Result<Self> {
-        let secret_path = &config.jwt_config.jwt_secret_path;
-        let secret = std::fs::read_to_string(secret_path)?;
+        let jwt_config = &config.jwt_config;
+        let signing_key = &jwt_config.signing_key;
+
+        let private_key = std::fs::read(&signing_key.private_key_path)?;
+        let encoding = match signing_key.algorithm {
+            JWTAlgorithm::EdDSA => EncodingKey::from_ed_pem(&private_key)?,
+            JWTAlgorithm::RS256 => EncodingKey::from_rsa_pem(&private_key)?,
+        };
+        let mut header = Header::new(signing_key.algorithm.to_algorithm());
+        header.kid = Some(signing_key.kid.clone());
 
-        Ok(
-            Self {
-                encoding: EncodingKey::from_secret(secret.as_bytes()),
-                decoding: DecodingKey::from_secret(secret.as_bytes()),
+        let mut jwks = JwkSet { keys: Vec::new() };
+        let mut verification_keys = Vec::new();
+        let public_keys = std::iter::once(signing_key.public_key())
+            .chain(jwt_config.previous_keys.iter().cloned());
+        for public_key in public_keys {
+            if verification_keys.iter().any(|(known_kid, _)| *known_kid == public_key.kid) {
+                return Err(anyhow!("JWT key id {} is used more than once", public_key.kid));
             }
-        )
+            let jwk = read_public_key(&public_key)?;
+            let verification_key = VerificationKey {
+                algorithm: public_key.algorithm.to_algorithm(),
+                decoding: DecodingKey::from_jwk(&jwk)?,
+            };
+            verification_keys.push((public_key.kid, verification_key));
+            jwks.keys.push(jwk);
+        }
+
+        let keys = Self {
+            header,
+            encoding,
+            verification_keys: Arc::new(verification_keys),
+            jwks: Arc::new(jwks),
+        };
+        keys.check_signing_key_pair()?;
+        Ok(keys)
+    }
+
+    /// Signs the claims with the current key, the header names the key in `kid`
+    pub fn encode<T: Serialize>(&self, claims: &T) -> Result<String, jsonwebtoken::errors::Error> {
+        jsonwebtoken::encode(&self.header, claims, &self.encoding)
     }
 
-    pub fn verify_jwt_token<T>(&self, token: &str) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
-        let token_data = jsonwebtoken::decode::<T>(
-            &token,
-            &self.decoding,
-            &jsonwebtoken::Validation::default(),
+    pub fn verification_key(&self, kid: &str) -> Option<&VerificationKey> {
+        self.verification_keys
+            .iter()
+            .find(|(known_kid, _)| known_kid == kid)
+            .map(|(_, key)| key)
+    }
+
+    /// Public keys of the keyring, served so other services can verify our tokens
+    pub fn jwks(&self) -> &JwkSet {
+        &self.jwks
+    }
+
+    /// A private key that doesn't belong to the configured public key would only show up once the tokens are rejected
+    fn check_signing_key_pair(&self) -> Result<()> {
+        #[derive(Serialize, serde::Deserialize)]
+        struct KeyCheck {
+            exp: i64,
+        }
+        let token = self.encode(&KeyCheck { exp: chrono::Utc::now().timestamp() + 60 })?;
+        self.decode::<KeyCheck>(&token).map_err(
+            |e| anyhow!("JWT private key doesn't match its public key: {}", e)
         )?;
+        Ok(())
+    }
+}
 
-        Ok(token_data.claims)
+/// Reads a PEM encoded SubjectPublicKeyInfo into its JWK
+fn read_public_key(
+    key: &JWTVerificationKeyConfig
+) -> Result<Jwk> {
+    let public_key_path = &key.public_key_path;
+    let public_key = pem::parse(std::fs::read(public_key_path)?)?;
+    if public_key.tag() != "PUBLIC KEY" {
+        return Err(anyhow!("{} is not a PEM encoded public key", public_key_path));
     }
+    let der = public_key.contents();
+
+    let (key_algorithm, algorithm_parameters) = match key.algorithm {
+        JWTAlgorithm::EdDSA => {
+            if der.len() != ED25519_SPKI_PREFIX.len() + 32 || !der.starts_with(&ED25519_SPKI_PREFIX) {
+                return Err(anyhow!("{} is not an Ed25519 public key", public_key_path));
+            }
+            let parameters = OctetKeyPairParameters {
+                key_type: OctetKeyPairType::OctetKeyPair,
+                curve: EllipticCurve::Ed25519,
+                x: BASE64URL_NOPAD.encode(&der[ED25519_SPKI_PREFIX.len()..]),
+            };
+            (KeyAlgorithm::EdDSA, AlgorithmParameters::OctetKeyPair(parameters))
+        },
+        JWTAlgorithm::RS256 => {
+            let rsa_key = RsaPublicKey::from_public_key_der(der).map_err(
+                |e| anyhow!("{} is not an RSA public key: {}", public_key_path, e)
+            )?;
+            let parameters = RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: BASE64URL_NOPAD.encode(&rsa_key.n().to_bytes_be()),
+                e: BASE64URL_NOPAD.encode(&rsa_key.e().to_bytes_be()),
+            };
+            (KeyAlgorithm::RS256, AlgorithmParameters::RSA(parameters))
+        },
+    };
 
+    Ok(Jwk {
+        common: CommonParameters {
+            public_key_use: Some(PublicKeyUse::Signature),
+            key_algorithm: Some(key_algorithm),
+            key_id: Some(key.kid.clone()),
+            ..CommonParameters::default()
+        },
+        algorithm: algorithm_parameters,
+    })
 }
@@ -6,6 +6,8 @@ mod verification;
 
 mod authorization_extractor;
 
+mod tests;
+
 pub use extractors::extract_token_from_cookie;
 
 pub use claims::{
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{
+        encode,
+        jwk::AlgorithmParameters,
+        EncodingKey,
+        Header
+    };
+    use pretty_assertions::assert_eq;
+    use rsa::{
+        pkcs8::{
+            EncodePrivateKey,
+            EncodePublicKey,
+            LineEnding
+        },
+        RsaPrivateKey
+    };
+    use uuid::Uuid;
+    use crate::auth::{
+        AuthClaims,
+        JWTKeys,
+        VerificationError
+    };
+    use crate::configuration::{
+        JWTAlgorithm,
+        JWTSigningKeyConfig,
+        JWTVerificationKeyConfig
+    };
+    use crate::routes::tests::preparation::get_config;
+
+    /// Writes a new RS256 key pair to the temp dir, returns the paths of the private and the public key
+    fn generate_rsa_key() -> (String, String) {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let file_name = Uuid::new_v4();
+        let private_key_path = std::env::temp_dir().join(format!("{}.pem", file_name));
+        let public_key_path = std::env::temp_dir().join(format!("{}.pub.pem", file_name));
+        private_key.write_pkcs8_pem_file(&private_key_path, LineEnding::LF).unwrap();
+        private_key.to_public_key().write_public_key_pem_file(&public_key_path, LineEnding::LF).unwrap();
+        (
+            private_key_path.to_str().unwrap().to_string(),
+            public_key_path.to_str().unwrap().to_string()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_tokens_carry_kid() {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+
+        let claims = AuthClaims::new_access(60, 1, None);
+        let token = jwt_keys.encode(&claims).unwrap();
+        let header = jsonwebtoken::decode_header(&token).unwrap();
+        assert_eq!(header.kid, Some(config.jwt_config.signing_key.kid.clone()));
+        assert_eq!(header.alg, jsonwebtoken::Algorithm::EdDSA);
+        let decoded: AuthClaims = jwt_keys.verify_token_and_return_claims(&token).await.unwrap();
+        assert_eq!(decoded.user_id, 1);
+
+        let jwks = jwt_keys.jwks();
+        assert_eq!(jwks.keys.len(), 1);
+        let jwk = jwks.find(&config.jwt_config.signing_key.kid).unwrap();
+        assert!(matches!(jwk.algorithm, AlgorithmParameters::OctetKeyPair(_)));
+        // Other services verify our tokens with nothing but the published key
+        let decoding_key = jsonwebtoken::DecodingKey::from_jwk(jwk).unwrap();
+        let validation = jsonwebtoken::Validation::new(jsonwebtoken::Algorithm::EdDSA);
+        assert!(jsonwebtoken::decode::<AuthClaims>(&token, &decoding_key, &validation).is_ok());
+
+        // Shared secret tokens from before the keyring and tokens of unknown keys are rejected
+        let secret_token = encode(&Header::default(), &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+        assert!(matches!(
+            jwt_keys.verify_token_and_return_claims::<AuthClaims>(&secret_token).await,
+            Err(VerificationError::InvalidToken)
+        ));
+        let mut header = Header::new(jsonwebtoken::Algorithm::HS256);
+        header.kid = Some(config.jwt_config.signing_key.kid.clone());
+        let forged_token = encode(&header, &claims, &EncodingKey::from_secret(b"secret")).unwrap();
+        assert!(matches!(
+            jwt_keys.verify_token_and_return_claims::<AuthClaims>(&forged_token).await,
+            Err(VerificationError::InvalidToken)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_key_rotation() {
+        let config = get_config();
+        let (private_key_path, public_key_path) = generate_rsa_key();
+
+        let mut old_config = get_config();
+        old_config.jwt_config.signing_key = JWTSigningKeyConfig {
+            kid: "old-rsa-key".to_string(),
+            algorithm: JWTAlgorithm::RS256,
+            private_key_path: private_key_path.clone(),
+            public_key_path: public_key_path.clone(),
+        };
+        let old_keys = JWTKeys::new(&old_config).unwrap();
+        let old_token = old_keys.encode(&AuthClaims::new_access(60, 2, None)).unwrap();
+        assert!(matches!(
+            old_keys.jwks().find("old-rsa-key").unwrap().algorithm,
+            AlgorithmParameters::RSA(_)
+        ));
+
+        // The rotated out key still verifies its tokens
+        let mut rotated_config = get_config();
+        rotated_config.jwt_config.previous_keys.push(JWTVerificationKeyConfig {
+            kid: "old-rsa-key".to_string(),
+            algorithm: JWTAlgorithm::RS256,
+            public_key_path: public_key_path.clone(),
+        });
+        let rotated_keys = JWTKeys::new(&rotated_config).unwrap();
+        let claims: AuthClaims = rotated_keys.verify_token_and_return_claims(&old_token).await.unwrap();
+        assert_eq!(claims.user_id, 2);
+        let kids: Vec<_> = rotated_keys.jwks().keys.iter().map(|jwk| jwk.common.key_id.clone().unwrap()).collect();
+        assert_eq!(kids, vec![config.jwt_config.signing_key.kid.clone(), "old-rsa-key".to_string()]);
+
+        // Once it is dropped its tokens are rejected
+        let keys = JWTKeys::new(&config).unwrap();
+        assert!(matches!(
+            keys.verify_token_and_return_claims::<AuthClaims>(&old_token).await,
+            Err(VerificationError::InvalidToken)
+        ));
+
+        // Key pairs that don't match and keys of the wrong algorithm are configuration errors
+        let mut mismatched_config = get_config();
+        mismatched_config.jwt_config.signing_key.public_key_path = public_key_path.clone();
+        assert!(JWTKeys::new(&mismatched_config).is_err());
+        let mut mismatched_config = get_config();
+        mismatched_config.jwt_config.signing_key.algorithm = JWTAlgorithm::RS256;
+        assert!(JWTKeys::new(&mismatched_config).is_err());
+        let mut duplicated_config = get_config();
+        duplicated_config.jwt_config.previous_keys.push(JWTVerificationKeyConfig {
+            kid: config.jwt_config.signing_key.kid.clone(),
+            algorithm: JWTAlgorithm::RS256,
+            public_key_path: public_key_path.clone(),
+        });
+        assert!(JWTKeys::new(&duplicated_config).is_err());
+
+        let _ = std::fs::remove_file(private_key_path);
+        let _ = std::fs::remove_file(public_key_path);
+    }
+}
@@ -2,7 +2,7 @@ use axum::{response::{IntoResponse, Response}, Json};
 use axum::http::StatusCode;
 use jsonwebtoken::{
   decode,
-  Algorithm,
+  decode_header,
   Validation
 };
 use serde_json::json;
@@ -91,13 +91,31 @@ impl JWTKeys {
     ) -> Result<T, VerificationError>
     where T: serde::de::DeserializeOwned,
     {
+        self.decode(token)
+    }
+
+    /// Picks the key named by the `kid` header, tokens without one or signed with an unknown key are invalid
+    pub fn decode<T>(
+        &self,
+        token: &str,
+    ) -> Result<T, VerificationError>
+    where T: serde::de::DeserializeOwned,
+    {
+        let header = decode_header(token)?;
+        let key = header.kid
+            .as_deref()
+            .and_then(|kid| self.verification_key(kid))
+            .ok_or(VerificationError::InvalidToken)?;
+        if header.alg != key.algorithm {
+            return Err(VerificationError::InvalidToken);
+        }
+
         let token_data = decode::<T>(
             token,
-            &self.decoding,
-            &Validation::new(Algorithm::HS256),
+            &key.decoding,
+            &Validation::new(key.algorithm),
         )?;
         Ok(token_data.claims)
     }
 
 }
-
@@ -1,5 +1,6 @@
 mod authorization;
 mod authentication;
+mod account_status;
 
 mod jwt;
 
@@ -8,6 +9,23 @@ pub use authentication::{
     JWTKeys,
     AuthenticationPayload,
     AuthenticationBody,
+    MfaAuthenticationPayload,
+    MfaChallengeBody,
+};
+
+pub use authorization::{
+    AdminClaims,
+    compute_base_permissions,
+    compute_channel_permissions,
+    compute_dm_permissions,
+    ChannelPermissions,
+    GuildPermissions,
+    Permissions
+};
+
+pub use account_status::{
+    check_account_status,
+    check_denylist
 };
 
 pub use jwt::{
@@ -18,7 +36,10 @@ pub use jwt::{
 
 use serde_json::json;
 use axum::{
-    http::StatusCode,
+    http::{
+        header::RETRY_AFTER,
+        StatusCode
+    },
     response::{
         IntoResponse,
         Response
@@ -34,6 +55,18 @@ pub enum AuthError {
     InvalidToken,
     ExpiredToken,
     NoToken,
+    MissingPermissions(Permissions),
+    InvalidMfaCode,
+    // Seconds until the lockout of the email or the IP ends
+    TooManyAttempts(i64),
+    TurnstileRequired,
+    // reason is None for users flagged as banned without a ban record
+    Banned {
+        reason: Option<String>,
+        expires_at: Option<i64>,
+    },
+    UnverifiedEmail,
+    NotAdmin,
     InternalError(&'static str),
 }
 
@@ -46,6 +79,30 @@ impl IntoResponse for AuthError {
             AuthError::InvalidToken => (StatusCode::BAD_REQUEST, "Invalid token"),
             AuthError::ExpiredToken => (StatusCode::UNAUTHORIZED, "Expired token"),
             AuthError::NoToken => (StatusCode::BAD_REQUEST, "No token"),
+            AuthError::MissingPermissions(_) => (StatusCode::FORBIDDEN, "1301"),
+            AuthError::InvalidMfaCode => (StatusCode::UNAUTHORIZED, "1302"),
+            AuthError::TooManyAttempts(retry_after_s) => {
+                let body = Json(json!({
+                    "error": "1303",
+                    "retry_after": retry_after_s,
+                }));
+                return (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(RETRY_AFTER, retry_after_s.to_string())],
+                    body
+                ).into_response();
+            },
+            AuthError::TurnstileRequired => (StatusCode::FORBIDDEN, "1304"),
+            AuthError::Banned { reason, expires_at } => {
+                let body = Json(json!({
+                    "error": "1305",
+                    "reason": reason,
+                    "expires_at": expires_at,
+                }));
+                return (StatusCode::FORBIDDEN, body).into_response();
+            },
+            AuthError::UnverifiedEmail => (StatusCode::FORBIDDEN, "1306"),
+            AuthError::NotAdmin => (StatusCode::FORBIDDEN, "1307"),
             AuthError::InternalError(error_message) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, error_message)
             },
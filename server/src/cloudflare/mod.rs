@@ -11,6 +11,7 @@ pub use request_origin_verification::middleware::{
 pub use request_origin_verification::refresh::cloudflare_ip_refresh_cron_job;
 
 pub use turnstile_verification::{
+    TurnstileError,
     TurnstileResult,
     TurnstileState,
     TurnstileRequest,
@@ -21,6 +21,7 @@ use axum::{
 };
 
 use tracing::error;
+use crate::auth::AuthError;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -41,6 +42,21 @@ pub enum TurnstileError {
     SuccessFieldNotFound,
 }
 
+impl TurnstileError {
+    /// Used where Turnstile guards a login, a bad token asks the client to solve the challenge again
+    pub fn to_auth_error(&self) -> AuthError {
+        match self {
+            TurnstileError::InvalidBody => AuthError::TurnstileRequired,
+            TurnstileError::ReqwestError(_) => AuthError::InternalError("1500"),
+            TurnstileError::RequestFailed(_) => AuthError::InternalError("1501"),
+            TurnstileError::DeserializationFailed(_) => AuthError::InternalError("1502"),
+            TurnstileError::InvalidInputSecret => AuthError::InternalError("1503"),
+            TurnstileError::InvalidInputResponse => AuthError::TurnstileRequired,
+            TurnstileError::SuccessFieldNotFound => AuthError::InternalError("1505"),
+        }
+    }
+}
+
 impl IntoResponse for TurnstileError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
@@ -18,6 +18,26 @@ pub struct Config {
     pub cloudflare: Cloudflare,
     pub smtp: SMTPConfig,
     pub verification_email: VerificationEmail,
+    pub password_reset_email: PasswordResetEmail,
+    pub email_change_email: EmailChangeEmail,
+    pub account_locked_email: AccountLockedEmail,
+    pub new_login_email: NewLoginEmail,
+    pub email_templates: EmailTemplatesConfig,
+    pub email_queue: EmailQueueConfig,
+    pub snowflake: SnowflakeConfig,
+    pub messages: MessagesConfig,
+    pub gateway: GatewayConfig,
+    pub event_bus: EventBusConfig,
+    pub guilds: GuildsConfig,
+    pub dms: DmsConfig,
+    pub sessions: SessionsConfig,
+    pub mfa: MfaConfig,
+    pub passkeys: PasskeysConfig,
+    pub login_protection: LoginProtectionConfig,
+    pub admin: AdminConfig,
+    pub audit: AuditConfig,
+    pub rate_limit: RateLimitConfig,
+    pub metrics: MetricsConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -32,19 +52,124 @@ pub struct ServerConfig {
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SMTPConfig {
-    pub smtp_username: String,
-    pub smtp_password_path: String,
+    #[serde(default)]
+    pub transport: EmailTransportBackend,
+    // The server logs in only when both are set, local test servers like Mailpit take emails without
+    pub smtp_username: Option<String>,
+    pub smtp_password_path: Option<String>,
     pub smtp_host: String,
+    // Defaults to the port of `smtp_tls`, 465 for tls, 587 for starttls and 25 for none
+    pub smtp_port: Option<u16>,
+    #[serde(default)]
+    pub smtp_tls: SmtpTls,
+    // Where the file_spool transport writes the emails
+    pub spool_dir: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailTransportBackend {
+    // Emails are sent through the SMTP relay
+    #[default]
+    Smtp,
+    // Emails are written to `spool_dir` as .eml files instead of being sent
+    FileSpool,
+    // Emails are kept in memory and logged, nothing is sent
+    Memory,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    // The connection is encrypted from the start
+    #[default]
+    Tls,
+    // The connection is upgraded with STARTTLS and fails if the server doesn't offer it
+    Starttls,
+    // Plaintext, only for test servers on localhost
+    None,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct VerificationEmail {
     pub email_sender_name: String,
     pub email_sender_email_address: String,
-    pub email_subject: String,
     pub verification_url_domain: String,
     pub verification_url_endpoint: String,
     pub email_verification_jwt_lifetime_s: i64,
+    // How long a registration can be verified, new links can be requested until then
+    pub pending_registration_lifetime_s: i64,
+    // Between two verification emails to the same address
+    pub resend_cooldown_s: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PasswordResetEmail {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+    pub reset_url_domain: String,
+    pub reset_url_endpoint: String,
+    // How long the emailed link can be used, it's also gone once it was used
+    pub password_reset_jwt_lifetime_s: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmailChangeEmail {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+    pub confirmation_url_domain: String,
+    pub confirmation_url_endpoint: String,
+    pub email_change_jwt_lifetime_s: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AccountLockedEmail {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct NewLoginEmail {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+}
+
+// The subjects and bodies of all the emails
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EmailTemplatesConfig {
+    pub dir: String,
+    // Templates are read again for every email, so edits show up without a restart. For development
+    #[serde(default)]
+    pub hot_reload: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EmailQueueConfig {
+    // Workers sending emails on this server instance
+    pub workers: usize,
+    // Jobs a worker claims at once
+    pub batch_size: i64,
+    // How often idle workers look for due jobs, jobs enqueued on the same instance wake them right away
+    pub poll_interval_ms: u64,
+    // A claimed job that wasn't finished by then is handed to another worker
+    pub lease_s: i64,
+    // Failed attempts after which the job is dead
+    pub max_attempts: i32,
+    // Wait before the first retry, doubled for every further one
+    pub base_backoff_s: i64,
+    pub max_backoff_s: i64,
+    // Sent and dead jobs are deleted this long after they finished, enqueueing the same email again works after that
+    pub finished_retention_s: i64,
+    // How often every instance deletes the finished jobs past their retention
+    pub cleanup_interval_s: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    // Prometheus scrapes /metrics on this address
+    pub host: String,
+    pub port: u16,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -58,9 +183,220 @@ pub struct Cloudflare {
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct JWTConfig {
-    pub jwt_secret_path: String,
     pub refresh_key_lifetime_s: i64,
     pub access_key_lifetime_s: i64,
+    // Signs new tokens
+    pub signing_key: JWTSigningKeyConfig,
+    // Keys rotated out, tokens signed with them are accepted until they expire
+    #[serde(default)]
+    pub previous_keys: Vec<JWTVerificationKeyConfig>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum JWTAlgorithm {
+    // Ed25519
+    EdDSA,
+    RS256,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JWTSigningKeyConfig {
+    // Sent in the `kid` header of issued tokens
+    pub kid: String,
+    pub algorithm: JWTAlgorithm,
+    // PEM encoded PKCS#8 private key and its SubjectPublicKeyInfo
+    pub private_key_path: String,
+    pub public_key_path: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JWTVerificationKeyConfig {
+    pub kid: String,
+    pub algorithm: JWTAlgorithm,
+    pub public_key_path: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SnowflakeConfig {
+    // Custom epoch in unix milliseconds, ids are generated relative to it
+    pub epoch_ms: i64,
+    // Must be unique for every running server instance, max 1023
+    pub worker_id: u16,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MessagesConfig {
+    pub max_content_length: usize,
+    pub default_page_size: i64,
+    pub max_page_size: i64,
+    pub cache_ttl_s: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GuildsConfig {
+    // Applies to guild and channel names
+    pub max_name_length: usize,
+    pub max_channels_per_guild: usize,
+    pub invite_code_length: usize,
+    // Used when an invite is created without max_age_s
+    pub default_invite_max_age_s: i64,
+    pub cache_ttl_s: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct DmsConfig {
+    // Recipients of a group DM, the owner included
+    pub max_group_size: usize,
+    pub cache_ttl_s: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SessionsConfig {
+    // Longer labels picked by the client or taken from the user agent are cut off
+    pub max_device_label_length: usize,
+    pub cache_ttl_s: u64,
+    // Tells the user about logins from a device or IP none of their sessions has
+    pub notify_on_new_login: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MfaConfig {
+    // Shown next to the account in authenticator apps
+    pub issuer: String,
+    pub digits: u32,
+    pub period_s: i64,
+    // Codes of this many time steps before and after the current one are accepted
+    pub allowed_skew_steps: i64,
+    pub recovery_code_count: usize,
+    // Time between the password check and the second factor of a login
+    pub pending_token_lifetime_s: i64,
+    // Codes that can be tried with one mfa_token, after that the password has to be entered again
+    pub max_attempts: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PasskeysConfig {
+    // Domain the passkeys are bound to, the origins have to be on it or one of its subdomains
+    pub rp_id: String,
+    pub rp_name: String,
+    // Pages that may run the WebAuthn ceremonies, e.g. "https://example.com"
+    pub allowed_origins: Vec<String>,
+    pub challenge_ttl_s: u64,
+    // Passed on to the browser, should be shorter than the challenge TTL
+    pub timeout_ms: u64,
+    pub max_label_length: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LoginProtectionConfig {
+    // Failed logins are counted over a sliding window of this length
+    pub window_s: i64,
+    pub max_failures_per_email: u64,
+    pub max_failures_per_ip: u64,
+    // Applies instead of `max_failures_per_ip` while the client IP isn't trusted and the peer address is counted,
+    // reaching it locks out every client behind the same proxy
+    pub max_failures_per_peer_ip: u64,
+    // Logins need a Turnstile token once the email or the IP has this many failures
+    pub turnstile_after_failures: u64,
+    // The first lockout, every further failure in the window doubles it
+    pub base_lockout_s: i64,
+    pub max_lockout_s: i64,
+    // Tells the owner of an account when it gets locked
+    pub notify_on_lockout: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AdminConfig {
+    // Applies to user searches and the audit log
+    pub default_page_size: i64,
+    pub max_page_size: i64,
+    pub max_ban_reason_length: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct AuditConfig {
+    // Events waiting to be written, further events are dropped while the queue is full
+    pub channel_capacity: usize,
+    // Applies to the security log
+    pub default_page_size: i64,
+    pub max_page_size: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitBackend {
+    // Every server instance counts on its own
+    InProcess,
+    // Server instances share their counters in Redis
+    Redis,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    Ip,
+    // Falls back to the IP for requests without a valid token
+    User,
+    // One limit shared by every caller of the route
+    Route,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RateLimitPolicyConfig {
+    // Names the bucket, has to be unique
+    pub name: String,
+    // The route as registered in the router, e.g. "/sessions/:session_id"
+    pub route: String,
+    // Empty applies the policy to every method of the route
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub key: RateLimitKey,
+    // Requests allowed per period, they can all be made in one burst
+    pub limit: u64,
+    pub period_s: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RateLimitConfig {
+    pub backend: RateLimitBackend,
+    #[serde(default)]
+    pub policies: Vec<RateLimitPolicyConfig>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct GatewayConfig {
+    pub heartbeat_interval_ms: u64,
+    // Extra time a client gets on top of the interval before the session times out
+    pub heartbeat_grace_period_ms: u64,
+    // Events queued per session before it is considered too slow and dropped
+    pub event_buffer_size: usize,
+    pub max_subscribed_channels: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventBusBackend {
+    // Events only reach sessions on the same server instance
+    InProcess,
+    // Events are shared between server instances over Redis pub/sub
+    Redis,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EventBusConfig {
+    pub backend: EventBusBackend,
+    // Wait before reopening a broken pub/sub connection
+    pub reconnect_delay_ms: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationMode {
+    // Pending migrations are applied on startup
+    #[default]
+    Apply,
+    // Startup fails while migrations are pending, they are applied with `server migrate up`
+    Check,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -71,6 +407,8 @@ pub struct PostgresDatabaseConfig {
     pub host: String,
     pub database_name: String,
     pub max_connections: u32,
+    #[serde(default)]
+    pub migration_mode: MigrationMode,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1,8 +1,35 @@
 mod config;
 
 pub use config::{
+    AdminConfig,
+    AuditConfig,
     Config,
+    DmsConfig,
+    EmailQueueConfig,
+    EmailTemplatesConfig,
+    EmailTransportBackend,
+    EventBusBackend,
+    EventBusConfig,
+    GatewayConfig,
+    GuildsConfig,
+    JWTAlgorithm,
     JWTConfig,
+    JWTSigningKeyConfig,
+    JWTVerificationKeyConfig,
+    LoginProtectionConfig,
+    MessagesConfig,
+    MetricsConfig,
+    MfaConfig,
+    MigrationMode,
+    PasskeysConfig,
     PostgresDatabaseConfig,
+    RateLimitBackend,
+    RateLimitConfig,
+    RateLimitKey,
+    RateLimitPolicyConfig,
     RedisDatabaseConfig,
+    SessionsConfig,
+    SMTPConfig,
+    SmtpTls,
+    SnowflakeConfig,
 };
\ No newline at end of file
@@ -0,0 +1,51 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AdminAuditEntry,
+    database::{
+        AdminAuditStore,
+        DatabaseError
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl AdminAuditStore for InMemoryDatabase {
+    async fn insert_admin_audit_entry(
+        &self,
+        admin_id: i64,
+        action: &str,
+        target_user_id: Option<i64>,
+        details: &serde_json::Value
+    ) -> Result<i64, DatabaseError> {
+        let mut tables = self.tables();
+        let id = tables.admin_audit_entries.len() as i64 + 1;
+        tables.admin_audit_entries.push(AdminAuditEntry {
+            id,
+            admin_id,
+            action: action.to_string(),
+            target_user_id,
+            details: details.clone(),
+            created_at: chrono::Utc::now().timestamp(),
+        });
+        Ok(id)
+    }
+
+    async fn get_admin_audit_entries(
+        &self,
+        target_user_id: Option<i64>,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AdminAuditEntry>, DatabaseError> {
+        let entries = self.tables().admin_audit_entries
+            .iter()
+            .rev()
+            .filter(|entry| target_user_id.is_none_or(|target_user_id| entry.target_user_id == Some(target_user_id)))
+            .filter(|entry| before.is_none_or(|before| entry.id < before))
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok(entries)
+    }
+}
@@ -0,0 +1,44 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AuditEvent,
+    database::{
+        AuditEventStore,
+        DatabaseError
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl AuditEventStore for InMemoryDatabase {
+    async fn insert_audit_event(
+        &self,
+        audit_event: &AuditEvent
+    ) -> Result<i64, DatabaseError> {
+        let mut tables = self.tables();
+        let id = tables.audit_events.len() as i64 + 1;
+        tables.audit_events.push(AuditEvent {
+            id,
+            ..audit_event.clone()
+        });
+        Ok(id)
+    }
+
+    async fn get_user_audit_events(
+        &self,
+        target_user_id: i64,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AuditEvent>, DatabaseError> {
+        let audit_events = self.tables().audit_events
+            .iter()
+            .rev()
+            .filter(|audit_event| audit_event.target_user_id == Some(target_user_id))
+            .filter(|audit_event| before.is_none_or(|before| audit_event.id < before))
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok(audit_events)
+    }
+}
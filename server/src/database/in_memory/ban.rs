@@ -0,0 +1,57 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::UserBan,
+    database::{
+        BanStore,
+        DatabaseError
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl BanStore for InMemoryDatabase {
+    async fn ban_user(
+        &self,
+        user_ban: &UserBan
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        match tables.users.get_mut(&user_ban.user_id) {
+            Some(user) => user.banned = true,
+            None => return Err(DatabaseError::UserNotFound(user_ban.user_id)),
+        }
+        tables.user_bans.insert(user_ban.user_id, user_ban.clone());
+        Ok(())
+    }
+
+    async fn unban_user(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        tables.user_bans.remove(&user_id);
+        match tables.users.get_mut(&user_id) {
+            Some(user) if user.banned => {
+                user.banned = false;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn get_user_ban(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        Ok(self.tables().user_bans.get(&user_id).cloned())
+    }
+
+    /// There is no separate denylist, the bans themselves are read
+    async fn get_denylisted_user(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        self.get_user_ban(user_id).await
+    }
+}
@@ -0,0 +1,37 @@
+use axum::async_trait;
+
+use crate::database::{
+    CredentialStore,
+    DatabaseError
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl CredentialStore for InMemoryDatabase {
+    async fn get_password_hash_and_salt_by_user_id(
+        &self,
+        user_id: i64
+    ) -> Result<(String, String), DatabaseError> {
+        match self.tables().users.get(&user_id) {
+            Some(user) => Ok((user.password_hash.clone(), user.salt.clone())),
+            None => Err(DatabaseError::UserNotFound(user_id)),
+        }
+    }
+
+    async fn update_password_hash_and_salt(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+        salt: &str
+    ) -> Result<(), DatabaseError> {
+        match self.tables().users.get_mut(&user_id) {
+            Some(user) => {
+                user.password_hash = password_hash.to_string();
+                user.salt = salt.to_string();
+                Ok(())
+            },
+            None => Err(DatabaseError::UserNotFound(user_id)),
+        }
+    }
+}
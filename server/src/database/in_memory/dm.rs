@@ -0,0 +1,166 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        DmChannel,
+        DmKind
+    },
+    database::{
+        DatabaseError,
+        DmStore
+    }
+};
+
+use super::{
+    DmRecipient,
+    InMemoryDatabase,
+    UserBlock
+};
+
+#[async_trait]
+impl DmStore for InMemoryDatabase {
+    async fn open_dm_channel(
+        &self,
+        channel: &DmChannel,
+        _cache_ttl_s: u64
+    ) -> Result<DmChannel, DatabaseError> {
+        let mut tables = self.tables();
+        if channel.kind == DmKind::Direct {
+            let existing_id = tables.dm_channels
+                .keys()
+                .copied()
+                .find(|channel_id| {
+                    tables.dm_channel(*channel_id).is_some_and(|existing| {
+                        existing.kind == DmKind::Direct
+                            && channel.recipient_ids.iter().all(|user_id| existing.is_recipient(*user_id))
+                    })
+                });
+            if let Some(existing) = existing_id.and_then(|channel_id| tables.dm_channel(channel_id)) {
+                return Ok(existing);
+            }
+        }
+
+        let mut recipients: Vec<DmRecipient> = channel.recipient_ids
+            .iter()
+            .map(|user_id| DmRecipient {
+                user_id: *user_id,
+                joined_at: channel.created_at,
+            })
+            .collect();
+        recipients.sort_by_key(|recipient| (recipient.joined_at, recipient.user_id));
+        tables.dm_recipients.insert(channel.id, recipients);
+        tables.dm_channels.insert(channel.id, DmChannel {
+            recipient_ids: Vec::new(),
+            ..channel.clone()
+        });
+        Ok(channel.clone())
+    }
+
+    async fn get_dm_channel_by_id(
+        &self,
+        channel_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Option<DmChannel>, DatabaseError> {
+        Ok(self.tables().dm_channel(channel_id))
+    }
+
+    async fn get_user_dm_channels(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<DmChannel>, DatabaseError> {
+        let tables = self.tables();
+        let channels = tables.dm_channels
+            .keys()
+            .filter_map(|channel_id| tables.dm_channel(*channel_id))
+            .filter(|channel| channel.is_recipient(user_id))
+            .collect();
+        Ok(channels)
+    }
+
+    async fn add_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64,
+        joined_at: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.dm_channels.contains_key(&channel_id) {
+            return Err(DatabaseError::ChannelNotFound(channel_id));
+        }
+        let recipients = tables.dm_recipients.entry(channel_id).or_default();
+        if recipients.iter().any(|recipient| recipient.user_id == user_id) {
+            return Ok(());
+        }
+        recipients.push(DmRecipient {
+            user_id,
+            joined_at,
+        });
+        recipients.sort_by_key(|recipient| (recipient.joined_at, recipient.user_id));
+        Ok(())
+    }
+
+    async fn remove_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        if !self.tables().remove_dm_recipient(channel_id, user_id) {
+            return Err(DatabaseError::RecipientNotFound(user_id));
+        }
+        Ok(())
+    }
+
+    async fn block_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64,
+        created_at: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        let already_blocked = tables.user_blocks
+            .iter()
+            .any(|block| block.user_id == user_id && block.blocked_id == blocked_id);
+        if !already_blocked {
+            tables.user_blocks.push(UserBlock {
+                user_id,
+                blocked_id,
+                created_at,
+            });
+        }
+        Ok(())
+    }
+
+    async fn unblock_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64
+    ) -> Result<(), DatabaseError> {
+        self.tables().user_blocks.retain(|block| !(block.user_id == user_id && block.blocked_id == blocked_id));
+        Ok(())
+    }
+
+    async fn get_user_blocks(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<i64>, DatabaseError> {
+        let tables = self.tables();
+        let mut blocks: Vec<&UserBlock> = tables.user_blocks
+            .iter()
+            .filter(|block| block.user_id == user_id)
+            .collect();
+        blocks.sort_by_key(|block| (block.created_at, block.blocked_id));
+        Ok(blocks.into_iter().map(|block| block.blocked_id).collect())
+    }
+
+    async fn is_blocked_between(
+        &self,
+        user_id: i64,
+        other_user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let blocked = self.tables().user_blocks.iter().any(|block| {
+            (block.user_id == user_id && block.blocked_id == other_user_id)
+                || (block.user_id == other_user_id && block.blocked_id == user_id)
+        });
+        Ok(blocked)
+    }
+}
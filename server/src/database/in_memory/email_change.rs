@@ -0,0 +1,38 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PendingEmailChange,
+    database::{
+        DatabaseError,
+        EmailChangeStore
+    }
+};
+
+use super::{
+    Expiring,
+    InMemoryDatabase
+};
+
+#[async_trait]
+impl EmailChangeStore for InMemoryDatabase {
+    async fn create_email_change(
+        &self,
+        jti: Uuid,
+        email_change: &PendingEmailChange,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.tables().email_changes.insert(jti, Expiring::new(email_change.clone(), ttl_s));
+        Ok(())
+    }
+
+    async fn take_email_change(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<PendingEmailChange>, DatabaseError> {
+        let email_change = self.tables().email_changes
+            .remove(&jti)
+            .and_then(Expiring::into_live);
+        Ok(email_change)
+    }
+}
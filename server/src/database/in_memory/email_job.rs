@@ -0,0 +1,122 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        EmailJob,
+        EmailJobStatus
+    },
+    database::{
+        DatabaseError,
+        EmailJobStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl EmailJobStore for InMemoryDatabase {
+    async fn enqueue_email_job(
+        &self,
+        email_job: &EmailJob
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        if tables.email_jobs.iter().any(|other| other.idempotency_key == email_job.idempotency_key) {
+            return Ok(false);
+        }
+        // Finished jobs get deleted, the count can't be the next id
+        let id = tables.email_jobs.iter().map(|other| other.id).max().unwrap_or(0) + 1;
+        tables.email_jobs.push(EmailJob {
+            id,
+            status: EmailJobStatus::Pending.as_str().to_string(),
+            attempts: 0,
+            last_error: None,
+            locked_until: None,
+            updated_at: email_job.created_at,
+            ..email_job.clone()
+        });
+        Ok(true)
+    }
+
+    async fn claim_email_jobs(
+        &self,
+        now: i64,
+        lease_s: i64,
+        limit: i64
+    ) -> Result<Vec<EmailJob>, DatabaseError> {
+        let mut tables = self.tables();
+        let mut due: Vec<&mut EmailJob> = tables.email_jobs
+            .iter_mut()
+            .filter(|email_job| {
+                (email_job.status == EmailJobStatus::Pending.as_str() && email_job.run_at <= now) ||
+                (email_job.status == EmailJobStatus::Sending.as_str() && email_job.locked_until.is_some_and(|locked_until| locked_until <= now))
+            })
+            .collect();
+        due.sort_by_key(|email_job| email_job.run_at);
+
+        let claimed = due
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|email_job| {
+                email_job.status = EmailJobStatus::Sending.as_str().to_string();
+                email_job.attempts += 1;
+                email_job.locked_until = Some(now + lease_s);
+                email_job.updated_at = now;
+                email_job.clone()
+            })
+            .collect();
+        Ok(claimed)
+    }
+
+    async fn complete_email_job(
+        &self,
+        job_id: i64,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        if let Some(email_job) = self.tables().email_jobs.iter_mut().find(|email_job| email_job.id == job_id) {
+            email_job.status = EmailJobStatus::Sent.as_str().to_string();
+            email_job.message.clear();
+            email_job.locked_until = None;
+            email_job.last_error = None;
+            email_job.updated_at = now;
+        }
+        Ok(())
+    }
+
+    async fn fail_email_job(
+        &self,
+        job_id: i64,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        if let Some(email_job) = self.tables().email_jobs.iter_mut().find(|email_job| email_job.id == job_id) {
+            let status = match retry_at {
+                Some(_) => EmailJobStatus::Pending,
+                None => EmailJobStatus::Dead,
+            };
+            if status == EmailJobStatus::Dead {
+                email_job.message.clear();
+            }
+            email_job.status = status.as_str().to_string();
+            email_job.run_at = retry_at.unwrap_or(email_job.run_at);
+            email_job.locked_until = None;
+            email_job.last_error = Some(error.to_string());
+            email_job.updated_at = now;
+        }
+        Ok(())
+    }
+
+    async fn delete_finished_email_jobs(
+        &self,
+        updated_before: i64
+    ) -> Result<u64, DatabaseError> {
+        let mut tables = self.tables();
+        let before = tables.email_jobs.len();
+        tables.email_jobs.retain(|email_job| {
+            let finished = email_job.status == EmailJobStatus::Sent.as_str() ||
+                email_job.status == EmailJobStatus::Dead.as_str();
+            !finished || email_job.updated_at >= updated_before
+        });
+        Ok((before - tables.email_jobs.len()) as u64)
+    }
+}
@@ -0,0 +1,237 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        Channel,
+        Guild,
+        GuildMember,
+        Invite,
+        Role
+    },
+    database::{
+        DatabaseError,
+        GuildStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl GuildStore for InMemoryDatabase {
+    async fn create_guild(
+        &self,
+        guild: &Guild,
+        owner: &GuildMember,
+        everyone_role: &Role,
+        channels: &[Channel],
+        _cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        tables.guilds.insert(guild.id, guild.clone());
+        tables.guild_members.insert((owner.guild_id, owner.user_id), GuildMember {
+            role_ids: Vec::new(),
+            ..owner.clone()
+        });
+        tables.roles.insert(everyone_role.id, everyone_role.clone());
+        for channel in channels {
+            tables.channels.insert(channel.id, channel.clone());
+        }
+        Ok(())
+    }
+
+    async fn get_guild_by_id(
+        &self,
+        guild_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Option<Guild>, DatabaseError> {
+        Ok(self.tables().guilds.get(&guild_id).cloned())
+    }
+
+    async fn get_user_guilds(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Guild>, DatabaseError> {
+        let tables = self.tables();
+        let mut memberships: Vec<&GuildMember> = tables.guild_members
+            .values()
+            .filter(|member| member.user_id == user_id)
+            .collect();
+        memberships.sort_by_key(|member| (member.joined_at, member.guild_id));
+        let guilds = memberships
+            .into_iter()
+            .filter_map(|member| tables.guilds.get(&member.guild_id).cloned())
+            .collect();
+        Ok(guilds)
+    }
+
+    async fn delete_guild(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if tables.guilds.remove(&guild_id).is_none() {
+            return Err(DatabaseError::GuildNotFound(guild_id));
+        }
+
+        let channel_ids: Vec<i64> = tables.channels
+            .values()
+            .filter(|channel| channel.guild_id == guild_id)
+            .map(|channel| channel.id)
+            .collect();
+        for channel_id in channel_ids {
+            tables.delete_channel_messages(channel_id);
+            tables.channel_overwrites.retain(|(overwrite_channel_id, _), _| *overwrite_channel_id != channel_id);
+            tables.channels.remove(&channel_id);
+        }
+        tables.guild_members.retain(|(member_guild_id, _), _| *member_guild_id != guild_id);
+        tables.invites.retain(|_, invite| invite.guild_id != guild_id);
+        tables.roles.retain(|_, role| role.guild_id != guild_id);
+        Ok(())
+    }
+
+    async fn insert_channel(
+        &self,
+        channel: &Channel,
+        _cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.guilds.contains_key(&channel.guild_id) {
+            return Err(DatabaseError::GuildNotFound(channel.guild_id));
+        }
+        tables.channels.insert(channel.id, channel.clone());
+        Ok(())
+    }
+
+    async fn get_channel_by_id(
+        &self,
+        channel_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Option<Channel>, DatabaseError> {
+        Ok(self.tables().channels.get(&channel_id).cloned())
+    }
+
+    async fn get_guild_channels(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<Channel>, DatabaseError> {
+        Ok(self.tables().guild_channels(guild_id))
+    }
+
+    async fn rename_channel(
+        &self,
+        channel_id: i64,
+        name: &str,
+        _cache_ttl_s: u64
+    ) -> Result<Channel, DatabaseError> {
+        match self.tables().channels.get_mut(&channel_id) {
+            Some(channel) => {
+                channel.name = name.to_string();
+                Ok(channel.clone())
+            },
+            None => Err(DatabaseError::ChannelNotFound(channel_id)),
+        }
+    }
+
+    async fn update_channel_positions(
+        &self,
+        guild_id: i64,
+        positions: &[(i64, i32)],
+        _cache_ttl_s: u64
+    ) -> Result<Vec<Channel>, DatabaseError> {
+        let mut tables = self.tables();
+        for (channel_id, _) in positions {
+            match tables.channels.get(channel_id) {
+                Some(channel) if channel.guild_id == guild_id => {},
+                _ => return Err(DatabaseError::ChannelNotFound(*channel_id)),
+            }
+        }
+        for (channel_id, position) in positions {
+            if let Some(channel) = tables.channels.get_mut(channel_id) {
+                channel.position = *position;
+            }
+        }
+        Ok(tables.guild_channels(guild_id))
+    }
+
+    async fn delete_channel(
+        &self,
+        channel_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if tables.channels.remove(&channel_id).is_none() {
+            return Err(DatabaseError::ChannelNotFound(channel_id));
+        }
+        for channel in tables.channels.values_mut() {
+            if channel.parent_id == Some(channel_id) {
+                channel.parent_id = None;
+            }
+        }
+        tables.delete_channel_messages(channel_id);
+        tables.channel_overwrites.retain(|(overwrite_channel_id, _), _| *overwrite_channel_id != channel_id);
+        Ok(())
+    }
+
+    async fn insert_guild_member(
+        &self,
+        member: &GuildMember,
+        _cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.guilds.contains_key(&member.guild_id) {
+            return Err(DatabaseError::GuildNotFound(member.guild_id));
+        }
+        if tables.guild_members.contains_key(&(member.guild_id, member.user_id)) {
+            return Err(DatabaseError::MemberAlreadyExists(member.user_id));
+        }
+        tables.guild_members.insert((member.guild_id, member.user_id), GuildMember {
+            role_ids: Vec::new(),
+            ..member.clone()
+        });
+        Ok(())
+    }
+
+    async fn get_guild_member(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Option<GuildMember>, DatabaseError> {
+        Ok(self.tables().guild_members.get(&(guild_id, user_id)).cloned())
+    }
+
+    async fn insert_invite(
+        &self,
+        invite: &Invite
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.guilds.contains_key(&invite.guild_id) {
+            return Err(DatabaseError::GuildNotFound(invite.guild_id));
+        }
+        tables.invites.insert(invite.code.clone(), invite.clone());
+        Ok(())
+    }
+
+    async fn join_guild_with_invite(
+        &self,
+        code: &str,
+        user_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<GuildMember, DatabaseError> {
+        let mut tables = self.tables();
+        let now = chrono::Utc::now().timestamp();
+        let guild_id = match tables.invites.get(code) {
+            Some(invite) if invite.is_usable(now) => invite.guild_id,
+            _ => return Err(DatabaseError::InviteNotFound(code.to_string())),
+        };
+        if tables.guild_members.contains_key(&(guild_id, user_id)) {
+            return Err(DatabaseError::MemberAlreadyExists(user_id));
+        }
+
+        if let Some(invite) = tables.invites.get_mut(code) {
+            invite.uses += 1;
+        }
+        let member = GuildMember::new(guild_id, user_id, now);
+        tables.guild_members.insert((guild_id, user_id), member.clone());
+        Ok(member)
+    }
+}
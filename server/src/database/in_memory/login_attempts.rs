@@ -0,0 +1,80 @@
+use std::time::{
+    Duration,
+    Instant
+};
+
+use axum::async_trait;
+
+use crate::database::{
+    DatabaseError,
+    LoginAttemptStore
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl LoginAttemptStore for InMemoryDatabase {
+    async fn add_login_failure(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        let mut tables = self.tables();
+        let failures = tables.login_failures.entry(subject.to_string()).or_default();
+        failures.retain(|failed_at| *failed_at > now - window_s);
+        failures.push(now);
+        Ok(failures.len() as u64)
+    }
+
+    async fn count_login_failures(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        let mut tables = self.tables();
+        let failures = match tables.login_failures.get_mut(subject) {
+            Some(failures) => failures,
+            None => return Ok(0),
+        };
+        failures.retain(|failed_at| *failed_at > now - window_s);
+        Ok(failures.len() as u64)
+    }
+
+    async fn reset_login_failures(
+        &self,
+        subject: &str
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        tables.login_failures.remove(subject);
+        tables.login_lockouts.remove(subject);
+        Ok(())
+    }
+
+    async fn set_login_lockout(
+        &self,
+        subject: &str,
+        lockout_s: i64
+    ) -> Result<(), DatabaseError> {
+        let ends_at = Instant::now() + Duration::from_secs(lockout_s.max(0) as u64);
+        self.tables().login_lockouts.insert(subject.to_string(), ends_at);
+        Ok(())
+    }
+
+    async fn get_login_lockout(
+        &self,
+        subject: &str
+    ) -> Result<Option<i64>, DatabaseError> {
+        let ends_at = match self.tables().login_lockouts.get(subject) {
+            Some(ends_at) => *ends_at,
+            None => return Ok(None),
+        };
+        // Rounded like the TTL of the Redis key
+        let remaining_s = ends_at.saturating_duration_since(Instant::now()).as_secs_f64().round() as i64;
+        if remaining_s <= 0 {
+            return Ok(None);
+        }
+        Ok(Some(remaining_s))
+    }
+}
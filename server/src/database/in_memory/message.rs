@@ -0,0 +1,84 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::Message,
+    database::{
+        DatabaseError,
+        MessageStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl MessageStore for InMemoryDatabase {
+    async fn insert_message(
+        &self,
+        message: &Message,
+        _cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.tables().messages.insert(message.id, message.clone());
+        Ok(())
+    }
+
+    async fn get_message_by_id(
+        &self,
+        message_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Option<Message>, DatabaseError> {
+        Ok(self.tables().messages.get(&message_id).cloned())
+    }
+
+    async fn edit_message(
+        &self,
+        message_id: i64,
+        content: &str,
+        _cache_ttl_s: u64
+    ) -> Result<Message, DatabaseError> {
+        match self.tables().messages.get_mut(&message_id) {
+            Some(message) => {
+                message.content = content.to_string();
+                message.updated_at = Some(chrono::Utc::now().timestamp());
+                Ok(message.clone())
+            },
+            None => Err(DatabaseError::MessageNotFound(message_id)),
+        }
+    }
+
+    async fn delete_message(
+        &self,
+        message_id: i64
+    ) -> Result<(), DatabaseError> {
+        match self.tables().messages.remove(&message_id) {
+            Some(_) => Ok(()),
+            None => Err(DatabaseError::MessageNotFound(message_id)),
+        }
+    }
+
+    async fn get_channel_messages(
+        &self,
+        channel_id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<Message>, DatabaseError> {
+        let tables = self.tables();
+        let limit = limit.max(0) as usize;
+        let in_page = |message: &&Message| {
+            message.channel_id == channel_id
+                && before.is_none_or(|before| message.id < before)
+                && after.is_none_or(|after| message.id > after)
+        };
+
+        // The page closest to `after` or to `before`, but always newest first
+        let mut messages: Vec<Message> = if after.is_some() {
+            tables.messages.values().filter(in_page).take(limit).cloned().collect()
+        } else {
+            tables.messages.values().rev().filter(in_page).take(limit).cloned().collect()
+        };
+        if after.is_some() {
+            messages.reverse();
+        }
+        Ok(messages)
+    }
+}
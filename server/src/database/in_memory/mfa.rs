@@ -0,0 +1,170 @@
+use std::time::Instant;
+
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::UserMfa,
+    database::{
+        DatabaseError,
+        MfaStore
+    }
+};
+
+use super::{
+    Expiring,
+    InMemoryDatabase,
+    RecoveryCode
+};
+
+#[async_trait]
+impl MfaStore for InMemoryDatabase {
+    async fn get_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserMfa>, DatabaseError> {
+        Ok(self.tables().user_mfa.get(&user_id).cloned())
+    }
+
+    async fn start_mfa_enrollment(
+        &self,
+        user_mfa: &UserMfa
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        match tables.user_mfa.get_mut(&user_mfa.user_id) {
+            Some(existing) if existing.is_enabled() => Ok(false),
+            Some(existing) => {
+                existing.totp_secret = user_mfa.totp_secret.clone();
+                existing.created_at = user_mfa.created_at;
+                existing.last_used_step = None;
+                Ok(true)
+            },
+            None => {
+                tables.user_mfa.insert(user_mfa.user_id, UserMfa::new(
+                    user_mfa.user_id,
+                    user_mfa.totp_secret.clone(),
+                    user_mfa.created_at
+                ));
+                Ok(true)
+            },
+        }
+    }
+
+    async fn confirm_mfa(
+        &self,
+        user_id: i64,
+        step: i64,
+        confirmed_at: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        match tables.user_mfa.get_mut(&user_id) {
+            Some(user_mfa) if !user_mfa.is_enabled() => {
+                user_mfa.confirmed_at = Some(confirmed_at);
+                user_mfa.last_used_step = Some(step);
+            },
+            _ => return Ok(false),
+        }
+        tables.recovery_codes.insert(user_id, new_recovery_codes(recovery_code_hashes));
+        Ok(true)
+    }
+
+    async fn use_totp_step(
+        &self,
+        user_id: i64,
+        step: i64
+    ) -> Result<bool, DatabaseError> {
+        match self.tables().user_mfa.get_mut(&user_id) {
+            Some(user_mfa) if user_mfa.is_enabled() && user_mfa.last_used_step.is_none_or(|last_used_step| last_used_step < step) => {
+                user_mfa.last_used_step = Some(step);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn use_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        let recovery_code = tables.recovery_codes
+            .get_mut(&user_id)
+            .and_then(|recovery_codes| {
+                recovery_codes.iter_mut().find(|recovery_code| {
+                    recovery_code.code_hash == code_hash && recovery_code.used_at.is_none()
+                })
+            });
+        match recovery_code {
+            Some(recovery_code) => {
+                recovery_code.used_at = Some(used_at);
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    async fn replace_recovery_codes(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<(), DatabaseError> {
+        self.tables().recovery_codes.insert(user_id, new_recovery_codes(recovery_code_hashes));
+        Ok(())
+    }
+
+    async fn count_unused_recovery_codes(
+        &self,
+        user_id: i64
+    ) -> Result<i64, DatabaseError> {
+        let count = self.tables().recovery_codes
+            .get(&user_id)
+            .map(|recovery_codes| {
+                recovery_codes.iter().filter(|recovery_code| recovery_code.used_at.is_none()).count()
+            })
+            .unwrap_or(0);
+        Ok(count as i64)
+    }
+
+    async fn delete_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        tables.recovery_codes.remove(&user_id);
+        Ok(tables.user_mfa.remove(&user_id).is_some())
+    }
+
+    async fn use_mfa_pending_token(
+        &self,
+        jti: Uuid,
+        ttl_s: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        if tables.used_mfa_tokens.get(&jti).is_some_and(|used| used.expires_at > Instant::now()) {
+            return Ok(false);
+        }
+        tables.used_mfa_tokens.insert(jti, Expiring::new((), ttl_s.max(0) as u64));
+        Ok(true)
+    }
+
+    async fn release_mfa_pending_token(
+        &self,
+        jti: Uuid
+    ) -> Result<(), DatabaseError> {
+        self.tables().used_mfa_tokens.remove(&jti);
+        Ok(())
+    }
+}
+
+fn new_recovery_codes(recovery_code_hashes: &[String]) -> Vec<RecoveryCode> {
+    recovery_code_hashes
+        .iter()
+        .map(|code_hash| RecoveryCode {
+            code_hash: code_hash.clone(),
+            used_at: None,
+        })
+        .collect()
+}
@@ -0,0 +1,259 @@
+mod user;
+mod credential;
+mod refresh_token;
+mod session;
+mod mfa;
+mod passkey;
+mod password_reset;
+mod email_change;
+mod pending_registration;
+mod login_attempts;
+mod ban;
+mod message;
+mod guild;
+mod role;
+mod dm;
+mod admin_audit;
+mod audit;
+mod email_job;
+mod tests;
+
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap
+    },
+    sync::{
+        Arc,
+        Mutex,
+        MutexGuard
+    },
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+use uuid::Uuid;
+
+use crate::app_objects::{
+    AdminAuditEntry,
+    AuditEvent,
+    Channel,
+    EmailJob,
+    DmChannel,
+    Guild,
+    GuildMember,
+    Invite,
+    Message,
+    Passkey,
+    PasskeyChallenge,
+    PendingEmailChange,
+    PendingRegistration,
+    PermissionOverwrite,
+    RefreshToken,
+    Role,
+    Session,
+    User,
+    UserBan,
+    UserMfa
+};
+
+/// Keeps everything in memory behind a single lock, so every method sees and leaves a consistent state
+/// the way a Postgres transaction would. Only built for tests, clones share the data.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryDatabase {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tables(&self) -> MutexGuard<'_, Tables> {
+        // A test that panicked while holding the lock shouldn't take the other tests down with it
+        self.tables.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Value with the Redis TTL it would have been stored with
+#[derive(Debug)]
+struct Expiring<T> {
+    value: T,
+    expires_at: Instant,
+}
+
+impl<T> Expiring<T> {
+    fn new(value: T, ttl_s: u64) -> Self {
+        Self {
+            value,
+            expires_at: Instant::now() + Duration::from_secs(ttl_s),
+        }
+    }
+
+    fn into_live(self) -> Option<T> {
+        if self.expires_at <= Instant::now() {
+            return None;
+        }
+        Some(self.value)
+    }
+}
+
+#[derive(Debug)]
+struct RecoveryCode {
+    code_hash: String,
+    used_at: Option<i64>,
+}
+
+#[derive(Debug)]
+struct DmRecipient {
+    user_id: i64,
+    joined_at: i64,
+}
+
+#[derive(Debug)]
+struct UserBlock {
+    user_id: i64,
+    blocked_id: i64,
+    created_at: i64,
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    // Ordered by id like the user search
+    users: BTreeMap<i64, User>,
+    sessions: HashMap<Uuid, Session>,
+    refresh_tokens: HashMap<Uuid, RefreshToken>,
+    user_mfa: HashMap<i64, UserMfa>,
+    recovery_codes: HashMap<i64, Vec<RecoveryCode>>,
+    used_mfa_tokens: HashMap<Uuid, Expiring<()>>,
+    passkey_challenges: HashMap<Uuid, Expiring<PasskeyChallenge>>,
+    passkeys: HashMap<Vec<u8>, Passkey>,
+    password_resets: HashMap<Uuid, Expiring<i64>>,
+    email_changes: HashMap<Uuid, Expiring<PendingEmailChange>>,
+    pending_registrations: HashMap<Uuid, Expiring<PendingRegistration>>,
+    // The latest registration of every address
+    pending_registration_ids: HashMap<String, Expiring<Uuid>>,
+    verification_email_cooldowns: HashMap<String, Instant>,
+    // Timestamps of the failures, pruned to the window whenever they are counted
+    login_failures: HashMap<String, Vec<i64>>,
+    login_lockouts: HashMap<String, Instant>,
+    user_bans: HashMap<i64, UserBan>,
+    // Ordered by id, which orders them by the time they were sent
+    messages: BTreeMap<i64, Message>,
+    guilds: HashMap<i64, Guild>,
+    channels: HashMap<i64, Channel>,
+    // Keyed by (guild_id, user_id), the role ids are kept sorted
+    guild_members: BTreeMap<(i64, i64), GuildMember>,
+    invites: HashMap<String, Invite>,
+    roles: HashMap<i64, Role>,
+    // Keyed by (channel_id, target_id)
+    channel_overwrites: BTreeMap<(i64, i64), PermissionOverwrite>,
+    // The recipient ids of the channels are filled in from `dm_recipients` on every read
+    dm_channels: BTreeMap<i64, DmChannel>,
+    // Ordered by (joined_at, user_id)
+    dm_recipients: HashMap<i64, Vec<DmRecipient>>,
+    user_blocks: Vec<UserBlock>,
+    admin_audit_entries: Vec<AdminAuditEntry>,
+    audit_events: Vec<AuditEvent>,
+    email_jobs: Vec<EmailJob>,
+}
+
+impl Tables {
+    /// Drops everything that references the user, like the foreign keys of the users table do.
+    /// Group DMs pass to the next recipient like when the user leaves them
+    fn cascade_user_delete(&mut self, user_id: i64) {
+        self.sessions.retain(|_, session| session.user_id != user_id);
+        self.refresh_tokens.retain(|_, refresh_token| refresh_token.user_id != user_id);
+        self.user_mfa.remove(&user_id);
+        self.recovery_codes.remove(&user_id);
+        self.passkeys.retain(|_, passkey| passkey.user_id != user_id);
+        self.user_bans.remove(&user_id);
+        self.guild_members.retain(|(_, member_id), _| *member_id != user_id);
+        self.user_blocks.retain(|block| block.user_id != user_id && block.blocked_id != user_id);
+        let channel_ids: Vec<i64> = self.dm_recipients
+            .iter()
+            .filter(|(_, recipients)| recipients.iter().any(|recipient| recipient.user_id == user_id))
+            .map(|(channel_id, _)| *channel_id)
+            .collect();
+        for channel_id in channel_ids {
+            self.remove_dm_recipient(channel_id, user_id);
+        }
+    }
+
+    /// Ownership passes to the recipient that joined first, the channel is deleted with the last one.
+    /// Returns false if the user isn't a recipient
+    fn remove_dm_recipient(&mut self, channel_id: i64, user_id: i64) -> bool {
+        let recipients = match self.dm_recipients.get_mut(&channel_id) {
+            Some(recipients) if recipients.iter().any(|recipient| recipient.user_id == user_id) => recipients,
+            _ => return false,
+        };
+        recipients.retain(|recipient| recipient.user_id != user_id);
+
+        match recipients.first().map(|recipient| recipient.user_id) {
+            Some(next_owner_id) => {
+                if let Some(channel) = self.dm_channels.get_mut(&channel_id) {
+                    if channel.owner_id == Some(user_id) {
+                        channel.owner_id = Some(next_owner_id);
+                    }
+                }
+            },
+            None => {
+                self.delete_channel_messages(channel_id);
+                self.dm_recipients.remove(&channel_id);
+                self.dm_channels.remove(&channel_id);
+            },
+        }
+        true
+    }
+
+    fn delete_session(&mut self, session_id: Uuid) -> Option<Session> {
+        let session = self.sessions.remove(&session_id)?;
+        self.refresh_tokens.retain(|_, refresh_token| refresh_token.family_id != session_id);
+        Some(session)
+    }
+
+    fn delete_channel_messages(&mut self, channel_id: i64) {
+        self.messages.retain(|_, message| message.channel_id != channel_id);
+    }
+
+    /// In display order
+    fn guild_channels(&self, guild_id: i64) -> Vec<Channel> {
+        let mut channels: Vec<Channel> = self.channels
+            .values()
+            .filter(|channel| channel.guild_id == guild_id)
+            .cloned()
+            .collect();
+        channels.sort_by_key(|channel| (channel.position, channel.id));
+        channels
+    }
+
+    fn dm_channel(&self, channel_id: i64) -> Option<DmChannel> {
+        let mut channel = self.dm_channels.get(&channel_id)?.clone();
+        channel.recipient_ids = self.dm_recipients
+            .get(&channel_id)
+            .map(|recipients| recipients.iter().map(|recipient| recipient.user_id).collect())
+            .unwrap_or_default();
+        Some(channel)
+    }
+}
+
+/// Case insensitive SQL `LIKE`, `%` matches any run of characters, `_` a single one and `\` escapes the next character
+fn ilike(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.to_lowercase().chars().collect();
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    ilike_from(&value, &pattern)
+}
+
+fn ilike_from(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some(('%', rest)) => (0..=value.len()).any(|skip| ilike_from(&value[skip..], rest)),
+        Some(('_', rest)) => !value.is_empty() && ilike_from(&value[1..], rest),
+        Some(('\\', rest)) if !rest.is_empty() => {
+            value.first() == Some(&rest[0]) && ilike_from(&value[1..], &rest[1..])
+        },
+        Some((c, rest)) => value.first() == Some(c) && ilike_from(&value[1..], rest),
+    }
+}
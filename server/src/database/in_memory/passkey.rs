@@ -0,0 +1,109 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        Passkey,
+        PasskeyChallenge
+    },
+    database::{
+        DatabaseError,
+        PasskeyStore
+    }
+};
+
+use super::{
+    Expiring,
+    InMemoryDatabase
+};
+
+#[async_trait]
+impl PasskeyStore for InMemoryDatabase {
+    async fn create_passkey_challenge(
+        &self,
+        challenge: &PasskeyChallenge,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.tables().passkey_challenges.insert(
+            challenge.id,
+            Expiring::new(challenge.clone(), ttl_s)
+        );
+        Ok(())
+    }
+
+    async fn take_passkey_challenge(
+        &self,
+        challenge_id: Uuid
+    ) -> Result<Option<PasskeyChallenge>, DatabaseError> {
+        let challenge = self.tables().passkey_challenges
+            .remove(&challenge_id)
+            .and_then(Expiring::into_live);
+        Ok(challenge)
+    }
+
+    async fn insert_passkey(
+        &self,
+        passkey: &Passkey
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        if tables.passkeys.contains_key(&passkey.credential_id) {
+            return Ok(false);
+        }
+        tables.passkeys.insert(passkey.credential_id.clone(), passkey.clone());
+        Ok(true)
+    }
+
+    async fn get_passkey(
+        &self,
+        credential_id: &[u8]
+    ) -> Result<Option<Passkey>, DatabaseError> {
+        Ok(self.tables().passkeys.get(credential_id).cloned())
+    }
+
+    async fn get_user_passkeys(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Passkey>, DatabaseError> {
+        let mut passkeys: Vec<Passkey> = self.tables().passkeys
+            .values()
+            .filter(|passkey| passkey.user_id == user_id)
+            .cloned()
+            .collect();
+        passkeys.sort_by(|a, b| {
+            (a.created_at, &a.credential_id).cmp(&(b.created_at, &b.credential_id))
+        });
+        Ok(passkeys)
+    }
+
+    async fn update_passkey_sign_count(
+        &self,
+        credential_id: &[u8],
+        previous_sign_count: i64,
+        sign_count: i64,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        match self.tables().passkeys.get_mut(credential_id) {
+            Some(passkey) if passkey.sign_count == previous_sign_count => {
+                passkey.sign_count = sign_count;
+                passkey.last_used_at = Some(used_at);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete_passkey(
+        &self,
+        credential_id: &[u8],
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        match tables.passkeys.get(credential_id) {
+            Some(passkey) if passkey.user_id == user_id => {
+                tables.passkeys.remove(credential_id);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+}
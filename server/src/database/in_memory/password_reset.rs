@@ -0,0 +1,35 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::database::{
+    DatabaseError,
+    PasswordResetStore
+};
+
+use super::{
+    Expiring,
+    InMemoryDatabase
+};
+
+#[async_trait]
+impl PasswordResetStore for InMemoryDatabase {
+    async fn create_password_reset(
+        &self,
+        jti: Uuid,
+        user_id: i64,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.tables().password_resets.insert(jti, Expiring::new(user_id, ttl_s));
+        Ok(())
+    }
+
+    async fn take_password_reset(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<i64>, DatabaseError> {
+        let user_id = self.tables().password_resets
+            .remove(&jti)
+            .and_then(Expiring::into_live);
+        Ok(user_id)
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::{
+    Duration,
+    Instant
+};
+
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PendingRegistration,
+    database::{
+        DatabaseError,
+        PendingRegistrationStore
+    }
+};
+
+use super::{
+    Expiring,
+    InMemoryDatabase
+};
+
+#[async_trait]
+impl PendingRegistrationStore for InMemoryDatabase {
+    async fn create_pending_registration(
+        &self,
+        pending_registration: &PendingRegistration,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        let replaced_id = tables.pending_registration_ids.insert(
+            pending_registration.email.clone(),
+            Expiring::new(pending_registration.id, ttl_s)
+        );
+        if let Some(replaced_id) = replaced_id {
+            tables.pending_registrations.remove(&replaced_id.value);
+        }
+        tables.pending_registrations.insert(
+            pending_registration.id,
+            Expiring::new(pending_registration.clone(), ttl_s)
+        );
+        Ok(())
+    }
+
+    async fn get_pending_registration_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        let tables = self.tables();
+        let pending_registration = tables.pending_registration_ids
+            .get(email)
+            .filter(|registration_id| registration_id.expires_at > Instant::now())
+            .and_then(|registration_id| tables.pending_registrations.get(&registration_id.value))
+            .filter(|pending_registration| pending_registration.expires_at > Instant::now())
+            .map(|pending_registration| pending_registration.value.clone());
+        Ok(pending_registration)
+    }
+
+    async fn take_pending_registration(
+        &self,
+        registration_id: Uuid
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        let pending_registration = self.tables().pending_registrations
+            .remove(&registration_id)
+            .and_then(Expiring::into_live);
+        Ok(pending_registration)
+    }
+
+    async fn start_verification_email_cooldown(
+        &self,
+        email: &str,
+        cooldown_s: u64
+    ) -> Result<Option<i64>, DatabaseError> {
+        let mut tables = self.tables();
+        let now = Instant::now();
+        if let Some(ends_at) = tables.verification_email_cooldowns.get(email) {
+            // Rounded like the TTL of the Redis key
+            let remaining_s = ends_at.saturating_duration_since(now).as_secs_f64().round() as i64;
+            if remaining_s > 0 {
+                return Ok(Some(remaining_s));
+            }
+        }
+        tables.verification_email_cooldowns.insert(email.to_string(), now + Duration::from_secs(cooldown_s));
+        Ok(None)
+    }
+}
@@ -0,0 +1,69 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        RefreshToken,
+        RefreshTokenRotation
+    },
+    database::{
+        DatabaseError,
+        RefreshTokenStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl RefreshTokenStore for InMemoryDatabase {
+    async fn get_refresh_token(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<RefreshToken>, DatabaseError> {
+        Ok(self.tables().refresh_tokens.get(&jti).cloned())
+    }
+
+    async fn rotate_refresh_token(
+        &self,
+        jti: Uuid,
+        session_id: Uuid,
+        user_id: i64,
+        new_jti: Uuid,
+        issued_at: i64,
+        expires_at: i64
+    ) -> Result<RefreshTokenRotation, DatabaseError> {
+        let mut tables = self.tables();
+        let refresh_token = match tables.refresh_tokens.get_mut(&jti) {
+            Some(refresh_token) if refresh_token.family_id == session_id && refresh_token.user_id == user_id => {
+                refresh_token
+            },
+            _ => return Ok(RefreshTokenRotation::Invalid),
+        };
+
+        if refresh_token.rotated_at.is_some() {
+            tables.delete_session(session_id);
+            return Ok(RefreshTokenRotation::Reused);
+        }
+        if refresh_token.expires_at <= issued_at {
+            return Ok(RefreshTokenRotation::Invalid);
+        }
+
+        refresh_token.rotated_at = Some(issued_at);
+        tables.refresh_tokens.retain(|_, refresh_token| {
+            refresh_token.family_id != session_id || refresh_token.expires_at > issued_at
+        });
+        tables.refresh_tokens.insert(new_jti, RefreshToken {
+            jti: new_jti,
+            family_id: session_id,
+            user_id,
+            issued_at,
+            expires_at,
+            rotated_at: None,
+        });
+        if let Some(session) = tables.sessions.get_mut(&session_id) {
+            session.last_used_at = issued_at;
+            session.expires_at = expires_at;
+        }
+        Ok(RefreshTokenRotation::Rotated)
+    }
+}
@@ -0,0 +1,169 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        OverwriteKind,
+        PermissionOverwrite,
+        Role
+    },
+    database::{
+        DatabaseError,
+        RoleStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl RoleStore for InMemoryDatabase {
+    async fn insert_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.guilds.contains_key(&role.guild_id) {
+            return Err(DatabaseError::GuildNotFound(role.guild_id));
+        }
+        tables.roles.insert(role.id, role.clone());
+        Ok(())
+    }
+
+    async fn get_guild_roles(
+        &self,
+        guild_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Vec<Role>, DatabaseError> {
+        let mut roles: Vec<Role> = self.tables().roles
+            .values()
+            .filter(|role| role.guild_id == guild_id)
+            .cloned()
+            .collect();
+        roles.sort_by_key(|role| (role.position, role.id));
+        Ok(roles)
+    }
+
+    async fn update_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        match self.tables().roles.get_mut(&role.id) {
+            Some(stored) if stored.guild_id == role.guild_id => {
+                stored.name = role.name.clone();
+                stored.permissions = role.permissions;
+                stored.position = role.position;
+                Ok(())
+            },
+            _ => Err(DatabaseError::RoleNotFound(role.id)),
+        }
+    }
+
+    async fn delete_role(
+        &self,
+        guild_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        match tables.roles.get(&role_id) {
+            Some(role) if role.guild_id == guild_id => {},
+            _ => return Err(DatabaseError::RoleNotFound(role_id)),
+        }
+        tables.roles.remove(&role_id);
+
+        let guild_channel_ids: Vec<i64> = tables.channels
+            .values()
+            .filter(|channel| channel.guild_id == guild_id)
+            .map(|channel| channel.id)
+            .collect();
+        tables.channel_overwrites.retain(|(channel_id, target_id), overwrite| {
+            !(*target_id == role_id && overwrite.kind == OverwriteKind::Role && guild_channel_ids.contains(channel_id))
+        });
+        for ((member_guild_id, _), member) in tables.guild_members.iter_mut() {
+            if *member_guild_id == guild_id {
+                member.role_ids.retain(|member_role_id| *member_role_id != role_id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn add_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.roles.contains_key(&role_id) {
+            return Err(DatabaseError::RoleNotFound(role_id));
+        }
+        let member = match tables.guild_members.get_mut(&(guild_id, user_id)) {
+            Some(member) => member,
+            None => return Err(DatabaseError::MemberNotFound(user_id)),
+        };
+        if let Err(index) = member.role_ids.binary_search(&role_id) {
+            member.role_ids.insert(index, role_id);
+        }
+        Ok(())
+    }
+
+    async fn remove_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        if let Some(member) = self.tables().guild_members.get_mut(&(guild_id, user_id)) {
+            member.role_ids.retain(|member_role_id| *member_role_id != role_id);
+        }
+        Ok(())
+    }
+
+    async fn get_channel_overwrites(
+        &self,
+        channel_id: i64,
+        _cache_ttl_s: u64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        let overwrites = self.tables().channel_overwrites
+            .range((channel_id, i64::MIN)..=(channel_id, i64::MAX))
+            .map(|(_, overwrite)| overwrite.clone())
+            .collect();
+        Ok(overwrites)
+    }
+
+    async fn get_guild_overwrites(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        let tables = self.tables();
+        let overwrites = tables.channel_overwrites
+            .values()
+            .filter(|overwrite| {
+                tables.channels
+                    .get(&overwrite.channel_id)
+                    .is_some_and(|channel| channel.guild_id == guild_id)
+            })
+            .cloned()
+            .collect();
+        Ok(overwrites)
+    }
+
+    async fn set_channel_overwrite(
+        &self,
+        overwrite: &PermissionOverwrite
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.channels.contains_key(&overwrite.channel_id) {
+            return Err(DatabaseError::ChannelNotFound(overwrite.channel_id));
+        }
+        tables.channel_overwrites.insert((overwrite.channel_id, overwrite.target_id), overwrite.clone());
+        Ok(())
+    }
+
+    async fn delete_channel_overwrite(
+        &self,
+        channel_id: i64,
+        target_id: i64
+    ) -> Result<(), DatabaseError> {
+        self.tables().channel_overwrites.remove(&(channel_id, target_id));
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        RefreshToken,
+        Session
+    },
+    database::{
+        DatabaseError,
+        SessionStore
+    }
+};
+
+use super::InMemoryDatabase;
+
+#[async_trait]
+impl SessionStore for InMemoryDatabase {
+    async fn create_session(
+        &self,
+        session: &Session,
+        refresh_token: &RefreshToken,
+        _cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.users.contains_key(&session.user_id) {
+            return Err(DatabaseError::UserNotFound(session.user_id));
+        }
+
+        let expired_session_ids: Vec<Uuid> = tables.sessions
+            .values()
+            .filter(|other| other.user_id == session.user_id && other.expires_at <= session.created_at)
+            .map(|other| other.id)
+            .collect();
+        for session_id in expired_session_ids {
+            tables.delete_session(session_id);
+        }
+
+        tables.sessions.insert(session.id, session.clone());
+        tables.refresh_tokens.insert(refresh_token.jti, refresh_token.clone());
+        Ok(())
+    }
+
+    async fn get_session_by_id(
+        &self,
+        session_id: Uuid,
+        _cache_ttl_s: u64
+    ) -> Result<Option<Session>, DatabaseError> {
+        Ok(self.tables().sessions.get(&session_id).cloned())
+    }
+
+    async fn get_user_sessions(
+        &self,
+        user_id: i64,
+        now: i64
+    ) -> Result<Vec<Session>, DatabaseError> {
+        let mut sessions: Vec<Session> = self.tables().sessions
+            .values()
+            .filter(|session| session.user_id == user_id && session.expires_at > now)
+            .cloned()
+            .collect();
+        sessions.sort_by(|a, b| b.last_used_at.cmp(&a.last_used_at));
+        Ok(sessions)
+    }
+
+    async fn delete_session(
+        &self,
+        session_id: Uuid,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        match tables.sessions.get(&session_id) {
+            Some(session) if session.user_id == user_id => {
+                tables.delete_session(session_id);
+                Ok(())
+            },
+            _ => Err(DatabaseError::SessionNotFound(session_id)),
+        }
+    }
+
+    async fn delete_user_sessions(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        let session_ids: Vec<Uuid> = tables.sessions
+            .values()
+            .filter(|session| session.user_id == user_id)
+            .map(|session| session.id)
+            .collect();
+        for session_id in session_ids {
+            tables.delete_session(session_id);
+        }
+        Ok(())
+    }
+
+    async fn delete_other_user_sessions(
+        &self,
+        user_id: i64,
+        session_id: Uuid
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        let session_ids: Vec<Uuid> = tables.sessions
+            .values()
+            .filter(|session| session.user_id == user_id && session.id != session_id)
+            .map(|session| session.id)
+            .collect();
+        for session_id in session_ids {
+            tables.delete_session(session_id);
+        }
+        Ok(())
+    }
+}
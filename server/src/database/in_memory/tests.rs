@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    use crate::{
+        app_objects::{
+            DmChannel,
+            Message,
+            RefreshToken,
+            RefreshTokenRotation,
+            Session,
+            User
+        },
+        database::{
+            in_memory::ilike,
+            DatabaseError,
+            DmStore,
+            InMemoryDatabase,
+            MessageStore,
+            RefreshTokenStore,
+            SessionStore,
+            UserStore
+        }
+    };
+
+    async fn create_test_session(
+        db_client: &InMemoryDatabase,
+        user_id: i64
+    ) -> (Session, RefreshToken) {
+        let session = Session::new(
+            Uuid::new_v4(),
+            user_id,
+            "Test device".to_string(),
+            None,
+            None,
+            1000,
+            2000
+        );
+        let refresh_token = RefreshToken::new_family(
+            Uuid::new_v4(),
+            session.id,
+            user_id,
+            1000,
+            2000
+        );
+        db_client.create_session(&session, &refresh_token, 60).await.unwrap();
+        (session, refresh_token)
+    }
+
+    #[test]
+    fn test_ilike() {
+        assert_eq!(ilike("Alice@Example.com", "alice%"), true);
+        assert_eq!(ilike("alice", "a_ice"), true);
+        assert_eq!(ilike("alice", "bob%"), false);
+        assert_eq!(ilike("100%", "100\\%"), true);
+        assert_eq!(ilike("1000", "100\\%"), false);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_email_is_rejected() {
+        let db_client = InMemoryDatabase::new();
+        let user = User {
+            id: 1,
+            email: "test@example.com".to_string(),
+            ..User::default()
+        };
+        db_client.insert_user(&user).await.unwrap();
+
+        let other_user = User {
+            id: 2,
+            ..user.clone()
+        };
+        let res = db_client.insert_user(&other_user).await;
+        assert!(matches!(res, Err(DatabaseError::EmailAlreadyExists(_))));
+    }
+
+    #[tokio::test]
+    async fn test_deleting_user_drops_sessions() {
+        let db_client = InMemoryDatabase::new();
+        db_client.insert_user(&User { id: 1, ..User::default() }).await.unwrap();
+        let (session, refresh_token) = create_test_session(&db_client, 1).await;
+
+        db_client.delete_user(1).await.unwrap();
+        assert_eq!(db_client.get_session_by_id(session.id, 60).await.unwrap(), None);
+        assert_eq!(db_client.get_refresh_token(refresh_token.jti).await.unwrap(), None);
+
+        let res = db_client.delete_user(1).await;
+        assert!(matches!(res, Err(DatabaseError::UserNotFound(1))));
+    }
+
+    #[tokio::test]
+    async fn test_reused_refresh_token_revokes_session() {
+        let db_client = InMemoryDatabase::new();
+        db_client.insert_user(&User { id: 1, ..User::default() }).await.unwrap();
+        let (session, refresh_token) = create_test_session(&db_client, 1).await;
+
+        let new_jti = Uuid::new_v4();
+        let rotation = db_client.rotate_refresh_token(
+            refresh_token.jti,
+            session.id,
+            1,
+            new_jti,
+            1500,
+            2500
+        ).await.unwrap();
+        assert_eq!(rotation, RefreshTokenRotation::Rotated);
+        let session_after_rotation = db_client.get_session_by_id(session.id, 60).await.unwrap().unwrap();
+        assert_eq!(session_after_rotation.last_used_at, 1500);
+
+        let rotation = db_client.rotate_refresh_token(
+            refresh_token.jti,
+            session.id,
+            1,
+            Uuid::new_v4(),
+            1600,
+            2600
+        ).await.unwrap();
+        assert_eq!(rotation, RefreshTokenRotation::Reused);
+        assert_eq!(db_client.get_session_by_id(session.id, 60).await.unwrap(), None);
+        assert_eq!(db_client.get_refresh_token(new_jti).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_channel_message_pages_are_newest_first() {
+        let db_client = InMemoryDatabase::new();
+        for id in 1..=5 {
+            let message = Message::new(id, format!("message {}", id), 1, id, None, 10);
+            db_client.insert_message(&message, 60).await.unwrap();
+        }
+        db_client.insert_message(&Message::new(6, "other".to_string(), 1, 6, None, 11), 60).await.unwrap();
+
+        let ids = |messages: Vec<Message>| messages.iter().map(|message| message.id).collect::<Vec<i64>>();
+        let latest = db_client.get_channel_messages(10, None, None, 2).await.unwrap();
+        assert_eq!(ids(latest), vec![5, 4]);
+        let before = db_client.get_channel_messages(10, Some(4), None, 2).await.unwrap();
+        assert_eq!(ids(before), vec![3, 2]);
+        let after = db_client.get_channel_messages(10, None, Some(1), 2).await.unwrap();
+        assert_eq!(ids(after), vec![3, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_direct_dm_is_opened_once_per_pair() {
+        let db_client = InMemoryDatabase::new();
+        let channel = DmChannel::new_direct(100, 1, 2, 1000);
+        let opened = db_client.open_dm_channel(&channel, 60).await.unwrap();
+        assert_eq!(opened, channel);
+
+        let reopened = db_client.open_dm_channel(&DmChannel::new_direct(101, 2, 1, 1001), 60).await.unwrap();
+        assert_eq!(reopened.id, 100);
+        assert_eq!(db_client.get_user_dm_channels(2).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_last_group_dm_recipient_deletes_channel() {
+        let db_client = InMemoryDatabase::new();
+        let channel = DmChannel::new_group(100, 1, &[2], 1000);
+        db_client.open_dm_channel(&channel, 60).await.unwrap();
+        db_client.insert_message(&Message::new(1, "hi".to_string(), 1, 1000, None, 100), 60).await.unwrap();
+
+        db_client.remove_dm_recipient(100, 1).await.unwrap();
+        let channel = db_client.get_dm_channel_by_id(100, 60).await.unwrap().unwrap();
+        assert_eq!(channel.owner_id, Some(2));
+        assert_eq!(channel.recipient_ids, vec![2]);
+
+        db_client.remove_dm_recipient(100, 2).await.unwrap();
+        assert_eq!(db_client.get_dm_channel_by_id(100, 60).await.unwrap(), None);
+        assert_eq!(db_client.get_message_by_id(1, 60).await.unwrap(), None);
+
+        let res = db_client.remove_dm_recipient(100, 2).await;
+        assert!(matches!(res, Err(DatabaseError::RecipientNotFound(2))));
+    }
+}
@@ -0,0 +1,118 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::User,
+    database::{
+        DatabaseError,
+        UserStore
+    }
+};
+
+use super::{
+    ilike,
+    InMemoryDatabase
+};
+
+#[async_trait]
+impl UserStore for InMemoryDatabase {
+    async fn insert_user(
+        &self,
+        user: &User
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if tables.users.contains_key(&user.id) {
+            return Err(DatabaseError::UserAlreadyExists(user.id));
+        }
+        if tables.users.values().any(|other| other.email == user.email) {
+            return Err(DatabaseError::EmailAlreadyExists(user.email.clone()));
+        }
+        tables.users.insert(user.id, user.clone());
+        Ok(())
+    }
+
+    async fn get_user_by_id(
+        &self,
+        user_id: i64
+    ) -> Result<Option<User>, DatabaseError> {
+        Ok(self.tables().users.get(&user_id).cloned())
+    }
+
+    async fn get_user_id_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<i64>, DatabaseError> {
+        let user_id = self.tables().users
+            .values()
+            .find(|user| user.email == email)
+            .map(|user| user.id);
+        Ok(user_id)
+    }
+
+    async fn update_user_email(
+        &self,
+        user_id: i64,
+        old_email: &str,
+        new_email: &str
+    ) -> Result<bool, DatabaseError> {
+        let mut tables = self.tables();
+        let email_taken = tables.users
+            .values()
+            .any(|user| user.id != user_id && user.email == new_email);
+        let user = match tables.users.get_mut(&user_id) {
+            Some(user) if user.email == old_email => user,
+            _ => return Ok(false),
+        };
+        if email_taken {
+            return Err(DatabaseError::EmailAlreadyExists(new_email.to_string()));
+        }
+        user.email = new_email.to_string();
+        Ok(true)
+    }
+
+    async fn search_users(
+        &self,
+        user_id: Option<i64>,
+        email_pattern: Option<&str>,
+        username_pattern: Option<&str>,
+        limit: i64
+    ) -> Result<Vec<User>, DatabaseError> {
+        let users = self.tables().users
+            .values()
+            .filter(|user| user_id.is_none_or(|user_id| user.id == user_id))
+            .filter(|user| email_pattern.is_none_or(|pattern| ilike(&user.email, pattern)))
+            .filter(|user| username_pattern.is_none_or(|pattern| ilike(&user.username, pattern)))
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect();
+        Ok(users)
+    }
+
+    async fn set_user_verified(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        match self.tables().users.get_mut(&user_id) {
+            Some(user) => {
+                user.verified = true;
+                Ok(())
+            },
+            None => Err(DatabaseError::UserNotFound(user_id)),
+        }
+    }
+
+    async fn delete_user(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tables = self.tables();
+        if !tables.users.contains_key(&user_id) {
+            return Err(DatabaseError::UserNotFound(user_id));
+        }
+        if tables.guilds.values().any(|guild| guild.owner_id == user_id) {
+            return Err(DatabaseError::UserOwnsGuilds(user_id));
+        }
+        tables.users.remove(&user_id);
+        tables.cascade_user_delete(user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AdminAuditEntry,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        AdminAuditStore
+    }
+};
+
+
+/// The audit log is only read by admins, it's never cached
+#[async_trait]
+impl AdminAuditStore for DatabaseClientWithCaching {
+    async fn insert_admin_audit_entry(
+        &self,
+        admin_id: i64,
+        action: &str,
+        target_user_id: Option<i64>,
+        details: &serde_json::Value
+    ) -> Result<i64, DatabaseError> {
+        let now = chrono::Utc::now().timestamp();
+        self.postgres_insert_admin_audit_entry(
+            admin_id,
+            action,
+            target_user_id,
+            details,
+            now
+        ).await
+    }
+
+    async fn get_admin_audit_entries(
+        &self,
+        target_user_id: Option<i64>,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AdminAuditEntry>, DatabaseError> {
+        self.postgres_get_admin_audit_entries(target_user_id, before, limit).await
+    }
+}
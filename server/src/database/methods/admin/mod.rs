@@ -0,0 +1,4 @@
+mod postgres;
+mod cached;
+
+mod tests;
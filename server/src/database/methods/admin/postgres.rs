@@ -0,0 +1,61 @@
+use crate::{
+    app_objects::AdminAuditEntry,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    /// Returns the id given to the entry
+    pub async fn postgres_insert_admin_audit_entry(
+        &self,
+        admin_id: i64,
+        action: &str,
+        target_user_id: Option<i64>,
+        details: &serde_json::Value,
+        created_at: i64
+    ) -> Result<i64, DatabaseError> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO admin_audit_log (admin_id, action, target_user_id, details, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            admin_id,
+            action,
+            target_user_id,
+            details,
+            created_at
+        )
+        .fetch_one(&self.postgres_con)
+        .await?;
+        Ok(id)
+    }
+
+    /// Newest entries first, `before` is the id of the last entry of the previous page
+    pub async fn postgres_get_admin_audit_entries(
+        &self,
+        target_user_id: Option<i64>,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AdminAuditEntry>, DatabaseError> {
+        let entries = sqlx::query_as!(
+            AdminAuditEntry,
+            r#"
+            SELECT id, admin_id, action, target_user_id, details, created_at FROM admin_audit_log
+            WHERE ($1::BIGINT IS NULL OR target_user_id = $1)
+            AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+            target_user_id,
+            before,
+            limit
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(entries)
+    }
+}
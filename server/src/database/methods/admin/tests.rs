@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use serial_test::serial;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        AdminAuditStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_admin_audit_entries() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let first_id = db_client.insert_admin_audit_entry(
+            1,
+            "ban_user",
+            Some(470),
+            &json!({ "reason": "spam" })
+        ).await?;
+        let second_id = db_client.insert_admin_audit_entry(
+            1,
+            "unban_user",
+            Some(470),
+            &json!({})
+        ).await?;
+        db_client.insert_admin_audit_entry(
+            1,
+            "search_users",
+            None,
+            &json!({})
+        ).await?;
+
+        // The log is never cleared, only the newest entries are ours
+        let entries = db_client.get_admin_audit_entries(Some(470), None, 2).await?;
+        assert_eq!(entries.iter().map(|entry| entry.id).collect::<Vec<_>>(), vec![second_id, first_id]);
+        assert_eq!(entries[0].action, "unban_user");
+        assert_eq!(entries[1].details, json!({ "reason": "spam" }));
+        assert_eq!(entries[1].target_user_id, Some(470));
+
+        let entries = db_client.get_admin_audit_entries(Some(470), Some(second_id), 1).await?;
+        assert_eq!(entries[0].id, first_id);
+
+        let entries = db_client.get_admin_audit_entries(None, None, 1).await?;
+        assert_eq!(entries[0].target_user_id, None);
+        Ok(())
+    }
+}
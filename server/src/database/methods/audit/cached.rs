@@ -0,0 +1,31 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AuditEvent,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        AuditEventStore
+    }
+};
+
+
+/// Audit events are written once and rarely read, they're never cached
+#[async_trait]
+impl AuditEventStore for DatabaseClientWithCaching {
+    async fn insert_audit_event(
+        &self,
+        audit_event: &AuditEvent
+    ) -> Result<i64, DatabaseError> {
+        self.postgres_insert_audit_event(audit_event).await
+    }
+
+    async fn get_user_audit_events(
+        &self,
+        target_user_id: i64,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AuditEvent>, DatabaseError> {
+        self.postgres_get_user_audit_events(target_user_id, before, limit).await
+    }
+}
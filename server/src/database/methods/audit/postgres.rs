@@ -0,0 +1,60 @@
+use crate::{
+    app_objects::AuditEvent,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    /// Ignores the id of the event, returns the one given by Postgres
+    pub async fn postgres_insert_audit_event(
+        &self,
+        audit_event: &AuditEvent
+    ) -> Result<i64, DatabaseError> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO audit_events (event_type, actor_user_id, target_user_id, ip, user_agent, metadata, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id
+            "#,
+            audit_event.event_type,
+            audit_event.actor_user_id,
+            audit_event.target_user_id,
+            audit_event.ip,
+            audit_event.user_agent,
+            audit_event.metadata,
+            audit_event.created_at
+        )
+        .fetch_one(&self.postgres_con)
+        .await?;
+        Ok(id)
+    }
+
+    /// Newest events first, `before` is the id of the last event of the previous page
+    pub async fn postgres_get_user_audit_events(
+        &self,
+        target_user_id: i64,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AuditEvent>, DatabaseError> {
+        let audit_events = sqlx::query_as!(
+            AuditEvent,
+            r#"
+            SELECT id, event_type, actor_user_id, target_user_id, ip, user_agent, metadata, created_at
+            FROM audit_events
+            WHERE target_user_id = $1
+            AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+            target_user_id,
+            before,
+            limit
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(audit_events)
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use serial_test::serial;
+    use crate::app_objects::{
+        AuditEvent,
+        AuditEventType
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        AuditEventStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_user_audit_events() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let login = AuditEvent::new(AuditEventType::LoginSuccess, Some(490))
+            .with_client(Some("127.0.0.1".to_string()), Some("test agent".to_string()))
+            .with_metadata(json!({ "method": "password" }));
+        let login_id = db_client.insert_audit_event(&login).await?;
+        let ban = AuditEvent::new(AuditEventType::UserBanned, Some(490))
+            .with_actor(Some(1));
+        let ban_id = db_client.insert_audit_event(&ban).await?;
+        db_client.insert_audit_event(&AuditEvent::new(AuditEventType::LoginFailure, None)).await?;
+
+        // The table is append-only, only the newest events are ours
+        let audit_events = db_client.get_user_audit_events(490, None, 2).await?;
+        assert_eq!(audit_events, vec![
+            AuditEvent { id: ban_id, ..ban },
+            AuditEvent { id: login_id, ..login.clone() },
+        ]);
+        assert_eq!(audit_events[0].actor_user_id, Some(1));
+
+        let audit_events = db_client.get_user_audit_events(490, Some(ban_id), 1).await?;
+        assert_eq!(audit_events, vec![AuditEvent { id: login_id, ..login }]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_audit_events_are_append_only() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let id = db_client.insert_audit_event(&AuditEvent::new(AuditEventType::LoginSuccess, Some(490))).await?;
+        for query in ["UPDATE audit_events SET event_type = 'x' WHERE id = $1", "DELETE FROM audit_events WHERE id = $1"] {
+            let res = sqlx::query(query)
+                .bind(id)
+                .execute(&db_client.postgres_con)
+                .await;
+            assert!(res.is_err());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::{app_objects::DmChannel, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    DmStore
+}};
+
+
+#[async_trait]
+impl DmStore for DatabaseClientWithCaching {
+    /// Returns the existing channel when the two users of a direct DM already have one
+    async fn open_dm_channel(
+        &self,
+        channel: &DmChannel,
+        cache_ttl_s: u64
+    ) -> Result<DmChannel, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let channel = db_client.postgres_insert_dm_channel(channel).await?;
+
+        db_client.redis_set_dm_channel(&channel, cache_ttl_s).await?;
+
+        Ok(channel)
+    }
+
+    async fn get_dm_channel_by_id(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<DmChannel>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let channel = db_client.redis_get_dm_channel_by_id(channel_id).await?;
+        if channel.is_some() {
+            return Ok(channel);
+        }
+
+        let channel = db_client.postgres_get_dm_channel_by_id(channel_id).await?;
+        if let Some(channel) = &channel {
+            db_client.redis_set_dm_channel(channel, cache_ttl_s).await?;
+        }
+
+        Ok(channel)
+    }
+
+    /// DM lists are always read from Postgres, only single channels are cached
+    async fn get_user_dm_channels(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<DmChannel>, DatabaseError> {
+        self.postgres_get_user_dm_channels(user_id).await
+    }
+
+    /// Cached channels hold their recipients, so the channel is dropped from the cache
+    async fn add_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64,
+        joined_at: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_add_dm_recipient(channel_id, user_id, joined_at).await?;
+
+        db_client.redis_delete_dm_channel(channel_id).await?;
+
+        Ok(())
+    }
+
+    async fn remove_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_remove_dm_recipient(channel_id, user_id).await?;
+
+        db_client.redis_delete_dm_channel(channel_id).await?;
+
+        Ok(())
+    }
+
+    /// Blocks are always read from Postgres, checking them is a primary key lookup
+    async fn block_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64,
+        created_at: i64
+    ) -> Result<(), DatabaseError> {
+        self.postgres_insert_user_block(user_id, blocked_id, created_at).await
+    }
+
+    async fn unblock_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64
+    ) -> Result<(), DatabaseError> {
+        self.postgres_delete_user_block(user_id, blocked_id).await
+    }
+
+    async fn get_user_blocks(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<i64>, DatabaseError> {
+        self.postgres_get_user_blocks(user_id).await
+    }
+
+    async fn is_blocked_between(
+        &self,
+        user_id: i64,
+        other_user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_is_blocked_between(user_id, other_user_id).await
+    }
+}
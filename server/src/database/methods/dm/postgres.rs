@@ -0,0 +1,313 @@
+use crate::{app_objects::{
+    DmChannel,
+    DmKind
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    /// Inserts the channel with its recipients in a single transaction. Opening a direct DM between
+    /// two users that already have one returns the existing channel instead of the given one.
+    pub async fn postgres_insert_dm_channel(
+        &self,
+        channel: &DmChannel
+    ) -> Result<DmChannel, DatabaseError> {
+        // Only direct DMs fill the pair columns, NULLs never conflict with each other
+        let (user_low_id, user_high_id) = match channel.kind {
+            DmKind::Direct => (
+                channel.recipient_ids.iter().min().copied(),
+                channel.recipient_ids.iter().max().copied()
+            ),
+            DmKind::Group => (None, None),
+        };
+
+        let mut tx = self.postgres_con.begin().await?;
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO dm_channels (id, kind, owner_id, user_low_id, user_high_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_low_id, user_high_id) DO NOTHING
+            "#,
+            channel.id,
+            channel.kind as i16,
+            channel.owner_id,
+            user_low_id,
+            user_high_id,
+            channel.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            tx.rollback().await?;
+            let existing = self.postgres_get_direct_dm_channel(
+                channel.recipient_ids[0],
+                channel.recipient_ids[1]
+            ).await?;
+            return existing.ok_or(DatabaseError::ChannelNotFound(channel.id));
+        }
+        sqlx::query!(
+            r#"
+            INSERT INTO dm_recipients (channel_id, user_id, joined_at)
+            SELECT $1, user_id, $3 FROM UNNEST($2::BIGINT[]) AS user_id
+            "#,
+            channel.id,
+            &channel.recipient_ids,
+            channel.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(channel.clone())
+    }
+
+    pub async fn postgres_get_dm_channel_by_id(
+        &self,
+        channel_id: i64
+    ) -> Result<Option<DmChannel>, DatabaseError> {
+        let channel = sqlx::query_as!(
+            DmChannel,
+            r#"
+            SELECT id, kind AS "kind: DmKind", owner_id, ARRAY(
+                SELECT user_id FROM dm_recipients
+                WHERE dm_recipients.channel_id = dm_channels.id
+                ORDER BY joined_at, user_id
+            ) AS "recipient_ids!", created_at FROM dm_channels
+            WHERE id = $1
+            "#,
+            channel_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(channel)
+    }
+
+    pub async fn postgres_get_direct_dm_channel(
+        &self,
+        user_id: i64,
+        recipient_id: i64
+    ) -> Result<Option<DmChannel>, DatabaseError> {
+        let channel = sqlx::query_as!(
+            DmChannel,
+            r#"
+            SELECT id, kind AS "kind: DmKind", owner_id, ARRAY(
+                SELECT user_id FROM dm_recipients
+                WHERE dm_recipients.channel_id = dm_channels.id
+                ORDER BY joined_at, user_id
+            ) AS "recipient_ids!", created_at FROM dm_channels
+            WHERE user_low_id = LEAST($1::BIGINT, $2::BIGINT)
+                AND user_high_id = GREATEST($1::BIGINT, $2::BIGINT)
+            "#,
+            user_id,
+            recipient_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(channel)
+    }
+
+    /// Returns the DM channels the user is a recipient of, oldest first
+    pub async fn postgres_get_user_dm_channels(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<DmChannel>, DatabaseError> {
+        let channels = sqlx::query_as!(
+            DmChannel,
+            r#"
+            SELECT id, kind AS "kind: DmKind", owner_id, ARRAY(
+                SELECT user_id FROM dm_recipients
+                WHERE dm_recipients.channel_id = dm_channels.id
+                ORDER BY joined_at, user_id
+            ) AS "recipient_ids!", created_at FROM dm_channels
+            WHERE id IN (SELECT channel_id FROM dm_recipients WHERE user_id = $1)
+            ORDER BY id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(channels)
+    }
+
+    /// Adding a user that already is a recipient does nothing
+    pub async fn postgres_add_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64,
+        joined_at: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO dm_recipients (channel_id, user_id, joined_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            channel_id,
+            user_id,
+            joined_at
+        )
+        .execute(&self.postgres_con)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db_error) if db_error.is_foreign_key_violation() => {
+                DatabaseError::ChannelNotFound(channel_id)
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
+        Ok(())
+    }
+
+    /// Removes the recipient from a group DM. Ownership passes to the recipient that joined first,
+    /// the channel and its messages are deleted once the last recipient is gone.
+    pub async fn postgres_remove_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM dm_recipients
+            WHERE channel_id = $1 AND user_id = $2
+            "#,
+            channel_id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::RecipientNotFound(user_id));
+        }
+        let next_owner_id = sqlx::query_scalar!(
+            r#"
+            SELECT user_id FROM dm_recipients
+            WHERE channel_id = $1
+            ORDER BY joined_at, user_id
+            LIMIT 1
+            "#,
+            channel_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        match next_owner_id {
+            Some(next_owner_id) => {
+                sqlx::query!(
+                    r#"
+                    UPDATE dm_channels
+                    SET owner_id = $1
+                    WHERE id = $2 AND owner_id = $3
+                    "#,
+                    next_owner_id,
+                    channel_id,
+                    user_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            },
+            None => {
+                sqlx::query!(
+                    r#"
+                    DELETE FROM messages
+                    WHERE channel_id = $1
+                    "#,
+                    channel_id
+                )
+                .execute(&mut *tx)
+                .await?;
+                sqlx::query!(
+                    r#"
+                    DELETE FROM dm_channels
+                    WHERE id = $1
+                    "#,
+                    channel_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            },
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Blocking a user twice does nothing
+    pub async fn postgres_insert_user_block(
+        &self,
+        user_id: i64,
+        blocked_id: i64,
+        created_at: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO user_blocks (user_id, blocked_id, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            user_id,
+            blocked_id,
+            created_at
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    /// Unblocking a user that isn't blocked does nothing
+    pub async fn postgres_delete_user_block(
+        &self,
+        user_id: i64,
+        blocked_id: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM user_blocks
+            WHERE user_id = $1 AND blocked_id = $2
+            "#,
+            user_id,
+            blocked_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the ids of the users blocked by the user
+    pub async fn postgres_get_user_blocks(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<i64>, DatabaseError> {
+        let blocked_ids = sqlx::query_scalar!(
+            r#"
+            SELECT blocked_id FROM user_blocks
+            WHERE user_id = $1
+            ORDER BY created_at, blocked_id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(blocked_ids)
+    }
+
+    /// True if either of the users blocked the other
+    pub async fn postgres_is_blocked_between(
+        &self,
+        user_id: i64,
+        other_user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let blocked = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM user_blocks
+                WHERE (user_id = $1 AND blocked_id = $2)
+                    OR (user_id = $2 AND blocked_id = $1)
+            ) AS "blocked!"
+            "#,
+            user_id,
+            other_user_id
+        )
+        .fetch_one(&self.postgres_con)
+        .await?;
+        Ok(blocked)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{app_objects::DmChannel, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_dm_channel(
+        &self,
+        channel: &DmChannel,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("dm_channel:{}", channel.id)
+            )
+            .arg(channel.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_dm_channel_by_id(
+        &self,
+        channel_id: i64
+    ) -> Result<Option<DmChannel>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let channel: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("dm_channel:{}", channel_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match channel {
+            Some(channel) => Ok(Some(DmChannel::from_json(&channel)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_dm_channel(
+        &self,
+        channel_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("dm_channel:{}", channel_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        DmChannel,
+        User
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        DmStore
+    };
+
+    const TEST_DIRECT_ID: i64 = 7200;
+    const TEST_GROUP_ID: i64 = 7201;
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Inserts the users the tests refer to, guild members, recipients and blocks need an existing user
+    async fn create_test_users(db_client: &DatabaseClientWithCaching, user_ids: &[i64]) {
+        for user_id in user_ids {
+            if db_client.postgres_get_user_by_id(*user_id).await.unwrap().is_none() {
+                let user = User {
+                    id: *user_id,
+                    email: format!("user{}@test.local", user_id),
+                    ..User::default()
+                };
+                db_client.postgres_insert_user(&user).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_direct_dm_is_opened_once_per_pair() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_users(&db_client, &[7210, 7211]).await;
+
+        // The channel is kept between runs, opening it again has to return the same one
+        let channel = DmChannel::new_direct(TEST_DIRECT_ID, 7210, 7211, 0);
+        let opened = db_client.open_dm_channel(&channel, 60).await?;
+        assert_eq!(opened.id, TEST_DIRECT_ID);
+
+        let reversed = DmChannel::new_direct(TEST_DIRECT_ID + 100, 7211, 7210, 0);
+        let opened = db_client.open_dm_channel(&reversed, 60).await?;
+        assert_eq!(opened.id, TEST_DIRECT_ID);
+        assert_eq!(opened.owner_id, None);
+        assert!(opened.is_recipient(7210) && opened.is_recipient(7211));
+        assert_eq!(opened.other_recipient(7210), Some(7211));
+        assert!(db_client.get_user_dm_channels(7211).await?.iter().any(
+            |channel| channel.id == TEST_DIRECT_ID
+        ));
+
+        db_client.unblock_user(7210, 7211).await?;
+        assert!(!db_client.is_blocked_between(7211, 7210).await?);
+        db_client.block_user(7210, 7211, 0).await?;
+        // Blocking twice does nothing
+        db_client.block_user(7210, 7211, 0).await?;
+        assert_eq!(db_client.get_user_blocks(7210).await?, vec![7211]);
+        assert!(db_client.get_user_blocks(7211).await?.is_empty());
+        assert!(db_client.is_blocked_between(7211, 7210).await?);
+        db_client.unblock_user(7210, 7211).await?;
+        assert!(!db_client.is_blocked_between(7210, 7211).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_group_dm_recipients() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_users(&db_client, &[7210, 7211, 7212, 7213]).await;
+        for user_id in [7210, 7211, 7212, 7213] {
+            let _ = db_client.remove_dm_recipient(TEST_GROUP_ID, user_id).await;
+        }
+
+        let channel = DmChannel::new_group(TEST_GROUP_ID, 7210, &[7211, 7212], 0);
+        assert_eq!(db_client.open_dm_channel(&channel, 60).await?, channel);
+        assert_eq!(db_client.get_dm_channel_by_id(TEST_GROUP_ID, 60).await?, Some(channel));
+
+        db_client.add_dm_recipient(TEST_GROUP_ID, 7213, 1).await?;
+        // Adding a recipient twice does nothing
+        db_client.add_dm_recipient(TEST_GROUP_ID, 7213, 2).await?;
+        let channel = db_client.get_dm_channel_by_id(TEST_GROUP_ID, 60).await?.unwrap();
+        assert_eq!(channel.recipient_ids, vec![7210, 7211, 7212, 7213]);
+
+        // The recipient that joined first takes over
+        db_client.remove_dm_recipient(TEST_GROUP_ID, 7210).await?;
+        let channel = db_client.get_dm_channel_by_id(TEST_GROUP_ID, 60).await?.unwrap();
+        assert_eq!(channel.owner_id, Some(7211));
+        assert!(matches!(
+            db_client.remove_dm_recipient(TEST_GROUP_ID, 7210).await,
+            Err(DatabaseError::RecipientNotFound(7210))
+        ));
+
+        for user_id in [7211, 7212, 7213] {
+            db_client.remove_dm_recipient(TEST_GROUP_ID, user_id).await?;
+        }
+        assert_eq!(db_client.get_dm_channel_by_id(TEST_GROUP_ID, 60).await?, None);
+        assert!(matches!(
+            db_client.add_dm_recipient(TEST_GROUP_ID, 7210, 3).await,
+            Err(DatabaseError::ChannelNotFound(TEST_GROUP_ID))
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::EmailJob,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        EmailJobStore
+    }
+};
+
+
+/// The queue has to survive restarts and be shared by every instance, it lives in Postgres only
+#[async_trait]
+impl EmailJobStore for DatabaseClientWithCaching {
+    async fn enqueue_email_job(
+        &self,
+        email_job: &EmailJob
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_enqueue_email_job(email_job).await
+    }
+
+    async fn claim_email_jobs(
+        &self,
+        now: i64,
+        lease_s: i64,
+        limit: i64
+    ) -> Result<Vec<EmailJob>, DatabaseError> {
+        self.postgres_claim_email_jobs(now, lease_s, limit).await
+    }
+
+    async fn complete_email_job(
+        &self,
+        job_id: i64,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        self.postgres_complete_email_job(job_id, now).await
+    }
+
+    async fn fail_email_job(
+        &self,
+        job_id: i64,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        self.postgres_fail_email_job(job_id, error, retry_at, now).await
+    }
+
+    async fn delete_finished_email_jobs(
+        &self,
+        updated_before: i64
+    ) -> Result<u64, DatabaseError> {
+        self.postgres_delete_finished_email_jobs(updated_before).await
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    app_objects::{
+        EmailJob,
+        EmailJobStatus
+    },
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn postgres_enqueue_email_job(
+        &self,
+        email_job: &EmailJob
+    ) -> Result<bool, DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            INSERT INTO email_jobs (idempotency_key, sender, recipients, message, status, attempts, run_at, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, 0, $6, $7, $7)
+            ON CONFLICT (idempotency_key) DO NOTHING
+            "#,
+            email_job.idempotency_key,
+            email_job.sender,
+            &email_job.recipients,
+            email_job.message,
+            EmailJobStatus::Pending.as_str(),
+            email_job.run_at,
+            email_job.created_at
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(res.rows_affected() == 1)
+    }
+
+    /// `SKIP LOCKED` lets concurrent workers claim different jobs instead of waiting on each other
+    pub async fn postgres_claim_email_jobs(
+        &self,
+        now: i64,
+        lease_s: i64,
+        limit: i64
+    ) -> Result<Vec<EmailJob>, DatabaseError> {
+        let email_jobs = sqlx::query_as!(
+            EmailJob,
+            r#"
+            UPDATE email_jobs
+            SET status = $1, attempts = attempts + 1, locked_until = $4 + $5, updated_at = $4
+            WHERE id IN (
+                SELECT id FROM email_jobs
+                WHERE (status = $2 AND run_at <= $4)
+                OR (status = $1 AND locked_until <= $4)
+                ORDER BY run_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, idempotency_key, sender, recipients, message, status, attempts, last_error,
+                run_at, locked_until, created_at, updated_at
+            "#,
+            EmailJobStatus::Sending.as_str(),
+            EmailJobStatus::Pending.as_str(),
+            limit,
+            now,
+            lease_s
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(email_jobs)
+    }
+
+    pub async fn postgres_complete_email_job(
+        &self,
+        job_id: i64,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            UPDATE email_jobs
+            SET status = $2, message = '', locked_until = NULL, last_error = NULL, updated_at = $3
+            WHERE id = $1
+            "#,
+            job_id,
+            EmailJobStatus::Sent.as_str(),
+            now
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_fail_email_job(
+        &self,
+        job_id: i64,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        let status = match retry_at {
+            Some(_) => EmailJobStatus::Pending,
+            None => EmailJobStatus::Dead,
+        };
+        sqlx::query!(
+            r#"
+            UPDATE email_jobs
+            SET status = $2, run_at = COALESCE($3, run_at), locked_until = NULL, last_error = $4, updated_at = $5,
+                message = CASE WHEN $2 = $6 THEN '' ELSE message END
+            WHERE id = $1
+            "#,
+            job_id,
+            status.as_str(),
+            retry_at,
+            error,
+            now,
+            EmailJobStatus::Dead.as_str()
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_delete_finished_email_jobs(
+        &self,
+        updated_before: i64
+    ) -> Result<u64, DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM email_jobs
+            WHERE status IN ($1, $2) AND updated_at < $3
+            "#,
+            EmailJobStatus::Sent.as_str(),
+            EmailJobStatus::Dead.as_str(),
+            updated_before
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(res.rows_affected())
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        EmailJob,
+        EmailJobStatus
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        EmailJobStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_email_job_lifecycle() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        sqlx::query!("DELETE FROM email_jobs").execute(&db_client.postgres_con).await?;
+
+        let email_job = EmailJob::new(
+            "test:lifecycle".to_string(),
+            Some("sender@example.com".to_string()),
+            vec!["recipient@example.com".to_string()],
+            "raw email".to_string()
+        );
+        assert_eq!(db_client.enqueue_email_job(&email_job).await?, true);
+        assert_eq!(db_client.enqueue_email_job(&email_job).await?, false);
+
+        let now = email_job.run_at;
+        let claimed = db_client.claim_email_jobs(now, 60, 10).await?;
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].status, EmailJobStatus::Sending.as_str());
+        assert_eq!(claimed[0].attempts, 1);
+        assert_eq!(claimed[0].recipients, email_job.recipients);
+        // Claimed jobs aren't handed out again while their lease runs
+        assert_eq!(db_client.claim_email_jobs(now, 60, 10).await?.len(), 0);
+
+        db_client.fail_email_job(claimed[0].id, "timeout", Some(now + 30), now).await?;
+        assert_eq!(db_client.claim_email_jobs(now + 29, 60, 10).await?.len(), 0);
+        let claimed = db_client.claim_email_jobs(now + 30, 60, 10).await?;
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].attempts, 2);
+        assert_eq!(claimed[0].last_error.as_deref(), Some("timeout"));
+
+        // The worker died, the job is due again once the lease ran out
+        let claimed = db_client.claim_email_jobs(now + 90, 60, 10).await?;
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].attempts, 3);
+
+        db_client.complete_email_job(claimed[0].id, now + 91).await?;
+        assert_eq!(db_client.claim_email_jobs(now + 10_000, 60, 10).await?.len(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_dead_email_jobs_are_not_claimed() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        sqlx::query!("DELETE FROM email_jobs").execute(&db_client.postgres_con).await?;
+
+        let email_job = EmailJob::new(
+            "test:dead".to_string(),
+            None,
+            vec!["recipient@example.com".to_string()],
+            "raw email".to_string()
+        );
+        db_client.enqueue_email_job(&email_job).await?;
+        let now = email_job.run_at;
+        let claimed = db_client.claim_email_jobs(now, 60, 10).await?;
+        db_client.fail_email_job(claimed[0].id, "mailbox unavailable", None, now).await?;
+
+        assert_eq!(db_client.claim_email_jobs(now + 10_000, 60, 10).await?.len(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_finished_email_jobs_are_emptied_and_deleted() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        sqlx::query!("DELETE FROM email_jobs").execute(&db_client.postgres_con).await?;
+
+        let mut claimed = Vec::new();
+        for key in ["test:sent", "test:dead", "test:pending"] {
+            let email_job = EmailJob::new(
+                key.to_string(),
+                None,
+                vec!["recipient@example.com".to_string()],
+                "raw email with a token".to_string()
+            );
+            db_client.enqueue_email_job(&email_job).await?;
+            claimed.extend(db_client.claim_email_jobs(email_job.run_at, 60, 10).await?);
+        }
+        let now = claimed[0].run_at;
+        db_client.complete_email_job(claimed[0].id, now).await?;
+        db_client.fail_email_job(claimed[1].id, "mailbox unavailable", None, now).await?;
+        db_client.fail_email_job(claimed[2].id, "timeout", Some(now + 30), now).await?;
+
+        let messages = sqlx::query!("SELECT idempotency_key, message FROM email_jobs ORDER BY id")
+            .fetch_all(&db_client.postgres_con)
+            .await?
+            .into_iter()
+            .map(|row| (row.idempotency_key, row.message))
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec![
+            ("test:sent".to_string(), "".to_string()),
+            ("test:dead".to_string(), "".to_string()),
+            ("test:pending".to_string(), "raw email with a token".to_string()),
+        ]);
+
+        assert_eq!(db_client.delete_finished_email_jobs(now).await?, 0);
+        assert_eq!(db_client.delete_finished_email_jobs(now + 1).await?, 2);
+        assert_eq!(db_client.claim_email_jobs(now + 30, 60, 10).await?.len(), 1);
+        Ok(())
+    }
+}
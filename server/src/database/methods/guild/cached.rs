@@ -0,0 +1,233 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::{app_objects::{
+    Channel,
+    Guild,
+    GuildMember,
+    Invite,
+    Role
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    GuildStore
+}};
+
+
+#[async_trait]
+impl GuildStore for DatabaseClientWithCaching {
+    async fn create_guild(
+        &self,
+        guild: &Guild,
+        owner: &GuildMember,
+        everyone_role: &Role,
+        channels: &[Channel],
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_create_guild(guild, owner, everyone_role, channels).await?;
+
+        db_client.redis_set_guild(guild, cache_ttl_s).await?;
+        db_client.redis_set_guild_member(owner, cache_ttl_s).await?;
+        for channel in channels {
+            db_client.redis_set_channel(channel, cache_ttl_s).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_guild_by_id(
+        &self,
+        guild_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Guild>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let guild = db_client.redis_get_guild_by_id(guild_id).await?;
+        if guild.is_some() {
+            return Ok(guild);
+        }
+
+        let guild = db_client.postgres_get_guild_by_id(guild_id).await?;
+        if let Some(guild) = &guild {
+            db_client.redis_set_guild(guild, cache_ttl_s).await?;
+        }
+
+        Ok(guild)
+    }
+
+    /// Guild lists are always read from Postgres, only single guilds are cached
+    async fn get_user_guilds(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Guild>, DatabaseError> {
+        self.postgres_get_user_guilds(user_id).await
+    }
+
+    async fn delete_guild(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let channel_ids: Vec<i64> = db_client.postgres_get_guild_channels(guild_id).await?
+            .iter()
+            .map(|channel| channel.id)
+            .collect();
+        let member_ids = db_client.postgres_get_guild_member_ids(guild_id).await?;
+        db_client.postgres_delete_guild_by_id(guild_id).await?;
+        db_client.redis_delete_guild(guild_id).await?;
+        db_client.redis_delete_guild_roles(guild_id).await?;
+        db_client.redis_delete_channels(&channel_ids).await?;
+        db_client.redis_delete_guild_members(guild_id, &member_ids).await?;
+
+        Ok(())
+    }
+
+    async fn insert_channel(
+        &self,
+        channel: &Channel,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_insert_channel(channel).await?;
+
+        db_client.redis_set_channel(channel, cache_ttl_s).await?;
+
+        Ok(())
+    }
+
+    async fn get_channel_by_id(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Channel>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let channel = db_client.redis_get_channel_by_id(channel_id).await?;
+        if channel.is_some() {
+            return Ok(channel);
+        }
+
+        let channel = db_client.postgres_get_channel_by_id(channel_id).await?;
+        if let Some(channel) = &channel {
+            db_client.redis_set_channel(channel, cache_ttl_s).await?;
+        }
+
+        Ok(channel)
+    }
+
+    /// Channel lists are always read from Postgres, only single channels are cached
+    async fn get_guild_channels(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<Channel>, DatabaseError> {
+        self.postgres_get_guild_channels(guild_id).await
+    }
+
+    async fn rename_channel(
+        &self,
+        channel_id: i64,
+        name: &str,
+        cache_ttl_s: u64
+    ) -> Result<Channel, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let channel = db_client.postgres_rename_channel(channel_id, name).await?;
+        db_client.redis_set_channel(&channel, cache_ttl_s).await?;
+
+        Ok(channel)
+    }
+
+    /// Returns all channels of the guild in their new order
+    async fn update_channel_positions(
+        &self,
+        guild_id: i64,
+        positions: &[(i64, i32)],
+        cache_ttl_s: u64
+    ) -> Result<Vec<Channel>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        db_client.postgres_update_channel_positions(guild_id, positions).await?;
+        let channels = db_client.postgres_get_guild_channels(guild_id).await?;
+        for channel in &channels {
+            db_client.redis_set_channel(channel, cache_ttl_s).await?;
+        }
+
+        Ok(channels)
+    }
+
+    async fn delete_channel(
+        &self,
+        channel_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        // Drop the channel from Redis even if Postgres fails, the next read will repopulate it
+        let redis_res = db_client.redis_delete_channels(&[channel_id]).await;
+        let orphaned_channels = db_client.postgres_delete_channel_by_id(channel_id).await?;
+        redis_res?;
+        db_client.redis_delete_channels(&orphaned_channels).await?;
+
+        Ok(())
+    }
+
+    async fn insert_guild_member(
+        &self,
+        member: &GuildMember,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_insert_guild_member(member).await?;
+
+        db_client.redis_set_guild_member(member, cache_ttl_s).await?;
+
+        Ok(())
+    }
+
+    /// Only members are cached, a user that isn't in the guild is always looked up in Postgres
+    async fn get_guild_member(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<GuildMember>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let member = db_client.redis_get_guild_member(guild_id, user_id).await?;
+        if member.is_some() {
+            return Ok(member);
+        }
+
+        let member = db_client.postgres_get_guild_member(guild_id, user_id).await?;
+        if let Some(member) = &member {
+            db_client.redis_set_guild_member(member, cache_ttl_s).await?;
+        }
+
+        Ok(member)
+    }
+
+    /// Invites are only read when joining, which goes straight to Postgres
+    async fn insert_invite(
+        &self,
+        invite: &Invite
+    ) -> Result<(), DatabaseError> {
+        self.postgres_insert_invite(invite).await
+    }
+
+    async fn join_guild_with_invite(
+        &self,
+        code: &str,
+        user_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<GuildMember, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let now = chrono::Utc::now().timestamp();
+
+        let member = db_client.postgres_join_guild_with_invite(code, user_id, now).await?;
+        db_client.redis_set_guild_member(&member, cache_ttl_s).await?;
+
+        Ok(member)
+    }
+}
@@ -0,0 +1,442 @@
+use crate::{app_objects::{
+    Channel,
+    ChannelKind,
+    Guild,
+    GuildMember,
+    Invite,
+    Role
+}, database::{
+    methods::{
+        role::insert_role,
+        DatabaseError
+    },
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    /// Inserts the guild together with its owner, @everyone role and initial channels in a single transaction
+    pub async fn postgres_create_guild(
+        &self,
+        guild: &Guild,
+        owner: &GuildMember,
+        everyone_role: &Role,
+        channels: &[Channel]
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO guilds (id, name, owner_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            guild.id,
+            guild.name,
+            guild.owner_id,
+            guild.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO guild_members (guild_id, user_id, nickname, joined_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            owner.guild_id,
+            owner.user_id,
+            owner.nickname,
+            owner.joined_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        insert_role(&mut *tx, everyone_role).await?;
+        for channel in channels {
+            insert_channel(&mut *tx, channel).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_guild_by_id(
+        &self,
+        guild_id: i64
+    ) -> Result<Option<Guild>, DatabaseError> {
+        let guild = sqlx::query_as!(
+            Guild,
+            r#"
+            SELECT id, name, owner_id, created_at FROM guilds
+            WHERE id = $1
+            "#,
+            guild_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(guild)
+    }
+
+    pub async fn postgres_get_user_guilds(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Guild>, DatabaseError> {
+        let guilds = sqlx::query_as!(
+            Guild,
+            r#"
+            SELECT guilds.id, guilds.name, guilds.owner_id, guilds.created_at FROM guilds
+            JOIN guild_members ON guild_members.guild_id = guilds.id
+            WHERE guild_members.user_id = $1
+            ORDER BY guild_members.joined_at, guilds.id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(guilds)
+    }
+
+    pub async fn postgres_delete_guild_by_id(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        // Messages aren't tied to the channels table, everything else cascades
+        sqlx::query!(
+            r#"
+            DELETE FROM messages
+            WHERE channel_id IN (SELECT id FROM channels WHERE guild_id = $1)
+            "#,
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM guilds
+            WHERE id = $1
+            "#,
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::GuildNotFound(guild_id));
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn postgres_insert_channel(
+        &self,
+        channel: &Channel
+    ) -> Result<(), DatabaseError> {
+        insert_channel(&self.postgres_con, channel).await
+    }
+
+    pub async fn postgres_get_channel_by_id(
+        &self,
+        channel_id: i64
+    ) -> Result<Option<Channel>, DatabaseError> {
+        let channel = sqlx::query_as!(
+            Channel,
+            r#"
+            SELECT id, guild_id, name, kind AS "kind: ChannelKind", position, parent_id, created_at FROM channels
+            WHERE id = $1
+            "#,
+            channel_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(channel)
+    }
+
+    /// Returns the channels of the guild in display order
+    pub async fn postgres_get_guild_channels(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<Channel>, DatabaseError> {
+        let channels = sqlx::query_as!(
+            Channel,
+            r#"
+            SELECT id, guild_id, name, kind AS "kind: ChannelKind", position, parent_id, created_at FROM channels
+            WHERE guild_id = $1
+            ORDER BY position, id
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(channels)
+    }
+
+    pub async fn postgres_rename_channel(
+        &self,
+        channel_id: i64,
+        name: &str
+    ) -> Result<Channel, DatabaseError> {
+        let channel = sqlx::query_as!(
+            Channel,
+            r#"
+            UPDATE channels
+            SET name = $1
+            WHERE id = $2
+            RETURNING id, guild_id, name, kind AS "kind: ChannelKind", position, parent_id, created_at
+            "#,
+            name,
+            channel_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        match channel {
+            Some(channel) => Ok(channel),
+            None => Err(DatabaseError::ChannelNotFound(channel_id)),
+        }
+    }
+
+    /// Applies all position changes or none of them if one of the channels isn't in the guild
+    pub async fn postgres_update_channel_positions(
+        &self,
+        guild_id: i64,
+        positions: &[(i64, i32)]
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        for (channel_id, position) in positions {
+            let res = sqlx::query!(
+                r#"
+                UPDATE channels
+                SET position = $1
+                WHERE id = $2 AND guild_id = $3
+                "#,
+                position,
+                channel_id,
+                guild_id
+            )
+            .execute(&mut *tx)
+            .await?;
+            if res.rows_affected() == 0 {
+                return Err(DatabaseError::ChannelNotFound(*channel_id));
+            }
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Deletes the channel with its messages. Channels inside a deleted category are kept
+    /// without a parent, their ids are returned so cached copies can be dropped.
+    pub async fn postgres_delete_channel_by_id(
+        &self,
+        channel_id: i64
+    ) -> Result<Vec<i64>, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let orphaned_channels = sqlx::query_scalar!(
+            r#"
+            UPDATE channels
+            SET parent_id = NULL
+            WHERE parent_id = $1
+            RETURNING id
+            "#,
+            channel_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM messages
+            WHERE channel_id = $1
+            "#,
+            channel_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM channels
+            WHERE id = $1
+            "#,
+            channel_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::ChannelNotFound(channel_id));
+        }
+        tx.commit().await?;
+        Ok(orphaned_channels)
+    }
+
+    pub async fn postgres_insert_guild_member(
+        &self,
+        member: &GuildMember
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO guild_members (guild_id, user_id, nickname, joined_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            member.guild_id,
+            member.user_id,
+            member.nickname,
+            member.joined_at
+        )
+        .execute(&self.postgres_con)
+        .await
+        .map_err(|e| member_insert_error(e, member.user_id))?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_guild_member(
+        &self,
+        guild_id: i64,
+        user_id: i64
+    ) -> Result<Option<GuildMember>, DatabaseError> {
+        let member = sqlx::query_as!(
+            GuildMember,
+            r#"
+            SELECT guild_id, user_id, nickname, joined_at, ARRAY(
+                SELECT role_id FROM guild_member_roles
+                WHERE guild_member_roles.guild_id = guild_members.guild_id
+                    AND guild_member_roles.user_id = guild_members.user_id
+                ORDER BY role_id
+            ) AS "role_ids!" FROM guild_members
+            WHERE guild_id = $1 AND user_id = $2
+            "#,
+            guild_id,
+            user_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(member)
+    }
+
+    pub async fn postgres_get_guild_member_ids(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<i64>, DatabaseError> {
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT user_id FROM guild_members
+            WHERE guild_id = $1
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(user_ids)
+    }
+
+    pub async fn postgres_insert_invite(
+        &self,
+        invite: &Invite
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO invites (code, guild_id, creator_id, created_at, expires_at, max_uses, uses)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            invite.code,
+            invite.guild_id,
+            invite.creator_id,
+            invite.created_at,
+            invite.expires_at,
+            invite.max_uses,
+            invite.uses
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_invite_by_code(
+        &self,
+        code: &str
+    ) -> Result<Option<Invite>, DatabaseError> {
+        let invite = sqlx::query_as!(
+            Invite,
+            r#"
+            SELECT code, guild_id, creator_id, created_at, expires_at, max_uses, uses FROM invites
+            WHERE code = $1
+            "#,
+            code
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(invite)
+    }
+
+    /// Uses up the invite and adds the user to its guild. The use is only counted if the user
+    /// actually joined, an expired, used up or unknown invite returns `InviteNotFound`.
+    pub async fn postgres_join_guild_with_invite(
+        &self,
+        code: &str,
+        user_id: i64,
+        now: i64
+    ) -> Result<GuildMember, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let guild_id = sqlx::query_scalar!(
+            r#"
+            UPDATE invites
+            SET uses = uses + 1
+            WHERE code = $1
+                AND (expires_at IS NULL OR expires_at > $2)
+                AND (max_uses IS NULL OR uses < max_uses)
+            RETURNING guild_id
+            "#,
+            code,
+            now
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        let guild_id = match guild_id {
+            Some(guild_id) => guild_id,
+            None => return Err(DatabaseError::InviteNotFound(code.to_string())),
+        };
+
+        let member = GuildMember::new(guild_id, user_id, now);
+        sqlx::query!(
+            r#"
+            INSERT INTO guild_members (guild_id, user_id, nickname, joined_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            member.guild_id,
+            member.user_id,
+            member.nickname,
+            member.joined_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| member_insert_error(e, user_id))?;
+        tx.commit().await?;
+        Ok(member)
+    }
+}
+
+async fn insert_channel<'e, E: sqlx::PgExecutor<'e>>(
+    executor: E,
+    channel: &Channel
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO channels (id, guild_id, name, kind, position, parent_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        channel.id,
+        channel.guild_id,
+        channel.name,
+        channel.kind as i16,
+        channel.position,
+        channel.parent_id,
+        channel.created_at
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+fn member_insert_error(
+    e: sqlx::Error,
+    user_id: i64
+) -> DatabaseError {
+    match e {
+        sqlx::Error::Database(ref db_error) if db_error.is_unique_violation() => {
+            DatabaseError::MemberAlreadyExists(user_id)
+        },
+        e => DatabaseError::SQLXError(e),
+    }
+}
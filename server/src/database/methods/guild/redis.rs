@@ -0,0 +1,172 @@
+use crate::{app_objects::{
+    Channel,
+    Guild,
+    GuildMember
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_guild(
+        &self,
+        guild: &Guild,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("guild:{}", guild.id)
+            )
+            .arg(guild.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_guild_by_id(
+        &self,
+        guild_id: i64
+    ) -> Result<Option<Guild>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let guild: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("guild:{}", guild_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match guild {
+            Some(guild) => Ok(Some(Guild::from_json(&guild)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_guild(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("guild:{}", guild_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_set_channel(
+        &self,
+        channel: &Channel,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("channel:{}", channel.id)
+            )
+            .arg(channel.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_channel_by_id(
+        &self,
+        channel_id: i64
+    ) -> Result<Option<Channel>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let channel: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("channel:{}", channel_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match channel {
+            Some(channel) => Ok(Some(Channel::from_json(&channel)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Also drops the cached permission overwrites of the channels
+    pub async fn redis_delete_channels(
+        &self,
+        channel_ids: &[i64]
+    ) -> Result<(), DatabaseError> {
+        if channel_ids.is_empty() {
+            return Ok(());
+        }
+        let mut con = self.redis_con.clone();
+        let keys: Vec<String> = channel_ids.iter()
+            .flat_map(|channel_id| [
+                format!("channel:{}", channel_id),
+                format!("channel:{}:overwrites", channel_id)
+            ])
+            .collect();
+        let _: () = redis::cmd("DEL")
+            .arg(keys)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_set_guild_member(
+        &self,
+        member: &GuildMember,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("guild:{}:member:{}", member.guild_id, member.user_id)
+            )
+            .arg(member.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_guild_member(
+        &self,
+        guild_id: i64,
+        user_id: i64
+    ) -> Result<Option<GuildMember>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let member: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("guild:{}:member:{}", guild_id, user_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match member {
+            Some(member) => Ok(Some(GuildMember::from_json(&member)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_guild_members(
+        &self,
+        guild_id: i64,
+        user_ids: &[i64]
+    ) -> Result<(), DatabaseError> {
+        if user_ids.is_empty() {
+            return Ok(());
+        }
+        let mut con = self.redis_con.clone();
+        let keys: Vec<String> = user_ids.iter()
+            .map(|user_id| format!("guild:{}:member:{}", guild_id, user_id))
+            .collect();
+        let _: () = redis::cmd("DEL")
+            .arg(keys)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        Channel,
+        ChannelKind,
+        Guild,
+        GuildMember,
+        Invite,
+        Role,
+        User
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        GuildStore
+    };
+
+    const TEST_GUILD_ID: i64 = 6900;
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Inserts the users the tests refer to, guild members, recipients and blocks need an existing user
+    async fn create_test_users(db_client: &DatabaseClientWithCaching, user_ids: &[i64]) {
+        for user_id in user_ids {
+            if db_client.postgres_get_user_by_id(*user_id).await.unwrap().is_none() {
+                let user = User {
+                    id: *user_id,
+                    email: format!("user{}@test.local", user_id),
+                    ..User::default()
+                };
+                db_client.postgres_insert_user(&user).await.unwrap();
+            }
+        }
+    }
+
+    async fn delete_guild(db_client: &DatabaseClientWithCaching, guild_id: i64) {
+        let res = db_client.delete_guild(guild_id).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::GuildNotFound(_) => {},
+                e => panic!("Error deleting guild: {:?}", e)
+            }
+        }
+    }
+
+    fn get_test_channel(id: i64, kind: ChannelKind, position: i32, parent_id: Option<i64>) -> Channel {
+        Channel::new(
+            id,
+            TEST_GUILD_ID,
+            format!("channel {}", id),
+            kind,
+            position,
+            parent_id,
+            chrono::Utc::now().timestamp()
+        )
+    }
+
+    /// Creates the test guild owned by user 6910 with a category (6901) holding a text channel (6902)
+    async fn create_test_guild(db_client: &DatabaseClientWithCaching) -> (Guild, Vec<Channel>) {
+        delete_guild(db_client, TEST_GUILD_ID).await;
+        create_test_users(db_client, &[6910, 6911, 6912]).await;
+        let now = chrono::Utc::now().timestamp();
+        let guild = Guild::new(TEST_GUILD_ID, "test guild".to_string(), 6910, now);
+        let owner = GuildMember::new(TEST_GUILD_ID, 6910, now);
+        let channels = vec![
+            get_test_channel(6901, ChannelKind::Category, 0, None),
+            get_test_channel(6902, ChannelKind::Text, 1, Some(6901)),
+        ];
+        let everyone_role = Role::everyone(TEST_GUILD_ID, now);
+        db_client.create_guild(&guild, &owner, &everyone_role, &channels, 60).await.unwrap();
+        (guild, channels)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_and_get_guild_with_caching() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let (guild, channels) = create_test_guild(&db_client).await;
+
+        assert_eq!(db_client.redis_get_guild_by_id(TEST_GUILD_ID).await?, Some(guild.clone()));
+        db_client.redis_delete_guild(TEST_GUILD_ID).await?;
+        assert_eq!(db_client.get_guild_by_id(TEST_GUILD_ID, 60).await?, Some(guild.clone()));
+        assert_eq!(db_client.redis_get_guild_by_id(TEST_GUILD_ID).await?, Some(guild.clone()));
+
+        assert_eq!(db_client.get_guild_channels(TEST_GUILD_ID).await?, channels);
+        assert!(db_client.get_user_guilds(6910).await?.contains(&guild));
+        assert!(db_client.get_guild_member(TEST_GUILD_ID, 6910, 60).await?.is_some());
+        assert!(db_client.get_guild_member(TEST_GUILD_ID, 6911, 60).await?.is_none());
+
+        delete_guild(&db_client, TEST_GUILD_ID).await;
+        assert_eq!(db_client.get_guild_by_id(TEST_GUILD_ID, 60).await?, None);
+        assert_eq!(db_client.get_channel_by_id(6902, 60).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_channel_changes_refresh_cache() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_guild(&db_client).await;
+
+        let renamed = db_client.rename_channel(6902, "renamed", 60).await?;
+        assert_eq!(renamed.name, "renamed");
+        assert_eq!(db_client.redis_get_channel_by_id(6902).await?, Some(renamed));
+
+        let voice = get_test_channel(6903, ChannelKind::Voice, 2, None);
+        db_client.insert_channel(&voice, 60).await?;
+        let channels = db_client.update_channel_positions(
+            TEST_GUILD_ID,
+            &[(6903, 0), (6901, 1)],
+            60
+        ).await?;
+        let order: Vec<i64> = channels.iter().map(|channel| channel.id).collect();
+        assert_eq!(order, vec![6903, 6901, 6902]);
+        assert_eq!(db_client.redis_get_channel_by_id(6903).await?.unwrap().position, 0);
+
+        // Positions are applied all or nothing
+        let res = db_client.update_channel_positions(TEST_GUILD_ID, &[(6901, 5), (1, 0)], 60).await;
+        assert!(matches!(res, Err(DatabaseError::ChannelNotFound(1))));
+        assert_eq!(db_client.get_channel_by_id(6901, 60).await?.unwrap().position, 1);
+
+        // Deleting a category keeps its channels without a parent
+        db_client.delete_channel(6901).await?;
+        assert_eq!(db_client.get_channel_by_id(6901, 60).await?, None);
+        assert_eq!(db_client.get_channel_by_id(6902, 60).await?.unwrap().parent_id, None);
+        assert!(matches!(
+            db_client.delete_channel(6901).await,
+            Err(DatabaseError::ChannelNotFound(6901))
+        ));
+
+        delete_guild(&db_client, TEST_GUILD_ID).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_join_guild_with_invite() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_guild(&db_client).await;
+        let now = chrono::Utc::now().timestamp();
+
+        let invite = Invite {
+            code: "testinv1".to_string(),
+            guild_id: TEST_GUILD_ID,
+            creator_id: 6910,
+            created_at: now,
+            expires_at: Some(now + 60),
+            max_uses: Some(1),
+            uses: 0,
+        };
+        db_client.insert_invite(&invite).await?;
+
+        // Already a member, the use isn't counted
+        let res = db_client.join_guild_with_invite("testinv1", 6910, 60).await;
+        assert!(matches!(res, Err(DatabaseError::MemberAlreadyExists(6910))));
+        assert_eq!(db_client.postgres_get_invite_by_code("testinv1").await?.unwrap().uses, 0);
+
+        let member = db_client.join_guild_with_invite("testinv1", 6911, 60).await?;
+        assert_eq!(member.guild_id, TEST_GUILD_ID);
+        assert_eq!(db_client.redis_get_guild_member(TEST_GUILD_ID, 6911).await?, Some(member));
+
+        // Used up
+        let res = db_client.join_guild_with_invite("testinv1", 6912, 60).await;
+        assert!(matches!(res, Err(DatabaseError::InviteNotFound(_))));
+
+        let expired = Invite {
+            code: "testinv2".to_string(),
+            expires_at: Some(now - 1),
+            max_uses: None,
+            ..invite
+        };
+        db_client.insert_invite(&expired).await?;
+        let res = db_client.join_guild_with_invite("testinv2", 6912, 60).await;
+        assert!(matches!(res, Err(DatabaseError::InviteNotFound(_))));
+
+        // Invites are removed together with the guild
+        delete_guild(&db_client, TEST_GUILD_ID).await;
+        assert_eq!(db_client.postgres_get_invite_by_code("testinv1").await?, None);
+        Ok(())
+    }
+}
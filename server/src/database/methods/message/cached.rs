@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::{app_objects::Message, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    MessageStore
+}};
+
+
+#[async_trait]
+impl MessageStore for DatabaseClientWithCaching {
+    async fn insert_message(
+        &self,
+        message: &Message,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_insert_message(message).await?;
+
+        db_client.redis_set_message(message, cache_ttl_s).await?;
+
+        Ok(())
+    }
+
+    async fn get_message_by_id(
+        &self,
+        message_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Message>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        // Check Redis first
+        let message = db_client.redis_get_message_by_id(message_id).await?;
+
+        // If Redis has the message, return it
+        if message.is_some() {
+            return Ok(message);
+        }
+
+        // If Redis doesn't have the message, check Postgres
+        let message = db_client.postgres_get_message_by_id(message_id).await?;
+
+        // If Postgres doesn't have the message, return None
+        if message.is_none() {
+            return Ok(None);
+        }
+
+        // If Postgres has the message, set it in Redis
+        let message = message.unwrap();
+        db_client.redis_set_message(&message, cache_ttl_s).await?;
+
+        Ok(Some(message))
+    }
+
+    async fn edit_message(
+        &self,
+        message_id: i64,
+        content: &str,
+        cache_ttl_s: u64
+    ) -> Result<Message, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let updated_at = chrono::Utc::now().timestamp();
+
+        let message = db_client.postgres_update_message_content(
+            message_id,
+            content,
+            updated_at
+        ).await?;
+
+        db_client.redis_set_message(&message, cache_ttl_s).await?;
+
+        Ok(message)
+    }
+
+    async fn delete_message(
+        &self,
+        message_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        // Delete from Redis even if Postgres fails, the next read will repopulate it
+        let redis_res = db_client.redis_delete_message(message_id).await;
+        db_client.postgres_delete_message_by_id(message_id).await?;
+        redis_res?;
+
+        Ok(())
+    }
+
+    /// Pages are always read from Postgres, only single messages are cached
+    async fn get_channel_messages(
+        &self,
+        channel_id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<Message>, DatabaseError> {
+        self.postgres_get_channel_messages(
+            channel_id,
+            before,
+            after,
+            limit
+        ).await
+    }
+}
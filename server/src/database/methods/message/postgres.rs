@@ -0,0 +1,137 @@
+
+use crate::{app_objects::Message, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn postgres_insert_message(
+        &self,
+        message: &Message
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO messages (id, content, author_id, channel_id, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            message.id,
+            message.content,
+            message.author_id,
+            message.channel_id,
+            message.created_at,
+            message.updated_at
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_message_by_id(
+        &self,
+        message_id: i64
+    ) -> Result<Option<Message>, DatabaseError> {
+        let message = sqlx::query_as!(
+            Message,
+            r#"
+            SELECT id, content, author_id, channel_id, created_at, updated_at FROM messages
+            WHERE id = $1
+            "#,
+            message_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(message)
+    }
+
+    pub async fn postgres_update_message_content(
+        &self,
+        message_id: i64,
+        content: &str,
+        updated_at: i64
+    ) -> Result<Message, DatabaseError> {
+        let message = sqlx::query_as!(
+            Message,
+            r#"
+            UPDATE messages
+            SET content = $1, updated_at = $2
+            WHERE id = $3
+            RETURNING id, content, author_id, channel_id, created_at, updated_at
+            "#,
+            content,
+            updated_at,
+            message_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        match message {
+            Some(message) => Ok(message),
+            None => Err(DatabaseError::MessageNotFound(message_id)),
+        }
+    }
+
+    pub async fn postgres_delete_message_by_id(
+        &self,
+        message_id: i64
+    ) -> Result<(), DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM messages
+            WHERE id = $1
+            "#,
+            message_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::MessageNotFound(message_id));
+        }
+        Ok(())
+    }
+
+    /// Returns at most `limit` messages from the channel, newest first.
+    /// With `after` set the page starts right after that message, otherwise it ends right before `before`
+    pub async fn postgres_get_channel_messages(
+        &self,
+        channel_id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<Message>, DatabaseError> {
+        if let Some(after) = after {
+            let mut messages = sqlx::query_as!(
+                Message,
+                r#"
+                SELECT id, content, author_id, channel_id, created_at, updated_at FROM messages
+                WHERE channel_id = $1 AND id > $2 AND ($3::BIGINT IS NULL OR id < $3)
+                ORDER BY id ASC
+                LIMIT $4
+                "#,
+                channel_id,
+                after,
+                before,
+                limit
+            )
+            .fetch_all(&self.postgres_con)
+            .await?;
+            messages.reverse();
+            return Ok(messages);
+        }
+
+        let messages = sqlx::query_as!(
+            Message,
+            r#"
+            SELECT id, content, author_id, channel_id, created_at, updated_at FROM messages
+            WHERE channel_id = $1 AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+            channel_id,
+            before,
+            limit
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(messages)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{app_objects::Message, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_message(
+        &self,
+        message: &Message,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("message:{}", message.id)
+            )
+            .arg(message.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_message_by_id(
+        &self,
+        message_id: i64
+    ) -> Result<Option<Message>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let message: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("message:{}", message_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match message {
+            Some(message) => Ok(Some(Message::from_json(&message)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_message(
+        &self,
+        message_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("message:{}", message_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::Message;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        MessageStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    async fn delete_message(db_client: &DatabaseClientWithCaching, message_id: i64) {
+        let res = db_client.delete_message(message_id).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::MessageNotFound(_) => {},
+                e => panic!("Error deleting message: {:?}", e)
+            }
+        }
+    }
+
+    fn get_test_message(id: i64, channel_id: i64) -> Message {
+        Message::new(
+            id,
+            format!("test message {}", id),
+            420,
+            chrono::Utc::now().timestamp(),
+            None,
+            channel_id
+        )
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_insert_and_get_message_with_caching() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        delete_message(&db_client, 420).await;
+
+        let message = get_test_message(420, 69);
+        db_client.insert_message(&message, 60).await?;
+
+        assert_eq!(db_client.postgres_get_message_by_id(420).await?, Some(message.clone()));
+        assert_eq!(db_client.redis_get_message_by_id(420).await?, Some(message.clone()));
+
+        // Cache miss goes to Postgres and warms Redis again
+        db_client.redis_delete_message(420).await?;
+        assert_eq!(db_client.get_message_by_id(420, 60).await?, Some(message.clone()));
+        assert_eq!(db_client.redis_get_message_by_id(420).await?, Some(message));
+
+        delete_message(&db_client, 420).await;
+        assert_eq!(db_client.get_message_by_id(420, 60).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_edit_message_with_caching() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        delete_message(&db_client, 420).await;
+
+        let message = get_test_message(420, 69);
+        db_client.insert_message(&message, 60).await?;
+
+        let edited = db_client.edit_message(420, "edited", 60).await?;
+        assert_eq!(edited.content, "edited");
+        assert!(edited.updated_at.is_some());
+        assert_eq!(edited.created_at, message.created_at);
+
+        assert_eq!(db_client.redis_get_message_by_id(420).await?, Some(edited.clone()));
+        assert_eq!(db_client.postgres_get_message_by_id(420).await?, Some(edited));
+
+        delete_message(&db_client, 420).await;
+
+        let res = db_client.edit_message(420, "edited", 60).await;
+        match res {
+            Err(DatabaseError::MessageNotFound(420)) => {},
+            other => panic!("Expected MessageNotFound, got {:?}", other)
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_get_channel_messages_pages() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        for id in 1000..1010 {
+            delete_message(&db_client, id).await;
+            db_client.insert_message(&get_test_message(id, 4200), 60).await?;
+        }
+
+        let ids = |messages: Vec<Message>| messages.iter().map(|m| m.id).collect::<Vec<i64>>();
+
+        let latest = db_client.get_channel_messages(4200, None, None, 3).await?;
+        assert_eq!(ids(latest), vec![1009, 1008, 1007]);
+
+        let before = db_client.get_channel_messages(4200, Some(1005), None, 3).await?;
+        assert_eq!(ids(before), vec![1004, 1003, 1002]);
+
+        let after = db_client.get_channel_messages(4200, None, Some(1005), 3).await?;
+        assert_eq!(ids(after), vec![1008, 1007, 1006]);
+
+        let between = db_client.get_channel_messages(4200, Some(1004), Some(1000), 10).await?;
+        assert_eq!(ids(between), vec![1003, 1002, 1001]);
+
+        let other_channel = db_client.get_channel_messages(4201, None, None, 10).await?;
+        assert_eq!(other_channel.len(), 0);
+
+        for id in 1000..1010 {
+            delete_message(&db_client, id).await;
+        }
+        Ok(())
+    }
+}
@@ -1,4 +1,11 @@
 mod user;
+mod message;
+mod guild;
+mod role;
+mod dm;
+mod admin;
+mod audit;
+mod email_job;
 
 use axum::response::IntoResponse;
 use thiserror::Error;
@@ -19,6 +26,30 @@ pub enum DatabaseError {
     UserNotFound(i64),
     #[error("User with id: {0} already exists")]
     UserAlreadyExists(i64),
+    #[error("User with email: {0} already exists")]
+    EmailAlreadyExists(String),
+    #[error("Message with id: {0} not found")]
+    MessageNotFound(i64),
+    #[error("Failed to (de)serialize cached value: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Guild with id: {0} not found")]
+    GuildNotFound(i64),
+    #[error("Channel with id: {0} not found")]
+    ChannelNotFound(i64),
+    #[error("Invite {0} not found, expired or used up")]
+    InviteNotFound(String),
+    #[error("User with id: {0} is already a member of the guild")]
+    MemberAlreadyExists(i64),
+    #[error("Role with id: {0} not found")]
+    RoleNotFound(i64),
+    #[error("User with id: {0} is not a member of the guild")]
+    MemberNotFound(i64),
+    #[error("User with id: {0} is not a recipient of the DM channel")]
+    RecipientNotFound(i64),
+    #[error("Session with id: {0} not found")]
+    SessionNotFound(uuid::Uuid),
+    #[error("User with id: {0} still owns a guild")]
+    UserOwnsGuilds(i64),
 }
 
 impl IntoResponse for DatabaseError {
@@ -42,6 +73,42 @@ impl IntoResponse for DatabaseError {
             DatabaseError::UserAlreadyExists(_) => {
                 (axum::http::StatusCode::BAD_REQUEST, "1205")
             },
+            DatabaseError::EmailAlreadyExists(_) => {
+                (axum::http::StatusCode::BAD_REQUEST, "1206")
+            },
+            DatabaseError::MessageNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1207")
+            },
+            DatabaseError::SerializationError(_) => {
+                (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "1208")
+            },
+            DatabaseError::GuildNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1209")
+            },
+            DatabaseError::ChannelNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1210")
+            },
+            DatabaseError::InviteNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1211")
+            },
+            DatabaseError::MemberAlreadyExists(_) => {
+                (axum::http::StatusCode::BAD_REQUEST, "1212")
+            },
+            DatabaseError::RoleNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1213")
+            },
+            DatabaseError::MemberNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1214")
+            },
+            DatabaseError::RecipientNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1215")
+            },
+            DatabaseError::SessionNotFound(_) => {
+                (axum::http::StatusCode::NOT_FOUND, "1216")
+            },
+            DatabaseError::UserOwnsGuilds(_) => {
+                (axum::http::StatusCode::CONFLICT, "1217")
+            },
         };
 
         axum::http::Response::builder()
@@ -60,6 +127,18 @@ impl DatabaseError {
             DatabaseError::TokioError(_) => "1203",
             DatabaseError::UserNotFound(_) => "1204",
             DatabaseError::UserAlreadyExists(_) => "1205",
+            DatabaseError::EmailAlreadyExists(_) => "1206",
+            DatabaseError::MessageNotFound(_) => "1207",
+            DatabaseError::SerializationError(_) => "1208",
+            DatabaseError::GuildNotFound(_) => "1209",
+            DatabaseError::ChannelNotFound(_) => "1210",
+            DatabaseError::InviteNotFound(_) => "1211",
+            DatabaseError::MemberAlreadyExists(_) => "1212",
+            DatabaseError::RoleNotFound(_) => "1213",
+            DatabaseError::MemberNotFound(_) => "1214",
+            DatabaseError::RecipientNotFound(_) => "1215",
+            DatabaseError::SessionNotFound(_) => "1216",
+            DatabaseError::UserOwnsGuilds(_) => "1217",
         }
     }
 
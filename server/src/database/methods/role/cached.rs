@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::{app_objects::{
+    PermissionOverwrite,
+    Role
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    RoleStore
+}};
+
+
+/// Roles and overwrites are cached as whole lists, every change drops the list so the next read repopulates it
+#[async_trait]
+impl RoleStore for DatabaseClientWithCaching {
+    async fn insert_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_insert_role(role).await?;
+
+        db_client.redis_delete_guild_roles(role.guild_id).await?;
+
+        Ok(())
+    }
+
+    async fn get_guild_roles(
+        &self,
+        guild_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Vec<Role>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let roles = db_client.redis_get_guild_roles(guild_id).await?;
+        if let Some(roles) = roles {
+            return Ok(roles);
+        }
+
+        let roles = db_client.postgres_get_guild_roles(guild_id).await?;
+        db_client.redis_set_guild_roles(guild_id, &roles, cache_ttl_s).await?;
+
+        Ok(roles)
+    }
+
+    async fn update_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_update_role(role).await?;
+
+        db_client.redis_delete_guild_roles(role.guild_id).await?;
+
+        Ok(())
+    }
+
+    async fn delete_role(
+        &self,
+        guild_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let (member_ids, channel_ids) = db_client.postgres_delete_role(guild_id, role_id).await?;
+        db_client.redis_delete_guild_roles(guild_id).await?;
+        db_client.redis_delete_guild_members(guild_id, &member_ids).await?;
+        db_client.redis_delete_channel_overwrites(&channel_ids).await?;
+
+        Ok(())
+    }
+
+    /// Cached members hold their role ids, so the member is dropped from the cache
+    async fn add_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_add_member_role(guild_id, user_id, role_id).await?;
+
+        db_client.redis_delete_guild_members(guild_id, &[user_id]).await?;
+
+        Ok(())
+    }
+
+    async fn remove_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_remove_member_role(guild_id, user_id, role_id).await?;
+
+        db_client.redis_delete_guild_members(guild_id, &[user_id]).await?;
+
+        Ok(())
+    }
+
+    async fn get_channel_overwrites(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let overwrites = db_client.redis_get_channel_overwrites(channel_id).await?;
+        if let Some(overwrites) = overwrites {
+            return Ok(overwrites);
+        }
+
+        let overwrites = db_client.postgres_get_channel_overwrites(channel_id).await?;
+        db_client.redis_set_channel_overwrites(channel_id, &overwrites, cache_ttl_s).await?;
+
+        Ok(overwrites)
+    }
+
+    /// Overwrite lists of a whole guild are always read from Postgres
+    async fn get_guild_overwrites(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        self.postgres_get_guild_overwrites(guild_id).await
+    }
+
+    async fn set_channel_overwrite(
+        &self,
+        overwrite: &PermissionOverwrite
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_set_channel_overwrite(overwrite).await?;
+
+        db_client.redis_delete_channel_overwrites(&[overwrite.channel_id]).await?;
+
+        Ok(())
+    }
+
+    async fn delete_channel_overwrite(
+        &self,
+        channel_id: i64,
+        target_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_delete_channel_overwrite(channel_id, target_id).await?;
+
+        db_client.redis_delete_channel_overwrites(&[channel_id]).await?;
+
+        Ok(())
+    }
+}
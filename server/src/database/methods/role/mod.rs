@@ -0,0 +1,7 @@
+mod postgres;
+mod redis;
+mod cached;
+
+mod tests;
+
+pub(super) use postgres::insert_role;
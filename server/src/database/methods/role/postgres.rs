@@ -0,0 +1,262 @@
+use crate::{app_objects::{
+    OverwriteKind,
+    PermissionOverwrite,
+    Role
+}, auth::Permissions, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn postgres_insert_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        insert_role(&self.postgres_con, role).await
+    }
+
+    /// Returns the roles of the guild from the lowest to the highest, @everyone included
+    pub async fn postgres_get_guild_roles(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<Role>, DatabaseError> {
+        let roles = sqlx::query_as!(
+            Role,
+            r#"
+            SELECT id, guild_id, name, permissions AS "permissions: Permissions", position, created_at FROM roles
+            WHERE guild_id = $1
+            ORDER BY position, id
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(roles)
+    }
+
+    /// Stores the name, permissions and position of the role
+    pub async fn postgres_update_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE roles
+            SET name = $1, permissions = $2, position = $3
+            WHERE id = $4 AND guild_id = $5
+            "#,
+            role.name,
+            role.permissions.bits() as i64,
+            role.position,
+            role.id,
+            role.guild_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::RoleNotFound(role.id));
+        }
+        Ok(())
+    }
+
+    /// Deletes the role together with its channel overwrites. Returns the ids of the members
+    /// that had the role and of the channels that had an overwrite for it, so cached copies can be dropped.
+    pub async fn postgres_delete_role(
+        &self,
+        guild_id: i64,
+        role_id: i64
+    ) -> Result<(Vec<i64>, Vec<i64>), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let channel_ids = sqlx::query_scalar!(
+            r#"
+            DELETE FROM channel_overwrites
+            WHERE target_id = $1 AND kind = $2
+                AND channel_id IN (SELECT id FROM channels WHERE guild_id = $3)
+            RETURNING channel_id
+            "#,
+            role_id,
+            OverwriteKind::Role as i16,
+            guild_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let member_ids = sqlx::query_scalar!(
+            r#"
+            DELETE FROM guild_member_roles
+            WHERE guild_id = $1 AND role_id = $2
+            RETURNING user_id
+            "#,
+            guild_id,
+            role_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM roles
+            WHERE id = $1 AND guild_id = $2
+            "#,
+            role_id,
+            guild_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::RoleNotFound(role_id));
+        }
+        tx.commit().await?;
+        Ok((member_ids, channel_ids))
+    }
+
+    /// Giving a member a role they already have does nothing
+    pub async fn postgres_add_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO guild_member_roles (guild_id, user_id, role_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            guild_id,
+            user_id,
+            role_id
+        )
+        .execute(&self.postgres_con)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db_error) if db_error.is_foreign_key_violation() => {
+                match db_error.constraint() {
+                    Some("guild_member_roles_role_id_fkey") => DatabaseError::RoleNotFound(role_id),
+                    _ => DatabaseError::MemberNotFound(user_id),
+                }
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
+        Ok(())
+    }
+
+    pub async fn postgres_remove_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM guild_member_roles
+            WHERE guild_id = $1 AND user_id = $2 AND role_id = $3
+            "#,
+            guild_id,
+            user_id,
+            role_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_channel_overwrites(
+        &self,
+        channel_id: i64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        let overwrites = sqlx::query_as!(
+            PermissionOverwrite,
+            r#"
+            SELECT channel_id, target_id, kind AS "kind: OverwriteKind",
+                allow AS "allow: Permissions", deny AS "deny: Permissions" FROM channel_overwrites
+            WHERE channel_id = $1
+            "#,
+            channel_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(overwrites)
+    }
+
+    /// Overwrites of every channel in the guild, used to filter channel lists
+    pub async fn postgres_get_guild_overwrites(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError> {
+        let overwrites = sqlx::query_as!(
+            PermissionOverwrite,
+            r#"
+            SELECT channel_id, target_id, channel_overwrites.kind AS "kind: OverwriteKind",
+                allow AS "allow: Permissions", deny AS "deny: Permissions" FROM channel_overwrites
+            JOIN channels ON channels.id = channel_overwrites.channel_id
+            WHERE channels.guild_id = $1
+            "#,
+            guild_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(overwrites)
+    }
+
+    /// Replaces the overwrite the channel already has for the target
+    pub async fn postgres_set_channel_overwrite(
+        &self,
+        overwrite: &PermissionOverwrite
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO channel_overwrites (channel_id, target_id, kind, allow, deny)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (channel_id, target_id)
+            DO UPDATE SET kind = $3, allow = $4, deny = $5
+            "#,
+            overwrite.channel_id,
+            overwrite.target_id,
+            overwrite.kind as i16,
+            overwrite.allow.bits() as i64,
+            overwrite.deny.bits() as i64
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn postgres_delete_channel_overwrite(
+        &self,
+        channel_id: i64,
+        target_id: i64
+    ) -> Result<(), DatabaseError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM channel_overwrites
+            WHERE channel_id = $1 AND target_id = $2
+            "#,
+            channel_id,
+            target_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        Ok(())
+    }
+}
+
+pub(in crate::database::methods) async fn insert_role<'e, E: sqlx::PgExecutor<'e>>(
+    executor: E,
+    role: &Role
+) -> Result<(), DatabaseError> {
+    sqlx::query!(
+        r#"
+        INSERT INTO roles (id, guild_id, name, permissions, position, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+        role.id,
+        role.guild_id,
+        role.name,
+        role.permissions.bits() as i64,
+        role.position,
+        role.created_at
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use crate::{app_objects::{
+    PermissionOverwrite,
+    Role
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_guild_roles(
+        &self,
+        guild_id: i64,
+        roles: &[Role],
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("guild:{}:roles", guild_id)
+            )
+            .arg(serde_json::to_string(roles)?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_guild_roles(
+        &self,
+        guild_id: i64
+    ) -> Result<Option<Vec<Role>>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let roles: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("guild:{}:roles", guild_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match roles {
+            Some(roles) => Ok(Some(serde_json::from_str(&roles)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_guild_roles(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("guild:{}:roles", guild_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_set_channel_overwrites(
+        &self,
+        channel_id: i64,
+        overwrites: &[PermissionOverwrite],
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("channel:{}:overwrites", channel_id)
+            )
+            .arg(serde_json::to_string(overwrites)?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_channel_overwrites(
+        &self,
+        channel_id: i64
+    ) -> Result<Option<Vec<PermissionOverwrite>>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let overwrites: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("channel:{}:overwrites", channel_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match overwrites {
+            Some(overwrites) => Ok(Some(serde_json::from_str(&overwrites)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_channel_overwrites(
+        &self,
+        channel_ids: &[i64]
+    ) -> Result<(), DatabaseError> {
+        if channel_ids.is_empty() {
+            return Ok(());
+        }
+        let mut con = self.redis_con.clone();
+        let keys: Vec<String> = channel_ids.iter()
+            .map(|channel_id| format!("channel:{}:overwrites", channel_id))
+            .collect();
+        let _: () = redis::cmd("DEL")
+            .arg(keys)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        Channel,
+        ChannelKind,
+        Guild,
+        GuildMember,
+        OverwriteKind,
+        PermissionOverwrite,
+        Role,
+        User
+    };
+    use crate::auth::Permissions;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        GuildStore,
+        RoleStore
+    };
+
+    const TEST_GUILD_ID: i64 = 7100;
+    const TEST_CHANNEL_ID: i64 = 7101;
+    const TEST_ROLE_ID: i64 = 7102;
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Inserts the users the tests refer to, guild members, recipients and blocks need an existing user
+    async fn create_test_users(db_client: &DatabaseClientWithCaching, user_ids: &[i64]) {
+        for user_id in user_ids {
+            if db_client.postgres_get_user_by_id(*user_id).await.unwrap().is_none() {
+                let user = User {
+                    id: *user_id,
+                    email: format!("user{}@test.local", user_id),
+                    ..User::default()
+                };
+                db_client.postgres_insert_user(&user).await.unwrap();
+            }
+        }
+    }
+
+    /// Creates the test guild owned by user 7110 with user 7111 as a member and a single text channel
+    async fn create_test_guild(db_client: &DatabaseClientWithCaching) {
+        let _ = db_client.delete_guild(TEST_GUILD_ID).await;
+        create_test_users(db_client, &[7110, 7111]).await;
+        let now = chrono::Utc::now().timestamp();
+        let guild = Guild::new(TEST_GUILD_ID, "test guild".to_string(), 7110, now);
+        let owner = GuildMember::new(TEST_GUILD_ID, 7110, now);
+        let channel = Channel::new(TEST_CHANNEL_ID, TEST_GUILD_ID, "general".to_string(), ChannelKind::Text, 0, None, now);
+        db_client.create_guild(&guild, &owner, &Role::everyone(TEST_GUILD_ID, now), &[channel], 60).await.unwrap();
+        db_client.insert_guild_member(&GuildMember::new(TEST_GUILD_ID, 7111, now), 60).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_roles_with_caching() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_guild(&db_client).await;
+
+        let roles = db_client.get_guild_roles(TEST_GUILD_ID, 60).await?;
+        assert_eq!(roles.len(), 1);
+        assert!(roles[0].is_everyone());
+        assert_eq!(roles[0].permissions, Permissions::DEFAULT_EVERYONE);
+
+        let mut role = Role::new(TEST_ROLE_ID, TEST_GUILD_ID, "mod".to_string(), Permissions::MANAGE_MESSAGES, 1, 0);
+        db_client.insert_role(&role).await?;
+        assert_eq!(db_client.redis_get_guild_roles(TEST_GUILD_ID).await?, None);
+        assert_eq!(db_client.get_guild_roles(TEST_GUILD_ID, 60).await?[1], role);
+
+        role.permissions |= Permissions::KICK_MEMBERS;
+        db_client.update_role(&role).await?;
+        assert_eq!(db_client.get_guild_roles(TEST_GUILD_ID, 60).await?[1], role);
+
+        db_client.add_member_role(TEST_GUILD_ID, 7111, TEST_ROLE_ID).await?;
+        // Adding it twice does nothing
+        db_client.add_member_role(TEST_GUILD_ID, 7111, TEST_ROLE_ID).await?;
+        let member = db_client.get_guild_member(TEST_GUILD_ID, 7111, 60).await?.unwrap();
+        assert_eq!(member.role_ids, vec![TEST_ROLE_ID]);
+
+        let res = db_client.add_member_role(TEST_GUILD_ID, 7112, TEST_ROLE_ID).await;
+        assert!(matches!(res, Err(DatabaseError::MemberNotFound(7112))));
+
+        db_client.remove_member_role(TEST_GUILD_ID, 7111, TEST_ROLE_ID).await?;
+        let member = db_client.get_guild_member(TEST_GUILD_ID, 7111, 60).await?.unwrap();
+        assert!(member.role_ids.is_empty());
+
+        let _ = db_client.delete_guild(TEST_GUILD_ID).await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_role_drops_assignments_and_overwrites() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_guild(&db_client).await;
+
+        let role = Role::new(TEST_ROLE_ID, TEST_GUILD_ID, "muted".to_string(), Permissions::empty(), 1, 0);
+        db_client.insert_role(&role).await?;
+        db_client.add_member_role(TEST_GUILD_ID, 7111, TEST_ROLE_ID).await?;
+        let overwrite = PermissionOverwrite {
+            channel_id: TEST_CHANNEL_ID,
+            target_id: TEST_ROLE_ID,
+            kind: OverwriteKind::Role,
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+        };
+        db_client.set_channel_overwrite(&overwrite).await?;
+        assert_eq!(db_client.get_channel_overwrites(TEST_CHANNEL_ID, 60).await?, vec![overwrite.clone()]);
+        assert_eq!(db_client.get_guild_overwrites(TEST_GUILD_ID).await?, vec![overwrite.clone()]);
+
+        // Setting it again replaces it
+        let overwrite = PermissionOverwrite {
+            allow: Permissions::VIEW_CHANNEL,
+            ..overwrite
+        };
+        db_client.set_channel_overwrite(&overwrite).await?;
+        assert_eq!(db_client.get_channel_overwrites(TEST_CHANNEL_ID, 60).await?, vec![overwrite]);
+        assert!(db_client.get_guild_member(TEST_GUILD_ID, 7111, 60).await?.unwrap().role_ids.contains(&TEST_ROLE_ID));
+
+        db_client.delete_role(TEST_GUILD_ID, TEST_ROLE_ID).await?;
+        assert!(db_client.get_channel_overwrites(TEST_CHANNEL_ID, 60).await?.is_empty());
+        assert!(db_client.get_guild_member(TEST_GUILD_ID, 7111, 60).await?.unwrap().role_ids.is_empty());
+        assert_eq!(db_client.get_guild_roles(TEST_GUILD_ID, 60).await?.len(), 1);
+        assert!(matches!(
+            db_client.delete_role(TEST_GUILD_ID, TEST_ROLE_ID).await,
+            Err(DatabaseError::RoleNotFound(TEST_ROLE_ID))
+        ));
+
+        let _ = db_client.delete_guild(TEST_GUILD_ID).await;
+        assert_eq!(db_client.redis_get_guild_roles(TEST_GUILD_ID).await?, None);
+        Ok(())
+    }
+}
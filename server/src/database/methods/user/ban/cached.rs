@@ -0,0 +1,51 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::UserBan,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        BanStore
+    }
+};
+
+
+/// Postgres has the final say at login and refresh, the Redis denylist lets the access token
+/// extractor reject banned users without a Postgres query
+#[async_trait]
+impl BanStore for DatabaseClientWithCaching {
+    async fn ban_user(
+        &self,
+        user_ban: &UserBan
+    ) -> Result<(), DatabaseError> {
+        self.postgres_ban_user(user_ban).await?;
+        let now = chrono::Utc::now().timestamp();
+        if user_ban.is_active(now) {
+            self.redis_set_denylisted_user(user_ban, now).await?;
+        }
+        Ok(())
+    }
+
+    async fn unban_user(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let unbanned = self.postgres_unban_user(user_id).await?;
+        self.redis_delete_denylisted_user(user_id).await?;
+        Ok(unbanned)
+    }
+
+    async fn get_user_ban(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        self.postgres_get_user_ban(user_id).await
+    }
+
+    async fn get_denylisted_user(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        self.redis_get_denylisted_user(user_id).await
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    app_objects::UserBan,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    /// Sets the banned flag and stores the details, a second ban replaces the first one
+    pub async fn postgres_ban_user(
+        &self,
+        user_ban: &UserBan
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let res = sqlx::query!(
+            r#"
+            UPDATE users
+            SET banned = TRUE
+            WHERE id = $1
+            "#,
+            user_ban.user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::UserNotFound(user_ban.user_id));
+        }
+        sqlx::query!(
+            r#"
+            INSERT INTO user_bans (user_id, reason, banned_at, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id) DO UPDATE
+            SET reason = EXCLUDED.reason, banned_at = EXCLUDED.banned_at, expires_at = EXCLUDED.expires_at
+            "#,
+            user_ban.user_id,
+            user_ban.reason,
+            user_ban.banned_at,
+            user_ban.expires_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Returns false if the user wasn't banned
+    pub async fn postgres_unban_user(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let unbanned = sqlx::query!(
+            r#"
+            UPDATE users
+            SET banned = FALSE
+            WHERE id = $1 AND banned = TRUE
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() > 0;
+        sqlx::query!(
+            r#"
+            DELETE FROM user_bans
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(unbanned)
+    }
+
+    pub async fn postgres_get_user_ban(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        let user_ban = sqlx::query_as!(
+            UserBan,
+            r#"
+            SELECT user_id, reason, banned_at, expires_at FROM user_bans
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(user_ban)
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    app_objects::UserBan,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+/// The denylist is looked up on every authenticated request, entries of temporary bans expire with the ban
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_denylisted_user(
+        &self,
+        user_ban: &UserBan,
+        now: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let mut cmd = redis::cmd("SET");
+        cmd
+            .arg(format!("user_denylist:{}", user_ban.user_id))
+            .arg(user_ban.to_json()?);
+        if let Some(expires_at) = user_ban.expires_at {
+            cmd.arg("EX").arg((expires_at - now).max(1));
+        }
+        let _: () = cmd.query_async(&mut con).await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_denylisted_user(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let user_ban: Option<String> = redis::cmd("GET")
+            .arg(format!("user_denylist:{}", user_id))
+            .query_async(&mut con)
+            .await?;
+        match user_ban {
+            Some(user_ban) => Ok(Some(UserBan::from_json(&user_ban)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_denylisted_user(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(format!("user_denylist:{}", user_id))
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        User,
+        UserBan
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        BanStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Recreates user 450, which drops its ban
+    async fn create_test_user(db_client: &DatabaseClientWithCaching) {
+        let user = User {
+            id: 450,
+            email: "banned@example.com".to_string(),
+            ..User::default()
+        };
+        let res = db_client.postgres_delete_user_by_id(450).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::UserNotFound(_) => {},
+                e => panic!("Error deleting user: {:?}", e)
+            }
+        }
+        db_client.redis_delete_denylisted_user(450).await.unwrap();
+        db_client.postgres_insert_user(&user).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_ban_and_unban_user() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+        let now = chrono::Utc::now().timestamp();
+
+        let user_ban = UserBan::new(450, "spam".to_string(), now, None);
+        db_client.ban_user(&user_ban).await?;
+        assert!(db_client.postgres_get_user_by_id(450).await?.unwrap().banned);
+        assert_eq!(db_client.get_user_ban(450).await?, Some(user_ban.clone()));
+        assert_eq!(db_client.get_denylisted_user(450).await?, Some(user_ban));
+
+        // A second ban replaces the first
+        let user_ban = UserBan::new(450, "more spam".to_string(), now, Some(now + 60));
+        db_client.ban_user(&user_ban).await?;
+        assert_eq!(db_client.get_user_ban(450).await?, Some(user_ban.clone()));
+        assert_eq!(db_client.get_denylisted_user(450).await?, Some(user_ban));
+
+        assert!(db_client.unban_user(450).await?);
+        assert!(!db_client.postgres_get_user_by_id(450).await?.unwrap().banned);
+        assert_eq!(db_client.get_user_ban(450).await?, None);
+        assert_eq!(db_client.get_denylisted_user(450).await?, None);
+        assert!(!db_client.unban_user(450).await?);
+
+        db_client.postgres_delete_user_by_id(450).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_expired_ban_is_not_denylisted() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+        let now = chrono::Utc::now().timestamp();
+
+        let user_ban = UserBan::new(450, "spam".to_string(), now - 120, Some(now - 60));
+        db_client.ban_user(&user_ban).await?;
+        assert_eq!(db_client.get_user_ban(450).await?, Some(user_ban));
+        assert_eq!(db_client.get_denylisted_user(450).await?, None);
+
+        assert_eq!(
+            db_client.ban_user(&UserBan::new(451, "spam".to_string(), now, None)).await.unwrap_err().into_internal_error_code(),
+            DatabaseError::UserNotFound(451).into_internal_error_code()
+        );
+
+        db_client.postgres_delete_user_by_id(450).await?;
+        Ok(())
+    }
+}
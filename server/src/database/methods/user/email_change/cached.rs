@@ -0,0 +1,31 @@
+use axum::async_trait;
+
+use uuid::Uuid;
+
+use crate::{app_objects::PendingEmailChange, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    EmailChangeStore
+}};
+
+
+/// Pending changes only live in Redis, they expire together with the emailed link
+#[async_trait]
+impl EmailChangeStore for DatabaseClientWithCaching {
+    async fn create_email_change(
+        &self,
+        jti: Uuid,
+        email_change: &PendingEmailChange,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.redis_set_email_change(jti, email_change, ttl_s).await
+    }
+
+    /// Returns `None` if the change expired or was already confirmed
+    async fn take_email_change(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<PendingEmailChange>, DatabaseError> {
+        self.redis_take_email_change(jti).await
+    }
+}
@@ -0,0 +1,46 @@
+use uuid::Uuid;
+
+use crate::{app_objects::PendingEmailChange, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_email_change(
+        &self,
+        jti: Uuid,
+        email_change: &PendingEmailChange,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("email_change:{}", jti)
+            )
+            .arg(email_change.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    /// Reads and deletes the change in one command, so a link can only be used once
+    pub async fn redis_take_email_change(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<PendingEmailChange>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let email_change: Option<String> = redis::cmd("GETDEL")
+            .arg(
+                format!("email_change:{}", jti)
+            )
+            .query_async(&mut con)
+            .await?;
+        match email_change {
+            Some(email_change) => Ok(Some(PendingEmailChange::from_json(&email_change)?)),
+            None => Ok(None),
+        }
+    }
+}
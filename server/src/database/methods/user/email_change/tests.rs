@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::app_objects::PendingEmailChange;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        EmailChangeStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_email_change_can_be_taken_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+
+        let jti = Uuid::new_v4();
+        let email_change = PendingEmailChange::new(420, "test_email".to_string(), "new_test_email".to_string());
+        db_client.create_email_change(jti, &email_change, 60).await?;
+        assert_eq!(db_client.take_email_change(jti).await?, Some(email_change));
+        assert_eq!(db_client.take_email_change(jti).await?, None);
+
+        assert_eq!(db_client.take_email_change(Uuid::new_v4()).await?, None);
+        Ok(())
+    }
+}
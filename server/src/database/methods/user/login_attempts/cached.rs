@@ -0,0 +1,55 @@
+use axum::async_trait;
+
+use crate::database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    LoginAttemptStore
+};
+
+
+/// Login attempts only live in Redis, they expire with the counting window or the lockout
+#[async_trait]
+impl LoginAttemptStore for DatabaseClientWithCaching {
+    /// Returns the failures in the window including the new one
+    async fn add_login_failure(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        self.redis_add_login_failure(subject, now, window_s).await
+    }
+
+    async fn count_login_failures(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        self.redis_count_login_failures(subject, now, window_s).await
+    }
+
+    /// Forgets the failures and the lockout of the subject
+    async fn reset_login_failures(
+        &self,
+        subject: &str
+    ) -> Result<(), DatabaseError> {
+        self.redis_delete_login_failures(subject).await?;
+        self.redis_delete_login_lockout(subject).await
+    }
+
+    async fn set_login_lockout(
+        &self,
+        subject: &str,
+        lockout_s: i64
+    ) -> Result<(), DatabaseError> {
+        self.redis_set_login_lockout(subject, lockout_s).await
+    }
+
+    async fn get_login_lockout(
+        &self,
+        subject: &str
+    ) -> Result<Option<i64>, DatabaseError> {
+        self.redis_get_login_lockout(subject).await
+    }
+}
@@ -0,0 +1,128 @@
+use uuid::Uuid;
+
+use crate::database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+};
+
+
+/// Failed logins are kept in a sorted set scored by their time, entries older than the window are
+/// dropped before counting, so the count always covers the last `window_s` seconds
+impl DatabaseClientWithCaching {
+    pub async fn redis_add_login_failure(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let key = format!("login_failures:{}", subject);
+        let (failures,): (u64,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(&key)
+            .arg("-inf")
+            .arg(now - window_s)
+            .ignore()
+            .cmd("ZADD")
+            .arg(&key)
+            .arg(now)
+            .arg(Uuid::new_v4().to_string())
+            .ignore()
+            .cmd("ZCARD")
+            .arg(&key)
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(window_s)
+            .ignore()
+            .query_async(&mut con)
+            .await?;
+        Ok(failures)
+    }
+
+    pub async fn redis_count_login_failures(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let key = format!("login_failures:{}", subject);
+        let (failures,): (u64,) = redis::pipe()
+            .atomic()
+            .cmd("ZREMRANGEBYSCORE")
+            .arg(&key)
+            .arg("-inf")
+            .arg(now - window_s)
+            .ignore()
+            .cmd("ZCARD")
+            .arg(&key)
+            .query_async(&mut con)
+            .await?;
+        Ok(failures)
+    }
+
+    pub async fn redis_delete_login_failures(
+        &self,
+        subject: &str
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("login_failures:{}", subject)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_set_login_lockout(
+        &self,
+        subject: &str,
+        lockout_s: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("login_lockout:{}", subject)
+            )
+            .arg(1)
+            .arg("EX")
+            .arg(lockout_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    /// Seconds until the lockout ends, `None` if there is none
+    pub async fn redis_get_login_lockout(
+        &self,
+        subject: &str
+    ) -> Result<Option<i64>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let ttl: i64 = redis::cmd("TTL")
+            .arg(
+                format!("login_lockout:{}", subject)
+            )
+            .query_async(&mut con)
+            .await?;
+        if ttl <= 0 {
+            return Ok(None);
+        }
+        Ok(Some(ttl))
+    }
+
+    pub async fn redis_delete_login_lockout(
+        &self,
+        subject: &str
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("login_lockout:{}", subject)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        LoginAttemptStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_login_failures_slide_out_of_the_window() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let subject = "email:login_failures_test";
+        db_client.reset_login_failures(subject).await?;
+
+        assert_eq!(db_client.add_login_failure(subject, 1000, 100).await?, 1);
+        assert_eq!(db_client.add_login_failure(subject, 1050, 100).await?, 2);
+        assert_eq!(db_client.add_login_failure(subject, 1050, 100).await?, 3);
+        assert_eq!(db_client.count_login_failures(subject, 1099, 100).await?, 3);
+        // The first failure is older than the window now
+        assert_eq!(db_client.count_login_failures(subject, 1101, 100).await?, 2);
+        assert_eq!(db_client.add_login_failure(subject, 1151, 100).await?, 1);
+
+        db_client.reset_login_failures(subject).await?;
+        assert_eq!(db_client.count_login_failures(subject, 1151, 100).await?, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_login_lockout() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let subject = "ip:127.0.0.2";
+        db_client.reset_login_failures(subject).await?;
+        assert_eq!(db_client.get_login_lockout(subject).await?, None);
+
+        db_client.set_login_lockout(subject, 60).await?;
+        let lockout = db_client.get_login_lockout(subject).await?.unwrap();
+        assert!(lockout > 55 && lockout <= 60);
+
+        db_client.reset_login_failures(subject).await?;
+        assert_eq!(db_client.get_login_lockout(subject).await?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::UserMfa,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        MfaStore
+    }
+};
+
+
+/// TOTP secrets never leave Postgres, and the used time steps and recovery codes have to be
+/// checked and updated in one statement so a code can't be accepted twice.
+#[async_trait]
+impl MfaStore for DatabaseClientWithCaching {
+    async fn get_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserMfa>, DatabaseError> {
+        self.postgres_get_user_mfa(user_id).await
+    }
+
+    async fn start_mfa_enrollment(
+        &self,
+        user_mfa: &UserMfa
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_start_mfa_enrollment(user_mfa).await
+    }
+
+    async fn confirm_mfa(
+        &self,
+        user_id: i64,
+        step: i64,
+        confirmed_at: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_confirm_mfa(user_id, step, confirmed_at, recovery_code_hashes).await
+    }
+
+    async fn use_totp_step(
+        &self,
+        user_id: i64,
+        step: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_use_totp_step(user_id, step).await
+    }
+
+    async fn use_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_use_recovery_code(user_id, code_hash, used_at).await
+    }
+
+    async fn replace_recovery_codes(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<(), DatabaseError> {
+        self.postgres_replace_recovery_codes(user_id, recovery_code_hashes).await
+    }
+
+    async fn count_unused_recovery_codes(
+        &self,
+        user_id: i64
+    ) -> Result<i64, DatabaseError> {
+        self.postgres_count_unused_recovery_codes(user_id).await
+    }
+
+    async fn delete_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_delete_user_mfa(user_id).await
+    }
+
+    /// Used tokens are only marked in Redis, the mark expires together with the token
+    async fn use_mfa_pending_token(
+        &self,
+        jti: Uuid,
+        ttl_s: i64
+    ) -> Result<bool, DatabaseError> {
+        self.redis_use_mfa_pending_token(jti, ttl_s).await
+    }
+
+    async fn release_mfa_pending_token(
+        &self,
+        jti: Uuid
+    ) -> Result<(), DatabaseError> {
+        self.redis_release_mfa_pending_token(jti).await
+    }
+}
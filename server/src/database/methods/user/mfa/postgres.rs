@@ -0,0 +1,220 @@
+use crate::{
+    app_objects::UserMfa,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn postgres_get_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserMfa>, DatabaseError> {
+        let user_mfa = sqlx::query_as!(
+            UserMfa,
+            r#"
+            SELECT user_id, totp_secret, confirmed_at, last_used_step, created_at FROM user_mfa
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(user_mfa)
+    }
+
+    /// Stores the secret of a new enrollment, replacing an unfinished one.
+    /// Returns false if the user already has a confirmed second factor.
+    pub async fn postgres_start_mfa_enrollment(
+        &self,
+        user_mfa: &UserMfa
+    ) -> Result<bool, DatabaseError> {
+        let started = sqlx::query!(
+            r#"
+            INSERT INTO user_mfa (user_id, totp_secret, created_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id) DO UPDATE
+            SET totp_secret = EXCLUDED.totp_secret, created_at = EXCLUDED.created_at, last_used_step = NULL
+            WHERE user_mfa.confirmed_at IS NULL
+            "#,
+            user_mfa.user_id,
+            user_mfa.totp_secret,
+            user_mfa.created_at
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(started)
+    }
+
+    /// Enables the enrolled secret and replaces the recovery codes of the user in a single transaction.
+    /// `step` is the time step of the code that confirmed the enrollment, it can't be used again.
+    /// Returns false if there is no unfinished enrollment.
+    pub async fn postgres_confirm_mfa(
+        &self,
+        user_id: i64,
+        step: i64,
+        confirmed_at: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<bool, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let confirmed = sqlx::query!(
+            r#"
+            UPDATE user_mfa
+            SET confirmed_at = $3, last_used_step = $2
+            WHERE user_id = $1 AND confirmed_at IS NULL
+            "#,
+            user_id,
+            step,
+            confirmed_at
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() > 0;
+        if !confirmed {
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM user_recovery_codes
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::TEXT[]) AS code_hash
+            "#,
+            user_id,
+            recovery_code_hashes
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Accepts a code of the time step once, returns false for the last used step and the ones before it
+    pub async fn postgres_use_totp_step(
+        &self,
+        user_id: i64,
+        step: i64
+    ) -> Result<bool, DatabaseError> {
+        let used = sqlx::query!(
+            r#"
+            UPDATE user_mfa
+            SET last_used_step = $2
+            WHERE user_id = $1 AND confirmed_at IS NOT NULL AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+            user_id,
+            step
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(used)
+    }
+
+    /// Marks the recovery code as used, returns false for unknown and already used codes
+    pub async fn postgres_use_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        let used = sqlx::query!(
+            r#"
+            UPDATE user_recovery_codes
+            SET used_at = $3
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+            user_id,
+            code_hash,
+            used_at
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(used)
+    }
+
+    pub async fn postgres_replace_recovery_codes(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM user_recovery_codes
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO user_recovery_codes (user_id, code_hash)
+            SELECT $1, code_hash FROM UNNEST($2::TEXT[]) AS code_hash
+            "#,
+            user_id,
+            recovery_code_hashes
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn postgres_count_unused_recovery_codes(
+        &self,
+        user_id: i64
+    ) -> Result<i64, DatabaseError> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM user_recovery_codes
+            WHERE user_id = $1 AND used_at IS NULL
+            "#,
+            user_id
+        )
+        .fetch_one(&self.postgres_con)
+        .await?;
+        Ok(count)
+    }
+
+    /// Drops the secret together with the recovery codes, returns false if the user had neither
+    pub async fn postgres_delete_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM user_recovery_codes
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        let deleted = sqlx::query!(
+            r#"
+            DELETE FROM user_mfa
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() > 0;
+        tx.commit().await?;
+        Ok(deleted)
+    }
+}
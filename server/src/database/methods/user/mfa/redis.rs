@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use crate::database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+};
+
+
+impl DatabaseClientWithCaching {
+    /// Only the first request sets the key, so a token can't start two sessions
+    pub async fn redis_use_mfa_pending_token(
+        &self,
+        jti: Uuid,
+        ttl_s: i64
+    ) -> Result<bool, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let res: Option<String> = redis::cmd("SET")
+            .arg(
+                format!("mfa_token_used:{}", jti)
+            )
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(res.is_some())
+    }
+
+    pub async fn redis_release_mfa_pending_token(
+        &self,
+        jti: Uuid
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("mfa_token_used:{}", jti)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
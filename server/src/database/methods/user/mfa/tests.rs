@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use crate::app_objects::{
+        User,
+        UserMfa
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        MfaStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Recreates user 430, which drops its second factor and recovery codes
+    async fn create_test_user(db_client: &DatabaseClientWithCaching) {
+        let user = User {
+            id: 430,
+            ..User::default()
+        };
+        let res = db_client.postgres_delete_user_by_id(430).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::UserNotFound(_) => {},
+                e => panic!("Error deleting user: {:?}", e)
+            }
+        }
+        db_client.postgres_insert_user(&user).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mfa_enrollment() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+        assert_eq!(db_client.get_user_mfa(430).await?, None);
+
+        // An unfinished enrollment is replaced by the next one
+        assert!(db_client.start_mfa_enrollment(&UserMfa::new(430, vec![1; 20], 100)).await?);
+        assert!(db_client.start_mfa_enrollment(&UserMfa::new(430, vec![2; 20], 200)).await?);
+        let user_mfa = db_client.get_user_mfa(430).await?.unwrap();
+        assert_eq!(user_mfa, UserMfa::new(430, vec![2; 20], 200));
+        assert!(!user_mfa.is_enabled());
+        // Codes of an unconfirmed secret are never accepted
+        assert!(!db_client.use_totp_step(430, 10).await?);
+
+        let hashes = vec!["first".to_string(), "second".to_string()];
+        assert!(db_client.confirm_mfa(430, 10, 300, &hashes).await?);
+        let user_mfa = db_client.get_user_mfa(430).await?.unwrap();
+        assert_eq!(user_mfa.confirmed_at, Some(300));
+        assert_eq!(user_mfa.last_used_step, Some(10));
+        assert_eq!(db_client.count_unused_recovery_codes(430).await?, 2);
+
+        // A confirmed secret can't be replaced or confirmed again
+        assert!(!db_client.start_mfa_enrollment(&UserMfa::new(430, vec![3; 20], 400)).await?);
+        assert!(!db_client.confirm_mfa(430, 11, 400, &hashes).await?);
+        assert_eq!(db_client.get_user_mfa(430).await?.unwrap().totp_secret, vec![2; 20]);
+
+        assert!(db_client.delete_user_mfa(430).await?);
+        assert!(!db_client.delete_user_mfa(430).await?);
+        assert_eq!(db_client.get_user_mfa(430).await?, None);
+        assert_eq!(db_client.count_unused_recovery_codes(430).await?, 0);
+
+        db_client.postgres_delete_user_by_id(430).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mfa_codes_are_used_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+        db_client.start_mfa_enrollment(&UserMfa::new(430, vec![1; 20], 100)).await?;
+        let hashes = vec!["first".to_string(), "second".to_string()];
+        db_client.confirm_mfa(430, 10, 100, &hashes).await?;
+
+        // The confirming step and older ones are spent
+        assert!(!db_client.use_totp_step(430, 9).await?);
+        assert!(!db_client.use_totp_step(430, 10).await?);
+        assert!(db_client.use_totp_step(430, 11).await?);
+        assert!(!db_client.use_totp_step(430, 11).await?);
+        assert!(!db_client.use_totp_step(431, 12).await?);
+
+        assert!(db_client.use_recovery_code(430, "first", 200).await?);
+        assert!(!db_client.use_recovery_code(430, "first", 200).await?);
+        assert!(!db_client.use_recovery_code(430, "unknown", 200).await?);
+        assert_eq!(db_client.count_unused_recovery_codes(430).await?, 1);
+
+        db_client.replace_recovery_codes(430, &["third".to_string()]).await?;
+        assert!(!db_client.use_recovery_code(430, "second", 300).await?);
+        assert_eq!(db_client.count_unused_recovery_codes(430).await?, 1);
+        assert!(db_client.use_recovery_code(430, "third", 300).await?);
+
+        db_client.postgres_delete_user_by_id(430).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_mfa_pending_token_is_used_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let jti = uuid::Uuid::new_v4();
+
+        assert!(db_client.use_mfa_pending_token(jti, 60).await?);
+        assert!(!db_client.use_mfa_pending_token(jti, 60).await?);
+        assert!(db_client.use_mfa_pending_token(uuid::Uuid::new_v4(), 60).await?);
+
+        db_client.release_mfa_pending_token(jti).await?;
+        assert!(db_client.use_mfa_pending_token(jti, 60).await?);
+        Ok(())
+    }
+}
@@ -1,3 +1,11 @@
 mod user;
 mod refresh_token;
+mod session;
+mod mfa;
+mod passkey;
+mod password_reset;
+mod email_change;
+mod pending_registration;
+mod login_attempts;
+mod ban;
 mod password_and_salt;
\ No newline at end of file
@@ -0,0 +1,75 @@
+use axum::async_trait;
+
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        Passkey,
+        PasskeyChallenge
+    },
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching,
+        PasskeyStore
+    }
+};
+
+
+/// Challenges only live in Redis. Passkeys are always read from Postgres, a cached
+/// signature counter would miss cloned authenticators.
+#[async_trait]
+impl PasskeyStore for DatabaseClientWithCaching {
+    async fn create_passkey_challenge(
+        &self,
+        challenge: &PasskeyChallenge,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.redis_set_passkey_challenge(challenge, ttl_s).await
+    }
+
+    async fn take_passkey_challenge(
+        &self,
+        challenge_id: Uuid
+    ) -> Result<Option<PasskeyChallenge>, DatabaseError> {
+        self.redis_take_passkey_challenge(challenge_id).await
+    }
+
+    async fn insert_passkey(
+        &self,
+        passkey: &Passkey
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_insert_passkey(passkey).await
+    }
+
+    async fn get_passkey(
+        &self,
+        credential_id: &[u8]
+    ) -> Result<Option<Passkey>, DatabaseError> {
+        self.postgres_get_passkey(credential_id).await
+    }
+
+    async fn get_user_passkeys(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Passkey>, DatabaseError> {
+        self.postgres_get_user_passkeys(user_id).await
+    }
+
+    async fn update_passkey_sign_count(
+        &self,
+        credential_id: &[u8],
+        previous_sign_count: i64,
+        sign_count: i64,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_update_passkey_sign_count(credential_id, previous_sign_count, sign_count, used_at).await
+    }
+
+    async fn delete_passkey(
+        &self,
+        credential_id: &[u8],
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        self.postgres_delete_passkey(credential_id, user_id).await
+    }
+}
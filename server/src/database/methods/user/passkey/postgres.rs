@@ -0,0 +1,117 @@
+use crate::{
+    app_objects::Passkey,
+    database::{
+        methods::DatabaseError,
+        DatabaseClientWithCaching
+    }
+};
+
+
+impl DatabaseClientWithCaching {
+    /// Returns false if the credential is already registered, to this or another user
+    pub async fn postgres_insert_passkey(
+        &self,
+        passkey: &Passkey
+    ) -> Result<bool, DatabaseError> {
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO passkeys (credential_id, user_id, public_key, sign_count, label, created_at, last_used_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (credential_id) DO NOTHING
+            "#,
+            passkey.credential_id,
+            passkey.user_id,
+            passkey.public_key,
+            passkey.sign_count,
+            passkey.label,
+            passkey.created_at,
+            passkey.last_used_at
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(inserted)
+    }
+
+    pub async fn postgres_get_passkey(
+        &self,
+        credential_id: &[u8]
+    ) -> Result<Option<Passkey>, DatabaseError> {
+        let passkey = sqlx::query_as!(
+            Passkey,
+            r#"
+            SELECT credential_id, user_id, public_key, sign_count, label, created_at, last_used_at FROM passkeys
+            WHERE credential_id = $1
+            "#,
+            credential_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(passkey)
+    }
+
+    /// Oldest first
+    pub async fn postgres_get_user_passkeys(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Passkey>, DatabaseError> {
+        let passkeys = sqlx::query_as!(
+            Passkey,
+            r#"
+            SELECT credential_id, user_id, public_key, sign_count, label, created_at, last_used_at FROM passkeys
+            WHERE user_id = $1
+            ORDER BY created_at, credential_id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(passkeys)
+    }
+
+    /// Stores the counter of an accepted assertion. Returns false if another login with the
+    /// credential changed the counter since `previous_sign_count` was read.
+    pub async fn postgres_update_passkey_sign_count(
+        &self,
+        credential_id: &[u8],
+        previous_sign_count: i64,
+        sign_count: i64,
+        used_at: i64
+    ) -> Result<bool, DatabaseError> {
+        let updated = sqlx::query!(
+            r#"
+            UPDATE passkeys
+            SET sign_count = $3, last_used_at = $4
+            WHERE credential_id = $1 AND sign_count = $2
+            "#,
+            credential_id,
+            previous_sign_count,
+            sign_count,
+            used_at
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(updated)
+    }
+
+    /// Returns false if the user has no such passkey
+    pub async fn postgres_delete_passkey(
+        &self,
+        credential_id: &[u8],
+        user_id: i64
+    ) -> Result<bool, DatabaseError> {
+        let deleted = sqlx::query!(
+            r#"
+            DELETE FROM passkeys
+            WHERE credential_id = $1 AND user_id = $2
+            "#,
+            credential_id,
+            user_id
+        )
+        .execute(&self.postgres_con)
+        .await?
+        .rows_affected() > 0;
+        Ok(deleted)
+    }
+}
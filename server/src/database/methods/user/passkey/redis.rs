@@ -0,0 +1,45 @@
+use uuid::Uuid;
+
+use crate::{app_objects::PasskeyChallenge, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_passkey_challenge(
+        &self,
+        challenge: &PasskeyChallenge,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("passkey_challenge:{}", challenge.id)
+            )
+            .arg(challenge.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    /// Reads and deletes the challenge in one command, so it can only be answered once
+    pub async fn redis_take_passkey_challenge(
+        &self,
+        challenge_id: Uuid
+    ) -> Result<Option<PasskeyChallenge>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let challenge: Option<String> = redis::cmd("GETDEL")
+            .arg(
+                format!("passkey_challenge:{}", challenge_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match challenge {
+            Some(challenge) => Ok(Some(PasskeyChallenge::from_json(&challenge)?)),
+            None => Ok(None),
+        }
+    }
+}
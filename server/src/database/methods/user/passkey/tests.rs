@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::app_objects::{
+        Passkey,
+        PasskeyCeremony,
+        PasskeyChallenge,
+        User
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        PasskeyStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Recreates users 440 and 441, which drops their passkeys
+    async fn create_test_users(db_client: &DatabaseClientWithCaching) {
+        for user_id in [440, 441] {
+            let user = User {
+                id: user_id,
+                email: format!("passkey_test_{}", user_id),
+                ..User::default()
+            };
+            let res = db_client.postgres_delete_user_by_id(user_id).await;
+            if res.is_err() {
+                match res.err().unwrap() {
+                    DatabaseError::UserNotFound(_) => {},
+                    e => panic!("Error deleting user: {:?}", e)
+                }
+            }
+            db_client.postgres_insert_user(&user).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_passkeys() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_users(&db_client).await;
+
+        let first = Passkey::new(vec![1, 2, 3], 440, vec![10], 0, "laptop".to_string(), 100);
+        let second = Passkey::new(vec![4, 5, 6], 440, vec![20], 5, "phone".to_string(), 200);
+        assert!(db_client.insert_passkey(&second).await?);
+        assert!(db_client.insert_passkey(&first).await?);
+        // Credential ids are unique across users
+        let taken = Passkey::new(vec![1, 2, 3], 441, vec![30], 0, "stolen".to_string(), 300);
+        assert!(!db_client.insert_passkey(&taken).await?);
+
+        assert_eq!(db_client.get_passkey(&[1, 2, 3]).await?, Some(first.clone()));
+        assert_eq!(db_client.get_passkey(&[7]).await?, None);
+        assert_eq!(db_client.get_user_passkeys(440).await?, vec![first.clone(), second.clone()]);
+        assert_eq!(db_client.get_user_passkeys(441).await?, vec![]);
+
+        // The counter only moves from the value the login read
+        assert!(db_client.update_passkey_sign_count(&[4, 5, 6], 5, 6, 400).await?);
+        assert!(!db_client.update_passkey_sign_count(&[4, 5, 6], 5, 7, 500).await?);
+        let updated = db_client.get_passkey(&[4, 5, 6]).await?.unwrap();
+        assert_eq!(updated.sign_count, 6);
+        assert_eq!(updated.last_used_at, Some(400));
+
+        assert!(!db_client.delete_passkey(&[1, 2, 3], 441).await?);
+        assert!(db_client.delete_passkey(&[1, 2, 3], 440).await?);
+        assert_eq!(db_client.get_passkey(&[1, 2, 3]).await?, None);
+
+        db_client.postgres_delete_user_by_id(440).await?;
+        assert_eq!(db_client.get_passkey(&[4, 5, 6]).await?, None);
+        db_client.postgres_delete_user_by_id(441).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_passkey_challenge_is_taken_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let challenge = PasskeyChallenge::new(PasskeyCeremony::Registration, vec![9; 32], Some(440), 100);
+        db_client.create_passkey_challenge(&challenge, 60).await?;
+
+        assert_eq!(db_client.take_passkey_challenge(challenge.id).await?, Some(challenge.clone()));
+        assert_eq!(db_client.take_passkey_challenge(challenge.id).await?, None);
+        assert_eq!(db_client.take_passkey_challenge(Uuid::new_v4()).await?, None);
+        Ok(())
+    }
+}
@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
+use axum::async_trait;
+
 use crate::database::{
     methods::DatabaseError,
-    DatabaseClientWithCaching
+    DatabaseClientWithCaching,
+    CredentialStore
 };
 
 
 
-impl DatabaseClientWithCaching {
-    pub async fn cached_get_password_hash_and_salt_by_user_id(
+#[async_trait]
+impl CredentialStore for DatabaseClientWithCaching {
+    async fn get_password_hash_and_salt_by_user_id(
         &self,
         user_id: i64
     ) -> Result<(String, String), DatabaseError> {
@@ -34,4 +38,19 @@ impl DatabaseClientWithCaching {
         return Ok((password_hash, salt));
     }
 
-}
\ No newline at end of file
+    async fn update_password_hash_and_salt(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+        salt: &str
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_update_password_hash_and_salt(user_id, password_hash, salt).await?;
+
+        // Logins read the hash from Redis first, the old password must not keep working
+        db_client.redis_delete_password_hash_by_user_id(user_id).await?;
+        db_client.redis_delete_salt_by_user_id(user_id).await?;
+
+        Ok(())
+    }
+}
@@ -37,7 +37,26 @@ impl DatabaseClientWithCaching {
         Ok((password_hash, salt))
     }
 
-
-
-
-}
\ No newline at end of file
+    pub async fn postgres_update_password_hash_and_salt(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+        salt: &str
+    ) -> Result<(), DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE users SET password_hash = $2, salt = $3
+            WHERE id = $1
+            "#,
+            user_id,
+            password_hash,
+            salt
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::UserNotFound(user_id));
+        }
+        Ok(())
+    }
+}
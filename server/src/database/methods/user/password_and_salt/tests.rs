@@ -7,7 +7,10 @@ mod tests {
     use crate::app_objects::User;
     use crate::configuration::Config;
     use crate::database::methods::DatabaseError;
-    use crate::database::DatabaseClientWithCaching;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        CredentialStore
+    };
 
     async fn get_db_client() -> DatabaseClientWithCaching {
         let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -47,7 +50,7 @@ mod tests {
         assert!(db_client.redis_get_password_hash_by_user_id(420).await.unwrap().is_none());
         assert!(db_client.redis_get_salt_by_user_id(420).await.unwrap().is_none());
 
-        let (password_hash, salt) = db_client.cached_get_password_hash_and_salt_by_user_id(420).await.unwrap();
+        let (password_hash, salt) = db_client.get_password_hash_and_salt_by_user_id(420).await.unwrap();
         assert_eq!(password_hash, "test_password".to_string());
         assert_eq!(salt, "test_salt".to_string());
 
@@ -56,4 +59,41 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    #[serial]
+    pub async fn test_update_password_hash_and_salt() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let user = User {
+            password_hash: "test_password".to_string(),
+            salt: "test_salt".to_string(),
+            id: 420,
+            ..User::default()
+        };
+        let res = db_client.postgres_delete_user_by_id(420).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::UserNotFound(_) => {},
+                e => {
+                    return Err(e);
+                }
+            }
+        }
+        db_client.postgres_insert_user(&user).await?;
+        // Fills the cache with the old password
+        db_client.get_password_hash_and_salt_by_user_id(420).await?;
+
+        db_client.update_password_hash_and_salt(420, "new_password", "new_salt").await?;
+        assert!(db_client.redis_get_password_hash_by_user_id(420).await?.is_none());
+        assert!(db_client.redis_get_salt_by_user_id(420).await?.is_none());
+        let (password_hash, salt) = db_client.get_password_hash_and_salt_by_user_id(420).await?;
+        assert_eq!(password_hash, "new_password".to_string());
+        assert_eq!(salt, "new_salt".to_string());
+
+        db_client.postgres_delete_user_by_id(420).await?;
+        let res = db_client.update_password_hash_and_salt(420, "new_password", "new_salt").await;
+        assert!(matches!(res, Err(DatabaseError::UserNotFound(420))));
+
+        Ok(())
+    }
+}
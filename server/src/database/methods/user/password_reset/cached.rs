@@ -0,0 +1,31 @@
+use axum::async_trait;
+
+use uuid::Uuid;
+
+use crate::database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    PasswordResetStore
+};
+
+
+/// Pending resets only live in Redis, they expire together with the emailed link
+#[async_trait]
+impl PasswordResetStore for DatabaseClientWithCaching {
+    async fn create_password_reset(
+        &self,
+        jti: Uuid,
+        user_id: i64,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.redis_set_password_reset(jti, user_id, ttl_s).await
+    }
+
+    /// Returns the user of the reset, `None` if it expired or was already used
+    async fn take_password_reset(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<i64>, DatabaseError> {
+        self.redis_take_password_reset(jti).await
+    }
+}
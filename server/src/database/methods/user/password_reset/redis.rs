@@ -0,0 +1,43 @@
+use uuid::Uuid;
+
+use crate::database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_password_reset(
+        &self,
+        jti: Uuid,
+        user_id: i64,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("password_reset:{}", jti)
+            )
+            .arg(user_id)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    /// Reads and deletes the reset in one command, so a link can only be used once
+    pub async fn redis_take_password_reset(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<i64>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let user_id: Option<i64> = redis::cmd("GETDEL")
+            .arg(
+                format!("password_reset:{}", jti)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(user_id)
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        PasswordResetStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_password_reset_can_be_taken_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+
+        let jti = Uuid::new_v4();
+        db_client.create_password_reset(jti, 420, 60).await?;
+        assert_eq!(db_client.take_password_reset(jti).await?, Some(420));
+        assert_eq!(db_client.take_password_reset(jti).await?, None);
+
+        assert_eq!(db_client.take_password_reset(Uuid::new_v4()).await?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use axum::async_trait;
+
+use uuid::Uuid;
+
+use crate::{app_objects::PendingRegistration, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    PendingRegistrationStore
+}};
+
+
+/// Pending registrations and cooldowns only live in Redis, a registration that was never verified just expires
+#[async_trait]
+impl PendingRegistrationStore for DatabaseClientWithCaching {
+    async fn create_pending_registration(
+        &self,
+        pending_registration: &PendingRegistration,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        self.redis_set_pending_registration(pending_registration, ttl_s).await
+    }
+
+    async fn get_pending_registration_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        self.redis_get_pending_registration_by_email(email).await
+    }
+
+    async fn take_pending_registration(
+        &self,
+        registration_id: Uuid
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        self.redis_take_pending_registration(registration_id).await
+    }
+
+    async fn start_verification_email_cooldown(
+        &self,
+        email: &str,
+        cooldown_s: u64
+    ) -> Result<Option<i64>, DatabaseError> {
+        self.redis_start_verification_email_cooldown(email, cooldown_s).await
+    }
+}
@@ -0,0 +1,4 @@
+mod redis;
+mod cached;
+
+mod tests;
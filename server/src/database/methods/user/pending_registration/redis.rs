@@ -0,0 +1,118 @@
+use uuid::Uuid;
+
+use crate::{app_objects::PendingRegistration, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+/// The registration is stored under its id, `pending_registration_id:<email>` points to the latest one of the address
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_pending_registration(
+        &self,
+        pending_registration: &PendingRegistration,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let email_key = format!("pending_registration_id:{}", pending_registration.email);
+        let _: () = redis::cmd("SET")
+            .arg(format!("pending_registration:{}", pending_registration.id))
+            .arg(pending_registration.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        // Moving the pointer and reading the old one in one command hands every concurrent registration of the
+        // address a different id to delete, so only the latest one is left. WATCH would need a connection of its
+        // own, the multiplexed one is shared by every request
+        let replaced_id: Option<String> = redis::cmd("SET")
+            .arg(&email_key)
+            .arg(pending_registration.id.to_string())
+            .arg("EX")
+            .arg(ttl_s)
+            .arg("GET")
+            .query_async(&mut con)
+            .await?;
+        if let Some(replaced_id) = replaced_id {
+            let _: () = redis::cmd("DEL")
+                .arg(format!("pending_registration:{}", replaced_id))
+                .query_async(&mut con)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn redis_get_pending_registration_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let registration_id: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("pending_registration_id:{}", email)
+            )
+            .query_async(&mut con)
+            .await?;
+        let Some(registration_id) = registration_id else {
+            return Ok(None);
+        };
+        let pending_registration: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("pending_registration:{}", registration_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match pending_registration {
+            Some(pending_registration) => Ok(Some(PendingRegistration::from_json(&pending_registration)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads and deletes the registration in one command, so it can only be verified once
+    pub async fn redis_take_pending_registration(
+        &self,
+        registration_id: Uuid
+    ) -> Result<Option<PendingRegistration>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let pending_registration: Option<String> = redis::cmd("GETDEL")
+            .arg(
+                format!("pending_registration:{}", registration_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match pending_registration {
+            Some(pending_registration) => Ok(Some(PendingRegistration::from_json(&pending_registration)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// `SET NX` only succeeds for the first caller, the others get the TTL of the key
+    pub async fn redis_start_verification_email_cooldown(
+        &self,
+        email: &str,
+        cooldown_s: u64
+    ) -> Result<Option<i64>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let key = format!("verification_email_cooldown:{}", email);
+        let started: Option<String> = redis::cmd("SET")
+            .arg(&key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(cooldown_s)
+            .query_async(&mut con)
+            .await?;
+        if started.is_some() {
+            return Ok(None);
+        }
+        let ttl: i64 = redis::cmd("TTL")
+            .arg(&key)
+            .query_async(&mut con)
+            .await?;
+        // The key expired in between
+        if ttl <= 0 {
+            return Ok(None);
+        }
+        Ok(Some(ttl))
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::app_objects::PendingRegistration;
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        PendingRegistrationStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    fn get_pending_registration(email: &str) -> PendingRegistration {
+        PendingRegistration::new(
+            email.to_string(),
+            "test_username".to_string(),
+            "test_password_hash".to_string(),
+            "test_password_salt".to_string(),
+            NaiveDate::from_ymd_opt(2024, 10, 27).unwrap()
+        )
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_pending_registration_can_be_taken_once() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let email = format!("{}@example.com", Uuid::new_v4());
+
+        let pending_registration = get_pending_registration(&email);
+        db_client.create_pending_registration(&pending_registration, 60).await?;
+        assert_eq!(db_client.get_pending_registration_by_email(&email).await?, Some(pending_registration.clone()));
+        assert_eq!(db_client.take_pending_registration(pending_registration.id).await?, Some(pending_registration));
+        assert_eq!(db_client.take_pending_registration(Uuid::new_v4()).await?, None);
+        assert_eq!(db_client.get_pending_registration_by_email(&email).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_pending_registration_is_replaced() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let email = format!("{}@example.com", Uuid::new_v4());
+
+        let first_registration = get_pending_registration(&email);
+        let second_registration = get_pending_registration(&email);
+        db_client.create_pending_registration(&first_registration, 60).await?;
+        db_client.create_pending_registration(&second_registration, 60).await?;
+        assert_eq!(db_client.get_pending_registration_by_email(&email).await?, Some(second_registration.clone()));
+        assert_eq!(db_client.take_pending_registration(first_registration.id).await?, None);
+        assert_eq!(db_client.take_pending_registration(second_registration.id).await?, Some(second_registration));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_concurrent_pending_registrations_leave_one() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let email = format!("{}@example.com", Uuid::new_v4());
+
+        let registrations: Vec<PendingRegistration> = (0..10)
+            .map(|_| get_pending_registration(&email))
+            .collect();
+        let handles: Vec<_> = registrations.iter()
+            .cloned()
+            .map(|registration| {
+                let db_client = db_client.clone();
+                tokio::spawn(async move {
+                    db_client.create_pending_registration(&registration, 60).await
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap()?;
+        }
+
+        let latest = db_client.get_pending_registration_by_email(&email).await?.unwrap();
+        for registration in registrations {
+            let taken = db_client.take_pending_registration(registration.id).await?;
+            assert_eq!(taken.is_some(), registration.id == latest.id);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_verification_email_cooldown() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        let email = format!("{}@example.com", Uuid::new_v4());
+
+        assert_eq!(db_client.start_verification_email_cooldown(&email, 60).await?, None);
+        let remaining_s = db_client.start_verification_email_cooldown(&email, 60).await?.unwrap();
+        assert!(remaining_s > 0 && remaining_s <= 60);
+        Ok(())
+    }
+}
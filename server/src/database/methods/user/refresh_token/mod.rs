@@ -1,5 +1,4 @@
 mod postgres;
-mod redis;
 mod cached;
 
-mod tests;
\ No newline at end of file
+mod tests;
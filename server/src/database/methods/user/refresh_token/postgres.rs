@@ -1,80 +1,127 @@
+use uuid::Uuid;
 
-use crate::{app_objects::User, database::{
+use crate::{app_objects::{
+    RefreshToken,
+    RefreshTokenRotation
+}, database::{
     methods::DatabaseError,
     DatabaseClientWithCaching
 }};
 
 
 impl DatabaseClientWithCaching {
-    pub async fn postgres_set_user_refresh_token(
+    pub async fn postgres_get_refresh_token(
         &self,
-        user_id: i64,
-        refresh_token: &str
-    ) -> Result<(), DatabaseError> {
-        let res = sqlx::query!(
+        jti: Uuid
+    ) -> Result<Option<RefreshToken>, DatabaseError> {
+        let refresh_token = sqlx::query_as!(
+            RefreshToken,
             r#"
-            UPDATE users
-            SET valid_refresh_token = $1
-            WHERE id = $2
+            SELECT jti, family_id, user_id, issued_at, expires_at, rotated_at FROM refresh_tokens
+            WHERE jti = $1
             "#,
-            refresh_token,
-            user_id
+            jti
         )
-        .execute(&self.postgres_con)
+        .fetch_optional(&self.postgres_con)
         .await?;
-        if res.rows_affected() == 0 {
-            return Err(DatabaseError::UserNotFound(user_id));
-        }
-        Ok(())
+        Ok(refresh_token)
     }
-    
-    pub async fn postgres_delete_user_refresh_token(
+
+    /// Marks the token as rotated and stores `new_jti` in the family of the session in a single transaction,
+    /// so a token can only be exchanged once. Presenting a rotated token again deletes the session.
+    /// Rotated tokens are kept until they expire to recognize them.
+    pub async fn postgres_rotate_refresh_token(
         &self,
-        user_id: i64
-    ) -> Result<(), DatabaseError> {
-        let res = sqlx::query!(
+        jti: Uuid,
+        session_id: Uuid,
+        user_id: i64,
+        new_jti: Uuid,
+        issued_at: i64,
+        expires_at: i64
+    ) -> Result<RefreshTokenRotation, DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        let rotated = sqlx::query!(
             r#"
-            UPDATE users
-            SET valid_refresh_token = NULL
-            WHERE id = $1
+            UPDATE refresh_tokens
+            SET rotated_at = $4
+            WHERE jti = $1 AND family_id = $2 AND user_id = $3 AND rotated_at IS NULL AND expires_at > $4
             "#,
+            jti,
+            session_id,
+            user_id,
+            issued_at
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() > 0;
+
+        if rotated {
+            sqlx::query!(
+                r#"
+                DELETE FROM refresh_tokens
+                WHERE family_id = $1 AND expires_at <= $2
+                "#,
+                session_id,
+                issued_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!(
+                r#"
+                INSERT INTO refresh_tokens (jti, family_id, user_id, issued_at, expires_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                new_jti,
+                session_id,
+                user_id,
+                issued_at,
+                expires_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!(
+                r#"
+                UPDATE sessions
+                SET last_used_at = $2, expires_at = $3
+                WHERE id = $1
+                "#,
+                session_id,
+                issued_at,
+                expires_at
+            )
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            return Ok(RefreshTokenRotation::Rotated);
+        }
+
+        let reused = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM refresh_tokens
+                WHERE jti = $1 AND family_id = $2 AND user_id = $3 AND rotated_at IS NOT NULL
+            ) AS "exists!"
+            "#,
+            jti,
+            session_id,
             user_id
         )
-        .execute(&self.postgres_con)
+        .fetch_one(&mut *tx)
         .await?;
-        if res.rows_affected() == 0 {
-            return Err(DatabaseError::UserNotFound(user_id));
+        if !reused {
+            return Ok(RefreshTokenRotation::Invalid);
         }
-        Ok(())
-    }
-    
-    
-    pub async fn postgres_get_user_refresh_token_by_user_id(
-        &self,
-        user_id: i64
-    ) -> Result<Option<String>, DatabaseError> {
-        let refresh_token = sqlx::query!(
+        // Drops the refresh tokens of the family with it
+        sqlx::query!(
             r#"
-            SELECT valid_refresh_token FROM users
+            DELETE FROM sessions
             WHERE id = $1
             "#,
-            user_id
+            session_id
         )
-            .fetch_one(&self.postgres_con)
-            .await;
-        if refresh_token.is_err() {
-            match refresh_token.err().unwrap() {
-                sqlx::Error::RowNotFound => {
-                    return Err(DatabaseError::UserNotFound(user_id));
-                },
-                e => {
-                    return Err(DatabaseError::SQLXError(e));
-                }
-            }
-        }
-        let refresh_token = refresh_token.unwrap();
-    
-        let valid_refresh_token = refresh_token.valid_refresh_token;
-        Ok(valid_refresh_token)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(RefreshTokenRotation::Reused)
     }
 }
@@ -3,10 +3,20 @@ mod tests {
     use std::path::PathBuf;
     use pretty_assertions::assert_eq;
     use serial_test::serial;
-    use crate::app_objects::User;
+    use uuid::Uuid;
+    use crate::app_objects::{
+        RefreshToken,
+        RefreshTokenRotation,
+        Session,
+        User
+    };
     use crate::configuration::Config;
     use crate::database::methods::DatabaseError;
-    use crate::database::DatabaseClientWithCaching;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        RefreshTokenStore,
+        SessionStore
+    };
 
     async fn get_db_client() -> DatabaseClientWithCaching {
         let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -19,139 +29,99 @@ mod tests {
         db_client
     }
 
-    #[tokio::test]
-    #[serial]
-    async fn test_get_refresh_token_by_user_id() -> Result<(), DatabaseError> {
-        let db_client: DatabaseClientWithCaching = get_db_client().await;
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-        db_client.redis_set_user_refresh_token(420, "test_token").await.unwrap();
-        let token = db_client.redis_get_user_refresh_token_by_user_id(420).await;
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-        assert_eq!(token.unwrap(), Some("test_token".to_string()));
-        Ok(())
-    }
-
-    #[tokio::test]
-    #[serial]
-    async fn test_delete_token_by_user_id() -> Result<(), DatabaseError> {
-        let db_client: DatabaseClientWithCaching = get_db_client().await;
-        db_client.redis_set_user_refresh_token(420, "test_token").await.unwrap();
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-        let token = db_client.redis_get_user_refresh_token_by_user_id(420).await;
-        assert_eq!(token.unwrap(), None);
-        Ok(())
-    }
-
-    #[tokio::test]
-    #[serial]
-    async fn test_update_refresh_token_with_caching() -> Result<(), DatabaseError> {
-        let db_client: DatabaseClientWithCaching = get_db_client().await;
-        // prepare test
+    /// Recreates user 420, which drops all of its sessions and refresh tokens
+    async fn create_test_user(db_client: &DatabaseClientWithCaching) {
         let user = User {
             id: 420,
             ..User::default()
         };
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-
         let res = db_client.postgres_delete_user_by_id(420).await;
         if res.is_err() {
             match res.err().unwrap() {
                 DatabaseError::UserNotFound(_) => {},
-                e => {
-                    return Err(e);
-                }
+                e => panic!("Error deleting user: {:?}", e)
             }
         }
-
         db_client.postgres_insert_user(&user).await.unwrap();
+    }
 
-
-        db_client.cached_update_user_refresh_token(420, "test_token").await.unwrap();
-
-        // check if the token is in Redis and Postgres
-        let token_from_redis = db_client.redis_get_user_refresh_token_by_user_id(420).await.unwrap();
-        let token_from_postgres = db_client.postgres_get_user_refresh_token_by_user_id(420).await.unwrap();
-        assert_eq!(token_from_redis.unwrap(), token_from_postgres.unwrap());
-        Ok(())
+    /// Logs user 420 in, returns the first refresh token of the new session
+    async fn create_session(
+        db_client: &DatabaseClientWithCaching,
+        issued_at: i64,
+        expires_at: i64
+    ) -> RefreshToken {
+        let session_id = Uuid::new_v4();
+        let session = Session::new(session_id, 420, "test device".to_string(), None, None, issued_at, expires_at);
+        let refresh_token = RefreshToken::new_family(Uuid::new_v4(), session_id, 420, issued_at, expires_at);
+        db_client.create_session(&session, &refresh_token, 60).await.unwrap();
+        refresh_token
     }
 
     #[tokio::test]
     #[serial]
-    async fn test_delete_refresh_token_with_caching() -> Result<(), DatabaseError> {
-        let db_client: DatabaseClientWithCaching = get_db_client().await;
-        // prepare test
-        let user = User {
-            valid_refresh_token: Some("test_token".to_string()),
-            id: 420,
-            ..User::default()
-        };
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-
-        let res = db_client.postgres_delete_user_by_id(420).await;
-        if res.is_err() {
-            match res.err().unwrap() {
-                DatabaseError::UserNotFound(_) => {},
-                e => {
-                    return Err(e);
-                }
-            }
-        }
-
-        db_client.postgres_insert_user(&user).await.unwrap();
-
-        let token_from_redis = db_client.redis_get_user_refresh_token_by_user_id(420).await.unwrap();
-        let token_from_postgres = db_client.postgres_get_user_refresh_token_by_user_id(420).await.unwrap();
-        assert_eq!(token_from_redis, None);
-        assert_eq!(token_from_postgres, Some("test_token".to_string()));
-
-        let _ = db_client.cached_delete_user_refresh_token(420).await;
-
-        // check if the token is in Redis and Postgres
-        let token_from_redis = db_client.redis_get_user_refresh_token_by_user_id(420).await.unwrap();
-        let token_from_postgres = db_client.postgres_get_user_refresh_token_by_user_id(420).await.unwrap();
-        assert_eq!(token_from_redis, None);
-        assert_eq!(token_from_postgres, None);
+    async fn test_rotate_refresh_token() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+
+        let first = create_session(&db_client, 100, 1000).await;
+        let session_id = first.family_id;
+        assert_eq!(db_client.get_refresh_token(first.jti).await?, Some(first.clone()));
+
+        let second_jti = Uuid::new_v4();
+        let rotation = db_client.rotate_refresh_token(first.jti, session_id, 420, second_jti, 200, 1100).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Rotated);
+        assert_eq!(db_client.get_refresh_token(first.jti).await?.unwrap().rotated_at, Some(200));
+        let second = db_client.get_refresh_token(second_jti).await?.unwrap();
+        assert_eq!(second.family_id, session_id);
+        assert_eq!(second.rotated_at, None);
+
+        // The session is kept alive by the rotation
+        let session = db_client.get_session_by_id(session_id, 60).await?.unwrap();
+        assert_eq!(session.created_at, 100);
+        assert_eq!(session.last_used_at, 200);
+        assert_eq!(session.expires_at, 1100);
+
+        // Other users, other sessions and expired tokens can't be rotated
+        let rotation = db_client.rotate_refresh_token(second_jti, session_id, 421, Uuid::new_v4(), 300, 1200).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Invalid);
+        let rotation = db_client.rotate_refresh_token(second_jti, Uuid::new_v4(), 420, Uuid::new_v4(), 300, 1200).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Invalid);
+        let rotation = db_client.rotate_refresh_token(second_jti, session_id, 420, Uuid::new_v4(), 1100, 2000).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Invalid);
+        let rotation = db_client.rotate_refresh_token(Uuid::new_v4(), session_id, 420, Uuid::new_v4(), 300, 1200).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Invalid);
+
+        db_client.postgres_delete_user_by_id(420).await?;
+        assert_eq!(db_client.get_refresh_token(second_jti).await?, None);
         Ok(())
     }
 
     #[tokio::test]
     #[serial]
-    async fn test_get_user_refresh_token_with_caching() -> Result<(), DatabaseError> {
-        let db_client: DatabaseClientWithCaching = get_db_client().await;
-        // prepare test
-        let user = User {
-            id: 420,
-            ..User::default()
-        };
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-
-        let res = db_client.postgres_delete_user_by_id(420).await;
-        if res.is_err() {
-            match res.err().unwrap() {
-                DatabaseError::UserNotFound(_) => {},
-                e => {
-                    return Err(e);
-                }
-            }
-        }
-
-
-        db_client.postgres_insert_user(&user).await.unwrap();
-
-        db_client.postgres_set_user_refresh_token(420, "test_token").await.unwrap();
-
-        let token = db_client.cached_get_user_refresh_token(420).await.unwrap();
-        assert_eq!(token, Some("test_token".to_string()));
-
-
-        let token = db_client.redis_get_user_refresh_token_by_user_id(420).await.unwrap();
-
-        // clean up
-        db_client.redis_delete_user_refresh_token(420).await.unwrap();
-        db_client.postgres_delete_user_refresh_token(420).await.unwrap();
-
-        assert_eq!(token, Some("test_token".to_string()));
-
+    async fn test_reused_refresh_token_revokes_session() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+
+        let first = create_session(&db_client, 100, 1000).await;
+        let other_session = create_session(&db_client, 100, 1000).await;
+        let second_jti = Uuid::new_v4();
+        db_client.rotate_refresh_token(first.jti, first.family_id, 420, second_jti, 200, 1100).await?;
+
+        let rotation = db_client.rotate_refresh_token(first.jti, first.family_id, 420, Uuid::new_v4(), 300, 1200).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Reused);
+        assert_eq!(db_client.get_refresh_token(first.jti).await?, None);
+        assert_eq!(db_client.get_refresh_token(second_jti).await?, None);
+        assert_eq!(db_client.get_session_by_id(first.family_id, 60).await?, None);
+        let rotation = db_client.rotate_refresh_token(second_jti, first.family_id, 420, Uuid::new_v4(), 300, 1200).await?;
+        assert_eq!(rotation, RefreshTokenRotation::Invalid);
+
+        // Other logins of the user are kept
+        assert_eq!(db_client.get_refresh_token(other_session.jti).await?, Some(other_session.clone()));
+        db_client.delete_session(other_session.family_id, 420).await?;
+        assert_eq!(db_client.get_refresh_token(other_session.jti).await?, None);
+
+        db_client.postgres_delete_user_by_id(420).await?;
         Ok(())
     }
-}
\ No newline at end of file
+}
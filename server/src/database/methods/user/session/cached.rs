@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use uuid::Uuid;
+
+use crate::{app_objects::{
+    RefreshToken,
+    Session
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching,
+    SessionStore
+}};
+
+
+#[async_trait]
+impl SessionStore for DatabaseClientWithCaching {
+    async fn create_session(
+        &self,
+        session: &Session,
+        refresh_token: &RefreshToken,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_insert_session(session, refresh_token).await?;
+
+        db_client.redis_set_session(session, cache_ttl_s).await?;
+
+        Ok(())
+    }
+
+    async fn get_session_by_id(
+        &self,
+        session_id: Uuid,
+        cache_ttl_s: u64
+    ) -> Result<Option<Session>, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+
+        let session = db_client.redis_get_session_by_id(session_id).await?;
+        if session.is_some() {
+            return Ok(session);
+        }
+
+        let session = db_client.postgres_get_session_by_id(session_id).await?;
+        if let Some(session) = &session {
+            db_client.redis_set_session(session, cache_ttl_s).await?;
+        }
+
+        Ok(session)
+    }
+
+    /// Session lists are always read from Postgres, only single sessions are cached
+    async fn get_user_sessions(
+        &self,
+        user_id: i64,
+        now: i64
+    ) -> Result<Vec<Session>, DatabaseError> {
+        self.postgres_get_user_sessions(user_id, now).await
+    }
+
+    async fn delete_session(
+        &self,
+        session_id: Uuid,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        db_client.postgres_delete_session(session_id, user_id).await?;
+
+        db_client.redis_delete_session(session_id).await?;
+
+        Ok(())
+    }
+
+    /// Logs the user out everywhere
+    async fn delete_user_sessions(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let session_ids = db_client.postgres_delete_user_sessions(user_id).await?;
+
+        for session_id in session_ids {
+            db_client.redis_delete_session(session_id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Logs the user out on every device except the one of the session
+    async fn delete_other_user_sessions(
+        &self,
+        user_id: i64,
+        session_id: Uuid
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let session_ids = db_client.postgres_delete_other_user_sessions(user_id, session_id).await?;
+
+        for session_id in session_ids {
+            db_client.redis_delete_session(session_id).await?;
+        }
+
+        Ok(())
+    }
+}
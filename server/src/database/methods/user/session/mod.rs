@@ -0,0 +1,5 @@
+mod postgres;
+mod redis;
+mod cached;
+
+mod tests;
@@ -0,0 +1,168 @@
+use uuid::Uuid;
+
+use crate::{app_objects::{
+    RefreshToken,
+    Session
+}, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    /// Stores the session together with the first refresh token of its family.
+    /// Expired sessions of the user are dropped on the way.
+    pub async fn postgres_insert_session(
+        &self,
+        session: &Session,
+        refresh_token: &RefreshToken
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.postgres_con.begin().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM sessions
+            WHERE user_id = $1 AND expires_at <= $2
+            "#,
+            session.user_id,
+            session.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO sessions (id, user_id, device_label, ip, user_agent, created_at, last_used_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            session.id,
+            session.user_id,
+            session.device_label,
+            session.ip,
+            session.user_agent,
+            session.created_at,
+            session.last_used_at,
+            session.expires_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(ref db_error) if db_error.is_foreign_key_violation() => {
+                DatabaseError::UserNotFound(session.user_id)
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
+        sqlx::query!(
+            r#"
+            INSERT INTO refresh_tokens (jti, family_id, user_id, issued_at, expires_at, rotated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+            refresh_token.jti,
+            refresh_token.family_id,
+            refresh_token.user_id,
+            refresh_token.issued_at,
+            refresh_token.expires_at,
+            refresh_token.rotated_at
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    pub async fn postgres_get_session_by_id(
+        &self,
+        session_id: Uuid
+    ) -> Result<Option<Session>, DatabaseError> {
+        let session = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id, user_id, device_label, ip, user_agent, created_at, last_used_at, expires_at FROM sessions
+            WHERE id = $1
+            "#,
+            session_id
+        )
+        .fetch_optional(&self.postgres_con)
+        .await?;
+        Ok(session)
+    }
+
+    /// Sessions that didn't expire yet, the most recently used first
+    pub async fn postgres_get_user_sessions(
+        &self,
+        user_id: i64,
+        now: i64
+    ) -> Result<Vec<Session>, DatabaseError> {
+        let sessions = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id, user_id, device_label, ip, user_agent, created_at, last_used_at, expires_at FROM sessions
+            WHERE user_id = $1 AND expires_at > $2
+            ORDER BY last_used_at DESC
+            "#,
+            user_id,
+            now
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(sessions)
+    }
+
+    /// Revokes the refresh tokens of the session as well
+    pub async fn postgres_delete_session(
+        &self,
+        session_id: Uuid,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM sessions
+            WHERE id = $1 AND user_id = $2
+            "#,
+            session_id,
+            user_id
+        )
+        .execute(&self.postgres_con)
+        .await?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::SessionNotFound(session_id));
+        }
+        Ok(())
+    }
+
+    /// Returns the ids of the deleted sessions
+    pub async fn postgres_delete_user_sessions(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Uuid>, DatabaseError> {
+        let session_ids = sqlx::query_scalar!(
+            r#"
+            DELETE FROM sessions
+            WHERE user_id = $1
+            RETURNING id
+            "#,
+            user_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(session_ids)
+    }
+
+    /// Keeps the session with the id, returns the ids of the deleted sessions
+    pub async fn postgres_delete_other_user_sessions(
+        &self,
+        user_id: i64,
+        session_id: Uuid
+    ) -> Result<Vec<Uuid>, DatabaseError> {
+        let session_ids = sqlx::query_scalar!(
+            r#"
+            DELETE FROM sessions
+            WHERE user_id = $1 AND id != $2
+            RETURNING id
+            "#,
+            user_id,
+            session_id
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(session_ids)
+    }
+}
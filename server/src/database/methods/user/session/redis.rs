@@ -0,0 +1,58 @@
+use uuid::Uuid;
+
+use crate::{app_objects::Session, database::{
+    methods::DatabaseError,
+    DatabaseClientWithCaching
+}};
+
+
+impl DatabaseClientWithCaching {
+    pub async fn redis_set_session(
+        &self,
+        session: &Session,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("SET")
+            .arg(
+                format!("session:{}", session.id)
+            )
+            .arg(session.to_json()?)
+            .arg("EX")
+            .arg(ttl_s)
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn redis_get_session_by_id(
+        &self,
+        session_id: Uuid
+    ) -> Result<Option<Session>, DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let session: Option<String> = redis::cmd("GET")
+            .arg(
+                format!("session:{}", session_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        match session {
+            Some(session) => Ok(Some(Session::from_json(&session)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn redis_delete_session(
+        &self,
+        session_id: Uuid
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::cmd("DEL")
+            .arg(
+                format!("session:{}", session_id)
+            )
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
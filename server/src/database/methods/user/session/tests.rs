@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+    use uuid::Uuid;
+    use crate::app_objects::{
+        RefreshToken,
+        Session,
+        User
+    };
+    use crate::configuration::Config;
+    use crate::database::methods::DatabaseError;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        RefreshTokenStore,
+        SessionStore
+    };
+
+    async fn get_db_client() -> DatabaseClientWithCaching {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let db_client = DatabaseClientWithCaching::new(
+            &config.redis_database,
+            &config.postgres_database
+        ).await.unwrap();
+        db_client
+    }
+
+    /// Recreates user 420, which drops all of its sessions
+    async fn create_test_user(db_client: &DatabaseClientWithCaching) {
+        let user = User {
+            id: 420,
+            ..User::default()
+        };
+        let _ = db_client.delete_user_sessions(420).await;
+        let res = db_client.postgres_delete_user_by_id(420).await;
+        if res.is_err() {
+            match res.err().unwrap() {
+                DatabaseError::UserNotFound(_) => {},
+                e => panic!("Error deleting user: {:?}", e)
+            }
+        }
+        db_client.postgres_insert_user(&user).await.unwrap();
+    }
+
+    fn new_session(
+        user_id: i64,
+        device_label: &str,
+        created_at: i64,
+        expires_at: i64
+    ) -> (Session, RefreshToken) {
+        let session = Session::new(
+            Uuid::new_v4(),
+            user_id,
+            device_label.to_string(),
+            Some("127.0.0.1".to_string()),
+            Some("test agent".to_string()),
+            created_at,
+            expires_at
+        );
+        let refresh_token = RefreshToken::new_family(Uuid::new_v4(), session.id, user_id, created_at, expires_at);
+        (session, refresh_token)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_create_and_delete_sessions() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+
+        // Expired sessions are dropped once the user logs in again
+        let (expired, expired_token) = new_session(420, "old phone", 100, 1000);
+        db_client.create_session(&expired, &expired_token, 60).await?;
+        let (desktop, desktop_token) = new_session(420, "desktop", 1000, 2000);
+        db_client.create_session(&desktop, &desktop_token, 60).await?;
+        let (phone, phone_token) = new_session(420, "phone", 1100, 2100);
+        db_client.create_session(&phone, &phone_token, 60).await?;
+        assert_eq!(db_client.postgres_get_session_by_id(expired.id).await?, None);
+        assert_eq!(db_client.get_refresh_token(expired_token.jti).await?, None);
+
+        assert_eq!(db_client.get_session_by_id(desktop.id, 60).await?, Some(desktop.clone()));
+        assert_eq!(db_client.postgres_get_session_by_id(phone.id).await?, Some(phone.clone()));
+        assert_eq!(db_client.get_user_sessions(420, 1500).await?, vec![phone.clone(), desktop.clone()]);
+        assert_eq!(db_client.get_user_sessions(420, 2050).await?, vec![phone.clone()]);
+
+        // Sessions of other users can't be deleted
+        assert!(matches!(
+            db_client.delete_session(desktop.id, 421).await,
+            Err(DatabaseError::SessionNotFound(_))
+        ));
+        db_client.delete_session(desktop.id, 420).await?;
+        assert_eq!(db_client.get_session_by_id(desktop.id, 60).await?, None);
+        assert_eq!(db_client.get_refresh_token(desktop_token.jti).await?, None);
+        assert!(matches!(
+            db_client.delete_session(desktop.id, 420).await,
+            Err(DatabaseError::SessionNotFound(_))
+        ));
+
+        db_client.get_session_by_id(phone.id, 60).await?;
+        db_client.delete_user_sessions(420).await?;
+        assert_eq!(db_client.get_session_by_id(phone.id, 60).await?, None);
+        assert_eq!(db_client.get_refresh_token(phone_token.jti).await?, None);
+
+        let (missing_user, missing_user_token) = new_session(429, "desktop", 100, 1000);
+        assert!(matches!(
+            db_client.create_session(&missing_user, &missing_user_token, 60).await,
+            Err(DatabaseError::UserNotFound(429))
+        ));
+
+        db_client.postgres_delete_user_by_id(420).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_delete_other_user_sessions() -> Result<(), DatabaseError> {
+        let db_client = get_db_client().await;
+        create_test_user(&db_client).await;
+
+        let (desktop, desktop_token) = new_session(420, "desktop", 1000, 2000);
+        db_client.create_session(&desktop, &desktop_token, 60).await?;
+        let (phone, phone_token) = new_session(420, "phone", 1100, 2100);
+        db_client.create_session(&phone, &phone_token, 60).await?;
+        db_client.get_session_by_id(phone.id, 60).await?;
+
+        db_client.delete_other_user_sessions(420, desktop.id).await?;
+        assert_eq!(db_client.get_session_by_id(phone.id, 60).await?, None);
+        assert_eq!(db_client.get_refresh_token(phone_token.jti).await?, None);
+        assert_eq!(db_client.get_user_sessions(420, 1500).await?, vec![desktop.clone()]);
+        assert_eq!(db_client.get_refresh_token(desktop_token.jti).await?, Some(desktop_token.clone()));
+
+        db_client.postgres_delete_user_by_id(420).await?;
+        Ok(())
+    }
+}
@@ -1,15 +1,22 @@
 use std::sync::Arc;
 
+use axum::async_trait;
+
 use crate::{app_objects::User, database::{
     methods::DatabaseError,
-    DatabaseClientWithCaching
+    DatabaseClientWithCaching,
+    DmStore,
+    GuildStore,
+    SessionStore,
+    UserStore
 }};
 
 
 
-impl DatabaseClientWithCaching {
+#[async_trait]
+impl UserStore for DatabaseClientWithCaching {
 
-    pub async fn cached_insert_user(
+    async fn insert_user(
         &self,
         user: &User
     ) -> Result<(), DatabaseError> {
@@ -25,7 +32,15 @@ impl DatabaseClientWithCaching {
         Ok(())
     }
 
-    pub async fn cached_get_user_id_by_email(
+    /// Users are always read from Postgres, the banned and verified flags must never be stale
+    async fn get_user_by_id(
+        &self,
+        user_id: i64
+    ) -> Result<Option<User>, DatabaseError> {
+        self.postgres_get_user_by_id(user_id).await
+    }
+
+    async fn get_user_id_by_email(
         &self,
         email: &str
     ) -> Result<Option<i64>, DatabaseError> {
@@ -54,4 +69,73 @@ impl DatabaseClientWithCaching {
         return Ok(Some(user_id));
     }
 
-}
\ No newline at end of file
+    /// Returns false if the email of the user isn't `old_email` anymore
+    async fn update_user_email(
+        &self,
+        user_id: i64,
+        old_email: &str,
+        new_email: &str
+    ) -> Result<bool, DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let updated = db_client.postgres_update_user_email(user_id, old_email, new_email).await?;
+        if !updated {
+            return Ok(false);
+        }
+
+        db_client.redis_move_email(old_email, new_email, user_id).await?;
+
+        Ok(true)
+    }
+
+    /// Search results are always read from Postgres
+    async fn search_users(
+        &self,
+        user_id: Option<i64>,
+        email_pattern: Option<&str>,
+        username_pattern: Option<&str>,
+        limit: i64
+    ) -> Result<Vec<User>, DatabaseError> {
+        self.postgres_search_users(user_id, email_pattern, username_pattern, limit).await
+    }
+
+    async fn set_user_verified(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        self.postgres_set_user_verified(user_id).await
+    }
+
+    /// Deletes the user together with everything Redis keeps about them. The user leaves their DM channels
+    /// first, so group DMs pass to the next recipient, the other rows referencing the user in Postgres are
+    /// deleted by their foreign keys. Fails with `UserOwnsGuilds` while the user owns a guild.
+    async fn delete_user(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let db_client = Arc::new(self.clone());
+        let user = db_client.postgres_get_user_by_id(user_id).await?
+            .ok_or(DatabaseError::UserNotFound(user_id))?;
+
+        let guilds = db_client.get_user_guilds(user_id).await?;
+        if guilds.iter().any(|guild| guild.owner_id == user_id) {
+            return Err(DatabaseError::UserOwnsGuilds(user_id));
+        }
+        for channel in db_client.get_user_dm_channels(user_id).await? {
+            db_client.remove_dm_recipient(channel.id, user_id).await?;
+        }
+
+        db_client.delete_user_sessions(user_id).await?;
+        db_client.postgres_delete_user_by_id(user_id).await?;
+
+        for guild in &guilds {
+            db_client.redis_delete_guild_members(guild.id, &[user_id]).await?;
+        }
+
+        db_client.redis_delete_email(&user.email).await?;
+        db_client.redis_delete_password_hash_by_user_id(user_id).await?;
+        db_client.redis_delete_salt_by_user_id(user_id).await?;
+        db_client.redis_delete_denylisted_user(user_id).await?;
+
+        Ok(())
+    }
+}
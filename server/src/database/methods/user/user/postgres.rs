@@ -68,7 +68,7 @@ impl DatabaseClientWithCaching {
     ) -> Result<(), DatabaseError> {
         let res = sqlx::query!(
             r#"
-            INSERT INTO users (id, username, password_hash, salt, email, created_at, valid_refresh_token, verified, banned, date_of_birth)
+            INSERT INTO users (id, username, password_hash, salt, email, created_at, verified, banned, date_of_birth, admin)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
             user.id,
@@ -77,13 +77,25 @@ impl DatabaseClientWithCaching {
             user.salt,
             user.email,
             user.created_at,
-            user.valid_refresh_token,
             user.verified,
             user.banned,
-            user.date_of_birth
+            user.date_of_birth,
+            user.admin
         )
         .execute(&self.postgres_con)
-        .await?;
+        .await
+        .map_err(|e| match e {
+            // Two verification links for the same email can be used at the same time,
+            // the unique constraint on email makes sure only one of them succeeds
+            sqlx::Error::Database(ref db_error) if db_error.is_unique_violation() => {
+                if db_error.constraint() == Some("users_email_key") {
+                    DatabaseError::EmailAlreadyExists(user.email.clone())
+                } else {
+                    DatabaseError::UserAlreadyExists(user.id)
+                }
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
         if res.rows_affected() == 0 {
             return Err(DatabaseError::UserAlreadyExists(user.id));
         }
@@ -102,10 +114,93 @@ impl DatabaseClientWithCaching {
             user_id
         )
         .execute(&self.postgres_con)
+        .await
+        .map_err(|e| match e {
+            // Guilds can't be left without an owner, they have to be handed over or deleted first
+            sqlx::Error::Database(ref db_error) if db_error.constraint() == Some("guilds_owner_id_fkey") => {
+                DatabaseError::UserOwnsGuilds(user_id)
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
+        if res.rows_affected() == 0 {
+            return Err(DatabaseError::UserNotFound(user_id));
+        }
+        Ok(())
+    }
+
+    /// Returns false if the email of the user isn't `old_email` anymore
+    pub async fn postgres_update_user_email(
+        &self,
+        user_id: i64,
+        old_email: &str,
+        new_email: &str
+    ) -> Result<bool, DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE users SET email = $3
+            WHERE id = $1 AND email = $2
+            "#,
+            user_id,
+            old_email,
+            new_email
+        )
+        .execute(&self.postgres_con)
+        .await
+        .map_err(|e| match e {
+            // The address was registered or taken by another change after the link was sent
+            sqlx::Error::Database(ref db_error) if db_error.is_unique_violation() => {
+                DatabaseError::EmailAlreadyExists(new_email.to_string())
+            },
+            e => DatabaseError::SQLXError(e),
+        })?;
+        Ok(res.rows_affected() == 1)
+    }
+
+    /// Filters left as None match every user, `email_pattern` and `username_pattern` are ILIKE patterns
+    pub async fn postgres_search_users(
+        &self,
+        user_id: Option<i64>,
+        email_pattern: Option<&str>,
+        username_pattern: Option<&str>,
+        limit: i64
+    ) -> Result<Vec<User>, DatabaseError> {
+        let users = sqlx::query_as!(
+            User,
+            r#"
+            SELECT * FROM users
+            WHERE ($1::BIGINT IS NULL OR id = $1)
+                AND ($2::TEXT IS NULL OR email ILIKE $2)
+                AND ($3::TEXT IS NULL OR username ILIKE $3)
+            ORDER BY id
+            LIMIT $4
+            "#,
+            user_id,
+            email_pattern,
+            username_pattern,
+            limit
+        )
+        .fetch_all(&self.postgres_con)
+        .await?;
+        Ok(users)
+    }
+
+    pub async fn postgres_set_user_verified(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE users
+            SET verified = TRUE
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.postgres_con)
         .await?;
         if res.rows_affected() == 0 {
             return Err(DatabaseError::UserNotFound(user_id));
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
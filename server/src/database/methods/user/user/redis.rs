@@ -59,4 +59,31 @@ impl DatabaseClientWithCaching {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Points the new email at the user and drops the old one in a single transaction,
+    /// a login never sees both or neither of them
+    pub async fn redis_move_email(
+        &self,
+        old_email: &str,
+        new_email: &str,
+        user_id: i64
+    ) -> Result<(), DatabaseError> {
+        let mut con = self.redis_con.clone();
+        let _: () = redis::pipe()
+            .atomic()
+            .cmd("DEL")
+            .arg(
+                format!("email:{}:id", old_email)
+            )
+            .ignore()
+            .cmd("SET")
+            .arg(
+                format!("email:{}:id", new_email)
+            )
+            .arg(user_id)
+            .ignore()
+            .query_async(&mut con)
+            .await?;
+        Ok(())
+    }
+}
@@ -8,7 +8,11 @@ mod tests {
     use crate::app_objects::User;
     use crate::configuration::Config;
     use crate::database::methods::DatabaseError;
-    use crate::database::DatabaseClientWithCaching;
+    use crate::database::{
+        DatabaseClientWithCaching,
+        CredentialStore,
+        UserStore
+    };
 
     async fn get_db_client() -> DatabaseClientWithCaching {
         let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -49,7 +53,7 @@ mod tests {
             }
         }
 
-        db_client.cached_insert_user(&user).await.unwrap();
+        db_client.insert_user(&user).await.unwrap();
         let user_db = db_client.postgres_get_user_by_id(420).await.unwrap();
         assert_eq!(user_db, Some(user));
 
@@ -90,7 +94,6 @@ mod tests {
             id: 420,
             ..User::default()
         };
-        let _ = db_client.postgres_delete_user_refresh_token(420).await;
         let res = db_client.postgres_delete_user_by_id(420).await;
         if res.is_err() {
             match res.err().unwrap() {
@@ -135,7 +138,7 @@ mod tests {
 
         db_client.postgres_insert_user(&user).await.unwrap();
 
-        let user_id = db_client.cached_get_user_id_by_email("test_email").await.unwrap();
+        let user_id = db_client.get_user_id_by_email("test_email").await.unwrap();
         assert_eq!(user_id, Some(420));
 
         assert_eq!(db_client.redis_get_user_id_by_email("test_email").await.unwrap(), Some(420));
@@ -143,5 +146,102 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_update_user_email() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        for (user_id, email) in [(420, "test_email"), (421, "taken_email")] {
+            let _ = db_client.postgres_delete_user_by_id(user_id).await;
+            db_client.redis_delete_email(email).await?;
+            let user = User {
+                email: email.to_string(),
+                id: user_id,
+                ..User::default()
+            };
+            db_client.postgres_insert_user(&user).await?;
+        }
+        db_client.redis_delete_email("new_test_email").await?;
+        assert_eq!(db_client.get_user_id_by_email("test_email").await?, Some(420));
+
+        assert!(db_client.update_user_email(420, "test_email", "new_test_email").await?);
+        assert_eq!(db_client.redis_get_user_id_by_email("test_email").await?, None);
+        assert_eq!(db_client.redis_get_user_id_by_email("new_test_email").await?, Some(420));
+        assert_eq!(db_client.get_user_id_by_email("test_email").await?, None);
+        assert_eq!(db_client.postgres_get_user_by_id(420).await?.unwrap().email, "new_test_email".to_string());
+
+        // The email changed since, the old change is dropped
+        assert!(!db_client.update_user_email(420, "test_email", "other_email").await?);
+        assert!(matches!(
+            db_client.update_user_email(420, "new_test_email", "taken_email").await,
+            Err(DatabaseError::EmailAlreadyExists(_))
+        ));
+        assert_eq!(db_client.get_user_id_by_email("new_test_email").await?, Some(420));
+
+        db_client.postgres_delete_user_by_id(420).await?;
+        db_client.postgres_delete_user_by_id(421).await?;
+        db_client.redis_delete_email("new_test_email").await?;
+        db_client.redis_delete_email("taken_email").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_search_users() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let users = [
+            (460, "search_a@example.com", "searched_alice"),
+            (461, "search_b@example.com", "searched_bob")
+        ];
+        for (user_id, email, username) in users {
+            let _ = db_client.postgres_delete_user_by_id(user_id).await;
+            let user = User {
+                id: user_id,
+                email: email.to_string(),
+                username: username.to_string(),
+                verified: false,
+                created_at: chrono::Utc::now().timestamp(),
+                ..User::default()
+            };
+            db_client.postgres_insert_user(&user).await?;
+        }
+
+        let ids = |users: Vec<User>| users.into_iter().map(|user| user.id).collect::<Vec<_>>();
+        assert_eq!(ids(db_client.search_users(None, Some("SEARCH_%@example.com"), None, 10).await?), vec![460, 461]);
+        assert_eq!(ids(db_client.search_users(None, Some("search_%"), Some("searched_b%"), 10).await?), vec![461]);
+        assert_eq!(ids(db_client.search_users(Some(460), None, None, 10).await?), vec![460]);
+        assert_eq!(ids(db_client.search_users(None, Some("search_%"), None, 1).await?), vec![460]);
 
-}
\ No newline at end of file
+        db_client.set_user_verified(460).await?;
+        assert!(db_client.postgres_get_user_by_id(460).await?.unwrap().verified);
+        assert!(matches!(db_client.set_user_verified(462).await, Err(DatabaseError::UserNotFound(462))));
+
+        db_client.postgres_delete_user_by_id(460).await?;
+        db_client.postgres_delete_user_by_id(461).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_cached_delete_user() -> Result<(), DatabaseError> {
+        let db_client: DatabaseClientWithCaching = get_db_client().await;
+        let _ = db_client.postgres_delete_user_by_id(460).await;
+        let user = User {
+            id: 460,
+            email: "deleted@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            salt: "salt".to_string(),
+            ..User::default()
+        };
+        db_client.insert_user(&user).await?;
+        db_client.get_password_hash_and_salt_by_user_id(460).await?;
+        assert_eq!(db_client.redis_get_password_hash_by_user_id(460).await?, Some("hash".to_string()));
+
+        db_client.delete_user(460).await?;
+        assert_eq!(db_client.postgres_get_user_by_id(460).await?, None);
+        assert_eq!(db_client.redis_get_user_id_by_email("deleted@example.com").await?, None);
+        assert_eq!(db_client.redis_get_password_hash_by_user_id(460).await?, None);
+        assert_eq!(db_client.redis_get_salt_by_user_id(460).await?, None);
+        assert!(matches!(db_client.delete_user(460).await, Err(DatabaseError::UserNotFound(460))));
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+mod tests;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use sqlx::migrate::{
+    Migrate, MigrateError, Migrator
+};
+use sqlx::postgres::PgPool;
+use thiserror::Error;
+use tracing::info;
+
+// Embeds server/migrations, build.rs rebuilds the server when a file is added there
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+#[derive(Error, Debug)]
+pub enum MigrationError {
+    #[error("An error occurred while interacting with the database: {0}")]
+    SQLXError(#[from] sqlx::Error),
+    #[error("Migration failed: {0}")]
+    MigrateError(#[from] MigrateError),
+    #[error("Database schema is behind, pending migrations: {0:?}. Run `server migrate up`")]
+    SchemaBehind(Vec<i64>),
+    #[error("Applied migration {0} was modified after it was applied")]
+    ChecksumMismatch(i64),
+    #[error("Applied migration {0} is unknown to this server, the schema is ahead of it")]
+    UnknownMigration(i64),
+    #[error("No applied migration to revert")]
+    NothingToRevert,
+    #[error("Migration {0} has no down migration")]
+    Irreversible(i64),
+    #[error("Unknown migrate command: {0:?}, expected up, down or status")]
+    UnknownCommand(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    // Applied, but the file changed since
+    Modified,
+    // Applied by a newer server
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrateCommand {
+    Up,
+    Down,
+    Status,
+}
+
+impl FromStr for MigrateCommand {
+    type Err = MigrationError;
+
+    fn from_str(command: &str) -> Result<Self, Self::Err> {
+        match command {
+            "up" => Ok(MigrateCommand::Up),
+            "down" => Ok(MigrateCommand::Down),
+            "status" => Ok(MigrateCommand::Status),
+            _ => Err(MigrationError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+pub async fn apply_migrations(
+    pool: &PgPool,
+) -> Result<(), MigrationError> {
+    MIGRATOR.run(pool).await?;
+    Ok(())
+}
+
+/// Every migration the server knows of and every applied one, ordered by version
+pub async fn migration_status(
+    pool: &PgPool,
+) -> Result<Vec<MigrationStatus>, MigrationError> {
+    let mut con = pool.acquire().await?;
+    con.ensure_migrations_table().await?;
+    if let Some(version) = con.dirty_version().await? {
+        return Err(MigrateError::Dirty(version).into());
+    }
+    let mut applied: HashMap<i64, Vec<u8>> = con.list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|migration| (migration.version, migration.checksum.into_owned()))
+        .collect();
+
+    let mut status: Vec<MigrationStatus> = MIGRATOR.iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .map(|migration| {
+            let state = match applied.remove(&migration.version) {
+                Some(checksum) if checksum == *migration.checksum => MigrationState::Applied,
+                Some(_) => MigrationState::Modified,
+                None => MigrationState::Pending,
+            };
+            MigrationStatus {
+                version: migration.version,
+                description: migration.description.to_string(),
+                state,
+            }
+        })
+        .collect();
+    status.extend(applied.into_keys().map(|version| MigrationStatus {
+        version,
+        description: String::new(),
+        state: MigrationState::Unknown,
+    }));
+    status.sort_by_key(|migration| migration.version);
+    Ok(status)
+}
+
+/// Fails unless every migration is applied unchanged, for deployments that migrate out of band
+pub async fn ensure_schema_current(
+    pool: &PgPool,
+) -> Result<(), MigrationError> {
+    let status = migration_status(pool).await?;
+    let mut pending = Vec::new();
+    for migration in status {
+        match migration.state {
+            MigrationState::Applied => {},
+            MigrationState::Pending => pending.push(migration.version),
+            MigrationState::Modified => return Err(MigrationError::ChecksumMismatch(migration.version)),
+            MigrationState::Unknown => return Err(MigrationError::UnknownMigration(migration.version)),
+        }
+    }
+    if !pending.is_empty() {
+        return Err(MigrationError::SchemaBehind(pending));
+    }
+    Ok(())
+}
+
+/// Reverts the newest applied migration and returns its version
+pub async fn revert_last_migration(
+    pool: &PgPool,
+) -> Result<i64, MigrationError> {
+    let mut applied: Vec<i64> = migration_status(pool)
+        .await?
+        .into_iter()
+        .filter(|migration| migration.state != MigrationState::Pending)
+        .map(|migration| migration.version)
+        .collect();
+    let version = applied.pop().ok_or(MigrationError::NothingToRevert)?;
+    let reversible = MIGRATOR.iter().any(|migration| {
+        migration.version == version && migration.migration_type.is_down_migration()
+    });
+    if !reversible {
+        return Err(MigrationError::Irreversible(version));
+    }
+
+    // Reverts everything newer than the target, which is only the newest migration
+    let target = applied.pop().unwrap_or(0);
+    MIGRATOR.undo(pool, target).await?;
+    Ok(version)
+}
+
+pub async fn run_migrate_command(
+    command: MigrateCommand,
+    pool: &PgPool,
+) -> Result<(), MigrationError> {
+    match command {
+        MigrateCommand::Up => {
+            apply_migrations(pool).await?;
+            info!("Database schema is up to date");
+        },
+        MigrateCommand::Down => {
+            let version = revert_last_migration(pool).await?;
+            info!("Reverted migration {}", version);
+        },
+        MigrateCommand::Status => {
+            for migration in migration_status(pool).await? {
+                let state = match migration.state {
+                    MigrationState::Applied => "applied",
+                    MigrationState::Pending => "pending",
+                    MigrationState::Modified => "modified",
+                    MigrationState::Unknown => "unknown",
+                };
+                println!("{} {:<8} {}", migration.version, state, migration.description);
+            }
+        },
+    }
+    Ok(())
+}
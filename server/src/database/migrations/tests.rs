@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use serial_test::serial;
+    use crate::configuration::Config;
+    use crate::database::connect_postgres;
+    use super::super::{
+        ensure_schema_current,
+        migration_status,
+        MigrateCommand,
+        MigrationError,
+        MigrationState,
+        MIGRATOR
+    };
+
+    async fn get_pool() -> sqlx::PgPool {
+        let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        cfg_path.push("../configuration/server/config.toml");
+        let config = Config::from_file(cfg_path).unwrap();
+        let pool = connect_postgres(&config.postgres_database).await.unwrap();
+        super::super::apply_migrations(&pool).await.unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_migration_status_after_apply() {
+        let pool = get_pool().await;
+
+        let status = migration_status(&pool).await.unwrap();
+        let known = MIGRATOR.iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .count();
+        assert_eq!(status.len(), known);
+        assert!(status.iter().all(|migration| migration.state == MigrationState::Applied));
+        assert!(ensure_schema_current(&pool).await.is_ok());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_users_email_key_after_apply() {
+        let pool = get_pool().await;
+
+        let constraints: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pg_constraint WHERE conname = 'users_email_key' AND conrelid = 'users'::regclass"
+        )
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(constraints, 1);
+    }
+
+    #[test]
+    fn test_parse_migrate_command() {
+        assert_eq!("up".parse::<MigrateCommand>().unwrap(), MigrateCommand::Up);
+        assert_eq!("down".parse::<MigrateCommand>().unwrap(), MigrateCommand::Down);
+        assert_eq!("status".parse::<MigrateCommand>().unwrap(), MigrateCommand::Status);
+        assert!(matches!(
+            "sideways".parse::<MigrateCommand>(),
+            Err(MigrationError::UnknownCommand(_))
+        ));
+    }
+}
@@ -2,11 +2,43 @@ mod redis_preparation;
 mod postgres_preparation;
 
 mod client;
+mod migrations;
 
 mod methods;
+mod stores;
+#[cfg(test)]
+mod in_memory;
 
 pub use methods::DatabaseError;
 
 pub (super) use redis_preparation::prepare_redis_con;
 pub (super) use postgres_preparation::prepare_postgres_con;
-pub use client::DatabaseClientWithCaching;
\ No newline at end of file
+pub use postgres_preparation::connect_postgres;
+pub use migrations::{
+    run_migrate_command, MigrateCommand, MigrationError
+};
+pub use client::DatabaseClientWithCaching;
+pub use stores::{
+    Storage,
+    DatabaseClient,
+    UserStore,
+    CredentialStore,
+    RefreshTokenStore,
+    SessionStore,
+    MfaStore,
+    PasskeyStore,
+    PasswordResetStore,
+    EmailChangeStore,
+    PendingRegistrationStore,
+    LoginAttemptStore,
+    BanStore,
+    MessageStore,
+    GuildStore,
+    RoleStore,
+    DmStore,
+    AdminAuditStore,
+    AuditEventStore,
+    EmailJobStore
+};
+#[cfg(test)]
+pub use in_memory::InMemoryDatabase;
@@ -1,11 +1,17 @@
-use crate::configuration::PostgresDatabaseConfig;
+use crate::configuration::{
+    MigrationMode, PostgresDatabaseConfig
+};
+
+use super::migrations::{
+    apply_migrations, ensure_schema_current, MigrationError
+};
 
 use sqlx::postgres::{
     PgConnectOptions, PgPool
 };
 use sqlx::ConnectOptions;
 
-pub async fn prepare_postgres_con(
+pub async fn connect_postgres(
     postgres_config: &PostgresDatabaseConfig,
 ) -> Result<PgPool, sqlx::Error> {
 
@@ -18,10 +24,17 @@ pub async fn prepare_postgres_con(
 
     db_connect_options = db_connect_options.log_statements(log::LevelFilter::Debug);
 
-    let pool = PgPool::connect_with(db_connect_options).await?;
-    sqlx::query_file!("sql/init_messages_db.sql")
-        .execute(&pool)
-        .await?;
+    PgPool::connect_with(db_connect_options).await
+}
+
+pub async fn prepare_postgres_con(
+    postgres_config: &PostgresDatabaseConfig,
+) -> Result<PgPool, MigrationError> {
+    let pool = connect_postgres(postgres_config).await?;
+    match postgres_config.migration_mode {
+        MigrationMode::Apply => apply_migrations(&pool).await?,
+        MigrationMode::Check => ensure_schema_current(&pool).await?,
+    }
 
     Ok(pool)
-}
\ No newline at end of file
+}
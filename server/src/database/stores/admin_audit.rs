@@ -0,0 +1,26 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AdminAuditEntry,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait AdminAuditStore: Send + Sync {
+    /// Returns the id given to the entry
+    async fn insert_admin_audit_entry(
+        &self,
+        admin_id: i64,
+        action: &str,
+        target_user_id: Option<i64>,
+        details: &serde_json::Value
+    ) -> Result<i64, DatabaseError>;
+
+    /// Newest entries first, `before` is the id of the last entry of the previous page
+    async fn get_admin_audit_entries(
+        &self,
+        target_user_id: Option<i64>,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AdminAuditEntry>, DatabaseError>;
+}
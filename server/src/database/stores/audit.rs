@@ -0,0 +1,24 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::AuditEvent,
+    database::DatabaseError
+};
+
+/// Audit events are append-only, there is no way to change or delete one
+#[async_trait]
+pub trait AuditEventStore: Send + Sync {
+    /// Ignores the id of the event, returns the one given by the store
+    async fn insert_audit_event(
+        &self,
+        audit_event: &AuditEvent
+    ) -> Result<i64, DatabaseError>;
+
+    /// Newest events first, `before` is the id of the last event of the previous page
+    async fn get_user_audit_events(
+        &self,
+        target_user_id: i64,
+        before: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<AuditEvent>, DatabaseError>;
+}
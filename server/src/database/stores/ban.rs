@@ -0,0 +1,33 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::UserBan,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait BanStore: Send + Sync {
+    /// Sets the banned flag and stores the details, a second ban replaces the first one
+    async fn ban_user(
+        &self,
+        user_ban: &UserBan
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns false if the user wasn't banned
+    async fn unban_user(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError>;
+
+    async fn get_user_ban(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError>;
+
+    /// Looked up on every authenticated request, has to stay cheap.
+    /// May still return a ban that ran out, callers check `UserBan::is_active`.
+    async fn get_denylisted_user(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserBan>, DatabaseError>;
+}
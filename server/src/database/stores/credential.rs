@@ -0,0 +1,20 @@
+use axum::async_trait;
+
+use crate::database::DatabaseError;
+
+#[async_trait]
+pub trait CredentialStore: Send + Sync {
+    /// Fails with `UserNotFound` for unknown users
+    async fn get_password_hash_and_salt_by_user_id(
+        &self,
+        user_id: i64
+    ) -> Result<(String, String), DatabaseError>;
+
+    /// The old password must stop working right away
+    async fn update_password_hash_and_salt(
+        &self,
+        user_id: i64,
+        password_hash: &str,
+        salt: &str
+    ) -> Result<(), DatabaseError>;
+}
@@ -0,0 +1,73 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::DmChannel,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait DmStore: Send + Sync {
+    /// Stores the channel with its recipients. Opening a direct DM between two users
+    /// that already have one returns the existing channel instead of the given one.
+    async fn open_dm_channel(
+        &self,
+        channel: &DmChannel,
+        cache_ttl_s: u64
+    ) -> Result<DmChannel, DatabaseError>;
+
+    async fn get_dm_channel_by_id(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<DmChannel>, DatabaseError>;
+
+    /// Returns the DM channels the user is a recipient of, oldest first
+    async fn get_user_dm_channels(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<DmChannel>, DatabaseError>;
+
+    /// Adding a user that already is a recipient does nothing
+    async fn add_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64,
+        joined_at: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Removes the recipient from a group DM. Ownership passes to the recipient that joined first,
+    /// the channel and its messages are deleted once the last recipient is gone.
+    async fn remove_dm_recipient(
+        &self,
+        channel_id: i64,
+        user_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Blocking a user twice does nothing
+    async fn block_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64,
+        created_at: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Unblocking a user that isn't blocked does nothing
+    async fn unblock_user(
+        &self,
+        user_id: i64,
+        blocked_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns the ids of the users blocked by the user
+    async fn get_user_blocks(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<i64>, DatabaseError>;
+
+    /// True if either of the users blocked the other
+    async fn is_blocked_between(
+        &self,
+        user_id: i64,
+        other_user_id: i64
+    ) -> Result<bool, DatabaseError>;
+}
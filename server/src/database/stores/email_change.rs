@@ -0,0 +1,24 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PendingEmailChange,
+    database::DatabaseError
+};
+
+/// Pending changes expire together with the emailed link
+#[async_trait]
+pub trait EmailChangeStore: Send + Sync {
+    async fn create_email_change(
+        &self,
+        jti: Uuid,
+        email_change: &PendingEmailChange,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns `None` if the change expired or was already confirmed
+    async fn take_email_change(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<PendingEmailChange>, DatabaseError>;
+}
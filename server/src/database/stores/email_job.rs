@@ -0,0 +1,48 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::EmailJob,
+    database::DatabaseError
+};
+
+/// Queue of outbound emails, several workers can claim from it at once without getting the same job
+#[async_trait]
+pub trait EmailJobStore: Send + Sync {
+    /// False when a job with the same idempotency key was already enqueued, the job isn't added then
+    async fn enqueue_email_job(
+        &self,
+        email_job: &EmailJob
+    ) -> Result<bool, DatabaseError>;
+
+    /// Marks up to `limit` due jobs as sending until `now + lease_s` and counts the attempt.
+    /// Jobs whose lease ran out are due again, their worker is assumed dead
+    async fn claim_email_jobs(
+        &self,
+        now: i64,
+        lease_s: i64,
+        limit: i64
+    ) -> Result<Vec<EmailJob>, DatabaseError>;
+
+    /// Empties the message, the links in it may still be valid and don't have to be kept once it's sent
+    async fn complete_email_job(
+        &self,
+        job_id: i64,
+        now: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// The job is retried at `retry_at`, without one it's dead, never sent and its message is emptied
+    async fn fail_email_job(
+        &self,
+        job_id: i64,
+        error: &str,
+        retry_at: Option<i64>,
+        now: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Deletes the sent and dead jobs last updated before `updated_before`, returns how many.
+    /// Their idempotency keys go with them
+    async fn delete_finished_email_jobs(
+        &self,
+        updated_before: i64
+    ) -> Result<u64, DatabaseError>;
+}
@@ -0,0 +1,111 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        Channel,
+        Guild,
+        GuildMember,
+        Invite,
+        Role
+    },
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait GuildStore: Send + Sync {
+    /// Stores the guild together with its owner, @everyone role and initial channels, all or nothing
+    async fn create_guild(
+        &self,
+        guild: &Guild,
+        owner: &GuildMember,
+        everyone_role: &Role,
+        channels: &[Channel],
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_guild_by_id(
+        &self,
+        guild_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Guild>, DatabaseError>;
+
+    /// The guilds the user is a member of, in the order they joined them
+    async fn get_user_guilds(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Guild>, DatabaseError>;
+
+    /// Deletes the guild with everything in it, messages of its channels included
+    async fn delete_guild(
+        &self,
+        guild_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    async fn insert_channel(
+        &self,
+        channel: &Channel,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_channel_by_id(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Channel>, DatabaseError>;
+
+    /// Returns the channels of the guild in display order
+    async fn get_guild_channels(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<Channel>, DatabaseError>;
+
+    async fn rename_channel(
+        &self,
+        channel_id: i64,
+        name: &str,
+        cache_ttl_s: u64
+    ) -> Result<Channel, DatabaseError>;
+
+    /// Applies all position changes or none of them if one of the channels isn't in the guild.
+    /// Returns all channels of the guild in their new order.
+    async fn update_channel_positions(
+        &self,
+        guild_id: i64,
+        positions: &[(i64, i32)],
+        cache_ttl_s: u64
+    ) -> Result<Vec<Channel>, DatabaseError>;
+
+    /// Deletes the channel with its messages. Channels inside a deleted category are kept without a parent.
+    async fn delete_channel(
+        &self,
+        channel_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Fails with `MemberAlreadyExists` if the user already is a member
+    async fn insert_guild_member(
+        &self,
+        member: &GuildMember,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_guild_member(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<GuildMember>, DatabaseError>;
+
+    async fn insert_invite(
+        &self,
+        invite: &Invite
+    ) -> Result<(), DatabaseError>;
+
+    /// Uses up the invite and adds the user to its guild. The use is only counted if the user
+    /// actually joined, an expired, used up or unknown invite returns `InviteNotFound`.
+    async fn join_guild_with_invite(
+        &self,
+        code: &str,
+        user_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<GuildMember, DatabaseError>;
+}
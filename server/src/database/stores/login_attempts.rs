@@ -0,0 +1,40 @@
+use axum::async_trait;
+
+use crate::database::DatabaseError;
+
+/// Failed logins are counted per subject over a sliding window of `window_s` seconds
+#[async_trait]
+pub trait LoginAttemptStore: Send + Sync {
+    /// Returns the failures in the window including the new one
+    async fn add_login_failure(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError>;
+
+    async fn count_login_failures(
+        &self,
+        subject: &str,
+        now: i64,
+        window_s: i64
+    ) -> Result<u64, DatabaseError>;
+
+    /// Forgets the failures and the lockout of the subject
+    async fn reset_login_failures(
+        &self,
+        subject: &str
+    ) -> Result<(), DatabaseError>;
+
+    async fn set_login_lockout(
+        &self,
+        subject: &str,
+        lockout_s: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Seconds until the lockout ends, `None` if there is none
+    async fn get_login_lockout(
+        &self,
+        subject: &str
+    ) -> Result<Option<i64>, DatabaseError>;
+}
@@ -0,0 +1,44 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::Message,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait MessageStore: Send + Sync {
+    async fn insert_message(
+        &self,
+        message: &Message,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_message_by_id(
+        &self,
+        message_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Option<Message>, DatabaseError>;
+
+    /// Replaces the content and sets `updated_at` to now
+    async fn edit_message(
+        &self,
+        message_id: i64,
+        content: &str,
+        cache_ttl_s: u64
+    ) -> Result<Message, DatabaseError>;
+
+    async fn delete_message(
+        &self,
+        message_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns at most `limit` messages from the channel, newest first.
+    /// With `after` set the page starts right after that message, otherwise it ends right before `before`
+    async fn get_channel_messages(
+        &self,
+        channel_id: i64,
+        before: Option<i64>,
+        after: Option<i64>,
+        limit: i64
+    ) -> Result<Vec<Message>, DatabaseError>;
+}
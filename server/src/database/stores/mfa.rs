@@ -0,0 +1,79 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::UserMfa,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait MfaStore: Send + Sync {
+    async fn get_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<Option<UserMfa>, DatabaseError>;
+
+    /// Stores the secret of a new enrollment, replacing an unfinished one.
+    /// Returns false if the user already has a confirmed second factor.
+    async fn start_mfa_enrollment(
+        &self,
+        user_mfa: &UserMfa
+    ) -> Result<bool, DatabaseError>;
+
+    /// Enables the enrolled secret and replaces the recovery codes of the user.
+    /// `step` is the time step of the code that confirmed the enrollment, it can't be used again.
+    /// Returns false if there is no unfinished enrollment.
+    async fn confirm_mfa(
+        &self,
+        user_id: i64,
+        step: i64,
+        confirmed_at: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<bool, DatabaseError>;
+
+    /// Accepts a code of the time step once, returns false for the last used step and the ones before it
+    async fn use_totp_step(
+        &self,
+        user_id: i64,
+        step: i64
+    ) -> Result<bool, DatabaseError>;
+
+    /// Marks the recovery code as used, returns false for unknown and already used codes
+    async fn use_recovery_code(
+        &self,
+        user_id: i64,
+        code_hash: &str,
+        used_at: i64
+    ) -> Result<bool, DatabaseError>;
+
+    async fn replace_recovery_codes(
+        &self,
+        user_id: i64,
+        recovery_code_hashes: &[String]
+    ) -> Result<(), DatabaseError>;
+
+    async fn count_unused_recovery_codes(
+        &self,
+        user_id: i64
+    ) -> Result<i64, DatabaseError>;
+
+    /// Drops the secret together with the recovery codes, returns false if the user had neither
+    async fn delete_user_mfa(
+        &self,
+        user_id: i64
+    ) -> Result<bool, DatabaseError>;
+
+    /// Marks the pending login token as exchanged for a session, returns false if it already was.
+    /// The mark has to be kept for the `ttl_s` the token is valid.
+    async fn use_mfa_pending_token(
+        &self,
+        jti: Uuid,
+        ttl_s: i64
+    ) -> Result<bool, DatabaseError>;
+
+    /// Takes the mark back when the token turned out not to start a session, e.g. for a wrong code
+    async fn release_mfa_pending_token(
+        &self,
+        jti: Uuid
+    ) -> Result<(), DatabaseError>;
+}
@@ -0,0 +1,92 @@
+mod user;
+mod credential;
+mod refresh_token;
+mod session;
+mod mfa;
+mod passkey;
+mod password_reset;
+mod email_change;
+mod pending_registration;
+mod login_attempts;
+mod ban;
+mod message;
+mod guild;
+mod role;
+mod dm;
+mod admin_audit;
+mod audit;
+mod email_job;
+
+use std::{
+    fmt::Debug,
+    sync::Arc
+};
+
+pub use user::UserStore;
+pub use credential::CredentialStore;
+pub use refresh_token::RefreshTokenStore;
+pub use session::SessionStore;
+pub use mfa::MfaStore;
+pub use passkey::PasskeyStore;
+pub use password_reset::PasswordResetStore;
+pub use email_change::EmailChangeStore;
+pub use pending_registration::PendingRegistrationStore;
+pub use login_attempts::LoginAttemptStore;
+pub use ban::BanStore;
+pub use message::MessageStore;
+pub use guild::GuildStore;
+pub use role::RoleStore;
+pub use dm::DmStore;
+pub use admin_audit::AdminAuditStore;
+pub use audit::AuditEventStore;
+pub use email_job::EmailJobStore;
+
+/// Everything the server keeps, implemented by Postgres with a Redis cache in front of it
+/// and by an in-memory backend for tests
+pub trait Storage:
+    UserStore +
+    CredentialStore +
+    RefreshTokenStore +
+    SessionStore +
+    MfaStore +
+    PasskeyStore +
+    PasswordResetStore +
+    EmailChangeStore +
+    PendingRegistrationStore +
+    LoginAttemptStore +
+    BanStore +
+    MessageStore +
+    GuildStore +
+    RoleStore +
+    DmStore +
+    AdminAuditStore +
+    AuditEventStore +
+    EmailJobStore +
+    Debug
+{}
+
+impl<T> Storage for T
+where
+    T: UserStore +
+        CredentialStore +
+        RefreshTokenStore +
+        SessionStore +
+        MfaStore +
+        PasskeyStore +
+        PasswordResetStore +
+        EmailChangeStore +
+        PendingRegistrationStore +
+        LoginAttemptStore +
+        BanStore +
+        MessageStore +
+        GuildStore +
+        RoleStore +
+        DmStore +
+        AdminAuditStore +
+        AuditEventStore +
+        EmailJobStore +
+        Debug
+{}
+
+/// Shared by every state of the router
+pub type DatabaseClient = Arc<dyn Storage>;
@@ -0,0 +1,59 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        Passkey,
+        PasskeyChallenge
+    },
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait PasskeyStore: Send + Sync {
+    async fn create_passkey_challenge(
+        &self,
+        challenge: &PasskeyChallenge,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    /// Reads and deletes the challenge at once, so it can only be answered once
+    async fn take_passkey_challenge(
+        &self,
+        challenge_id: Uuid
+    ) -> Result<Option<PasskeyChallenge>, DatabaseError>;
+
+    /// Returns false if the credential is already registered, to this or another user
+    async fn insert_passkey(
+        &self,
+        passkey: &Passkey
+    ) -> Result<bool, DatabaseError>;
+
+    async fn get_passkey(
+        &self,
+        credential_id: &[u8]
+    ) -> Result<Option<Passkey>, DatabaseError>;
+
+    /// Oldest first
+    async fn get_user_passkeys(
+        &self,
+        user_id: i64
+    ) -> Result<Vec<Passkey>, DatabaseError>;
+
+    /// Stores the counter of an accepted assertion. Returns false if another login with the
+    /// credential changed the counter since `previous_sign_count` was read.
+    async fn update_passkey_sign_count(
+        &self,
+        credential_id: &[u8],
+        previous_sign_count: i64,
+        sign_count: i64,
+        used_at: i64
+    ) -> Result<bool, DatabaseError>;
+
+    /// Returns false if the user has no such passkey
+    async fn delete_passkey(
+        &self,
+        credential_id: &[u8],
+        user_id: i64
+    ) -> Result<bool, DatabaseError>;
+}
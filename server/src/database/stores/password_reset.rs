@@ -0,0 +1,21 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::database::DatabaseError;
+
+/// Pending resets expire together with the emailed link
+#[async_trait]
+pub trait PasswordResetStore: Send + Sync {
+    async fn create_password_reset(
+        &self,
+        jti: Uuid,
+        user_id: i64,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns the user of the reset, `None` if it expired or was already used
+    async fn take_password_reset(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<i64>, DatabaseError>;
+}
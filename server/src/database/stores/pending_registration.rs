@@ -0,0 +1,36 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PendingRegistration,
+    database::DatabaseError
+};
+
+/// Registrations outlive their verification links, so a new link can be sent without the form
+#[async_trait]
+pub trait PendingRegistrationStore: Send + Sync {
+    /// Replaces the pending registration of the address, links to the one before stop working
+    async fn create_pending_registration(
+        &self,
+        pending_registration: &PendingRegistration,
+        ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_pending_registration_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<PendingRegistration>, DatabaseError>;
+
+    /// Returns `None` if the registration expired, was replaced or was already verified
+    async fn take_pending_registration(
+        &self,
+        registration_id: Uuid
+    ) -> Result<Option<PendingRegistration>, DatabaseError>;
+
+    /// Starts the cooldown of the address unless one is running, returns the seconds left of a running one
+    async fn start_verification_email_cooldown(
+        &self,
+        email: &str,
+        cooldown_s: u64
+    ) -> Result<Option<i64>, DatabaseError>;
+}
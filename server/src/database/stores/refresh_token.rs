@@ -0,0 +1,31 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        RefreshToken,
+        RefreshTokenRotation
+    },
+    database::DatabaseError
+};
+
+/// Refresh tokens are created together with their session, see `SessionStore::create_session`
+#[async_trait]
+pub trait RefreshTokenStore: Send + Sync {
+    async fn get_refresh_token(
+        &self,
+        jti: Uuid
+    ) -> Result<Option<RefreshToken>, DatabaseError>;
+
+    /// Marks the token as rotated and stores `new_jti` in the family of the session, all at once,
+    /// so a token can only be exchanged once. Presenting a rotated token again deletes the session.
+    async fn rotate_refresh_token(
+        &self,
+        jti: Uuid,
+        session_id: Uuid,
+        user_id: i64,
+        new_jti: Uuid,
+        issued_at: i64,
+        expires_at: i64
+    ) -> Result<RefreshTokenRotation, DatabaseError>;
+}
@@ -0,0 +1,76 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::{
+        PermissionOverwrite,
+        Role
+    },
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait RoleStore: Send + Sync {
+    async fn insert_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError>;
+
+    /// Returns the roles of the guild from the lowest to the highest, @everyone included
+    async fn get_guild_roles(
+        &self,
+        guild_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Vec<Role>, DatabaseError>;
+
+    /// Stores the name, permissions and position of the role
+    async fn update_role(
+        &self,
+        role: &Role
+    ) -> Result<(), DatabaseError>;
+
+    /// Deletes the role together with its channel overwrites and takes it away from the members
+    async fn delete_role(
+        &self,
+        guild_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Giving a member a role they already have does nothing
+    async fn add_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    async fn remove_member_role(
+        &self,
+        guild_id: i64,
+        user_id: i64,
+        role_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_channel_overwrites(
+        &self,
+        channel_id: i64,
+        cache_ttl_s: u64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError>;
+
+    /// Overwrites of every channel in the guild, used to filter channel lists
+    async fn get_guild_overwrites(
+        &self,
+        guild_id: i64
+    ) -> Result<Vec<PermissionOverwrite>, DatabaseError>;
+
+    /// Replaces the overwrite the channel already has for the target
+    async fn set_channel_overwrite(
+        &self,
+        overwrite: &PermissionOverwrite
+    ) -> Result<(), DatabaseError>;
+
+    async fn delete_channel_overwrite(
+        &self,
+        channel_id: i64,
+        target_id: i64
+    ) -> Result<(), DatabaseError>;
+}
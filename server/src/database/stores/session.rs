@@ -0,0 +1,55 @@
+use axum::async_trait;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        RefreshToken,
+        Session
+    },
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Stores the session together with the first refresh token of its family.
+    /// Fails with `UserNotFound` for unknown users.
+    async fn create_session(
+        &self,
+        session: &Session,
+        refresh_token: &RefreshToken,
+        cache_ttl_s: u64
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_session_by_id(
+        &self,
+        session_id: Uuid,
+        cache_ttl_s: u64
+    ) -> Result<Option<Session>, DatabaseError>;
+
+    /// Sessions that didn't expire yet, the most recently used first
+    async fn get_user_sessions(
+        &self,
+        user_id: i64,
+        now: i64
+    ) -> Result<Vec<Session>, DatabaseError>;
+
+    /// Revokes the refresh tokens of the session as well
+    async fn delete_session(
+        &self,
+        session_id: Uuid,
+        user_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Logs the user out everywhere
+    async fn delete_user_sessions(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Logs the user out on every device except the one of the session
+    async fn delete_other_user_sessions(
+        &self,
+        user_id: i64,
+        session_id: Uuid
+    ) -> Result<(), DatabaseError>;
+}
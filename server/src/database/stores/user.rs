@@ -0,0 +1,55 @@
+use axum::async_trait;
+
+use crate::{
+    app_objects::User,
+    database::DatabaseError
+};
+
+#[async_trait]
+pub trait UserStore: Send + Sync {
+    /// Fails with `EmailAlreadyExists` if another user has the email
+    async fn insert_user(
+        &self,
+        user: &User
+    ) -> Result<(), DatabaseError>;
+
+    async fn get_user_by_id(
+        &self,
+        user_id: i64
+    ) -> Result<Option<User>, DatabaseError>;
+
+    async fn get_user_id_by_email(
+        &self,
+        email: &str
+    ) -> Result<Option<i64>, DatabaseError>;
+
+    /// Returns false if the email of the user isn't `old_email` anymore
+    async fn update_user_email(
+        &self,
+        user_id: i64,
+        old_email: &str,
+        new_email: &str
+    ) -> Result<bool, DatabaseError>;
+
+    /// Filters left as None match every user, `email_pattern` and `username_pattern` are ILIKE patterns
+    async fn search_users(
+        &self,
+        user_id: Option<i64>,
+        email_pattern: Option<&str>,
+        username_pattern: Option<&str>,
+        limit: i64
+    ) -> Result<Vec<User>, DatabaseError>;
+
+    async fn set_user_verified(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError>;
+
+    /// Deletes the user together with their sessions, second factors, passkeys, ban, guild memberships
+    /// and blocks, and takes them out of their DM channels. Fails with `UserOwnsGuilds` while the user
+    /// owns a guild.
+    async fn delete_user(
+        &self,
+        user_id: i64
+    ) -> Result<(), DatabaseError>;
+}
@@ -0,0 +1,49 @@
+use crate::{
+    app_objects::DmChannel,
+    database::{
+        DatabaseClient,
+        DatabaseError,
+        DmStore,
+        UserStore
+    }
+};
+
+use super::DmError;
+
+/// Loads the DM channel, failing if the user isn't one of its recipients
+pub async fn get_recipient_dm_channel(
+    db_client: &DatabaseClient,
+    channel_id: i64,
+    user_id: i64,
+    cache_ttl_s: u64
+) -> Result<DmChannel, DmError> {
+    let channel = db_client.get_dm_channel_by_id(channel_id, cache_ttl_s).await?;
+    match channel {
+        Some(channel) if channel.is_recipient(user_id) => Ok(channel),
+        Some(_) => Err(DmError::NotRecipient),
+        None => Err(DatabaseError::ChannelNotFound(channel_id).into()),
+    }
+}
+
+/// DMs can only be opened with and group DMs extended by users that exist
+pub async fn check_user_exists(
+    db_client: &DatabaseClient,
+    user_id: i64
+) -> Result<(), DmError> {
+    if db_client.get_user_by_id(user_id).await?.is_none() {
+        return Err(DatabaseError::UserNotFound(user_id).into());
+    }
+    Ok(())
+}
+
+/// Fails if either of the users blocked the other
+pub async fn check_not_blocked(
+    db_client: &DatabaseClient,
+    user_id: i64,
+    other_user_id: i64
+) -> Result<(), DmError> {
+    if db_client.is_blocked_between(user_id, other_user_id).await? {
+        return Err(DmError::Blocked(other_user_id));
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+mod payload;
+mod access;
+
+pub use payload::OpenDmPayload;
+pub use access::{
+    check_not_blocked,
+    check_user_exists,
+    get_recipient_dm_channel
+};
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    database::DatabaseError,
+    snowflake::SnowflakeError
+};
+
+#[derive(Debug, Error)]
+pub enum DmError {
+    #[error("Recipients must be other users and listed at most once")]
+    InvalidRecipients,
+    #[error("Group DMs can't have more than {0} recipients")]
+    GroupTooLarge(usize),
+    #[error("User with id: {0} blocked you or is blocked by you")]
+    Blocked(i64),
+    #[error("User is not a recipient of the DM channel")]
+    NotRecipient,
+    #[error("Only the group owner can remove other recipients")]
+    NotGroupOwner,
+    #[error("Recipients can only be added to or removed from group DMs")]
+    NotGroupDm,
+    #[error("Users can't block themselves")]
+    BlockSelf,
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    SnowflakeError(#[from] SnowflakeError),
+}
+
+impl IntoResponse for DmError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            DmError::InvalidRecipients => (StatusCode::BAD_REQUEST, "2100"),
+            DmError::GroupTooLarge(_) => (StatusCode::BAD_REQUEST, "2101"),
+            DmError::Blocked(_) => (StatusCode::FORBIDDEN, "2102"),
+            DmError::NotRecipient => (StatusCode::FORBIDDEN, "2103"),
+            DmError::NotGroupOwner => (StatusCode::FORBIDDEN, "2104"),
+            DmError::NotGroupDm => (StatusCode::BAD_REQUEST, "2105"),
+            DmError::BlockSelf => (StatusCode::BAD_REQUEST, "2106"),
+            DmError::DatabaseError(e) => return e.into_response(),
+            DmError::SnowflakeError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
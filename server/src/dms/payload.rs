@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+use super::DmError;
+
+/// A single recipient opens a direct DM, more of them create a group DM owned by the caller
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpenDmPayload {
+    pub recipient_ids: Vec<i64>,
+}
+
+impl OpenDmPayload {
+    /// Checks that the recipients are other users listed once and fit in a group DM together with the caller
+    pub fn validated_recipients(
+        &self,
+        user_id: i64,
+        max_group_size: usize
+    ) -> Result<Vec<i64>, DmError> {
+        let mut recipient_ids = HashSet::new();
+        for recipient_id in &self.recipient_ids {
+            if *recipient_id == user_id || !recipient_ids.insert(*recipient_id) {
+                return Err(DmError::InvalidRecipients);
+            }
+        }
+        if recipient_ids.is_empty() {
+            return Err(DmError::InvalidRecipients);
+        }
+        if self.recipient_ids.len() + 1 > max_group_size {
+            return Err(DmError::GroupTooLarge(max_group_size));
+        }
+        Ok(self.recipient_ids.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validated_recipients() {
+        let payload = OpenDmPayload { recipient_ids: vec![2, 3] };
+        assert_eq!(payload.validated_recipients(1, 3).unwrap(), vec![2, 3]);
+        assert!(matches!(payload.validated_recipients(1, 2), Err(DmError::GroupTooLarge(2))));
+        assert!(matches!(payload.validated_recipients(2, 3), Err(DmError::InvalidRecipients)));
+
+        let payload = OpenDmPayload { recipient_ids: vec![2, 2] };
+        assert!(matches!(payload.validated_recipients(1, 10), Err(DmError::InvalidRecipients)));
+
+        let payload = OpenDmPayload { recipient_ids: vec![] };
+        assert!(matches!(payload.validated_recipients(1, 10), Err(DmError::InvalidRecipients)));
+    }
+}
@@ -0,0 +1,25 @@
+use lettre::{message::Mailbox, Message};
+use minijinja::context;
+
+use crate::email::{templates::EmailTemplate, EmailHandler, EmailHandlerError};
+
+
+
+impl EmailHandler{
+    pub fn create_account_locked_email(
+        &self,
+        recipient: Mailbox,
+        lockout_s: i64
+    ) -> Result<Message, EmailHandlerError> {
+        let account_locked_email_state = &self.state.account_locked_email_state;
+        let email_author = account_locked_email_state.get_account_locked_email_author_mailbox();
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::AccountLocked,
+            context! {
+                lockout_minutes => (lockout_s + 59) / 60,
+            }
+        )
+    }
+}
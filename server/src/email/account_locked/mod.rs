@@ -0,0 +1,4 @@
+mod email_content;
+mod state;
+
+pub(crate) use state::AccountLockedEmailState;
@@ -0,0 +1,51 @@
+use lettre::{message::Mailbox, Message};
+use minijinja::context;
+
+use crate::email::{templates::EmailTemplate, EmailHandler, EmailHandlerError};
+
+
+
+impl EmailHandler{
+    /// Sent to the new address, the change is only made once the link is used
+    pub fn create_email_change_confirmation_email(
+        &self,
+        recipient: Mailbox,
+        email_change_token: String
+    ) -> Result<Message, EmailHandlerError> {
+        let email_change_email_state = &self.state.email_change_email_state;
+        let email_author = email_change_email_state.get_email_change_email_author_mailbox();
+        let confirmation_url = format!(
+            "{}{}?token={}",
+            email_change_email_state.confirmation_url_domain,
+            email_change_email_state.confirmation_url_endpoint,
+            email_change_token
+        );
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::EmailChangeConfirmation,
+            context! {
+                confirmation_url,
+                expires_in_minutes => email_change_email_state.email_change_jwt_lifetime_s / 60,
+            }
+        )
+    }
+
+    /// Sent to the old address, so the owner notices when someone else took over the account
+    pub fn create_email_change_notice_email(
+        &self,
+        recipient: Mailbox,
+        new_email: &str
+    ) -> Result<Message, EmailHandlerError> {
+        let email_change_email_state = &self.state.email_change_email_state;
+        let email_author = email_change_email_state.get_email_change_email_author_mailbox();
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::EmailChangeNotice,
+            context! {
+                new_email,
+            }
+        )
+    }
+}
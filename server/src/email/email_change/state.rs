@@ -0,0 +1,24 @@
+use lettre::{message::Mailbox, Address};
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct EmailChangeEmailState {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+    pub confirmation_url_domain: String,
+    pub confirmation_url_endpoint: String,
+    pub email_change_jwt_lifetime_s: i64,
+}
+
+impl EmailChangeEmailState {
+    fn get_email_change_email_email_address(&self) -> Address {
+        self.email_sender_email_address.clone().parse().unwrap()
+    }
+    pub fn get_email_change_email_author_mailbox(&self) -> Mailbox {
+        Mailbox::new(
+            Some(self.email_sender_name.clone()),
+            self.get_email_change_email_email_address()
+        )
+    }
+}
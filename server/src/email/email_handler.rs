@@ -1,74 +1,179 @@
-use crate::configuration::Config;
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+
+use crate::{
+    app_objects::EmailJob,
+    configuration::{
+        Config,
+        EmailQueueConfig
+    },
+    database::{
+        DatabaseClient,
+        EmailJobStore
+    }
+};
 
 use super::{
-    email_handler_state::EmailHandlerState, email_verification::EmailVerificationEmailState, EmailHandlerError
+    email_handler_state::EmailHandlerState, email_verification::EmailVerificationEmailState, password_reset::PasswordResetEmailState, email_change::EmailChangeEmailState, account_locked::AccountLockedEmailState, new_login::NewLoginEmailState, templates::{EmailTemplate, EmailTemplates}, queue::{spawn_email_workers, EmailWorker}, EmailHandlerError, EmailTransport
 };
 
 use lettre::{
-    Address, AsyncTransport, Executor, Message, Tokio1Executor, Transport
+    message::{
+        Mailbox,
+        MultiPart
+    },
+    Message
 };
+use serde::Serialize;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct EmailHandler {
-    pub state: &'static EmailHandlerState,
-    //smtp_host: &'static str,
+    pub state: Arc<EmailHandlerState>,
+    transport: EmailTransport,
+    db_client: DatabaseClient,
+    // Wakes a worker of this instance up as soon as an email was enqueued
+    new_jobs: Arc<Notify>,
 }
 
 impl EmailHandler {
+    /// Uses the transport selected in the `[smtp]` config
     pub fn new(
         config: &Config,
+        db_client: DatabaseClient
     ) -> anyhow::Result<Self> {
-        let smtp_username = &config.smtp.smtp_username;
-        let smtp_password = &{
-            let path = &config.smtp.smtp_password_path;
-            let password = std::fs::read_to_string(path)?;
-            password
-        };
+        let transport = EmailTransport::new(&config.smtp)?;
+        Self::with_transport(config, transport, db_client)
+    }
 
-        let smtp_host = &config.smtp.smtp_host;
+    /// Emails are enqueued in `db_client` and sent through `transport` by the workers
+    pub fn with_transport(
+        config: &Config,
+        transport: EmailTransport,
+        db_client: DatabaseClient
+    ) -> anyhow::Result<Self> {
+        let templates = EmailTemplates::new(&config.email_templates)?;
 
         let verification_email_state = EmailVerificationEmailState {
             email_sender_name: config.verification_email.email_sender_name.clone(),
             email_sender_email_address: config.verification_email.email_sender_email_address.clone(),
-            email_subject: config.verification_email.email_subject.clone(),
             verification_url_domain: config.verification_email.verification_url_domain.clone(),
             verification_url_endpoint: config.verification_email.verification_url_endpoint.clone(),
             email_verification_jwt_lifetime_s: config.verification_email.email_verification_jwt_lifetime_s,
+            pending_registration_lifetime_s: config.verification_email.pending_registration_lifetime_s,
+            resend_cooldown_s: config.verification_email.resend_cooldown_s,
+        };
+
+        let password_reset_email_state = PasswordResetEmailState {
+            email_sender_name: config.password_reset_email.email_sender_name.clone(),
+            email_sender_email_address: config.password_reset_email.email_sender_email_address.clone(),
+            reset_url_domain: config.password_reset_email.reset_url_domain.clone(),
+            reset_url_endpoint: config.password_reset_email.reset_url_endpoint.clone(),
+            password_reset_jwt_lifetime_s: config.password_reset_email.password_reset_jwt_lifetime_s,
         };
 
+        let email_change_email_state = EmailChangeEmailState {
+            email_sender_name: config.email_change_email.email_sender_name.clone(),
+            email_sender_email_address: config.email_change_email.email_sender_email_address.clone(),
+            confirmation_url_domain: config.email_change_email.confirmation_url_domain.clone(),
+            confirmation_url_endpoint: config.email_change_email.confirmation_url_endpoint.clone(),
+            email_change_jwt_lifetime_s: config.email_change_email.email_change_jwt_lifetime_s,
+        };
+
+        let account_locked_email_state = AccountLockedEmailState {
+            email_sender_name: config.account_locked_email.email_sender_name.clone(),
+            email_sender_email_address: config.account_locked_email.email_sender_email_address.clone(),
+        };
+
+        let new_login_email_state = NewLoginEmailState {
+            email_sender_name: config.new_login_email.email_sender_name.clone(),
+            email_sender_email_address: config.new_login_email.email_sender_email_address.clone(),
+        };
 
         let state = EmailHandlerState::new(
-            smtp_username,
-            smtp_password,
-            smtp_host,
-            verification_email_state
+            verification_email_state,
+            password_reset_email_state,
+            email_change_email_state,
+            account_locked_email_state,
+            new_login_email_state,
+            templates
         );
-        // let smtp_host = Box::leak(
-        //     Into::<String>::into(
-        //         smtp_host
-        //     ).into_boxed_str()
-        // );
 
         Ok(Self {
-            state: Box::leak(
-                Box::new(state)
-            ),
-            //smtp_host,
+            state: Arc::new(state),
+            transport,
+            db_client,
+            new_jobs: Arc::new(Notify::new()),
         })
     }
 
-    pub async fn send_email(
+    /// Renders the template into a multipart/alternative email with the plain text and the HTML version
+    pub(super) fn create_templated_email<C: Serialize>(
         &self,
-        mail: Message
-    ) -> Result<(), EmailHandlerError> {
-
-        self.state.mailer().send(mail).await
+        author: Mailbox,
+        recipient: Mailbox,
+        template: EmailTemplate,
+        context: C
+    ) -> Result<Message, EmailHandlerError> {
+        let rendered = self.state.templates.render(template, context)?;
+        Message::builder()
+            .from(author)
+            .to(recipient)
+            .subject(rendered.subject)
+            .multipart(
+                MultiPart::alternative_plain_html(rendered.text, rendered.html)
+            )
             .map_err(
-                |e| EmailHandlerError::EmailSendingFailed(e.to_string())
-            )?;
+                |e| EmailHandlerError::EmailCreationFailed(
+                    e.to_string()
+                )
+            )
+    }
 
-        Ok(())
+    /// Stores the email in the queue and returns, a worker sends it. Returns false when an email
+    /// with the same idempotency key was already enqueued, it isn't sent twice then
+    pub async fn enqueue_email(
+        &self,
+        idempotency_key: String,
+        mail: Message
+    ) -> Result<bool, EmailHandlerError> {
+        let envelope = mail.envelope();
+        let email_job = EmailJob::new(
+            idempotency_key,
+            envelope.from().map(ToString::to_string),
+            envelope.to().iter().map(ToString::to_string).collect(),
+            String::from_utf8_lossy(&mail.formatted()).into_owned()
+        );
+        let enqueued = self.db_client.enqueue_email_job(&email_job).await.map_err(
+            |e| EmailHandlerError::EmailQueueingFailed(e.to_string())
+        )?;
+        match enqueued {
+            true => {
+                metrics::counter!("email_jobs_enqueued_total").increment(1);
+                self.new_jobs.notify_one();
+            },
+            false => metrics::counter!("email_jobs_duplicate_total").increment(1),
+        }
+        Ok(enqueued)
     }
-}
 
+    /// A worker sending through the transport of the handler, see `spawn_workers`
+    pub fn worker(
+        &self,
+        queue_config: &EmailQueueConfig
+    ) -> EmailWorker {
+        EmailWorker::new(
+            self.db_client.clone(),
+            self.transport.clone(),
+            queue_config.clone()
+        )
+    }
 
+    /// Starts the workers of this instance, they run as long as the server does
+    pub fn spawn_workers(
+        &self,
+        queue_config: &EmailQueueConfig
+    ) {
+        spawn_email_workers(self.worker(queue_config), self.new_jobs.clone());
+    }
+}
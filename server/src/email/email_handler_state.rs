@@ -1,46 +1,38 @@
-use lettre::{message::Mailbox, transport::smtp::authentication::Credentials, Address, AsyncSmtpTransport, Executor, SmtpTransport, Tokio1Executor};
+use super::{
+    email_verification::EmailVerificationEmailState,
+    password_reset::PasswordResetEmailState,
+    email_change::EmailChangeEmailState,
+    account_locked::AccountLockedEmailState,
+    new_login::NewLoginEmailState,
+    templates::EmailTemplates
+};
 
-use super::email_verification::EmailVerificationEmailState;
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug)]
 pub struct EmailHandlerState {
-    mailer: &'static AsyncSmtpTransport<Tokio1Executor>,
-    pub verification_email_state: &'static EmailVerificationEmailState,
+    pub verification_email_state: EmailVerificationEmailState,
+    pub password_reset_email_state: PasswordResetEmailState,
+    pub email_change_email_state: EmailChangeEmailState,
+    pub account_locked_email_state: AccountLockedEmailState,
+    pub new_login_email_state: NewLoginEmailState,
+    pub templates: EmailTemplates,
 }
 
 impl EmailHandlerState {
-    // statics are kinda useless here but at least Im having fun
-    pub fn new<T>(
-        smtp_username: T,
-        smtp_password: T,
-        smtp_host: T,
-        verification_email_state: EmailVerificationEmailState
-
-    ) -> EmailHandlerState
-    where
-        T: AsRef<str> {
-
-        let smtp_credentials = Credentials::new(
-            smtp_username.as_ref().to_string(),
-            smtp_password.as_ref().to_string()
-        );
-        let mailer = AsyncSmtpTransport::<Tokio1Executor>::relay(
-            smtp_host.as_ref()
-        ).unwrap().credentials(
-            smtp_credentials
-        ).build();
-
+    pub fn new(
+        verification_email_state: EmailVerificationEmailState,
+        password_reset_email_state: PasswordResetEmailState,
+        email_change_email_state: EmailChangeEmailState,
+        account_locked_email_state: AccountLockedEmailState,
+        new_login_email_state: NewLoginEmailState,
+        templates: EmailTemplates
+    ) -> EmailHandlerState {
         Self {
-            mailer: Box::leak(
-                Box::new(mailer)
-            ),
-            verification_email_state: Box::leak(
-                Box::new(verification_email_state)
-            ),
+            verification_email_state,
+            password_reset_email_state,
+            email_change_email_state,
+            account_locked_email_state,
+            new_login_email_state,
+            templates,
         }
     }
-
-    pub fn mailer(&self) -> &'static AsyncSmtpTransport<Tokio1Executor> {
-        self.mailer
-    }
 }
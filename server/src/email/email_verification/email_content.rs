@@ -1,6 +1,7 @@
-use lettre::{message::Mailbox, Executor, Message};
+use lettre::{message::Mailbox, Message};
+use minijinja::context;
 
-use crate::email::{EmailHandler, EmailHandlerError};
+use crate::email::{templates::EmailTemplate, EmailHandler, EmailHandlerError};
 
 
 
@@ -10,27 +11,22 @@ impl EmailHandler{
         recipient: Mailbox,
         jwt_encoded_registration_form: String
     ) -> Result<Message, EmailHandlerError> {
-        let email_author = self.state.verification_email_state.get_verification_email_author_mailbox();
-        let body = format!(
-            "{}?token={}",
-            "http://localhost:3000/verify_email",
+        let verification_email_state = &self.state.verification_email_state;
+        let email_author = verification_email_state.get_verification_email_author_mailbox();
+        let verification_url = format!(
+            "{}{}?token={}",
+            verification_email_state.verification_url_domain,
+            verification_email_state.verification_url_endpoint,
             jwt_encoded_registration_form
         );
-        let message = Message::builder()
-            .from(email_author)
-            .to(recipient)
-            .subject(
-                self.state.verification_email_state.email_subject.clone()
-            )
-            .body(body)
-            .map_err(
-                |e| EmailHandlerError::EmailCreationFailed(
-                    e.to_string()
-                )
-            )?;
-
-        Ok(message)
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::Verification,
+            context! {
+                verification_url,
+                expires_in_minutes => verification_email_state.email_verification_jwt_lifetime_s / 60,
+            }
+        )
     }
 }
-
-
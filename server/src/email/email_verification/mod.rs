@@ -1,5 +1,4 @@
 mod email_content;
-mod verification_jwt;
 mod state;
 
 pub(crate) use state::EmailVerificationEmailState;
\ No newline at end of file
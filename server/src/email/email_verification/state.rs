@@ -6,10 +6,11 @@ use serde::{Deserialize, Serialize};
 pub struct EmailVerificationEmailState {
     pub email_sender_name: String,
     pub email_sender_email_address: String,
-    pub email_subject: String,
     pub verification_url_domain: String,
     pub verification_url_endpoint: String,
     pub email_verification_jwt_lifetime_s: i64,
+    pub pending_registration_lifetime_s: i64,
+    pub resend_cooldown_s: i64,
 }
 
 impl EmailVerificationEmailState {
@@ -1,11 +1,25 @@
 mod email_verification;
+mod password_reset;
+mod email_change;
+mod account_locked;
+mod new_login;
 mod email_handler;
 mod email_handler_state;
+mod transport;
+mod templates;
+mod queue;
 
 mod tests;
 
 use axum::response::IntoResponse;
 pub use email_handler::EmailHandler;
+pub use transport::EmailTransport;
+pub use queue::idempotency_key;
+#[cfg(test)]
+pub use transport::{
+    MemoryTransport,
+    SentEmail
+};
 use thiserror::Error;
 
 #[derive(Debug, Clone, Error)]
@@ -14,6 +28,8 @@ pub enum EmailHandlerError {
     EmailCreationFailed(String),
     #[error("Email sending error: {0}")]
     EmailSendingFailed(String),
+    #[error("Email queueing error: {0}")]
+    EmailQueueingFailed(String),
 }
 
 impl IntoResponse for EmailHandlerError {
@@ -25,6 +41,9 @@ impl IntoResponse for EmailHandlerError {
             EmailHandlerError::EmailSendingFailed(_) => {
                 (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "1601")
             },
+            EmailHandlerError::EmailQueueingFailed(_) => {
+                (axum::http::StatusCode::INTERNAL_SERVER_ERROR, "1602")
+            },
         };
 
         axum::http::Response::builder()
@@ -0,0 +1,27 @@
+use lettre::{message::Mailbox, Message};
+use minijinja::context;
+
+use crate::email::{templates::EmailTemplate, EmailHandler, EmailHandlerError};
+
+
+
+impl EmailHandler{
+    pub fn create_new_login_email(
+        &self,
+        recipient: Mailbox,
+        device_label: &str,
+        ip: Option<&str>
+    ) -> Result<Message, EmailHandlerError> {
+        let new_login_email_state = &self.state.new_login_email_state;
+        let email_author = new_login_email_state.get_new_login_email_author_mailbox();
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::NewLogin,
+            context! {
+                device_label => device_label,
+                ip => ip.unwrap_or("unknown"),
+            }
+        )
+    }
+}
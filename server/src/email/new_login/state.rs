@@ -0,0 +1,21 @@
+use lettre::{message::Mailbox, Address};
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct NewLoginEmailState {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+}
+
+impl NewLoginEmailState {
+    fn get_new_login_email_email_address(&self) -> Address {
+        self.email_sender_email_address.clone().parse().unwrap()
+    }
+    pub fn get_new_login_email_author_mailbox(&self) -> Mailbox {
+        Mailbox::new(
+            Some(self.email_sender_name.clone()),
+            self.get_new_login_email_email_address()
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use lettre::{message::Mailbox, Message};
+use minijinja::context;
+
+use crate::email::{templates::EmailTemplate, EmailHandler, EmailHandlerError};
+
+
+
+impl EmailHandler{
+    pub fn create_password_reset_email(
+        &self,
+        recipient: Mailbox,
+        password_reset_token: String
+    ) -> Result<Message, EmailHandlerError> {
+        let password_reset_email_state = &self.state.password_reset_email_state;
+        let email_author = password_reset_email_state.get_password_reset_email_author_mailbox();
+        let reset_url = format!(
+            "{}{}?token={}",
+            password_reset_email_state.reset_url_domain,
+            password_reset_email_state.reset_url_endpoint,
+            password_reset_token
+        );
+        self.create_templated_email(
+            email_author,
+            recipient,
+            EmailTemplate::PasswordReset,
+            context! {
+                reset_url,
+                expires_in_minutes => password_reset_email_state.password_reset_jwt_lifetime_s / 60,
+            }
+        )
+    }
+}
@@ -0,0 +1,24 @@
+use lettre::{message::Mailbox, Address};
+use serde::{Deserialize, Serialize};
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PasswordResetEmailState {
+    pub email_sender_name: String,
+    pub email_sender_email_address: String,
+    pub reset_url_domain: String,
+    pub reset_url_endpoint: String,
+    pub password_reset_jwt_lifetime_s: i64,
+}
+
+impl PasswordResetEmailState {
+    fn get_password_reset_email_email_address(&self) -> Address {
+        self.email_sender_email_address.clone().parse().unwrap()
+    }
+    pub fn get_password_reset_email_author_mailbox(&self) -> Mailbox {
+        Mailbox::new(
+            Some(self.email_sender_name.clone()),
+            self.get_password_reset_email_email_address()
+        )
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        Instant
+    }
+};
+
+use data_encoding::HEXLOWER;
+use lettre::{
+    address::Envelope,
+    Address
+};
+use sha2::{
+    Digest,
+    Sha256
+};
+use tokio::sync::Notify;
+use tracing::{
+    error,
+    info,
+    warn
+};
+
+use crate::{
+    app_objects::EmailJob,
+    configuration::EmailQueueConfig,
+    database::{
+        DatabaseClient,
+        DatabaseError,
+        EmailJobStore
+    }
+};
+
+use super::EmailTransport;
+
+/// Enqueueing twice with the same key sends the email once. `unique` is hashed, the tokens in
+/// the links shouldn't end up in the queue table next to the emails carrying them
+pub fn idempotency_key(
+    kind: &str,
+    unique: &str
+) -> String {
+    format!("{}:{}", kind, HEXLOWER.encode(&Sha256::digest(unique.as_bytes())))
+}
+
+/// Sends the queued emails through the transport. Any number of workers, on any number of
+/// instances, can share the queue, a job is claimed by one of them at a time
+#[derive(Debug, Clone)]
+pub struct EmailWorker {
+    db_client: DatabaseClient,
+    transport: EmailTransport,
+    queue_config: EmailQueueConfig,
+}
+
+impl EmailWorker {
+    pub fn new(
+        db_client: DatabaseClient,
+        transport: EmailTransport,
+        queue_config: EmailQueueConfig
+    ) -> Self {
+        Self {
+            db_client,
+            transport,
+            queue_config,
+        }
+    }
+
+    /// Runs forever, waking up every `poll_interval_ms` or when `new_jobs` is notified
+    pub async fn run(
+        self,
+        new_jobs: Arc<Notify>
+    ) {
+        let poll_interval = Duration::from_millis(self.queue_config.poll_interval_ms);
+        loop {
+            match self.run_due_jobs().await {
+                // A full batch, there may be more waiting
+                Ok(sent) if sent as i64 >= self.queue_config.batch_size => continue,
+                Ok(_) => {},
+                Err(e) => error!("Error claiming email jobs: {:?}", e),
+            }
+            tokio::select! {
+                _ = new_jobs.notified() => {},
+                _ = tokio::time::sleep(poll_interval) => {},
+            }
+        }
+    }
+
+    /// Runs forever, deleting the finished jobs past their retention every `cleanup_interval_s`
+    pub async fn run_cleanup(self) {
+        let mut cleanup_interval = tokio::time::interval(
+            Duration::from_secs(self.queue_config.cleanup_interval_s)
+        );
+        loop {
+            cleanup_interval.tick().await;
+            match self.delete_finished_jobs().await {
+                Ok(0) => {},
+                Ok(deleted) => info!("Deleted {} finished email jobs", deleted),
+                Err(e) => error!("Error deleting finished email jobs: {:?}", e),
+            }
+        }
+    }
+
+    pub async fn delete_finished_jobs(&self) -> Result<u64, DatabaseError> {
+        let now = chrono::Utc::now().timestamp();
+        self.db_client.delete_finished_email_jobs(now - self.queue_config.finished_retention_s).await
+    }
+
+    /// Claims one batch of due jobs and tries to send each of them, returns how many were claimed
+    pub async fn run_due_jobs(&self) -> Result<usize, DatabaseError> {
+        let now = chrono::Utc::now().timestamp();
+        let email_jobs = self.db_client.claim_email_jobs(
+            now,
+            self.queue_config.lease_s,
+            self.queue_config.batch_size
+        ).await?;
+        for email_job in &email_jobs {
+            // The job stays claimed and is retried once its lease ran out, the rest of the batch still goes out
+            if let Err(e) = self.send_job(email_job).await {
+                error!("Error finishing email job {}, retrying after the lease: {:?}", email_job.id, e);
+            }
+        }
+        Ok(email_jobs.len())
+    }
+
+    async fn send_job(
+        &self,
+        email_job: &EmailJob
+    ) -> Result<(), DatabaseError> {
+        let envelope = match envelope(email_job) {
+            Ok(envelope) => envelope,
+            // Retrying won't make the addresses valid
+            Err(e) => {
+                error!("Email job {} is dead, invalid envelope: {}", email_job.id, e);
+                metrics::counter!("email_jobs_dead_total").increment(1);
+                let now = chrono::Utc::now().timestamp();
+                return self.db_client.fail_email_job(email_job.id, &e, None, now).await;
+            },
+        };
+
+        let started = Instant::now();
+        let res = self.transport.send_raw(&envelope, email_job.message.as_bytes()).await;
+        metrics::histogram!("email_job_send_duration_seconds").record(started.elapsed().as_secs_f64());
+
+        let now = chrono::Utc::now().timestamp();
+        let Err(e) = res.map_err(|e| e.to_string()) else {
+            metrics::counter!("email_jobs_sent_total").increment(1);
+            return self.db_client.complete_email_job(email_job.id, now).await;
+        };
+        if email_job.attempts >= self.queue_config.max_attempts {
+            error!("Email job {} is dead after {} attempts: {}", email_job.id, email_job.attempts, e);
+            metrics::counter!("email_jobs_dead_total").increment(1);
+            return self.db_client.fail_email_job(email_job.id, &e, None, now).await;
+        }
+        let retry_at = now + backoff_s(&self.queue_config, email_job.attempts);
+        warn!("Email job {} failed on attempt {}, retrying at {}: {}", email_job.id, email_job.attempts, retry_at, e);
+        metrics::counter!("email_jobs_retried_total").increment(1);
+        self.db_client.fail_email_job(email_job.id, &e, Some(retry_at), now).await
+    }
+}
+
+/// Starts `workers` workers sharing the queue and one task deleting the finished jobs
+pub fn spawn_email_workers(
+    worker: EmailWorker,
+    new_jobs: Arc<Notify>
+) {
+    info!("Starting {} email workers", worker.queue_config.workers);
+    for _ in 0..worker.queue_config.workers {
+        tokio::spawn(worker.clone().run(new_jobs.clone()));
+    }
+    tokio::spawn(worker.run_cleanup());
+}
+
+/// Doubles with every failed attempt, starting at `base_backoff_s` and capped at `max_backoff_s`
+pub(super) fn backoff_s(
+    queue_config: &EmailQueueConfig,
+    attempts: i32
+) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 32) as u32;
+    queue_config.base_backoff_s
+        .saturating_mul(2_i64.saturating_pow(exponent))
+        .min(queue_config.max_backoff_s)
+}
+
+fn envelope(
+    email_job: &EmailJob
+) -> Result<Envelope, String> {
+    let sender = email_job.sender.as_deref()
+        .map(str::parse::<Address>)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let recipients = email_job.recipients.iter()
+        .map(|recipient| recipient.parse::<Address>())
+        .collect::<Result<Vec<Address>, _>>()
+        .map_err(|e| e.to_string())?;
+    Envelope::new(sender, recipients).map_err(|e| e.to_string())
+}
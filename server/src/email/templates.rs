@@ -0,0 +1,123 @@
+use std::path::Path;
+
+use minijinja::{
+    path_loader,
+    Environment
+};
+use serde::Serialize;
+
+use crate::configuration::EmailTemplatesConfig;
+
+use super::EmailHandlerError;
+
+/// Every transactional email, each one has `<name>.subject.txt`, `<name>.txt` and `<name>.html` in the template directory
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmailTemplate {
+    Verification,
+    PasswordReset,
+    EmailChangeConfirmation,
+    EmailChangeNotice,
+    AccountLocked,
+    NewLogin,
+}
+
+impl EmailTemplate {
+    pub const ALL: [EmailTemplate; 6] = [
+        EmailTemplate::Verification,
+        EmailTemplate::PasswordReset,
+        EmailTemplate::EmailChangeConfirmation,
+        EmailTemplate::EmailChangeNotice,
+        EmailTemplate::AccountLocked,
+        EmailTemplate::NewLogin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmailTemplate::Verification => "verification",
+            EmailTemplate::PasswordReset => "password_reset",
+            EmailTemplate::EmailChangeConfirmation => "email_change_confirmation",
+            EmailTemplate::EmailChangeNotice => "email_change_notice",
+            EmailTemplate::AccountLocked => "account_locked",
+            EmailTemplate::NewLogin => "new_login",
+        }
+    }
+
+    fn file_names(&self) -> [String; 3] {
+        [
+            format!("{}.subject.txt", self.name()),
+            format!("{}.txt", self.name()),
+            format!("{}.html", self.name()),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+/// Values in the `.html` templates are HTML escaped, the other ones are used as they are
+#[derive(Debug)]
+pub struct EmailTemplates {
+    env: Environment<'static>,
+    dir: String,
+    hot_reload: bool,
+}
+
+impl EmailTemplates {
+    /// Every template is loaded and parsed once, so a missing or broken one fails the startup and not the first email
+    pub fn new(
+        templates_config: &EmailTemplatesConfig
+    ) -> Result<Self, minijinja::Error> {
+        let env = Self::load(&templates_config.dir)?;
+        Ok(Self {
+            env,
+            dir: templates_config.dir.clone(),
+            hot_reload: templates_config.hot_reload,
+        })
+    }
+
+    fn load(
+        dir: impl AsRef<Path>
+    ) -> Result<Environment<'static>, minijinja::Error> {
+        let mut env = Environment::new();
+        env.set_loader(path_loader(dir));
+        for template in EmailTemplate::ALL {
+            for file_name in template.file_names() {
+                env.get_template(&file_name)?;
+            }
+        }
+        Ok(env)
+    }
+
+    pub fn render<C: Serialize>(
+        &self,
+        template: EmailTemplate,
+        context: C
+    ) -> Result<RenderedEmail, EmailHandlerError> {
+        // The loader caches what it read, a fresh environment sees the edits made since
+        let reloaded_env;
+        let env = match self.hot_reload {
+            true => {
+                reloaded_env = Self::load(&self.dir)
+                    .map_err(|e| EmailHandlerError::EmailCreationFailed(e.to_string()))?;
+                &reloaded_env
+            },
+            false => &self.env,
+        };
+
+        let context = minijinja::Value::from_serialize(&context);
+        let [subject, text, html] = template.file_names().map(|file_name| {
+            env.get_template(&file_name)
+                .and_then(|template| template.render(&context))
+                .map_err(|e| EmailHandlerError::EmailCreationFailed(e.to_string()))
+        });
+        Ok(RenderedEmail {
+            subject: subject?.trim().to_string(),
+            text: text?,
+            html: html?,
+        })
+    }
+}
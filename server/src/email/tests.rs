@@ -2,29 +2,232 @@
 
 #[cfg(test)]
 mod tests {
-    use chrono::NaiveDate;
     use lettre::message::Mailbox;
-    use crate::email::EmailHandler;
+    use minijinja::context;
+    use std::sync::Arc;
+    use crate::configuration::{
+        EmailQueueConfig,
+        EmailTemplatesConfig,
+        EmailTransportBackend,
+        SmtpTls
+    };
+    use crate::database::{
+        DatabaseClient,
+        EmailJobStore,
+        InMemoryDatabase
+    };
+    use crate::email::{
+        idempotency_key,
+        queue::backoff_s,
+        templates::{
+            EmailTemplate,
+            EmailTemplates
+        },
+        transport::FileSpoolTransport,
+        EmailHandler,
+        EmailTransport,
+        MemoryTransport
+    };
     use crate::registration::UserRegistrationFormJWT;
     use crate::routes::tests::preparation;
+    use std::path::PathBuf;
     use std::thread::sleep;
     use std::time::Duration;
 
     use pretty_assertions::assert_eq;
 
     #[tokio::test]
-    async fn test_email_handler_error() {
+    async fn test_memory_transport_captures_emails() {
         let config = preparation::get_config();
-        let email_handler = EmailHandler::new(&config).unwrap();
+        let memory_transport = MemoryTransport::default();
+        let email_handler = EmailHandler::with_transport(
+            &config,
+            EmailTransport::Memory(memory_transport.clone()),
+            Arc::new(InMemoryDatabase::new())
+        ).unwrap();
 
         let recipient: Mailbox = "bogolskibob56@gmail.com".parse().unwrap();
+        let email = email_handler.create_email_verification_email(
+            recipient.clone(),
+            "test".to_string()
+        ).unwrap();
+        email_handler.enqueue_email("test".to_string(), email).await.unwrap();
+        // Nothing is sent until a worker picks the email up
+        assert_eq!(memory_transport.sent_emails().len(), 0);
+        assert_eq!(email_handler.worker(&config.email_queue).run_due_jobs().await.unwrap(), 1);
+
+        let sent_emails = memory_transport.sent_emails();
+        assert_eq!(sent_emails.len(), 1);
+        assert_eq!(sent_emails[0].to, vec![recipient.email]);
+        assert_eq!(sent_emails[0].subject, Some("Discord-Sucks verify your email address".to_string()));
+        let verification_url = format!(
+            "{}{}?token=test",
+            config.verification_email.verification_url_domain,
+            config.verification_email.verification_url_endpoint
+        );
+        assert!(sent_emails[0].text().contains(&verification_url));
+        assert!(sent_emails[0].html().contains("Verify email address"));
+    }
+
+    #[test]
+    fn test_html_templates_escape_values() {
+        let config = preparation::get_config();
+        let templates = EmailTemplates::new(&config.email_templates).unwrap();
+
+        let rendered = templates.render(
+            EmailTemplate::EmailChangeNotice,
+            context! { new_email => "<b>evil</b>@example.com" }
+        ).unwrap();
+        assert_eq!(rendered.subject, "Discord-Sucks your email address is being changed");
+        assert!(rendered.text.contains("<b>evil</b>@example.com"));
+        assert!(rendered.html.contains("&lt;b&gt;evil&lt;"));
+        assert!(!rendered.html.contains("<b>evil</b>"));
+    }
+
+    #[test]
+    fn test_hot_reload_picks_up_template_edits() {
+        let config = preparation::get_config();
+        let templates_dir = std::env::temp_dir().join(format!("email_templates_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&templates_dir).unwrap();
+        for entry in std::fs::read_dir(&config.email_templates.dir).unwrap() {
+            let path = entry.unwrap().path();
+            std::fs::copy(&path, templates_dir.join(path.file_name().unwrap())).unwrap();
+        }
+        let templates = EmailTemplates::new(&EmailTemplatesConfig {
+            dir: templates_dir.to_str().unwrap().to_string(),
+            hot_reload: true,
+        }).unwrap();
+
+        let rendered = templates.render(EmailTemplate::AccountLocked, context! { lockout_minutes => 5 }).unwrap();
+        assert_eq!(rendered.subject, "Discord-Sucks your account was locked");
+
+        std::fs::write(templates_dir.join("account_locked.subject.txt"), "Locked for {{ lockout_minutes }} minutes").unwrap();
+        let rendered = templates.render(EmailTemplate::AccountLocked, context! { lockout_minutes => 5 }).unwrap();
+        assert_eq!(rendered.subject, "Locked for 5 minutes");
+
+        std::fs::remove_dir_all(&templates_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_smtp_without_credentials() {
+        let mut config = preparation::get_config();
+        config.smtp.transport = EmailTransportBackend::Smtp;
+        config.smtp.smtp_host = "localhost".to_string();
+        config.smtp.smtp_port = Some(1025);
+        config.smtp.smtp_tls = SmtpTls::None;
+        config.smtp.smtp_username = None;
+        config.smtp.smtp_password_path = None;
+        assert!(matches!(EmailTransport::new(&config.smtp), Ok(EmailTransport::Smtp(_))));
+
+        // A username without a password is a broken config, not an anonymous login
+        config.smtp.smtp_username = Some("mailpit".to_string());
+        assert!(EmailTransport::new(&config.smtp).is_err());
+    }
 
+    #[tokio::test]
+    async fn test_file_spool_transport_writes_eml_files() {
+        let config = preparation::get_config();
+        let spool_dir = std::env::temp_dir().join(format!("mail_spool_{}", uuid::Uuid::new_v4()));
+        let spool_transport = FileSpoolTransport::new(spool_dir.to_str().unwrap()).unwrap();
+        let email_handler = EmailHandler::with_transport(
+            &config,
+            EmailTransport::FileSpool(spool_transport),
+            Arc::new(InMemoryDatabase::new())
+        ).unwrap();
+
+        let recipient: Mailbox = "bogolskibob56@gmail.com".parse().unwrap();
         let email = email_handler.create_email_verification_email(
             recipient,
             "test".to_string()
         ).unwrap();
+        email_handler.enqueue_email("test".to_string(), email).await.unwrap();
+        email_handler.worker(&config.email_queue).run_due_jobs().await.unwrap();
+
+        let files: Vec<PathBuf> = std::fs::read_dir(&spool_dir).unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "eml");
+        let raw = std::fs::read_to_string(&files[0]).unwrap();
+        assert!(raw.contains("To: bogolskibob56@gmail.com"));
+
+        std::fs::remove_dir_all(&spool_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_emails_with_the_same_idempotency_key_are_sent_once() {
+        let config = preparation::get_config();
+        let memory_transport = MemoryTransport::default();
+        let email_handler = EmailHandler::with_transport(
+            &config,
+            EmailTransport::Memory(memory_transport.clone()),
+            Arc::new(InMemoryDatabase::new())
+        ).unwrap();
+
+        let key = idempotency_key("verification", "token");
+        assert!(!key.contains("token"));
+        for _ in 0..2 {
+            let email = email_handler.create_email_verification_email(
+                "bogolskibob56@gmail.com".parse().unwrap(),
+                "token".to_string()
+            ).unwrap();
+            email_handler.enqueue_email(key.clone(), email).await.unwrap();
+        }
+        let email_worker = email_handler.worker(&config.email_queue);
+        assert_eq!(email_worker.run_due_jobs().await.unwrap(), 1);
+        assert_eq!(email_worker.run_due_jobs().await.unwrap(), 0);
+        assert_eq!(memory_transport.sent_emails().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_emails_are_retried_and_then_dead() {
+        let mut config = preparation::get_config();
+        config.email_queue.max_attempts = 2;
+        config.email_queue.base_backoff_s = 0;
+        let db_client: DatabaseClient = Arc::new(InMemoryDatabase::new());
+        // Writing into a directory that's gone fails every time
+        let spool_dir = std::env::temp_dir().join(format!("mail_spool_{}", uuid::Uuid::new_v4()));
+        let spool_transport = FileSpoolTransport::new(spool_dir.to_str().unwrap()).unwrap();
+        std::fs::remove_dir_all(&spool_dir).unwrap();
+        let email_handler = EmailHandler::with_transport(
+            &config,
+            EmailTransport::FileSpool(spool_transport),
+            db_client.clone()
+        ).unwrap();
+
+        let email = email_handler.create_account_locked_email(
+            "bogolskibob56@gmail.com".parse().unwrap(),
+            300
+        ).unwrap();
+        email_handler.enqueue_email("test".to_string(), email).await.unwrap();
+        let email_worker = email_handler.worker(&config.email_queue);
+        // Retried right away without a backoff, then given up on
+        assert_eq!(email_worker.run_due_jobs().await.unwrap(), 1);
+        assert_eq!(email_worker.run_due_jobs().await.unwrap(), 1);
+        assert_eq!(email_worker.run_due_jobs().await.unwrap(), 0);
+
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(db_client.claim_email_jobs(now + 1_000_000, 60, 10).await.unwrap().len(), 0);
+    }
 
-        email_handler.send_email(email).await.unwrap();
+    #[test]
+    fn test_email_backoff_doubles_up_to_the_max() {
+        let queue_config = EmailQueueConfig {
+            workers: 1,
+            batch_size: 10,
+            poll_interval_ms: 1000,
+            lease_s: 300,
+            max_attempts: 8,
+            base_backoff_s: 30,
+            max_backoff_s: 3600,
+            finished_retention_s: 604800,
+            cleanup_interval_s: 3600,
+        };
+        assert_eq!(backoff_s(&queue_config, 1), 30);
+        assert_eq!(backoff_s(&queue_config, 2), 60);
+        assert_eq!(backoff_s(&queue_config, 3), 120);
+        assert_eq!(backoff_s(&queue_config, 8), 3600);
+        assert_eq!(backoff_s(&queue_config, 1000), 3600);
     }
 
     #[tokio::test]
@@ -32,16 +235,12 @@ mod tests {
         let config = preparation::get_config();
 
         let registration_form = UserRegistrationFormJWT::new(
-            "123@123.pl".to_string(),
-            "123".to_string(),
-            "123".to_string(),
-            "123".to_string(),
-            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            uuid::Uuid::new_v4(),
             10
         );
 
         let jwt_keys = crate::auth::JWTKeys::new(&config).unwrap();
-        let jwt_token = registration_form.into_jwt_token(&jwt_keys).unwrap();
+        let jwt_token = jwt_keys.encode(&registration_form).unwrap();
         let registration_form_from_jwt = UserRegistrationFormJWT::from_jwt_token(&jwt_token, &jwt_keys).unwrap();
 
         assert_eq!(registration_form, registration_form_from_jwt);
@@ -52,15 +251,11 @@ mod tests {
         let config = preparation::get_config();
 
         let registration_form = UserRegistrationFormJWT::new(
-            "123@123.pl".to_string(),
-            "123".to_string(),
-            "123".to_string(),
-            "123".to_string(),
-            NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            uuid::Uuid::new_v4(),
             1
         );
         let jwt_keys = crate::auth::JWTKeys::new(&config).unwrap();
-        let jwt_token = registration_form.into_jwt_token(&jwt_keys).unwrap();
+        let jwt_token = jwt_keys.encode(&registration_form).unwrap();
 
 
         sleep(Duration::from_secs_f32(2.0));
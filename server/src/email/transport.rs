@@ -0,0 +1,266 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        Mutex
+    }
+};
+
+use lettre::{
+    address::Envelope,
+    transport::smtp::authentication::Credentials,
+    Address,
+    AsyncSmtpTransport,
+    AsyncTransport,
+    Tokio1Executor
+};
+use tracing::{
+    debug,
+    info,
+    warn
+};
+use uuid::Uuid;
+
+use crate::configuration::{
+    EmailTransportBackend,
+    SMTPConfig,
+    SmtpTls
+};
+
+use super::EmailHandlerError;
+
+/// Where the emails created by the handler end up
+#[derive(Clone, Debug)]
+pub enum EmailTransport {
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    FileSpool(FileSpoolTransport),
+    Memory(MemoryTransport),
+}
+
+impl EmailTransport {
+    /// Only the SMTP backend reads the password file, and only when credentials are configured
+    pub fn new(
+        smtp_config: &SMTPConfig
+    ) -> anyhow::Result<Self> {
+        let transport = match smtp_config.transport {
+            EmailTransportBackend::Smtp => EmailTransport::Smtp(Self::smtp_mailer(smtp_config)?),
+            EmailTransportBackend::FileSpool => {
+                let Some(spool_dir) = &smtp_config.spool_dir else {
+                    anyhow::bail!("smtp.spool_dir has to be set for the file_spool transport");
+                };
+                info!("Writing emails to {}", spool_dir);
+                EmailTransport::FileSpool(FileSpoolTransport::new(spool_dir)?)
+            },
+            EmailTransportBackend::Memory => {
+                info!("Keeping emails in memory");
+                EmailTransport::Memory(MemoryTransport::default())
+            },
+        };
+        Ok(transport)
+    }
+
+    /// `none` connects in plaintext, for MailHog or Mailpit on `localhost:1025`
+    fn smtp_mailer(
+        smtp_config: &SMTPConfig
+    ) -> anyhow::Result<AsyncSmtpTransport<Tokio1Executor>> {
+        let smtp_host = &smtp_config.smtp_host;
+        info!("Sending emails through {}", smtp_host);
+        let mut builder = match smtp_config.smtp_tls {
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)?,
+            SmtpTls::Starttls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp_host)?,
+            SmtpTls::None => {
+                warn!("Sending emails to {} without TLS", smtp_host);
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host)
+            },
+        };
+        if let Some(smtp_port) = smtp_config.smtp_port {
+            builder = builder.port(smtp_port);
+        }
+        match (&smtp_config.smtp_username, &smtp_config.smtp_password_path) {
+            (Some(smtp_username), Some(smtp_password_path)) => {
+                let smtp_password = std::fs::read_to_string(smtp_password_path)?;
+                builder = builder.credentials(Credentials::new(
+                    smtp_username.clone(),
+                    smtp_password
+                ));
+            },
+            (None, None) => {},
+            _ => anyhow::bail!("smtp.smtp_username and smtp.smtp_password_path have to be set together"),
+        }
+        Ok(builder.build())
+    }
+
+    /// `raw` is the formatted email, as the queue stores it
+    pub async fn send_raw(
+        &self,
+        envelope: &Envelope,
+        raw: &[u8]
+    ) -> Result<(), EmailHandlerError> {
+        match self {
+            EmailTransport::Smtp(mailer) => {
+                mailer.send_raw(envelope, raw).await.map_err(
+                    |e| EmailHandlerError::EmailSendingFailed(e.to_string())
+                )?;
+            },
+            EmailTransport::FileSpool(spool) => spool.send_raw(raw).await?,
+            EmailTransport::Memory(memory) => memory.send_raw(envelope, raw),
+        }
+        Ok(())
+    }
+}
+
+/// Writes every email to its own `<uuid>.eml` file, they open in any mail client
+#[derive(Clone, Debug)]
+pub struct FileSpoolTransport {
+    dir: PathBuf,
+}
+
+impl FileSpoolTransport {
+    pub fn new(
+        dir: &str
+    ) -> std::io::Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: PathBuf::from(dir),
+        })
+    }
+
+    async fn send_raw(
+        &self,
+        raw: &[u8]
+    ) -> Result<(), EmailHandlerError> {
+        let path = self.dir.join(format!("{}.eml", Uuid::new_v4()));
+        tokio::fs::write(&path, raw).await.map_err(
+            |e| EmailHandlerError::EmailSendingFailed(e.to_string())
+        )?;
+        debug!("Email written to {}", path.display());
+        Ok(())
+    }
+}
+
+/// Captured email, `raw` is the message as it would have been sent
+#[derive(Clone, Debug)]
+pub struct SentEmail {
+    pub from: Option<Address>,
+    pub to: Vec<Address>,
+    pub subject: Option<String>,
+    pub raw: String,
+}
+
+impl SentEmail {
+    fn new(
+        envelope: &Envelope,
+        raw: &[u8]
+    ) -> Self {
+        let raw = String::from_utf8_lossy(raw).into_owned();
+        let (headers, _) = split_headers(&raw);
+        Self {
+            from: envelope.from().cloned(),
+            to: envelope.to().to_vec(),
+            subject: header(&headers, "Subject").map(str::to_string),
+            raw,
+        }
+    }
+
+    /// The plain text part with its transfer encoding undone
+    #[cfg(test)]
+    pub fn text(&self) -> String {
+        self.part("text/plain").unwrap()
+    }
+
+    /// The HTML part with its transfer encoding undone
+    #[cfg(test)]
+    pub fn html(&self) -> String {
+        self.part("text/html").unwrap()
+    }
+
+    #[cfg(test)]
+    fn part(&self, content_type: &str) -> Option<String> {
+        let (headers, body) = split_headers(&self.raw);
+        let Some(boundary) = header(&headers, "Content-Type")
+            .and_then(|value| value.split_once("boundary=").map(|(_, boundary)| boundary.trim_matches('"').to_string()))
+        else {
+            return header(&headers, "Content-Type")
+                .is_some_and(|value| value.starts_with(content_type))
+                .then(|| decode_body(&headers, body));
+        };
+        body.split(&format!("--{}", boundary))
+            .map(split_headers)
+            .find(|(headers, _)| header(headers, "Content-Type").is_some_and(|value| value.starts_with(content_type)))
+            .map(|(headers, body)| decode_body(&headers, body))
+    }
+}
+
+/// Headers unfolded to a line each, and the body
+fn split_headers(raw: &str) -> (String, &str) {
+    let raw = raw.trim_start_matches("\r\n");
+    let (headers, body) = raw.split_once("\r\n\r\n").unwrap_or((raw, ""));
+    (headers.replace("\r\n ", " ").replace("\r\n\t", " "), body)
+}
+
+fn header<'a>(headers: &'a str, name: &str) -> Option<&'a str> {
+    headers.lines().find_map(|line| {
+        let (line_name, value) = line.split_once(':')?;
+        line_name.eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+#[cfg(test)]
+fn decode_body(headers: &str, body: &str) -> String {
+    match header(headers, "Content-Transfer-Encoding").unwrap_or("7bit") {
+        "quoted-printable" => decode_quoted_printable(body),
+        "base64" => {
+            let body: String = body.split_whitespace().collect();
+            let bytes = data_encoding::BASE64.decode(body.as_bytes()).unwrap();
+            String::from_utf8(bytes).unwrap()
+        },
+        _ => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+fn decode_quoted_printable(body: &str) -> String {
+    let body = body.replace("=\r\n", "");
+    let mut bytes = Vec::with_capacity(body.len());
+    let mut rest = body.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok());
+        match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(decoded) if byte == b'=' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            },
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            },
+        }
+    }
+    String::from_utf8(bytes).unwrap()
+}
+
+/// Keeps the emails instead of sending them, clones share them.
+/// The raw emails are logged at debug level so the links in them can be followed during local development
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTransport {
+    sent: Arc<Mutex<Vec<SentEmail>>>,
+}
+
+impl MemoryTransport {
+    fn send_raw(
+        &self,
+        envelope: &Envelope,
+        raw: &[u8]
+    ) {
+        let email = SentEmail::new(envelope, raw);
+        info!("Captured email to {:?}, subject: {:?}", email.to, email.subject);
+        debug!("Captured email from {:?}:\n{}", email.from, email.raw);
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).push(email);
+    }
+
+    /// In the order they were sent
+    #[cfg(test)]
+    pub fn sent_emails(&self) -> Vec<SentEmail> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
@@ -0,0 +1,67 @@
+use tracing::info;
+
+use crate::{
+    configuration::{
+        EventBusBackend,
+        EventBusConfig,
+        RedisDatabaseConfig
+    },
+    gateway::GatewayHub
+};
+
+use super::{
+    redis_backend::RedisEventBus,
+    BusEvent,
+    EventBusError
+};
+
+/// Carries gateway events to the sessions that should receive them,
+/// no matter which server instance they are connected to.
+#[derive(Clone, Debug)]
+pub enum EventBus {
+    /// Delivers straight to the local hub, for a single server instance and tests
+    InProcess(GatewayHub),
+    /// Fans events out to every server instance over Redis pub/sub
+    Redis(RedisEventBus),
+}
+
+impl EventBus {
+    pub async fn new(
+        event_bus_config: &EventBusConfig,
+        redis_config: &RedisDatabaseConfig,
+        hub: &GatewayHub
+    ) -> Result<Self, EventBusError> {
+        match event_bus_config.backend {
+            EventBusBackend::InProcess => {
+                info!("Using in-process event bus");
+                Ok(Self::in_process(hub))
+            },
+            EventBusBackend::Redis => {
+                info!("Using Redis event bus");
+                let bus = RedisEventBus::new(
+                    event_bus_config,
+                    redis_config,
+                    hub
+                ).await?;
+                Ok(EventBus::Redis(bus))
+            },
+        }
+    }
+
+    pub fn in_process(hub: &GatewayHub) -> Self {
+        EventBus::InProcess(hub.clone())
+    }
+
+    pub async fn publish(
+        &self,
+        event: BusEvent
+    ) -> Result<(), EventBusError> {
+        match self {
+            EventBus::InProcess(hub) => {
+                event.deliver(hub).await;
+                Ok(())
+            },
+            EventBus::Redis(bus) => bus.publish(&event).await,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{
+    Deserialize,
+    Serialize
+};
+use uuid::Uuid;
+
+use crate::gateway::{
+    GatewayCloseCode,
+    GatewayEvent,
+    GatewayHub
+};
+
+const CHANNEL_TOPIC_PREFIX: &str = "gateway:channel:";
+const USER_TOPIC_PREFIX: &str = "gateway:user:";
+
+/// Where an event is routed to. Every server instance only listens
+/// to the topics its own gateway sessions care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BusTopic {
+    Channel(i64),
+    User(i64),
+}
+
+impl BusTopic {
+    /// Name of the Redis pub/sub channel carrying the topic
+    pub fn key(&self) -> String {
+        match self {
+            BusTopic::Channel(channel_id) => format!("{}{}", CHANNEL_TOPIC_PREFIX, channel_id),
+            BusTopic::User(user_id) => format!("{}{}", USER_TOPIC_PREFIX, user_id),
+        }
+    }
+}
+
+/// Events shared between server instances
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum BusEvent {
+    /// Forwarded to every session subscribed to the event's channel
+    Dispatch(GatewayEvent),
+    /// Closes every gateway session of the user
+    UserBanned { user_id: i64 },
+    /// Closes the gateway sessions of the user, sent when their tokens are revoked. The sessions opened
+    /// with the tokens of `except_session_id` stay, it's the login that revoked the others.
+    SessionsRevoked {
+        user_id: i64,
+        #[serde(default)]
+        except_session_id: Option<Uuid>,
+    },
+    /// Closes the gateway sessions opened with the tokens of one login session, sent when it is logged out
+    SessionRevoked {
+        user_id: i64,
+        session_id: Uuid,
+    },
+    /// Sessions subscribed to the channel check whether they can still view it, sent when overwrites
+    /// or roles change or the channel is deleted
+    ChannelAccessChanged { channel_id: i64 },
+    /// Sessions of the user check which of their channels they can still view, sent when the
+    /// roles of the user change
+    UserAccessChanged { user_id: i64 },
+}
+
+impl BusEvent {
+    pub fn topic(&self) -> BusTopic {
+        match self {
+            BusEvent::Dispatch(event) => BusTopic::Channel(event.channel_id()),
+            BusEvent::UserBanned { user_id } => BusTopic::User(*user_id),
+            BusEvent::SessionsRevoked { user_id, .. } => BusTopic::User(*user_id),
+            BusEvent::SessionRevoked { user_id, .. } => BusTopic::User(*user_id),
+            BusEvent::ChannelAccessChanged { channel_id } => BusTopic::Channel(*channel_id),
+            BusEvent::UserAccessChanged { user_id } => BusTopic::User(*user_id),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Hands the event to the sessions connected to this server instance
+    pub async fn deliver(
+        self,
+        hub: &GatewayHub
+    ) {
+        match self {
+            BusEvent::Dispatch(event) => hub.publish(event).await,
+            BusEvent::UserBanned { user_id } => {
+                hub.close_user_sessions(user_id, GatewayCloseCode::UserBanned, None).await
+            },
+            BusEvent::SessionsRevoked { user_id, except_session_id } => {
+                hub.close_user_sessions(user_id, GatewayCloseCode::SessionRevoked, except_session_id).await
+            },
+            BusEvent::SessionRevoked { user_id, session_id } => {
+                hub.close_login_session(user_id, session_id, GatewayCloseCode::SessionRevoked).await
+            },
+            BusEvent::ChannelAccessChanged { channel_id } => hub.recheck_channel(channel_id).await,
+            BusEvent::UserAccessChanged { user_id } => hub.recheck_user(user_id).await,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+mod event;
+mod bus;
+mod redis_backend;
+
+mod tests;
+
+pub use event::{
+    BusEvent,
+    BusTopic
+};
+pub use bus::EventBus;
+
+use thiserror::Error;
+use axum::response::IntoResponse;
+
+#[derive(Error, Debug)]
+pub enum EventBusError {
+    #[error("Redis error: {0}")]
+    RedisError(#[from] redis::RedisError),
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+impl IntoResponse for EventBusError {
+    fn into_response(self) -> axum::response::Response {
+        let error_message = self.into_internal_error_code();
+
+        axum::http::Response::builder()
+            .status(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            .body(error_message.into())
+            .unwrap()
+    }
+}
+
+impl EventBusError {
+    pub fn into_internal_error_code(&self) -> &'static str {
+        match self {
+            EventBusError::RedisError(_) => "1900",
+            EventBusError::SerializationError(_) => "1901",
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::HashSet,
+    time::Duration
+};
+
+use futures_util::StreamExt;
+use redis::aio::{
+    MultiplexedConnection,
+    PubSubSink
+};
+use tracing::{
+    debug,
+    error,
+    warn
+};
+
+use crate::{
+    configuration::{
+        EventBusConfig,
+        RedisDatabaseConfig
+    },
+    gateway::GatewayHub
+};
+
+use super::{
+    BusEvent,
+    BusTopic,
+    EventBusError
+};
+
+#[derive(Clone, Debug)]
+pub struct RedisEventBus {
+    publish_con: MultiplexedConnection,
+}
+
+impl RedisEventBus {
+    /// Opens the publishing connection and spawns the task that listens for
+    /// events on the topics the local hub has sessions for.
+    pub async fn new(
+        event_bus_config: &EventBusConfig,
+        redis_config: &RedisDatabaseConfig,
+        hub: &GatewayHub
+    ) -> Result<Self, EventBusError> {
+        let client = redis::Client::open(
+            format!("redis://{}:{}", redis_config.host, redis_config.port)
+        )?;
+        let publish_con = client.get_multiplexed_async_connection().await?;
+        // Fail early if pub/sub connections can't be opened at all
+        let pubsub = client.get_async_pubsub().await?;
+
+        tokio::spawn(run_subscriber(
+            client,
+            pubsub,
+            hub.clone(),
+            Duration::from_millis(event_bus_config.reconnect_delay_ms)
+        ));
+
+        Ok(Self {
+            publish_con,
+        })
+    }
+
+    pub async fn publish(
+        &self,
+        event: &BusEvent
+    ) -> Result<(), EventBusError> {
+        let payload = event.to_json()?;
+        let mut con = self.publish_con.clone();
+        redis::cmd("PUBLISH")
+            .arg(event.topic().key())
+            .arg(payload)
+            .query_async::<()>(&mut con)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Receives events for this server instance until the process exits,
+/// reconnecting whenever the pub/sub connection breaks.
+async fn run_subscriber(
+    client: redis::Client,
+    pubsub: redis::aio::PubSub,
+    hub: GatewayHub,
+    reconnect_delay: Duration
+) {
+    let mut pubsub = Some(pubsub);
+    loop {
+        let connection = match pubsub.take() {
+            Some(pubsub) => Ok(pubsub),
+            None => client.get_async_pubsub().await,
+        };
+        match connection {
+            Ok(connection) => {
+                let e = receive_events(connection, &hub).await;
+                error!("Event bus subscription lost: {:?}", e);
+            },
+            Err(e) => {
+                error!("Failed to open event bus subscription: {:?}", e);
+            }
+        }
+        tokio::time::sleep(reconnect_delay).await;
+    }
+}
+
+async fn receive_events(
+    pubsub: redis::aio::PubSub,
+    hub: &GatewayHub
+) -> redis::RedisError {
+    let (mut sink, mut stream) = pubsub.split();
+    // A fresh connection starts without subscriptions
+    let mut subscribed_topics = HashSet::new();
+    if let Err(e) = sync_subscriptions(&mut sink, hub, &mut subscribed_topics).await {
+        return e;
+    }
+
+    loop {
+        tokio::select! {
+            _ = hub.topics_changed() => {
+                if let Err(e) = sync_subscriptions(&mut sink, hub, &mut subscribed_topics).await {
+                    return e;
+                }
+            },
+            message = stream.next() => {
+                let message = match message {
+                    Some(message) => message,
+                    None => return redis::RedisError::from((
+                        redis::ErrorKind::IoError,
+                        "Pub/sub stream closed"
+                    )),
+                };
+                let payload: String = match message.get_payload() {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        warn!("Invalid payload on {}: {:?}", message.get_channel_name(), e);
+                        continue;
+                    }
+                };
+                match BusEvent::from_json(&payload) {
+                    Ok(event) => event.deliver(hub).await,
+                    Err(e) => {
+                        warn!("Failed to deserialize event on {}: {:?}", message.get_channel_name(), e);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Subscribes to topics that gained local sessions and unsubscribes from the ones that lost them.
+/// Diffing against the hub instead of reacting to single changes keeps the order of
+/// SUBSCRIBE/UNSUBSCRIBE commands irrelevant.
+async fn sync_subscriptions(
+    sink: &mut PubSubSink,
+    hub: &GatewayHub,
+    subscribed_topics: &mut HashSet<BusTopic>
+) -> Result<(), redis::RedisError> {
+    let wanted_topics: HashSet<BusTopic> = hub.local_channel_ids().await
+        .into_iter()
+        .map(BusTopic::Channel)
+        .chain(hub.local_user_ids().await.into_iter().map(BusTopic::User))
+        .collect();
+
+    let to_subscribe: Vec<String> = wanted_topics
+        .difference(subscribed_topics)
+        .map(|topic| topic.key())
+        .collect();
+    let to_unsubscribe: Vec<String> = subscribed_topics
+        .difference(&wanted_topics)
+        .map(|topic| topic.key())
+        .collect();
+
+    if !to_subscribe.is_empty() {
+        debug!("Event bus subscribing to {:?}", to_subscribe);
+        sink.subscribe(to_subscribe).await?;
+    }
+    if !to_unsubscribe.is_empty() {
+        debug!("Event bus unsubscribing from {:?}", to_unsubscribe);
+        sink.unsubscribe(to_unsubscribe).await?;
+    }
+    *subscribed_topics = wanted_topics;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    use crate::{
+        app_objects::Message,
+        event_bus::{
+            BusEvent,
+            BusTopic,
+            EventBus
+        },
+        gateway::{
+            GatewayCloseCode,
+            GatewayEvent,
+            GatewayHub,
+            ServerPayload,
+            SessionMessage
+        }
+    };
+
+    fn get_test_message(channel_id: i64) -> Message {
+        Message::new(1, "hello".to_string(), 420, 0, None, channel_id)
+    }
+
+    #[test]
+    fn test_topic_keys() {
+        assert_eq!(BusTopic::Channel(7).key(), "gateway:channel:7");
+        assert_eq!(BusTopic::User(420).key(), "gateway:user:420");
+    }
+
+    #[test]
+    fn test_bus_event_serialization() {
+        let events = vec![
+            BusEvent::Dispatch(GatewayEvent::MessageCreate(get_test_message(7))),
+            BusEvent::Dispatch(GatewayEvent::MessageDelete { id: 1, channel_id: 7 }),
+            BusEvent::UserBanned { user_id: 420 },
+            BusEvent::SessionsRevoked { user_id: 420, except_session_id: None },
+            BusEvent::SessionsRevoked { user_id: 420, except_session_id: Some(Uuid::new_v4()) },
+            BusEvent::SessionRevoked { user_id: 420, session_id: Uuid::new_v4() },
+            BusEvent::ChannelAccessChanged { channel_id: 7 },
+            BusEvent::UserAccessChanged { user_id: 420 },
+        ];
+        for event in events {
+            let json = event.to_json().unwrap();
+            assert_eq!(BusEvent::from_json(&json).unwrap(), event);
+        }
+        // Sent by instances that don't know about `except_session_id` yet
+        assert_eq!(
+            BusEvent::from_json(r#"{"type": "sessions_revoked", "data": {"user_id": 420}}"#).unwrap(),
+            BusEvent::SessionsRevoked { user_id: 420, except_session_id: None }
+        );
+
+        assert_eq!(
+            BusEvent::Dispatch(GatewayEvent::MessageCreate(get_test_message(7))).topic(),
+            BusTopic::Channel(7)
+        );
+        assert_eq!(BusEvent::UserBanned { user_id: 420 }.topic(), BusTopic::User(420));
+        assert_eq!(BusEvent::ChannelAccessChanged { channel_id: 7 }.topic(), BusTopic::Channel(7));
+        assert_eq!(BusEvent::UserAccessChanged { user_id: 420 }.topic(), BusTopic::User(420));
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus_dispatches_to_subscribed_sessions() {
+        let hub = GatewayHub::new();
+        let event_bus = EventBus::in_process(&hub);
+        let (sender, mut receiver) = mpsc::channel(8);
+        let session_id = Uuid::new_v4();
+
+        hub.register_session(session_id, 1, None, sender).await;
+        hub.subscribe(session_id, &[7]).await;
+
+        event_bus.publish(
+            BusEvent::Dispatch(GatewayEvent::MessageDelete { id: 1, channel_id: 8 })
+        ).await.unwrap();
+        assert!(receiver.try_recv().is_err());
+
+        let event = GatewayEvent::MessageDelete { id: 2, channel_id: 7 };
+        event_bus.publish(BusEvent::Dispatch(event.clone())).await.unwrap();
+        assert_eq!(
+            receiver.try_recv().unwrap(),
+            SessionMessage::Dispatch(ServerPayload::Dispatch(event).to_json().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_process_bus_closes_user_sessions() {
+        let hub = GatewayHub::new();
+        let event_bus = EventBus::in_process(&hub);
+        let (banned_sender, mut banned_receiver) = mpsc::channel(8);
+        let (other_sender, mut other_receiver) = mpsc::channel(8);
+
+        hub.register_session(Uuid::new_v4(), 1, None, banned_sender).await;
+        hub.register_session(Uuid::new_v4(), 2, None, other_sender).await;
+
+        event_bus.publish(BusEvent::UserBanned { user_id: 1 }).await.unwrap();
+
+        assert_eq!(
+            banned_receiver.recv().await,
+            Some(SessionMessage::Close(GatewayCloseCode::UserBanned))
+        );
+        assert!(banned_receiver.recv().await.is_none());
+        assert!(other_receiver.try_recv().is_err());
+        assert_eq!(hub.session_count().await, 1);
+        assert_eq!(hub.local_user_ids().await, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_sessions_spare_the_current_login() {
+        let hub = GatewayHub::new();
+        let event_bus = EventBus::in_process(&hub);
+        let (current_sender, mut current_receiver) = mpsc::channel(8);
+        let (other_sender, mut other_receiver) = mpsc::channel(8);
+        let current_login = Uuid::new_v4();
+
+        hub.register_session(Uuid::new_v4(), 1, Some(current_login), current_sender).await;
+        hub.register_session(Uuid::new_v4(), 1, Some(Uuid::new_v4()), other_sender).await;
+
+        event_bus.publish(
+            BusEvent::SessionsRevoked { user_id: 1, except_session_id: Some(current_login) }
+        ).await.unwrap();
+
+        assert_eq!(
+            other_receiver.recv().await,
+            Some(SessionMessage::Close(GatewayCloseCode::SessionRevoked))
+        );
+        assert!(other_receiver.recv().await.is_none());
+        assert!(current_receiver.try_recv().is_err());
+        assert_eq!(hub.session_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_session_closes_only_its_connections() {
+        let hub = GatewayHub::new();
+        let event_bus = EventBus::in_process(&hub);
+        let (revoked_sender, mut revoked_receiver) = mpsc::channel(8);
+        let (other_sender, mut other_receiver) = mpsc::channel(8);
+        let (legacy_sender, mut legacy_receiver) = mpsc::channel(8);
+        let revoked_login = Uuid::new_v4();
+
+        hub.register_session(Uuid::new_v4(), 1, Some(revoked_login), revoked_sender).await;
+        hub.register_session(Uuid::new_v4(), 1, Some(Uuid::new_v4()), other_sender).await;
+        hub.register_session(Uuid::new_v4(), 1, None, legacy_sender).await;
+
+        event_bus.publish(
+            BusEvent::SessionRevoked { user_id: 1, session_id: revoked_login }
+        ).await.unwrap();
+
+        assert_eq!(
+            revoked_receiver.recv().await,
+            Some(SessionMessage::Close(GatewayCloseCode::SessionRevoked))
+        );
+        assert!(revoked_receiver.recv().await.is_none());
+        assert!(other_receiver.try_recv().is_err());
+        assert!(legacy_receiver.try_recv().is_err());
+        assert_eq!(hub.session_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_hub_signals_topic_changes() {
+        let hub = GatewayHub::new();
+        let (sender, _receiver) = mpsc::channel(8);
+        let session_id = Uuid::new_v4();
+        let wait_for_change = || tokio::time::timeout(Duration::from_millis(50), hub.topics_changed());
+
+        hub.register_session(session_id, 1, None, sender).await;
+        assert!(wait_for_change().await.is_ok());
+
+        hub.subscribe(session_id, &[7]).await;
+        assert!(wait_for_change().await.is_ok());
+        // Already subscribed, nothing changes for the event bus
+        hub.subscribe(session_id, &[7]).await;
+        assert!(wait_for_change().await.is_err());
+        assert_eq!(hub.local_channel_ids().await, vec![7]);
+
+        hub.remove_session(session_id).await;
+        assert!(wait_for_change().await.is_ok());
+        assert!(hub.local_channel_ids().await.is_empty());
+        assert!(hub.local_user_ids().await.is_empty());
+    }
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use axum::extract::ws::CloseFrame;
+
+/// Close codes sent by the gateway, also listed in docs/gateway.md.
+/// Codes in the 4000-4999 range are reserved for applications by RFC 6455.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayCloseCode {
+    /// The client sent a payload that is not valid JSON or has an unknown op
+    DecodeError,
+    /// The client sent a payload other than IDENTIFY or HEARTBEAT before identifying
+    NotIdentified,
+    /// The access token used to open the connection expired, refresh it and reconnect
+    AuthenticationExpired,
+    /// The client sent IDENTIFY more than once
+    AlreadyIdentified,
+    /// The client did not consume events fast enough and its buffer overflowed
+    EventBufferOverflow,
+    /// No HEARTBEAT (or IDENTIFY) was received within the heartbeat timeout
+    SessionTimedOut,
+    /// The client tried to subscribe to more channels than allowed
+    TooManySubscriptions,
+    /// The user's sessions were revoked, for example after logging out everywhere
+    SessionRevoked,
+    /// The user was banned
+    UserBanned,
+}
+
+impl GatewayCloseCode {
+    pub fn code(&self) -> u16 {
+        match self {
+            GatewayCloseCode::DecodeError => 4000,
+            GatewayCloseCode::NotIdentified => 4001,
+            GatewayCloseCode::AuthenticationExpired => 4002,
+            GatewayCloseCode::AlreadyIdentified => 4003,
+            GatewayCloseCode::EventBufferOverflow => 4004,
+            GatewayCloseCode::SessionTimedOut => 4005,
+            GatewayCloseCode::TooManySubscriptions => 4006,
+            GatewayCloseCode::SessionRevoked => 4007,
+            GatewayCloseCode::UserBanned => 4008,
+        }
+    }
+
+    pub fn reason(&self) -> &'static str {
+        match self {
+            GatewayCloseCode::DecodeError => "Decode error",
+            GatewayCloseCode::NotIdentified => "Not identified",
+            GatewayCloseCode::AuthenticationExpired => "Authentication expired",
+            GatewayCloseCode::AlreadyIdentified => "Already identified",
+            GatewayCloseCode::EventBufferOverflow => "Event buffer overflow",
+            GatewayCloseCode::SessionTimedOut => "Session timed out",
+            GatewayCloseCode::TooManySubscriptions => "Too many subscriptions",
+            GatewayCloseCode::SessionRevoked => "Session revoked",
+            GatewayCloseCode::UserBanned => "User banned",
+        }
+    }
+
+    pub fn into_close_frame(self) -> CloseFrame<'static> {
+        CloseFrame {
+            code: self.code(),
+            reason: Cow::Borrowed(self.reason()),
+        }
+    }
+}
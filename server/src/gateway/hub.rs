@@ -0,0 +1,375 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet
+    },
+    sync::Arc
+};
+
+use tokio::sync::{
+    mpsc,
+    Notify,
+    RwLock
+};
+use tracing::{
+    error,
+    warn
+};
+use uuid::Uuid;
+
+use super::{
+    GatewayCloseCode,
+    GatewayEvent,
+    ServerPayload
+};
+
+/// Queued by the hub for a single session task
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionMessage {
+    /// Serialized DISPATCH payload, forwarded to the socket as is
+    Dispatch(String),
+    /// Close the socket with the given code
+    Close(GatewayCloseCode),
+    /// Check the access to the channels again and unsubscribe from those the user can't view anymore
+    Recheck(Vec<i64>),
+}
+
+#[derive(Debug)]
+struct SessionHandle {
+    user_id: i64,
+    // `sid` of the access token the session was opened with
+    login_session_id: Option<Uuid>,
+    sender: mpsc::Sender<SessionMessage>,
+    channel_ids: HashSet<i64>,
+}
+
+#[derive(Debug, Default)]
+struct GatewayHubInner {
+    sessions: HashMap<Uuid, SessionHandle>,
+    channels: HashMap<i64, HashSet<Uuid>>,
+    users: HashMap<i64, HashSet<Uuid>>,
+}
+
+/// Keeps track of the gateway sessions connected to this server instance and
+/// of the channels they are subscribed to.
+#[derive(Debug, Clone, Default)]
+pub struct GatewayHub {
+    inner: Arc<RwLock<GatewayHubInner>>,
+    // Signalled whenever a channel or user gains its first or loses its last local session,
+    // the event bus uses it to keep its own subscriptions in sync
+    topics_changed: Arc<Notify>,
+}
+
+impl GatewayHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn register_session(
+        &self,
+        session_id: Uuid,
+        user_id: i64,
+        login_session_id: Option<Uuid>,
+        sender: mpsc::Sender<SessionMessage>
+    ) {
+        let mut inner = self.inner.write().await;
+        inner.sessions.insert(session_id, SessionHandle {
+            user_id,
+            login_session_id,
+            sender,
+            channel_ids: HashSet::new(),
+        });
+        let user_sessions = inner.users.entry(user_id).or_default();
+        if user_sessions.insert(session_id) && user_sessions.len() == 1 {
+            self.topics_changed.notify_one();
+        }
+    }
+
+    /// Returns the number of channels the session is subscribed to afterwards
+    pub async fn subscribe(
+        &self,
+        session_id: Uuid,
+        channel_ids: &[i64]
+    ) -> usize {
+        let mut inner = self.inner.write().await;
+        let GatewayHubInner { sessions, channels, .. } = &mut *inner;
+        let session = match sessions.get_mut(&session_id) {
+            Some(session) => session,
+            None => return 0,
+        };
+        let mut topics_changed = false;
+        for channel_id in channel_ids {
+            session.channel_ids.insert(*channel_id);
+            let channel_sessions = channels.entry(*channel_id).or_default();
+            topics_changed |= channel_sessions.insert(session_id) && channel_sessions.len() == 1;
+        }
+        if topics_changed {
+            self.topics_changed.notify_one();
+        }
+        session.channel_ids.len()
+    }
+
+    pub async fn unsubscribe(
+        &self,
+        session_id: Uuid,
+        channel_ids: &[i64]
+    ) {
+        let mut inner = self.inner.write().await;
+        let GatewayHubInner { sessions, channels, .. } = &mut *inner;
+        let session = match sessions.get_mut(&session_id) {
+            Some(session) => session,
+            None => return,
+        };
+        let mut topics_changed = false;
+        for channel_id in channel_ids {
+            session.channel_ids.remove(channel_id);
+            topics_changed |= remove_from_topic(channels, *channel_id, session_id);
+        }
+        if topics_changed {
+            self.topics_changed.notify_one();
+        }
+    }
+
+    pub async fn remove_session(
+        &self,
+        session_id: Uuid
+    ) {
+        let mut inner = self.inner.write().await;
+        if remove_session(&mut inner, session_id) {
+            self.topics_changed.notify_one();
+        }
+    }
+
+    /// Sends the event to every session subscribed to its channel.
+    /// Sessions that can't keep up are dropped, which closes their socket.
+    pub async fn publish(
+        &self,
+        event: GatewayEvent
+    ) {
+        let channel_id = event.channel_id();
+        let payload = match ServerPayload::Dispatch(event).to_json() {
+            Ok(payload) => payload,
+            Err(e) => {
+                error!("Failed to serialize gateway event: {:?}", e);
+                return;
+            }
+        };
+
+        let mut overflowed_sessions = Vec::new();
+        {
+            let inner = self.inner.read().await;
+            let session_ids = match inner.channels.get(&channel_id) {
+                Some(session_ids) => session_ids,
+                None => return,
+            };
+            for session_id in session_ids {
+                let session = match inner.sessions.get(session_id) {
+                    Some(session) => session,
+                    None => continue,
+                };
+                let message = SessionMessage::Dispatch(payload.clone());
+                if let Err(mpsc::error::TrySendError::Full(_)) = session.sender.try_send(message) {
+                    warn!(
+                        "Gateway session {} of user {} is too slow, dropping it",
+                        session_id, session.user_id
+                    );
+                    overflowed_sessions.push(*session_id);
+                }
+            }
+        }
+
+        if !overflowed_sessions.is_empty() {
+            self.remove_sessions(&overflowed_sessions).await;
+        }
+    }
+
+    /// Closes every session of the user connected to this server instance, except the ones opened
+    /// with the access tokens of `except_login_session_id`
+    pub async fn close_user_sessions(
+        &self,
+        user_id: i64,
+        close_code: GatewayCloseCode,
+        except_login_session_id: Option<Uuid>
+    ) {
+        self.close_user_sessions_where(user_id, close_code, |session| {
+            except_login_session_id.is_none() || session.login_session_id != except_login_session_id
+        }).await;
+    }
+
+    /// Closes the sessions of the user connected to this server instance that were opened with the
+    /// access tokens of `login_session_id`
+    pub async fn close_login_session(
+        &self,
+        user_id: i64,
+        login_session_id: Uuid,
+        close_code: GatewayCloseCode
+    ) {
+        self.close_user_sessions_where(user_id, close_code, |session| {
+            session.login_session_id == Some(login_session_id)
+        }).await;
+    }
+
+    async fn close_user_sessions_where(
+        &self,
+        user_id: i64,
+        close_code: GatewayCloseCode,
+        should_close: impl Fn(&SessionHandle) -> bool
+    ) {
+        let session_ids: Vec<Uuid> = {
+            let inner = self.inner.read().await;
+            let session_ids = match inner.users.get(&user_id) {
+                Some(session_ids) => session_ids,
+                None => return,
+            };
+            let mut closed_session_ids = Vec::new();
+            for session_id in session_ids {
+                let session = match inner.sessions.get(session_id) {
+                    Some(session) => session,
+                    None => continue,
+                };
+                if !should_close(session) {
+                    continue;
+                }
+                // If the buffer is full the session sees its stream end instead,
+                // it is closed either way
+                let _ = session.sender.try_send(SessionMessage::Close(close_code));
+                closed_session_ids.push(*session_id);
+            }
+            closed_session_ids
+        };
+        self.remove_sessions(&session_ids).await;
+    }
+
+    /// Has every session subscribed to the channel check its access to it again
+    pub async fn recheck_channel(
+        &self,
+        channel_id: i64
+    ) {
+        let overflowed_sessions = {
+            let inner = self.inner.read().await;
+            let session_ids = match inner.channels.get(&channel_id) {
+                Some(session_ids) => session_ids,
+                None => return,
+            };
+            queue_rechecks(&inner, session_ids, |_| vec![channel_id])
+        };
+        if !overflowed_sessions.is_empty() {
+            self.remove_sessions(&overflowed_sessions).await;
+        }
+    }
+
+    /// Has every session of the user check its access to all of its channels again
+    pub async fn recheck_user(
+        &self,
+        user_id: i64
+    ) {
+        let overflowed_sessions = {
+            let inner = self.inner.read().await;
+            let session_ids = match inner.users.get(&user_id) {
+                Some(session_ids) => session_ids,
+                None => return,
+            };
+            queue_rechecks(&inner, session_ids, |session| session.channel_ids.iter().copied().collect())
+        };
+        if !overflowed_sessions.is_empty() {
+            self.remove_sessions(&overflowed_sessions).await;
+        }
+    }
+
+    pub async fn session_count(&self) -> usize {
+        self.inner.read().await.sessions.len()
+    }
+
+    pub async fn channel_subscriber_count(&self, channel_id: i64) -> usize {
+        self.inner.read().await.channels
+            .get(&channel_id)
+            .map(|session_ids| session_ids.len())
+            .unwrap_or(0)
+    }
+
+    /// Channels with at least one subscribed session on this server instance
+    pub async fn local_channel_ids(&self) -> Vec<i64> {
+        self.inner.read().await.channels.keys().copied().collect()
+    }
+
+    /// Users with at least one session on this server instance
+    pub async fn local_user_ids(&self) -> Vec<i64> {
+        self.inner.read().await.users.keys().copied().collect()
+    }
+
+    /// Resolves once the set of local channels or users changed since the last call.
+    pub async fn topics_changed(&self) {
+        self.topics_changed.notified().await;
+    }
+
+    async fn remove_sessions(
+        &self,
+        session_ids: &[Uuid]
+    ) {
+        let mut inner = self.inner.write().await;
+        let mut topics_changed = false;
+        for session_id in session_ids {
+            topics_changed |= remove_session(&mut inner, *session_id);
+        }
+        if topics_changed {
+            self.topics_changed.notify_one();
+        }
+    }
+}
+
+/// Returns the sessions that couldn't take the recheck, they have to be dropped so they don't
+/// keep receiving events of channels they may not view anymore
+fn queue_rechecks(
+    inner: &GatewayHubInner,
+    session_ids: &HashSet<Uuid>,
+    channel_ids: impl Fn(&SessionHandle) -> Vec<i64>
+) -> Vec<Uuid> {
+    let mut overflowed_sessions = Vec::new();
+    for session_id in session_ids {
+        let session = match inner.sessions.get(session_id) {
+            Some(session) => session,
+            None => continue,
+        };
+        let message = SessionMessage::Recheck(channel_ids(session));
+        if let Err(mpsc::error::TrySendError::Full(_)) = session.sender.try_send(message) {
+            warn!(
+                "Gateway session {} of user {} is too slow to recheck its channels, dropping it",
+                session_id, session.user_id
+            );
+            overflowed_sessions.push(*session_id);
+        }
+    }
+    overflowed_sessions
+}
+
+/// Returns true if a channel or user lost its last session
+fn remove_session(
+    inner: &mut GatewayHubInner,
+    session_id: Uuid
+) -> bool {
+    let session = match inner.sessions.remove(&session_id) {
+        Some(session) => session,
+        None => return false,
+    };
+    let mut topics_changed = remove_from_topic(&mut inner.users, session.user_id, session_id);
+    for channel_id in session.channel_ids {
+        topics_changed |= remove_from_topic(&mut inner.channels, channel_id, session_id);
+    }
+    topics_changed
+}
+
+/// Returns true if the topic has no sessions left and was removed
+fn remove_from_topic(
+    topics: &mut HashMap<i64, HashSet<Uuid>>,
+    topic_id: i64,
+    session_id: Uuid
+) -> bool {
+    if let Some(session_ids) = topics.get_mut(&topic_id) {
+        session_ids.remove(&session_id);
+        if session_ids.is_empty() {
+            topics.remove(&topic_id);
+            return true;
+        }
+    }
+    false
+}
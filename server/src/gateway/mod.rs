@@ -0,0 +1,18 @@
+mod protocol;
+mod hub;
+mod session;
+mod close_codes;
+
+mod tests;
+
+pub use protocol::{
+    ClientPayload,
+    GatewayEvent,
+    ServerPayload
+};
+pub use hub::{
+    GatewayHub,
+    SessionMessage
+};
+pub use session::handle_gateway_socket;
+pub use close_codes::GatewayCloseCode;
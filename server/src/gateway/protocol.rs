@@ -0,0 +1,75 @@
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+use crate::app_objects::Message;
+
+/// Payloads sent by the client, e.g. `{"op": "HEARTBEAT"}` or
+/// `{"op": "SUBSCRIBE", "d": {"channel_ids": [1, 2]}}`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "op", content = "d", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClientPayload {
+    Identify {
+        #[serde(default)]
+        channel_ids: Vec<i64>,
+    },
+    Heartbeat,
+    Subscribe {
+        channel_ids: Vec<i64>,
+    },
+    Unsubscribe {
+        channel_ids: Vec<i64>,
+    },
+}
+
+/// Payloads sent by the server, e.g. `{"op": "HELLO", "d": {"heartbeat_interval_ms": 41250}}`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "op", content = "d", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ServerPayload {
+    Hello {
+        heartbeat_interval_ms: u64,
+    },
+    Ready {
+        session_id: String,
+        user_id: i64,
+        channel_ids: Vec<i64>,
+    },
+    HeartbeatAck,
+    Dispatch(GatewayEvent),
+}
+
+/// Events pushed to sessions subscribed to the channel, e.g.
+/// `{"op": "DISPATCH", "d": {"t": "MESSAGE_CREATE", "d": {"i": 1, ...}}}`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "t", content = "d", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GatewayEvent {
+    MessageCreate(Message),
+    MessageUpdate(Message),
+    MessageDelete {
+        id: i64,
+        channel_id: i64,
+    },
+}
+
+impl GatewayEvent {
+    pub fn channel_id(&self) -> i64 {
+        match self {
+            GatewayEvent::MessageCreate(message) => message.channel_id,
+            GatewayEvent::MessageUpdate(message) => message.channel_id,
+            GatewayEvent::MessageDelete { channel_id, .. } => *channel_id,
+        }
+    }
+}
+
+impl ServerPayload {
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
+impl ClientPayload {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
@@ -0,0 +1,243 @@
+use std::{
+    sync::Arc,
+    time::Duration
+};
+
+use axum::extract::ws::{
+    Message as WsMessage,
+    WebSocket
+};
+use tokio::{
+    sync::mpsc,
+    time::{
+        sleep,
+        Instant
+    }
+};
+use tracing::{
+    debug,
+    error,
+    info
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    guilds::{
+        get_channel_permissions,
+        GuildError
+    },
+    state::GatewayState
+};
+
+use super::{
+    ClientPayload,
+    GatewayCloseCode,
+    ServerPayload,
+    SessionMessage
+};
+
+async fn send_payload(
+    socket: &mut WebSocket,
+    payload: &ServerPayload
+) -> Result<(), axum::Error> {
+    // Serializing our own payloads can't fail
+    let payload = payload.to_json().unwrap();
+    socket.send(WsMessage::Text(payload)).await
+}
+
+/// Drops the channels the user can't view and those that don't hold messages, they are silently ignored instead of closing the session
+async fn readable_channels(
+    gateway_state: &GatewayState,
+    session_id: Uuid,
+    user_id: i64,
+    channel_ids: Vec<i64>
+) -> Vec<i64> {
+    let mut readable = Vec::with_capacity(channel_ids.len());
+    for channel_id in channel_ids {
+        let res = get_channel_permissions(
+            &gateway_state.db_client,
+            None,
+            channel_id,
+            user_id,
+            gateway_state.guilds_config.cache_ttl_s
+        ).await;
+        match res {
+            Ok(channel_permissions) if channel_permissions.accepts_messages => readable.push(channel_id),
+            Err(GuildError::DatabaseError(e)) => {
+                error!("|{}| Error checking access to channel {}: {:?}", session_id, channel_id, e);
+            },
+            _ => {},
+        }
+    }
+    readable
+}
+
+/// Runs a single gateway session until the client disconnects or the session is closed.
+///
+/// The server starts with HELLO, the client has to IDENTIFY and then send a HEARTBEAT
+/// at least every `heartbeat_interval_ms`, otherwise the socket is closed with
+/// `GatewayCloseCode::SessionTimedOut`.
+pub async fn handle_gateway_socket(
+    mut socket: WebSocket,
+    claims: AuthClaims,
+    gateway_state: Arc<GatewayState>
+) {
+    let hub = &gateway_state.hub;
+    let gateway_config = &gateway_state.gateway_config;
+    let session_id = Uuid::new_v4();
+    let user_id = claims.user_id;
+    info!("|{}| gateway session opened for user {}", session_id, user_id);
+
+    let heartbeat_timeout = Duration::from_millis(
+        gateway_config.heartbeat_interval_ms + gateway_config.heartbeat_grace_period_ms
+    );
+    let token_lifetime = Duration::from_secs(
+        (claims.exp - chrono::Utc::now().timestamp()).max(0) as u64
+    );
+
+    let hello = ServerPayload::Hello {
+        heartbeat_interval_ms: gateway_config.heartbeat_interval_ms,
+    };
+    if send_payload(&mut socket, &hello).await.is_err() {
+        return;
+    }
+
+    let (sender, mut receiver) = mpsc::channel::<SessionMessage>(gateway_config.event_buffer_size);
+    // Handed over to the hub on IDENTIFY
+    let mut sender = Some(sender);
+    let mut identified = false;
+
+    let heartbeat_deadline = sleep(heartbeat_timeout);
+    tokio::pin!(heartbeat_deadline);
+    let token_expiration = sleep(token_lifetime);
+    tokio::pin!(token_expiration);
+
+    let close_code: Option<GatewayCloseCode> = loop {
+        tokio::select! {
+            _ = &mut heartbeat_deadline => {
+                break Some(GatewayCloseCode::SessionTimedOut);
+            },
+            _ = &mut token_expiration => {
+                break Some(GatewayCloseCode::AuthenticationExpired);
+            },
+            message = receiver.recv(), if identified => {
+                match message {
+                    Some(SessionMessage::Dispatch(event)) => {
+                        if socket.send(WsMessage::Text(event)).await.is_err() {
+                            break None;
+                        }
+                    },
+                    Some(SessionMessage::Close(close_code)) => break Some(close_code),
+                    Some(SessionMessage::Recheck(channel_ids)) => {
+                        let readable = readable_channels(
+                            &gateway_state,
+                            session_id,
+                            user_id,
+                            channel_ids.clone()
+                        ).await;
+                        let revoked: Vec<i64> = channel_ids.into_iter()
+                            .filter(|channel_id| !readable.contains(channel_id))
+                            .collect();
+                        if !revoked.is_empty() {
+                            info!("|{}| lost access to channels {:?}", session_id, revoked);
+                            hub.unsubscribe(session_id, &revoked).await;
+                        }
+                    },
+                    // The hub dropped us because the buffer was full
+                    None => break Some(GatewayCloseCode::EventBufferOverflow),
+                }
+            },
+            message = socket.recv() => {
+                let message = match message {
+                    Some(Ok(message)) => message,
+                    // Connection closed or broken, nothing to send the close frame to
+                    _ => break None,
+                };
+                let payload = match message {
+                    WsMessage::Text(text) => ClientPayload::from_json(&text),
+                    WsMessage::Binary(_) => break Some(GatewayCloseCode::DecodeError),
+                    // Pings are answered by axum
+                    WsMessage::Ping(_) | WsMessage::Pong(_) => continue,
+                    WsMessage::Close(_) => break None,
+                };
+                let payload = match payload {
+                    Ok(payload) => payload,
+                    Err(e) => {
+                        debug!("|{}| invalid gateway payload: {:?}", session_id, e);
+                        break Some(GatewayCloseCode::DecodeError);
+                    }
+                };
+
+                match payload {
+                    ClientPayload::Heartbeat => {
+                        heartbeat_deadline.as_mut().reset(Instant::now() + heartbeat_timeout);
+                        if send_payload(&mut socket, &ServerPayload::HeartbeatAck).await.is_err() {
+                            break None;
+                        }
+                    },
+                    ClientPayload::Identify { channel_ids } => {
+                        if identified {
+                            break Some(GatewayCloseCode::AlreadyIdentified);
+                        }
+                        if channel_ids.len() > gateway_config.max_subscribed_channels {
+                            break Some(GatewayCloseCode::TooManySubscriptions);
+                        }
+                        let channel_ids = readable_channels(
+                            &gateway_state,
+                            session_id,
+                            user_id,
+                            channel_ids
+                        ).await;
+                        hub.register_session(session_id, user_id, claims.sid, sender.take().unwrap()).await;
+                        hub.subscribe(session_id, &channel_ids).await;
+                        identified = true;
+                        heartbeat_deadline.as_mut().reset(Instant::now() + heartbeat_timeout);
+
+                        let ready = ServerPayload::Ready {
+                            session_id: session_id.to_string(),
+                            user_id,
+                            channel_ids,
+                        };
+                        if send_payload(&mut socket, &ready).await.is_err() {
+                            break None;
+                        }
+                    },
+                    ClientPayload::Subscribe { channel_ids } => {
+                        if !identified {
+                            break Some(GatewayCloseCode::NotIdentified);
+                        }
+                        // Checked up front as well so a single payload can't trigger unlimited lookups
+                        if channel_ids.len() > gateway_config.max_subscribed_channels {
+                            break Some(GatewayCloseCode::TooManySubscriptions);
+                        }
+                        let channel_ids = readable_channels(
+                            &gateway_state,
+                            session_id,
+                            user_id,
+                            channel_ids
+                        ).await;
+                        let subscribed = hub.subscribe(session_id, &channel_ids).await;
+                        if subscribed > gateway_config.max_subscribed_channels {
+                            break Some(GatewayCloseCode::TooManySubscriptions);
+                        }
+                    },
+                    ClientPayload::Unsubscribe { channel_ids } => {
+                        if !identified {
+                            break Some(GatewayCloseCode::NotIdentified);
+                        }
+                        hub.unsubscribe(session_id, &channel_ids).await;
+                    },
+                }
+            },
+        }
+    };
+
+    hub.remove_session(session_id).await;
+    if let Some(close_code) = close_code {
+        info!("|{}| closing gateway session: {}", session_id, close_code.reason());
+        let _ = socket.send(WsMessage::Close(Some(close_code.into_close_frame()))).await;
+    } else {
+        info!("|{}| gateway session closed by client", session_id);
+    }
+}
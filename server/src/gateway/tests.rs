@@ -0,0 +1,180 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+    use uuid::Uuid;
+
+    use crate::{
+        app_objects::Message,
+        gateway::{
+            ClientPayload,
+            GatewayEvent,
+            GatewayHub,
+            ServerPayload,
+            SessionMessage
+        }
+    };
+
+    fn get_test_message(channel_id: i64) -> Message {
+        Message::new(1, "hello".to_string(), 420, 0, None, channel_id)
+    }
+
+    #[test]
+    fn test_client_payload_deserialization() {
+        assert_eq!(
+            ClientPayload::from_json(r#"{"op": "HEARTBEAT"}"#).unwrap(),
+            ClientPayload::Heartbeat
+        );
+        assert_eq!(
+            ClientPayload::from_json(r#"{"op": "IDENTIFY", "d": {}}"#).unwrap(),
+            ClientPayload::Identify { channel_ids: vec![] }
+        );
+        assert_eq!(
+            ClientPayload::from_json(r#"{"op": "SUBSCRIBE", "d": {"channel_ids": [1, 2]}}"#).unwrap(),
+            ClientPayload::Subscribe { channel_ids: vec![1, 2] }
+        );
+        assert!(ClientPayload::from_json(r#"{"op": "DISPATCH"}"#).is_err());
+        assert!(ClientPayload::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_dispatch_serialization() {
+        let payload = ServerPayload::Dispatch(GatewayEvent::MessageCreate(get_test_message(7)));
+        let json: serde_json::Value = serde_json::from_str(&payload.to_json().unwrap()).unwrap();
+        assert_eq!(json["op"], "DISPATCH");
+        assert_eq!(json["d"]["t"], "MESSAGE_CREATE");
+        assert_eq!(json["d"]["d"]["ch"], 7);
+
+        let payload = ServerPayload::Dispatch(GatewayEvent::MessageDelete { id: 1, channel_id: 7 });
+        let json: serde_json::Value = serde_json::from_str(&payload.to_json().unwrap()).unwrap();
+        assert_eq!(json["d"]["t"], "MESSAGE_DELETE");
+        assert_eq!(json["d"]["d"]["channel_id"], 7);
+    }
+
+    #[tokio::test]
+    async fn test_hub_publishes_only_to_subscribed_sessions() {
+        let hub = GatewayHub::new();
+        let (first_sender, mut first_receiver) = mpsc::channel(8);
+        let (second_sender, mut second_receiver) = mpsc::channel(8);
+        let first_session = Uuid::new_v4();
+        let second_session = Uuid::new_v4();
+
+        hub.register_session(first_session, 1, None, first_sender).await;
+        hub.register_session(second_session, 2, None, second_sender).await;
+        hub.subscribe(first_session, &[7]).await;
+        hub.subscribe(second_session, &[8]).await;
+
+        hub.publish(GatewayEvent::MessageCreate(get_test_message(7))).await;
+
+        let event = first_receiver.try_recv().unwrap();
+        let expected = ServerPayload::Dispatch(GatewayEvent::MessageCreate(get_test_message(7)));
+        assert_eq!(event, SessionMessage::Dispatch(expected.to_json().unwrap()));
+        assert!(second_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hub_unsubscribe_and_remove_session() {
+        let hub = GatewayHub::new();
+        let (sender, mut receiver) = mpsc::channel(8);
+        let session_id = Uuid::new_v4();
+
+        hub.register_session(session_id, 1, None, sender).await;
+        assert_eq!(hub.subscribe(session_id, &[7, 8]).await, 2);
+        assert_eq!(hub.channel_subscriber_count(7).await, 1);
+
+        hub.unsubscribe(session_id, &[7]).await;
+        assert_eq!(hub.channel_subscriber_count(7).await, 0);
+        hub.publish(GatewayEvent::MessageDelete { id: 1, channel_id: 7 }).await;
+        assert!(receiver.try_recv().is_err());
+
+        hub.remove_session(session_id).await;
+        assert_eq!(hub.session_count().await, 0);
+        assert_eq!(hub.channel_subscriber_count(8).await, 0);
+        // Dropping the session from the hub closes its event stream
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hub_drops_slow_sessions() {
+        let hub = GatewayHub::new();
+        let (sender, mut receiver) = mpsc::channel(1);
+        let session_id = Uuid::new_v4();
+
+        hub.register_session(session_id, 1, None, sender).await;
+        hub.subscribe(session_id, &[7]).await;
+
+        hub.publish(GatewayEvent::MessageDelete { id: 1, channel_id: 7 }).await;
+        hub.publish(GatewayEvent::MessageDelete { id: 2, channel_id: 7 }).await;
+
+        assert_eq!(hub.session_count().await, 0);
+        assert!(receiver.recv().await.is_some());
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_hub_rechecks_channel_subscribers() {
+        let hub = GatewayHub::new();
+        let (subscribed_sender, mut subscribed_receiver) = mpsc::channel(8);
+        let (other_sender, mut other_receiver) = mpsc::channel(8);
+        let subscribed_session = Uuid::new_v4();
+        let other_session = Uuid::new_v4();
+
+        hub.register_session(subscribed_session, 1, None, subscribed_sender).await;
+        hub.register_session(other_session, 2, None, other_sender).await;
+        hub.subscribe(subscribed_session, &[7, 8]).await;
+        hub.subscribe(other_session, &[8]).await;
+
+        hub.recheck_channel(7).await;
+
+        assert_eq!(subscribed_receiver.try_recv().unwrap(), SessionMessage::Recheck(vec![7]));
+        assert!(other_receiver.try_recv().is_err());
+        // Nothing to recheck without subscribers
+        hub.recheck_channel(9).await;
+        assert!(subscribed_receiver.try_recv().is_err());
+        assert!(other_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hub_rechecks_every_channel_of_user() {
+        let hub = GatewayHub::new();
+        let (first_sender, mut first_receiver) = mpsc::channel(8);
+        let (second_sender, mut second_receiver) = mpsc::channel(8);
+        let (other_sender, mut other_receiver) = mpsc::channel(8);
+        let first_session = Uuid::new_v4();
+        let second_session = Uuid::new_v4();
+
+        hub.register_session(first_session, 1, None, first_sender).await;
+        hub.register_session(second_session, 1, None, second_sender).await;
+        hub.register_session(Uuid::new_v4(), 2, None, other_sender).await;
+        hub.subscribe(first_session, &[7, 8]).await;
+
+        hub.recheck_user(1).await;
+
+        let SessionMessage::Recheck(mut channel_ids) = first_receiver.try_recv().unwrap() else {
+            panic!("expected a recheck");
+        };
+        channel_ids.sort();
+        assert_eq!(channel_ids, vec![7, 8]);
+        assert_eq!(second_receiver.try_recv().unwrap(), SessionMessage::Recheck(vec![]));
+        assert!(other_receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hub_drops_sessions_that_cant_take_a_recheck() {
+        let hub = GatewayHub::new();
+        let (sender, mut receiver) = mpsc::channel(1);
+        let session_id = Uuid::new_v4();
+
+        hub.register_session(session_id, 1, None, sender).await;
+        hub.subscribe(session_id, &[7]).await;
+
+        hub.publish(GatewayEvent::MessageDelete { id: 1, channel_id: 7 }).await;
+        hub.recheck_channel(7).await;
+
+        // A session that missed the recheck could keep receiving a channel it can't view anymore
+        assert_eq!(hub.session_count().await, 0);
+        assert_eq!(hub.channel_subscriber_count(7).await, 0);
+        assert!(matches!(receiver.recv().await, Some(SessionMessage::Dispatch(_))));
+        assert!(receiver.recv().await.is_none());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    auth::{
+        compute_base_permissions,
+        compute_channel_permissions,
+        compute_dm_permissions,
+        AuthError,
+        ChannelPermissions,
+        GuildPermissions,
+        Permissions
+    },
+    database::{
+        DatabaseClient,
+        DatabaseError,
+        DmStore,
+        GuildStore,
+        RoleStore
+    },
+    event_bus::{
+        BusEvent,
+        EventBus
+    }
+};
+use tracing::error;
+use uuid::Uuid;
+
+use super::GuildError;
+
+/// Loads the guild wide permissions of the user, failing if they aren't a member of the guild
+pub async fn get_guild_permissions(
+    db_client: &DatabaseClient,
+    guild_id: i64,
+    user_id: i64,
+    cache_ttl_s: u64
+) -> Result<GuildPermissions, GuildError> {
+    let guild = db_client.get_guild_by_id(guild_id, cache_ttl_s).await?;
+    let guild = match guild {
+        Some(guild) => guild,
+        None => return Err(DatabaseError::GuildNotFound(guild_id).into()),
+    };
+    let member = db_client.get_guild_member(guild_id, user_id, cache_ttl_s).await?;
+    let member = match member {
+        Some(member) => member,
+        None => return Err(GuildError::NotGuildMember),
+    };
+    let roles = db_client.get_guild_roles(guild_id, cache_ttl_s).await?;
+    let permissions = compute_base_permissions(&guild, &member, &roles);
+
+    Ok(GuildPermissions {
+        guild,
+        member,
+        roles,
+        permissions,
+    })
+}
+
+/// Loads the permissions of the user in the channel, failing if they can't view it.
+/// If `guild_id` is given the channel also has to belong to that guild, otherwise it can be a DM channel as well.
+pub async fn get_channel_permissions(
+    db_client: &DatabaseClient,
+    guild_id: Option<i64>,
+    channel_id: i64,
+    user_id: i64,
+    cache_ttl_s: u64
+) -> Result<ChannelPermissions, GuildError> {
+    let channel = db_client.get_channel_by_id(channel_id, cache_ttl_s).await?;
+    let channel = match channel {
+        Some(channel) if guild_id.is_none_or(|guild_id| guild_id == channel.guild_id) => channel,
+        None if guild_id.is_none() => {
+            return get_dm_channel_permissions(db_client, channel_id, user_id, cache_ttl_s).await;
+        },
+        _ => return Err(DatabaseError::ChannelNotFound(channel_id).into()),
+    };
+    let guild_permissions = get_guild_permissions(
+        db_client,
+        channel.guild_id,
+        user_id,
+        cache_ttl_s
+    ).await?;
+    let overwrites = db_client.get_channel_overwrites(channel_id, cache_ttl_s).await?;
+    let permissions = compute_channel_permissions(
+        guild_permissions.permissions,
+        channel.guild_id,
+        &guild_permissions.member,
+        &overwrites
+    );
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Err(AuthError::MissingPermissions(Permissions::VIEW_CHANNEL).into());
+    }
+
+    Ok(ChannelPermissions {
+        channel_id,
+        user_id,
+        accepts_messages: channel.accepts_messages(),
+        permissions,
+    })
+}
+
+/// Only recipients can view a DM channel, blocks are only checked for direct DMs
+async fn get_dm_channel_permissions(
+    db_client: &DatabaseClient,
+    channel_id: i64,
+    user_id: i64,
+    cache_ttl_s: u64
+) -> Result<ChannelPermissions, GuildError> {
+    let channel = db_client.get_dm_channel_by_id(channel_id, cache_ttl_s).await?;
+    let channel = match channel {
+        Some(channel) => channel,
+        None => return Err(DatabaseError::ChannelNotFound(channel_id).into()),
+    };
+    let blocked = match channel.other_recipient(user_id) {
+        Some(other_user_id) if channel.is_recipient(user_id) => {
+            db_client.is_blocked_between(user_id, other_user_id).await?
+        },
+        _ => false,
+    };
+    let permissions = compute_dm_permissions(&channel, user_id, blocked);
+    if !permissions.contains(Permissions::VIEW_CHANNEL) {
+        return Err(AuthError::MissingPermissions(Permissions::VIEW_CHANNEL).into());
+    }
+
+    Ok(ChannelPermissions {
+        channel_id,
+        user_id,
+        accepts_messages: true,
+        permissions,
+    })
+}
+
+/// Gateway sessions keep their subscriptions until told to check them again, call this after every
+/// stored change that can take VIEW_CHANNEL away. The change is already stored, a failed publish is
+/// only logged
+pub async fn recheck_channel_access(
+    event_bus: &EventBus,
+    channel_ids: impl IntoIterator<Item = i64>,
+    request_id: Uuid
+) {
+    for channel_id in channel_ids {
+        if let Err(e) = event_bus.publish(BusEvent::ChannelAccessChanged { channel_id }).await {
+            error!("|{}| Error publishing channel access event: {:?}", request_id, e);
+        }
+    }
+}
+
+/// Role changes can take VIEW_CHANNEL away in every channel of the guild
+pub async fn recheck_guild_access(
+    db_client: &DatabaseClient,
+    event_bus: &EventBus,
+    guild_id: i64,
+    request_id: Uuid
+) {
+    match db_client.get_guild_channels(guild_id).await {
+        Ok(channels) => {
+            recheck_channel_access(
+                event_bus,
+                channels.iter().map(|channel| channel.id),
+                request_id
+            ).await
+        },
+        Err(e) => error!("|{}| Error loading channels to recheck: {:?}", request_id, e),
+    }
+}
+
+/// Sessions of the user check every channel they're subscribed to
+pub async fn recheck_user_access(
+    event_bus: &EventBus,
+    user_id: i64,
+    request_id: Uuid
+) {
+    if let Err(e) = event_bus.publish(BusEvent::UserAccessChanged { user_id }).await {
+        error!("|{}| Error publishing user access event: {:?}", request_id, e);
+    }
+}
@@ -0,0 +1,107 @@
+mod payload;
+mod access;
+
+pub use payload::{
+    ChannelPositionPayload,
+    CreateChannelPayload,
+    CreateInvitePayload,
+    CreateRolePayload,
+    EditRolePayload,
+    NamePayload,
+    OverwritePayload
+};
+pub use access::{
+    get_channel_permissions,
+    get_guild_permissions,
+    recheck_channel_access,
+    recheck_guild_access,
+    recheck_user_access
+};
+
+use serde_json::json;
+use axum::{
+    extract::rejection::PathRejection,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    auth::AuthError,
+    database::DatabaseError,
+    snowflake::SnowflakeError
+};
+
+#[derive(Debug, Error)]
+pub enum GuildError {
+    #[error("Name must be between 1 and {0} characters")]
+    InvalidName(usize),
+    #[error("User is not a member of the guild")]
+    NotGuildMember,
+    #[error("Only the guild owner can do this")]
+    NotGuildOwner,
+    #[error("Channel with id: {0} can't be used as a parent")]
+    InvalidParent(i64),
+    #[error("Channel positions must list every channel at most once")]
+    InvalidChannelPositions,
+    #[error("Guild can't have more than {0} channels")]
+    TooManyChannels(usize),
+    #[error("Channel with id: {0} is not a text channel")]
+    NotTextChannel(i64),
+    #[error("Invite max age and max uses must be positive")]
+    InvalidInviteSettings,
+    #[error("Roles at or above your highest role can't be managed")]
+    RoleHierarchy,
+    #[error("Role position must be positive")]
+    InvalidRolePosition,
+    #[error("The @everyone role can't be renamed, moved, deleted, assigned or removed")]
+    EveryoneRole,
+    #[error("Overwrite target with id: {0} is not a role or member of the guild")]
+    InvalidOverwriteTarget(i64),
+    #[error("{0:?}")]
+    AuthError(AuthError),
+    #[error(transparent)]
+    PathRejection(#[from] PathRejection),
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    SnowflakeError(#[from] SnowflakeError),
+}
+
+impl IntoResponse for GuildError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            GuildError::InvalidName(_) => (StatusCode::BAD_REQUEST, "2000"),
+            GuildError::NotGuildMember => (StatusCode::FORBIDDEN, "2001"),
+            GuildError::NotGuildOwner => (StatusCode::FORBIDDEN, "2002"),
+            GuildError::InvalidParent(_) => (StatusCode::BAD_REQUEST, "2003"),
+            GuildError::InvalidChannelPositions => (StatusCode::BAD_REQUEST, "2004"),
+            GuildError::TooManyChannels(_) => (StatusCode::BAD_REQUEST, "2005"),
+            GuildError::NotTextChannel(_) => (StatusCode::BAD_REQUEST, "2006"),
+            GuildError::InvalidInviteSettings => (StatusCode::BAD_REQUEST, "2007"),
+            GuildError::RoleHierarchy => (StatusCode::FORBIDDEN, "2008"),
+            GuildError::InvalidRolePosition => (StatusCode::BAD_REQUEST, "2009"),
+            GuildError::EveryoneRole => (StatusCode::BAD_REQUEST, "2010"),
+            GuildError::InvalidOverwriteTarget(_) => (StatusCode::BAD_REQUEST, "2011"),
+            GuildError::AuthError(e) => return e.into_response(),
+            GuildError::PathRejection(e) => return e.into_response(),
+            GuildError::DatabaseError(e) => return e.into_response(),
+            GuildError::SnowflakeError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
+
+// AuthError doesn't implement Error, so it can't use #[from]
+impl From<AuthError> for GuildError {
+    fn from(e: AuthError) -> Self {
+        GuildError::AuthError(e)
+    }
+}
@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+use crate::{
+    app_objects::{
+        ChannelKind,
+        OverwriteKind,
+        Role
+    },
+    auth::Permissions
+};
+
+use super::GuildError;
+
+/// Trims the name and checks it against the configured maximum length
+fn validated_name(
+    name: &str,
+    max_name_length: usize
+) -> Result<String, GuildError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > max_name_length {
+        return Err(GuildError::InvalidName(max_name_length));
+    }
+    Ok(name.to_string())
+}
+
+/// Used to create a guild and to rename a channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct NamePayload {
+    pub name: String,
+}
+
+impl NamePayload {
+    pub fn validated_name(
+        &self,
+        max_name_length: usize
+    ) -> Result<String, GuildError> {
+        validated_name(&self.name, max_name_length)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateChannelPayload {
+    pub name: String,
+    pub kind: ChannelKind,
+    pub parent_id: Option<i64>,
+}
+
+impl CreateChannelPayload {
+    pub fn validated_name(
+        &self,
+        max_name_length: usize
+    ) -> Result<String, GuildError> {
+        validated_name(&self.name, max_name_length)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelPositionPayload {
+    pub id: i64,
+    pub position: i32,
+}
+
+impl ChannelPositionPayload {
+    pub fn validated_positions(
+        payloads: &[ChannelPositionPayload]
+    ) -> Result<Vec<(i64, i32)>, GuildError> {
+        let mut channel_ids = HashSet::new();
+        for payload in payloads {
+            if payload.position < 0 || !channel_ids.insert(payload.id) {
+                return Err(GuildError::InvalidChannelPositions);
+            }
+        }
+        if channel_ids.is_empty() {
+            return Err(GuildError::InvalidChannelPositions);
+        }
+        Ok(payloads.iter().map(|payload| (payload.id, payload.position)).collect())
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CreateInvitePayload {
+    // 0 creates an invite that never expires, None uses the configured default
+    pub max_age_s: Option<i64>,
+    pub max_uses: Option<i32>,
+}
+
+impl CreateInvitePayload {
+    /// Returns the expiration timestamp and the use limit of the invite
+    pub fn validated_limits(
+        &self,
+        now: i64,
+        default_max_age_s: i64
+    ) -> Result<(Option<i64>, Option<i32>), GuildError> {
+        let max_age_s = self.max_age_s.unwrap_or(default_max_age_s);
+        if max_age_s < 0 {
+            return Err(GuildError::InvalidInviteSettings);
+        }
+        if let Some(max_uses) = self.max_uses {
+            if max_uses < 1 {
+                return Err(GuildError::InvalidInviteSettings);
+            }
+        }
+        let expires_at = match max_age_s {
+            0 => None,
+            max_age_s => Some(now + max_age_s),
+        };
+        Ok((expires_at, self.max_uses))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRolePayload {
+    pub name: String,
+    pub permissions: Option<Permissions>,
+    // Placed right above @everyone by default
+    pub position: Option<i32>,
+}
+
+impl CreateRolePayload {
+    /// Returns the name, permissions and position of the new role
+    pub fn validated(
+        &self,
+        max_name_length: usize
+    ) -> Result<(String, Permissions, i32), GuildError> {
+        let name = validated_name(&self.name, max_name_length)?;
+        let position = self.position.unwrap_or(1);
+        if position < 1 {
+            return Err(GuildError::InvalidRolePosition);
+        }
+        Ok((name, self.permissions.unwrap_or(Permissions::empty()), position))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EditRolePayload {
+    pub name: Option<String>,
+    pub permissions: Option<Permissions>,
+    pub position: Option<i32>,
+}
+
+impl EditRolePayload {
+    /// Returns the role with the changes applied, only the permissions of @everyone can be changed
+    pub fn applied_to(
+        &self,
+        role: &Role,
+        max_name_length: usize
+    ) -> Result<Role, GuildError> {
+        if role.is_everyone() && (self.name.is_some() || self.position.is_some()) {
+            return Err(GuildError::EveryoneRole);
+        }
+        let mut role = role.clone();
+        if let Some(name) = &self.name {
+            role.name = validated_name(name, max_name_length)?;
+        }
+        if let Some(permissions) = self.permissions {
+            role.permissions = permissions;
+        }
+        if let Some(position) = self.position {
+            if position < 1 {
+                return Err(GuildError::InvalidRolePosition);
+            }
+            role.position = position;
+        }
+        Ok(role)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OverwritePayload {
+    pub kind: OverwriteKind,
+    pub allow: Permissions,
+    pub deny: Permissions,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validated_name() {
+        let payload = NamePayload { name: "  general  ".to_string() };
+        assert_eq!(payload.validated_name(10).unwrap(), "general");
+
+        let payload = NamePayload { name: "  ".to_string() };
+        assert!(matches!(payload.validated_name(10), Err(GuildError::InvalidName(10))));
+
+        let payload = NamePayload { name: "ąęśćżźół".to_string() };
+        assert!(payload.validated_name(8).is_ok());
+        assert!(payload.validated_name(7).is_err());
+    }
+
+    #[test]
+    fn test_validated_positions() {
+        let payloads = vec![
+            ChannelPositionPayload { id: 1, position: 1 },
+            ChannelPositionPayload { id: 2, position: 0 },
+        ];
+        assert_eq!(
+            ChannelPositionPayload::validated_positions(&payloads).unwrap(),
+            vec![(1, 1), (2, 0)]
+        );
+
+        let payloads = vec![
+            ChannelPositionPayload { id: 1, position: 1 },
+            ChannelPositionPayload { id: 1, position: 0 },
+        ];
+        assert!(ChannelPositionPayload::validated_positions(&payloads).is_err());
+        assert!(ChannelPositionPayload::validated_positions(&[]).is_err());
+
+        let payloads = vec![ChannelPositionPayload { id: 1, position: -1 }];
+        assert!(ChannelPositionPayload::validated_positions(&payloads).is_err());
+    }
+
+    #[test]
+    fn test_validated_invite_limits() {
+        let payload = CreateInvitePayload::default();
+        assert_eq!(payload.validated_limits(1000, 60).unwrap(), (Some(1060), None));
+
+        let payload = CreateInvitePayload { max_age_s: Some(0), max_uses: Some(5) };
+        assert_eq!(payload.validated_limits(1000, 60).unwrap(), (None, Some(5)));
+
+        let payload = CreateInvitePayload { max_age_s: Some(-1), max_uses: None };
+        assert!(payload.validated_limits(1000, 60).is_err());
+
+        let payload = CreateInvitePayload { max_age_s: None, max_uses: Some(0) };
+        assert!(payload.validated_limits(1000, 60).is_err());
+    }
+
+    #[test]
+    fn test_validated_role() {
+        let payload = CreateRolePayload {
+            name: " mod ".to_string(),
+            permissions: None,
+            position: None,
+        };
+        assert_eq!(payload.validated(10).unwrap(), ("mod".to_string(), Permissions::empty(), 1));
+
+        let payload = CreateRolePayload {
+            name: "mod".to_string(),
+            permissions: Some(Permissions::KICK_MEMBERS),
+            position: Some(0),
+        };
+        assert!(matches!(payload.validated(10), Err(GuildError::InvalidRolePosition)));
+    }
+
+    #[test]
+    fn test_edit_role() {
+        let role = Role::new(2, 1, "mod".to_string(), Permissions::empty(), 3, 0);
+        let payload = EditRolePayload {
+            permissions: Some(Permissions::BAN_MEMBERS),
+            position: Some(5),
+            ..Default::default()
+        };
+        let edited = payload.applied_to(&role, 10).unwrap();
+        assert_eq!(edited.name, "mod");
+        assert_eq!(edited.permissions, Permissions::BAN_MEMBERS);
+        assert_eq!(edited.position, 5);
+
+        let payload = EditRolePayload { position: Some(-1), ..Default::default() };
+        assert!(matches!(payload.applied_to(&role, 10), Err(GuildError::InvalidRolePosition)));
+
+        // Only the permissions of @everyone can change
+        let everyone = Role::everyone(1, 0);
+        let payload = EditRolePayload { name: Some("all".to_string()), ..Default::default() };
+        assert!(matches!(payload.applied_to(&everyone, 10), Err(GuildError::EveryoneRole)));
+        let payload = EditRolePayload { permissions: Some(Permissions::VIEW_CHANNEL), ..Default::default() };
+        assert_eq!(payload.applied_to(&everyone, 10).unwrap().permissions, Permissions::VIEW_CHANNEL);
+    }
+}
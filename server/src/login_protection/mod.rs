@@ -0,0 +1,120 @@
+mod tests;
+
+use std::net::IpAddr;
+
+use crate::{
+    configuration::LoginProtectionConfig,
+    database::{
+        DatabaseClient,
+        DatabaseError,
+        LoginAttemptStore
+    }
+};
+
+/// What failed logins are counted for, an attacker either guesses the password of one account
+/// or tries many accounts from one address
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoginSubject {
+    Email(String),
+    Ip(IpAddr),
+    // A peer address that may be a proxy shared by many clients, counted against a limit of its own
+    PeerIp(IpAddr),
+}
+
+impl LoginSubject {
+    /// Emails differing in case are the same subject, so the limit can't be dodged by changing it
+    pub fn email(email: &str) -> Self {
+        LoginSubject::Email(email.trim().to_lowercase())
+    }
+
+    pub fn key(&self) -> String {
+        match self {
+            LoginSubject::Email(email) => format!("email:{}", email),
+            LoginSubject::Ip(ip) => format!("ip:{}", ip),
+            LoginSubject::PeerIp(ip) => format!("peer_ip:{}", ip),
+        }
+    }
+
+    /// The email and the IP. An IP that isn't known to be the client's may belong to a proxy, it is
+    /// counted as a peer so it doesn't lock out everyone behind it as quickly
+    pub fn for_login(email: &str, client_ip: Option<IpAddr>, client_ip_is_trusted: bool) -> Vec<Self> {
+        let mut subjects = vec![LoginSubject::email(email)];
+        match client_ip {
+            Some(ip) if client_ip_is_trusted => subjects.push(LoginSubject::Ip(ip)),
+            Some(ip) => subjects.push(LoginSubject::PeerIp(ip)),
+            None => {}
+        }
+        subjects
+    }
+
+    fn max_failures(&self, config: &LoginProtectionConfig) -> u64 {
+        match self {
+            LoginSubject::Email(_) => config.max_failures_per_email,
+            LoginSubject::Ip(_) => config.max_failures_per_ip,
+            LoginSubject::PeerIp(_) => config.max_failures_per_peer_ip,
+        }
+    }
+}
+
+/// Doubles with every failure past the limit, the failures slide out of the window over time
+pub fn lockout_duration(
+    config: &LoginProtectionConfig,
+    failures: u64,
+    max_failures: u64
+) -> Option<i64> {
+    if failures < max_failures {
+        return None;
+    }
+    let doublings = (failures - max_failures).min(62) as u32;
+    let lockout_s = config.base_lockout_s.saturating_mul(1_i64 << doublings);
+    Some(lockout_s.min(config.max_lockout_s))
+}
+
+/// Seconds until the longest lockout of the subjects ends
+pub async fn get_lockout(
+    db_client: &DatabaseClient,
+    subjects: &[LoginSubject]
+) -> Result<Option<i64>, DatabaseError> {
+    let mut lockout_s = None;
+    for subject in subjects {
+        let subject_lockout_s = db_client.get_login_lockout(&subject.key()).await?;
+        lockout_s = lockout_s.max(subject_lockout_s);
+    }
+    Ok(lockout_s)
+}
+
+pub async fn requires_turnstile(
+    db_client: &DatabaseClient,
+    config: &LoginProtectionConfig,
+    subjects: &[LoginSubject],
+    now: i64
+) -> Result<bool, DatabaseError> {
+    // A shared peer would make every client behind it solve a challenge
+    for subject in subjects.iter().filter(|subject| !matches!(subject, LoginSubject::PeerIp(_))) {
+        let failures = db_client.count_login_failures(&subject.key(), now, config.window_s).await?;
+        if failures >= config.turnstile_after_failures {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Counts the failure for every subject and locks the ones that reached their limit,
+/// returns the new lockouts
+pub async fn record_login_failure(
+    db_client: &DatabaseClient,
+    config: &LoginProtectionConfig,
+    subjects: &[LoginSubject],
+    now: i64
+) -> Result<Vec<(LoginSubject, i64)>, DatabaseError> {
+    let mut lockouts = Vec::new();
+    for subject in subjects {
+        let key = subject.key();
+        let failures = db_client.add_login_failure(&key, now, config.window_s).await?;
+        if let Some(lockout_s) = lockout_duration(config, failures, subject.max_failures(config)) {
+            db_client.set_login_lockout(&key, lockout_s).await?;
+            lockouts.push((subject.clone(), lockout_s));
+        }
+    }
+    Ok(lockouts)
+}
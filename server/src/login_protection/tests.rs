@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use crate::configuration::LoginProtectionConfig;
+    use crate::database::LoginAttemptStore;
+    use crate::login_protection::{
+        get_lockout,
+        lockout_duration,
+        record_login_failure,
+        requires_turnstile,
+        LoginSubject
+    };
+    use crate::routes::tests::preparation::TestContext;
+
+    fn get_login_protection_config() -> LoginProtectionConfig {
+        LoginProtectionConfig {
+            window_s: 900,
+            max_failures_per_email: 3,
+            max_failures_per_ip: 5,
+            max_failures_per_peer_ip: 4,
+            turnstile_after_failures: 2,
+            base_lockout_s: 60,
+            max_lockout_s: 600,
+            notify_on_lockout: false,
+        }
+    }
+
+    #[test]
+    fn test_lockout_duration_doubles() {
+        let config = get_login_protection_config();
+        assert_eq!(lockout_duration(&config, 2, 3), None);
+        assert_eq!(lockout_duration(&config, 3, 3), Some(60));
+        assert_eq!(lockout_duration(&config, 4, 3), Some(120));
+        assert_eq!(lockout_duration(&config, 6, 3), Some(480));
+        assert_eq!(lockout_duration(&config, 7, 3), Some(600));
+        assert_eq!(lockout_duration(&config, 1000, 3), Some(600));
+    }
+
+    #[test]
+    fn test_email_subjects_ignore_case() {
+        assert_eq!(LoginSubject::email(" Test@Example.com"), LoginSubject::email("test@example.com"));
+        assert_eq!(LoginSubject::email("test@example.com").key(), "email:test@example.com");
+    }
+
+    #[test]
+    fn test_untrusted_ip_is_counted_as_peer() {
+        let ip = "127.0.0.3".parse().unwrap();
+        assert_eq!(
+            LoginSubject::for_login("test@example.com", Some(ip), true),
+            vec![LoginSubject::email("test@example.com"), LoginSubject::Ip(ip)]
+        );
+        assert_eq!(
+            LoginSubject::for_login("test@example.com", Some(ip), false),
+            vec![LoginSubject::email("test@example.com"), LoginSubject::PeerIp(ip)]
+        );
+        assert_ne!(LoginSubject::Ip(ip).key(), LoginSubject::PeerIp(ip).key());
+        assert_eq!(
+            LoginSubject::for_login("test@example.com", None, true),
+            vec![LoginSubject::email("test@example.com")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failures_lock_the_subject() {
+        let config = get_login_protection_config();
+        let db_client = TestContext::new().db_client();
+        let email = LoginSubject::email("lockout_test@example.com");
+        let ip = LoginSubject::Ip("127.0.0.3".parse().unwrap());
+        let subjects = [email.clone(), ip.clone()];
+        for subject in &subjects {
+            db_client.reset_login_failures(&subject.key()).await.unwrap();
+        }
+        let now = chrono::Utc::now().timestamp();
+
+        assert!(record_login_failure(&db_client, &config, &subjects, now).await.unwrap().is_empty());
+        assert!(!requires_turnstile(&db_client, &config, &subjects, now).await.unwrap());
+        assert!(record_login_failure(&db_client, &config, &subjects, now).await.unwrap().is_empty());
+        assert!(requires_turnstile(&db_client, &config, &subjects, now).await.unwrap());
+        assert_eq!(get_lockout(&db_client, &subjects).await.unwrap(), None);
+
+        // Only the email reached its limit
+        let lockouts = record_login_failure(&db_client, &config, &subjects, now).await.unwrap();
+        assert_eq!(lockouts, vec![(email.clone(), 60)]);
+        let lockout_s = get_lockout(&db_client, &subjects).await.unwrap().unwrap();
+        assert!(lockout_s > 55 && lockout_s <= 60);
+        assert_eq!(get_lockout(&db_client, std::slice::from_ref(&ip)).await.unwrap(), None);
+
+        for subject in &subjects {
+            db_client.reset_login_failures(&subject.key()).await.unwrap();
+        }
+        assert_eq!(get_lockout(&db_client, &subjects).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_peer_ip_locks_without_turnstile() {
+        let config = get_login_protection_config();
+        let db_client = TestContext::new().db_client();
+        let peer = LoginSubject::PeerIp("127.0.0.4".parse().unwrap());
+        let subjects = std::slice::from_ref(&peer);
+        let now = chrono::Utc::now().timestamp();
+
+        // Spraying passwords over many emails from one address still locks it
+        for _ in 0..3 {
+            assert!(record_login_failure(&db_client, &config, subjects, now).await.unwrap().is_empty());
+        }
+        assert!(!requires_turnstile(&db_client, &config, subjects, now).await.unwrap());
+        let lockouts = record_login_failure(&db_client, &config, subjects, now).await.unwrap();
+        assert_eq!(lockouts, vec![(peer.clone(), 60)]);
+        assert!(get_lockout(&db_client, subjects).await.unwrap().is_some());
+    }
+}
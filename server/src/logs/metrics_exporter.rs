@@ -0,0 +1,25 @@
+use std::net::SocketAddr;
+
+use anyhow::Result;
+use metrics_exporter_prometheus::PrometheusBuilder;
+use tracing::info;
+
+use crate::configuration::MetricsConfig;
+
+/// Serves the metrics recorded with the `metrics` macros on `/metrics` for Prometheus
+pub fn setup_metrics_exporter(
+    metrics_config: &MetricsConfig
+) -> Result<()> {
+    if !metrics_config.enabled {
+        return Ok(());
+    }
+    let metrics_addr = SocketAddr::new(
+        metrics_config.host.parse()?,
+        metrics_config.port
+    );
+    PrometheusBuilder::new()
+        .with_http_listener(metrics_addr)
+        .install()?;
+    info!("Serving metrics on {}", metrics_addr);
+    Ok(())
+}
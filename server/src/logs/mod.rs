@@ -1,3 +1,5 @@
 mod setup;
+mod metrics_exporter;
 
-pub use setup::setup_logging;
\ No newline at end of file
+pub use setup::setup_logging;
+pub use metrics_exporter::setup_metrics_exporter;
@@ -12,17 +12,39 @@ mod state;
 mod credentials;
 mod registration;
 mod email;
-
+mod snowflake;
+mod messaging;
+mod gateway;
+mod event_bus;
+mod guilds;
+mod dms;
+mod mfa;
+mod passkeys;
+mod password_reset;
+mod account;
+mod login_protection;
+mod rate_limit;
+mod admin;
+mod audit;
+
+use database::DatabaseClient;
 use email::EmailHandler;
+use event_bus::EventBus;
+use gateway::GatewayHub;
 use server::start_main_server;
 use auth::JWTKeys;
 use axum::middleware;
 use axum_server::tls_rustls::RustlsConfig;
 use cloudflare::{cloudflare_validation_middleware, TurnstileState};
+use rate_limit::RateLimiter;
 use reqwest::Method;
 use routes::configure_routes;
+use snowflake::SnowflakeGenerator;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{
+    info,
+    warn
+};
 
 use std::{
     net::SocketAddr,
@@ -39,14 +61,23 @@ use tower_http::{
     trace::{DefaultMakeSpan, TraceLayer},
 };
 
-use axum_client_ip::SecureClientIpSource;
-
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     logs::setup_logging()?;
     let config = configuration::Config::from_file("configuration/server/config.toml")?;
 
+    // `server migrate up|down|status` manages the schema and exits without starting the server
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("migrate") {
+        let command: database::MigrateCommand = args.next().unwrap_or_default().parse()?;
+        let pool = database::connect_postgres(&config.postgres_database).await?;
+        database::run_migrate_command(command, &pool).await?;
+        return Ok(());
+    }
+
+    logs::setup_metrics_exporter(&config.metrics)?;
+
     let cloudflare_ips = cloudflare::CloudflareIpAddresses::new_from_cloudflare_api().await;
     let cloudflare_ips = Arc::new(RwLock::new(cloudflare_ips?));
 
@@ -54,28 +85,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         cloudflare_ips: cloudflare_ips.clone(),
         allow_non_cloudflare_ips: config.cloudflare.allow_non_cloudflare_ips,
     };
+    if config.cloudflare.allow_non_cloudflare_ips {
+        warn!(
+            "allow_non_cloudflare_ips is on, per-IP rate limits and login lockouts count the peer address. \
+            Requests through Cloudflare share the address of its edge server"
+        );
+    }
 
     let assets_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("assets");
 
     let jwt_keys = JWTKeys::new(&config)?;
 
     info!("Connecting to databases");
-    let db_client = database::DatabaseClientWithCaching::new(
+    let db_client: DatabaseClient = Arc::new(database::DatabaseClientWithCaching::new(
         &config.redis_database,
         &config.postgres_database
-    ).await?;
+    ).await?);
 
     let turnstile_state = TurnstileState::new(
         &config
     ).unwrap();
 
     let email_handler = EmailHandler::new(
-        &config
+        &config,
+        db_client.clone()
     ).unwrap();
+    email_handler.spawn_workers(&config.email_queue);
+
+    let id_generator = SnowflakeGenerator::new(
+        &config.snowflake
+    )?;
+
+    let gateway_hub = GatewayHub::new();
+    let event_bus = EventBus::new(
+        &config.event_bus,
+        &config.redis_database,
+        &gateway_hub
+    ).await?;
+
+    let rate_limiter = RateLimiter::new(
+        &config.rate_limit,
+        &config.redis_database
+    ).await?;
 
     let cors = CorsLayer::new()
-        // allow `GET` and `POST` when accessing the resource
-        .allow_methods([Method::GET, Method::POST])
+        // allow `GET`, `POST`, `PUT`, `PATCH` and `DELETE` when accessing the resource
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
         // allow requests from any origin
         .allow_origin(Any);
 
@@ -91,6 +146,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         password_requirements,
         &turnstile_state,
         &email_handler,
+        &id_generator,
+        &gateway_hub,
+        &event_bus,
+        &rate_limiter,
         &config
     ).await;
     let app = app
@@ -99,7 +158,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             trace_layer.clone()
         )
         .layer(cors)
-        .layer(SecureClientIpSource::ConnectInfo.into_extension())
         .layer(middleware::from_fn_with_state(
             cloudflare_validation_state.clone(),
             cloudflare_validation_middleware
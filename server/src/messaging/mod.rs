@@ -0,0 +1,62 @@
+mod payload;
+
+pub use payload::{
+    MessageContentPayload,
+    MessagePageQuery
+};
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    database::DatabaseError,
+    guilds::GuildError,
+    snowflake::SnowflakeError
+};
+
+#[derive(Debug, Error)]
+pub enum MessagingError {
+    #[error("Message content is empty")]
+    EmptyContent,
+    #[error("Message content is longer than {0} characters")]
+    ContentTooLong(usize),
+    #[error("Page limit must be between 1 and {0}")]
+    InvalidLimit(i64),
+    #[error("Message with id: {0} not found in this channel")]
+    MessageNotFound(i64),
+    #[error("Only the author can modify a message")]
+    NotMessageAuthor,
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    SnowflakeError(#[from] SnowflakeError),
+    #[error(transparent)]
+    GuildError(#[from] GuildError),
+}
+
+impl IntoResponse for MessagingError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            MessagingError::EmptyContent => (StatusCode::BAD_REQUEST, "1800"),
+            MessagingError::ContentTooLong(_) => (StatusCode::BAD_REQUEST, "1801"),
+            MessagingError::InvalidLimit(_) => (StatusCode::BAD_REQUEST, "1802"),
+            MessagingError::MessageNotFound(_) => (StatusCode::NOT_FOUND, "1803"),
+            MessagingError::NotMessageAuthor => (StatusCode::FORBIDDEN, "1804"),
+            MessagingError::DatabaseError(e) => return e.into_response(),
+            MessagingError::SnowflakeError(e) => return e.into_response(),
+            MessagingError::GuildError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
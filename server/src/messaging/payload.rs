@@ -0,0 +1,80 @@
+use serde::Deserialize;
+
+use super::MessagingError;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageContentPayload {
+    pub content: String,
+}
+
+impl MessageContentPayload {
+    /// Trims the content and checks it against the configured maximum length
+    pub fn validated_content(
+        &self,
+        max_content_length: usize
+    ) -> Result<String, MessagingError> {
+        let content = self.content.trim();
+        if content.is_empty() {
+            return Err(MessagingError::EmptyContent);
+        }
+        if content.chars().count() > max_content_length {
+            return Err(MessagingError::ContentTooLong(max_content_length));
+        }
+        Ok(content.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessagePageQuery {
+    pub before: Option<i64>,
+    pub after: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl MessagePageQuery {
+    pub fn validated_limit(
+        &self,
+        default_page_size: i64,
+        max_page_size: i64
+    ) -> Result<i64, MessagingError> {
+        let limit = self.limit.unwrap_or(default_page_size);
+        if limit < 1 || limit > max_page_size {
+            return Err(MessagingError::InvalidLimit(max_page_size));
+        }
+        Ok(limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validated_content() {
+        let payload = MessageContentPayload { content: "  hello  ".to_string() };
+        assert_eq!(payload.validated_content(10).unwrap(), "hello");
+
+        let payload = MessageContentPayload { content: "   ".to_string() };
+        assert!(matches!(payload.validated_content(10), Err(MessagingError::EmptyContent)));
+
+        let payload = MessageContentPayload { content: "ąęśćżźół".to_string() };
+        assert!(payload.validated_content(8).is_ok());
+        assert!(matches!(payload.validated_content(7), Err(MessagingError::ContentTooLong(7))));
+    }
+
+    #[test]
+    fn test_validated_limit() {
+        let query = MessagePageQuery { before: None, after: None, limit: None };
+        assert_eq!(query.validated_limit(50, 100).unwrap(), 50);
+
+        let query = MessagePageQuery { before: None, after: None, limit: Some(100) };
+        assert_eq!(query.validated_limit(50, 100).unwrap(), 100);
+
+        let query = MessagePageQuery { before: None, after: None, limit: Some(0) };
+        assert!(query.validated_limit(50, 100).is_err());
+
+        let query = MessagePageQuery { before: None, after: None, limit: Some(101) };
+        assert!(query.validated_limit(50, 100).is_err());
+    }
+}
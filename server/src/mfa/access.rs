@@ -0,0 +1,39 @@
+use crate::{
+    app_objects::UserMfa,
+    configuration::MfaConfig,
+    database::{
+        DatabaseClient,
+        MfaStore
+    }
+};
+
+use super::{
+    verify_second_factor,
+    MfaCodePayload,
+    MfaError
+};
+
+/// Loads the second factor of the user, failing if it isn't enabled
+pub async fn get_enabled_user_mfa(
+    db_client: &DatabaseClient,
+    user_id: i64
+) -> Result<UserMfa, MfaError> {
+    match db_client.get_user_mfa(user_id).await? {
+        Some(user_mfa) if user_mfa.is_enabled() => Ok(user_mfa),
+        _ => Err(MfaError::NotEnabled),
+    }
+}
+
+/// Changes to the second factor need a valid code, an access token alone isn't enough
+pub async fn check_second_factor(
+    db_client: &DatabaseClient,
+    mfa_config: &MfaConfig,
+    user_mfa: &UserMfa,
+    payload: &MfaCodePayload
+) -> Result<(), MfaError> {
+    let second_factor = payload.second_factor().ok_or(MfaError::MissingCode)?;
+    if !verify_second_factor(db_client, mfa_config, user_mfa, second_factor).await? {
+        return Err(MfaError::InvalidCode);
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+mod totp;
+mod recovery_codes;
+mod payload;
+mod verification;
+mod access;
+
+mod tests;
+
+pub use totp::Totp;
+pub use recovery_codes::{
+    generate_recovery_codes,
+    hash_recovery_code
+};
+pub use payload::{
+    MfaCodePayload,
+    MfaConfirmPayload,
+    SecondFactor
+};
+pub use verification::verify_second_factor;
+pub use access::{
+    check_second_factor,
+    get_enabled_user_mfa
+};
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::database::DatabaseError;
+
+#[derive(Debug, Error)]
+pub enum MfaError {
+    #[error("Two-factor authentication is not enabled")]
+    NotEnabled,
+    #[error("Two-factor authentication is already enabled")]
+    AlreadyEnabled,
+    #[error("There is no enrollment to confirm")]
+    NotEnrolled,
+    #[error("Invalid two-factor authentication code")]
+    InvalidCode,
+    #[error("Either a code or a recovery code is required")]
+    MissingCode,
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+}
+
+impl IntoResponse for MfaError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            MfaError::NotEnabled => (StatusCode::BAD_REQUEST, "2200"),
+            MfaError::AlreadyEnabled => (StatusCode::CONFLICT, "2201"),
+            MfaError::NotEnrolled => (StatusCode::BAD_REQUEST, "2202"),
+            MfaError::InvalidCode => (StatusCode::FORBIDDEN, "2203"),
+            MfaError::MissingCode => (StatusCode::BAD_REQUEST, "2204"),
+            MfaError::DatabaseError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+
+/// Second factor sent with a login or a change of the MFA settings, exactly one of the fields has to be set
+#[derive(Debug, Deserialize)]
+pub struct MfaCodePayload {
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub recovery_code: Option<String>,
+}
+
+/// Code of the authenticator app that finishes an enrollment
+#[derive(Debug, Deserialize)]
+pub struct MfaConfirmPayload {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SecondFactor<'a> {
+    Totp(&'a str),
+    RecoveryCode(&'a str),
+}
+
+impl MfaCodePayload {
+    pub fn second_factor(&self) -> Option<SecondFactor<'_>> {
+        match (&self.code, &self.recovery_code) {
+            (Some(code), None) => Some(SecondFactor::Totp(code)),
+            (None, Some(recovery_code)) => Some(SecondFactor::RecoveryCode(recovery_code)),
+            _ => None,
+        }
+    }
+}
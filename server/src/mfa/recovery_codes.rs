@@ -0,0 +1,34 @@
+use data_encoding::HEXLOWER;
+use rand::Rng;
+use sha2::{
+    Digest,
+    Sha256
+};
+
+// Lowercase letters and digits without the ones that are easily mixed up
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+const RECOVERY_CODE_GROUP_LENGTH: usize = 5;
+
+/// One-time codes that replace a TOTP code when the authenticator app is lost, formatted as `xxxxx-xxxxx`
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    let mut random_group = || -> String {
+        (0..RECOVERY_CODE_GROUP_LENGTH)
+            .map(|_| RECOVERY_CODE_ALPHABET[rng.gen_range(0..RECOVERY_CODE_ALPHABET.len())] as char)
+            .collect()
+    };
+    (0..count)
+        .map(|_| format!("{}-{}", random_group(), random_group()))
+        .collect()
+}
+
+/// Only the hash of a code is stored. The codes are random enough that a salt isn't needed,
+/// the case, dashes and whitespace the user typed don't matter.
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    HEXLOWER.encode(&Sha256::digest(normalized.as_bytes()))
+}
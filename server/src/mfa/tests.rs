@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::mfa::{
+        generate_recovery_codes,
+        hash_recovery_code,
+        MfaCodePayload,
+        SecondFactor,
+        Totp
+    };
+
+    use pretty_assertions::assert_eq;
+
+    const RFC_6238_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_totp_rfc_6238_vectors() {
+        let totp = Totp::new(RFC_6238_SECRET, 8, 30);
+        let vectors = [
+            (59, "94287082"),
+            (1111111109, "07081804"),
+            (1111111111, "14050471"),
+            (1234567890, "89005924"),
+            (2000000000, "69279037"),
+            (20000000000, "65353130"),
+        ];
+        for (timestamp, code) in vectors {
+            assert_eq!(totp.code_at_step(totp.step_at(timestamp)), code);
+        }
+
+        // Six digit codes are the last digits of the same value
+        let totp = Totp::new(RFC_6238_SECRET, 6, 30);
+        assert_eq!(totp.code_at_step(totp.step_at(59)), "287082");
+    }
+
+    #[test]
+    fn test_totp_verify_allows_clock_skew() {
+        let totp = Totp::new(RFC_6238_SECRET, 6, 30);
+        let now = 1111111111;
+        let step = totp.step_at(now);
+        let previous_code = totp.code_at_step(step - 1);
+        let next_code = totp.code_at_step(step + 1);
+
+        assert_eq!(totp.verify(&totp.code_at_step(step), now, 1), Some(step));
+        assert_eq!(totp.verify(&previous_code, now, 1), Some(step - 1));
+        assert_eq!(totp.verify(&next_code, now, 1), Some(step + 1));
+        assert_eq!(totp.verify(&previous_code, now, 0), None);
+        assert_eq!(totp.verify(&totp.code_at_step(step - 2), now, 1), None);
+    }
+
+    #[test]
+    fn test_totp_verify_rejects_malformed_codes() {
+        let totp = Totp::new(RFC_6238_SECRET, 6, 30);
+        let code = totp.code_at_step(totp.step_at(59));
+        assert_eq!(totp.verify(&format!("{} {}", &code[..3], &code[3..]), 59, 0), Some(1));
+        assert_eq!(totp.verify(&code[..5], 59, 0), None);
+        assert_eq!(totp.verify(&format!("{}0", code), 59, 0), None);
+        assert_eq!(totp.verify("", 59, 0), None);
+    }
+
+    #[test]
+    fn test_totp_provisioning_uri() {
+        let totp = Totp::new(RFC_6238_SECRET, 6, 30);
+        assert_eq!(totp.encoded_secret(), "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(
+            totp.provisioning_uri("Discord Sucks", "user@example.com"),
+            "otpauth://totp/Discord%20Sucks:user%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+            &issuer=Discord%20Sucks&algorithm=SHA1&digits=6&period=30"
+        );
+        assert_eq!(Totp::generate_secret().len(), 20);
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes(10);
+        assert_eq!(codes.len(), 10);
+        for code in &codes {
+            assert_eq!(code.len(), 11);
+            assert_eq!(code.chars().nth(5), Some('-'));
+        }
+
+        let hash = hash_recovery_code("abcde-fghjk");
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash_recovery_code(" ABCDE fghjk "), hash);
+        assert_eq!(hash_recovery_code("abcdefghjk"), hash);
+        assert_ne!(hash_recovery_code("abcde-fghjm"), hash);
+    }
+
+    #[test]
+    fn test_second_factor_from_payload() {
+        let payload = MfaCodePayload { code: Some("123456".to_string()), recovery_code: None };
+        assert_eq!(payload.second_factor(), Some(SecondFactor::Totp("123456")));
+        let payload = MfaCodePayload { code: None, recovery_code: Some("abcde-fghjk".to_string()) };
+        assert_eq!(payload.second_factor(), Some(SecondFactor::RecoveryCode("abcde-fghjk")));
+        let payload = MfaCodePayload { code: None, recovery_code: None };
+        assert_eq!(payload.second_factor(), None);
+        let payload = MfaCodePayload { code: Some("123456".to_string()), recovery_code: Some("abcde-fghjk".to_string()) };
+        assert_eq!(payload.second_factor(), None);
+    }
+}
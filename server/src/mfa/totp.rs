@@ -0,0 +1,107 @@
+use data_encoding::BASE32_NOPAD;
+use hmac::{
+    Hmac,
+    Mac
+};
+use rand::RngCore;
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+
+// RFC 4226 recommends 160 bit secrets for HMAC-SHA1
+const SECRET_LENGTH: usize = 20;
+
+/// Time based one-time passwords as described in RFC 6238, with HMAC-SHA1 like every authenticator app expects
+#[derive(Debug, Clone)]
+pub struct Totp<'a> {
+    secret: &'a [u8],
+    digits: u32,
+    period_s: i64,
+}
+
+impl<'a> Totp<'a> {
+    pub fn new(
+        secret: &'a [u8],
+        digits: u32,
+        period_s: i64
+    ) -> Self {
+        Self {
+            secret,
+            digits,
+            period_s,
+        }
+    }
+
+    pub fn generate_secret() -> Vec<u8> {
+        let mut secret = vec![0; SECRET_LENGTH];
+        rand::thread_rng().fill_bytes(&mut secret);
+        secret
+    }
+
+    /// Base32 form of the secret that is typed into authenticator apps
+    pub fn encoded_secret(&self) -> String {
+        BASE32_NOPAD.encode(self.secret)
+    }
+
+    pub fn step_at(&self, timestamp: i64) -> i64 {
+        timestamp.div_euclid(self.period_s)
+    }
+
+    pub fn code_at_step(&self, step: i64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(self.secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(&step.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        // Dynamic truncation, RFC 4226 section 5.3
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        let code = binary % 10u32.pow(self.digits);
+        format!("{:0width$}", code, width = self.digits as usize)
+    }
+
+    /// Returns the time step the code belongs to. Codes of up to `allowed_skew_steps` steps
+    /// before and after the current one are accepted to make up for clock drift.
+    pub fn verify(
+        &self,
+        code: &str,
+        timestamp: i64,
+        allowed_skew_steps: i64
+    ) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != self.digits as usize {
+            return None;
+        }
+        let current_step = self.step_at(timestamp);
+        // Every candidate is compared so the time taken doesn't tell which step matched
+        let mut matched_step = None;
+        for step in (current_step - allowed_skew_steps)..=(current_step + allowed_skew_steps) {
+            let expected = self.code_at_step(step);
+            if bool::from(expected.as_bytes().ct_eq(code.as_bytes())) {
+                matched_step = Some(step);
+            }
+        }
+        matched_step
+    }
+
+    /// `otpauth://` URI authenticator apps read from a QR code
+    pub fn provisioning_uri(
+        &self,
+        issuer: &str,
+        account_name: &str
+    ) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            urlencoding::encode(issuer),
+            urlencoding::encode(account_name),
+            self.encoded_secret(),
+            urlencoding::encode(issuer),
+            self.digits,
+            self.period_s
+        )
+    }
+}
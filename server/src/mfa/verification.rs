@@ -0,0 +1,49 @@
+use crate::{
+    app_objects::UserMfa,
+    configuration::MfaConfig,
+    database::{
+        DatabaseClient,
+        DatabaseError,
+        MfaStore
+    }
+};
+
+use super::{
+    hash_recovery_code,
+    SecondFactor,
+    Totp
+};
+
+/// Checks a TOTP or recovery code of a user with a confirmed second factor and spends it,
+/// so every code is accepted once
+pub async fn verify_second_factor(
+    db_client: &DatabaseClient,
+    mfa_config: &MfaConfig,
+    user_mfa: &UserMfa,
+    second_factor: SecondFactor<'_>
+) -> Result<bool, DatabaseError> {
+    if !user_mfa.is_enabled() {
+        return Ok(false);
+    }
+    let now = chrono::Utc::now().timestamp();
+    match second_factor {
+        SecondFactor::Totp(code) => {
+            let totp = Totp::new(
+                &user_mfa.totp_secret,
+                mfa_config.digits,
+                mfa_config.period_s
+            );
+            match totp.verify(code, now, mfa_config.allowed_skew_steps) {
+                Some(step) => db_client.use_totp_step(user_mfa.user_id, step).await,
+                None => Ok(false),
+            }
+        },
+        SecondFactor::RecoveryCode(recovery_code) => {
+            db_client.use_recovery_code(
+                user_mfa.user_id,
+                &hash_recovery_code(recovery_code),
+                now
+            ).await
+        },
+    }
+}
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        PasskeyCeremony,
+        PasskeyChallenge
+    },
+    database::{
+        DatabaseClient,
+        PasskeyStore
+    }
+};
+
+use super::PasskeyError;
+
+/// Takes the challenge out of Redis, failing if it expired, was already answered or
+/// belongs to another ceremony or user
+pub async fn take_challenge(
+    db_client: &DatabaseClient,
+    challenge_id: Uuid,
+    ceremony: PasskeyCeremony,
+    user_id: Option<i64>
+) -> Result<PasskeyChallenge, PasskeyError> {
+    match db_client.take_passkey_challenge(challenge_id).await? {
+        Some(challenge) if challenge.ceremony == ceremony && challenge.user_id == user_id => Ok(challenge),
+        _ => Err(PasskeyError::ChallengeNotFound),
+    }
+}
@@ -0,0 +1,86 @@
+use super::{
+    cbor::CborValue,
+    PasskeyError
+};
+
+// Flags of the authenticator data, WebAuthn section 6.1
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+const FLAG_EXTENSION_DATA: u8 = 0x80;
+
+// RP id hash, flags and the signature counter
+const HEADER_LENGTH: usize = 37;
+const AAGUID_LENGTH: usize = 16;
+const MAX_CREDENTIAL_ID_LENGTH: usize = 1023;
+
+/// Credential the authenticator created during a registration
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    // COSE_Key as the authenticator encoded it
+    pub public_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    pub fn parse(data: &[u8]) -> Result<Self, PasskeyError> {
+        if data.len() < HEADER_LENGTH {
+            return Err(PasskeyError::InvalidAuthenticatorData);
+        }
+        let mut rp_id_hash = [0; 32];
+        rp_id_hash.copy_from_slice(&data[..32]);
+        let flags = data[32];
+        let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+        let mut rest = &data[HEADER_LENGTH..];
+
+        let mut attested_credential = None;
+        if flags & FLAG_ATTESTED_CREDENTIAL_DATA != 0 {
+            if rest.len() < AAGUID_LENGTH + 2 {
+                return Err(PasskeyError::InvalidAuthenticatorData);
+            }
+            let credential_id_length = u16::from_be_bytes([rest[AAGUID_LENGTH], rest[AAGUID_LENGTH + 1]]) as usize;
+            rest = &rest[AAGUID_LENGTH + 2..];
+            if credential_id_length > MAX_CREDENTIAL_ID_LENGTH || rest.len() < credential_id_length {
+                return Err(PasskeyError::InvalidAuthenticatorData);
+            }
+            let (credential_id, key_and_extensions) = rest.split_at(credential_id_length);
+            let (_, after_key) = CborValue::decode(key_and_extensions)?;
+            let public_key = &key_and_extensions[..key_and_extensions.len() - after_key.len()];
+            attested_credential = Some(AttestedCredential {
+                credential_id: credential_id.to_vec(),
+                public_key: public_key.to_vec(),
+            });
+            rest = after_key;
+        }
+        if flags & FLAG_EXTENSION_DATA != 0 {
+            let (_, after_extensions) = CborValue::decode(rest)?;
+            rest = after_extensions;
+        }
+        if !rest.is_empty() {
+            return Err(PasskeyError::InvalidAuthenticatorData);
+        }
+
+        Ok(Self {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
@@ -0,0 +1,149 @@
+use super::PasskeyError;
+
+// Authenticator data and COSE keys are only a few levels deep
+const MAX_DEPTH: usize = 8;
+
+/// The subset of CBOR (RFC 8949) authenticators produce. CTAP2 only uses definite lengths and no floats.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CborValue {
+    Integer(i128),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<CborValue>),
+    Map(Vec<(CborValue, CborValue)>),
+    Bool(bool),
+    Null,
+}
+
+impl CborValue {
+    /// Decodes the first item of `data`, returns it together with the bytes that follow it
+    pub fn decode(data: &[u8]) -> Result<(CborValue, &[u8]), PasskeyError> {
+        let mut decoder = Decoder { data, position: 0 };
+        let value = decoder.decode_item(0)?;
+        Ok((value, &data[decoder.position..]))
+    }
+
+    pub fn map_get(&self, key: &CborValue) -> Option<&CborValue> {
+        match self {
+            CborValue::Map(entries) => entries
+                .iter()
+                .find(|(entry_key, _)| entry_key == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn map_get_text(&self, key: &str) -> Option<&CborValue> {
+        self.map_get(&CborValue::Text(key.to_string()))
+    }
+
+    pub fn map_get_integer(&self, key: i128) -> Option<&CborValue> {
+        self.map_get(&CborValue::Integer(key))
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            CborValue::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            CborValue::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            CborValue::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], PasskeyError> {
+        let end = self.position.checked_add(length)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(PasskeyError::InvalidCbor)?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    /// Reads the argument that follows the initial byte
+    fn read_argument(&mut self, additional_info: u8) -> Result<u64, PasskeyError> {
+        let length = match additional_info {
+            0..=23 => return Ok(additional_info as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            // Indefinite lengths and reserved values
+            _ => return Err(PasskeyError::InvalidCbor),
+        };
+        Ok(self.take(length)?
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    fn read_length(&mut self, additional_info: u8) -> Result<usize, PasskeyError> {
+        let length = self.read_argument(additional_info)?;
+        // Every item takes at least a byte, longer lengths can't be valid
+        if length > (self.data.len() - self.position) as u64 {
+            return Err(PasskeyError::InvalidCbor);
+        }
+        Ok(length as usize)
+    }
+
+    fn decode_item(&mut self, depth: usize) -> Result<CborValue, PasskeyError> {
+        if depth > MAX_DEPTH {
+            return Err(PasskeyError::InvalidCbor);
+        }
+        let initial_byte = self.take(1)?[0];
+        let major_type = initial_byte >> 5;
+        let additional_info = initial_byte & 0x1f;
+        match major_type {
+            0 => Ok(CborValue::Integer(self.read_argument(additional_info)? as i128)),
+            1 => Ok(CborValue::Integer(-1 - self.read_argument(additional_info)? as i128)),
+            2 => {
+                let length = self.read_length(additional_info)?;
+                Ok(CborValue::Bytes(self.take(length)?.to_vec()))
+            },
+            3 => {
+                let length = self.read_length(additional_info)?;
+                let text = std::str::from_utf8(self.take(length)?).map_err(|_| PasskeyError::InvalidCbor)?;
+                Ok(CborValue::Text(text.to_string()))
+            },
+            4 => {
+                let length = self.read_length(additional_info)?;
+                let items = (0..length)
+                    .map(|_| self.decode_item(depth + 1))
+                    .collect::<Result<_, _>>()?;
+                Ok(CborValue::Array(items))
+            },
+            5 => {
+                let length = self.read_length(additional_info)?;
+                let entries = (0..length)
+                    .map(|_| Ok((self.decode_item(depth + 1)?, self.decode_item(depth + 1)?)))
+                    .collect::<Result<_, PasskeyError>>()?;
+                Ok(CborValue::Map(entries))
+            },
+            7 => match additional_info {
+                20 => Ok(CborValue::Bool(false)),
+                21 => Ok(CborValue::Bool(true)),
+                22 => Ok(CborValue::Null),
+                _ => Err(PasskeyError::InvalidCbor),
+            },
+            // Tags
+            _ => Err(PasskeyError::InvalidCbor),
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use rand::RngCore;
+use serde::Serialize;
+use sha2::{
+    Digest,
+    Sha256
+};
+
+use crate::{
+    app_objects::{
+        Passkey,
+        PasskeyChallenge
+    },
+    configuration::PasskeysConfig
+};
+
+use super::{
+    authenticator_data::AuthenticatorData,
+    cbor::CborValue,
+    client_data::CollectedClientData,
+    cose::{
+        CosePublicKey,
+        SUPPORTED_ALGORITHMS
+    },
+    decode_base64url,
+    encode_base64url,
+    AssertionResponse,
+    AttestationResponse,
+    PasskeyCredential,
+    PasskeyError
+};
+
+const CHALLENGE_LENGTH: usize = 32;
+const PUBLIC_KEY_CREDENTIAL_TYPE: &str = "public-key";
+
+pub fn generate_challenge() -> Vec<u8> {
+    let mut challenge = vec![0; CHALLENGE_LENGTH];
+    rand::thread_rng().fill_bytes(&mut challenge);
+    challenge
+}
+
+/// WebAuthn user handle of a user, the big endian bytes of the id
+pub fn user_handle(user_id: i64) -> Vec<u8> {
+    user_id.to_be_bytes().to_vec()
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyCredentialUser {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicKeyCredentialParameters {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub alg: i128,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicKeyCredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: &'static str,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub require_resident_key: bool,
+    pub user_verification: &'static str,
+}
+
+/// `PublicKeyCredentialCreationOptions` for `navigator.credentials.create()`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationOptions {
+    pub rp: RelyingParty,
+    pub user: PublicKeyCredentialUser,
+    pub challenge: String,
+    pub pub_key_cred_params: Vec<PublicKeyCredentialParameters>,
+    pub timeout: u64,
+    pub exclude_credentials: Vec<PublicKeyCredentialDescriptor>,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub attestation: &'static str,
+}
+
+/// `PublicKeyCredentialRequestOptions` for `navigator.credentials.get()`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestOptions {
+    pub challenge: String,
+    pub timeout: u64,
+    pub rp_id: String,
+    pub allow_credentials: Vec<PublicKeyCredentialDescriptor>,
+    pub user_verification: &'static str,
+}
+
+/// Passkeys are discoverable credentials with user verification, so a login needs neither
+/// the email nor the password
+pub fn creation_options(
+    passkeys_config: &PasskeysConfig,
+    challenge: &PasskeyChallenge,
+    user_id: i64,
+    user_name: &str,
+    display_name: &str,
+    existing_passkeys: &[Passkey]
+) -> CreationOptions {
+    CreationOptions {
+        rp: RelyingParty {
+            id: passkeys_config.rp_id.clone(),
+            name: passkeys_config.rp_name.clone(),
+        },
+        user: PublicKeyCredentialUser {
+            id: encode_base64url(&user_handle(user_id)),
+            name: user_name.to_string(),
+            display_name: display_name.to_string(),
+        },
+        challenge: encode_base64url(&challenge.challenge),
+        pub_key_cred_params: SUPPORTED_ALGORITHMS
+            .iter()
+            .map(|alg| PublicKeyCredentialParameters { credential_type: PUBLIC_KEY_CREDENTIAL_TYPE, alg: *alg })
+            .collect(),
+        timeout: passkeys_config.timeout_ms,
+        // Stops the authenticator from registering a second credential for the same account
+        exclude_credentials: existing_passkeys
+            .iter()
+            .map(|passkey| PublicKeyCredentialDescriptor {
+                credential_type: PUBLIC_KEY_CREDENTIAL_TYPE,
+                id: encode_base64url(&passkey.credential_id),
+            })
+            .collect(),
+        authenticator_selection: AuthenticatorSelection {
+            resident_key: "required",
+            require_resident_key: true,
+            user_verification: "required",
+        },
+        attestation: "none",
+    }
+}
+
+pub fn request_options(
+    passkeys_config: &PasskeysConfig,
+    challenge: &PasskeyChallenge
+) -> RequestOptions {
+    RequestOptions {
+        challenge: encode_base64url(&challenge.challenge),
+        timeout: passkeys_config.timeout_ms,
+        rp_id: passkeys_config.rp_id.clone(),
+        allow_credentials: Vec::new(),
+        user_verification: "required",
+    }
+}
+
+/// Checks the client data and the flags and RP id hash of the authenticator data, the steps
+/// registrations and logins share
+fn check_response(
+    passkeys_config: &PasskeysConfig,
+    challenge: &PasskeyChallenge,
+    ceremony_type: &str,
+    client_data_json: &[u8],
+    authenticator_data: &AuthenticatorData
+) -> Result<(), PasskeyError> {
+    CollectedClientData::parse(client_data_json)?.check(
+        ceremony_type,
+        &challenge.challenge,
+        &passkeys_config.allowed_origins
+    )?;
+    let rp_id_hash = Sha256::digest(passkeys_config.rp_id.as_bytes());
+    if authenticator_data.rp_id_hash[..] != rp_id_hash[..]
+        || !authenticator_data.user_present()
+        || !authenticator_data.user_verified()
+    {
+        return Err(PasskeyError::InvalidAuthenticatorData);
+    }
+    Ok(())
+}
+
+/// Credential of a registration the server accepted
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedRegistration {
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub sign_count: u32,
+}
+
+/// Verifies the response to `navigator.credentials.create()`. Only the `none` attestation format
+/// is accepted, the server asks for it and doesn't check which authenticator model was used.
+pub fn verify_registration(
+    passkeys_config: &PasskeysConfig,
+    challenge: &PasskeyChallenge,
+    credential: &PasskeyCredential<AttestationResponse>
+) -> Result<VerifiedRegistration, PasskeyError> {
+    if credential.credential_type != PUBLIC_KEY_CREDENTIAL_TYPE {
+        return Err(PasskeyError::InvalidClientData);
+    }
+    let client_data_json = decode_base64url(&credential.response.client_data_json)?;
+    let attestation_object = decode_base64url(&credential.response.attestation_object)?;
+    let (attestation_object, rest) = CborValue::decode(&attestation_object)?;
+    if !rest.is_empty() {
+        return Err(PasskeyError::InvalidCbor);
+    }
+    if attestation_object.map_get_text("fmt").and_then(CborValue::as_text) != Some("none") {
+        return Err(PasskeyError::UnsupportedAttestation);
+    }
+    let authenticator_data = attestation_object
+        .map_get_text("authData")
+        .and_then(CborValue::as_bytes)
+        .ok_or(PasskeyError::InvalidAuthenticatorData)?;
+    let authenticator_data = AuthenticatorData::parse(authenticator_data)?;
+
+    check_response(passkeys_config, challenge, "webauthn.create", &client_data_json, &authenticator_data)?;
+    let attested_credential = authenticator_data.attested_credential.ok_or(PasskeyError::InvalidAuthenticatorData)?;
+    if decode_base64url(&credential.id)? != attested_credential.credential_id {
+        return Err(PasskeyError::InvalidAuthenticatorData);
+    }
+    // Rejects keys the logins couldn't verify
+    CosePublicKey::from_bytes(&attested_credential.public_key)?;
+
+    Ok(VerifiedRegistration {
+        credential_id: attested_credential.credential_id,
+        public_key: attested_credential.public_key,
+        sign_count: authenticator_data.sign_count,
+    })
+}
+
+/// Verifies the response to `navigator.credentials.get()` made with the passkey, returns the new signature counter
+pub fn verify_assertion(
+    passkeys_config: &PasskeysConfig,
+    challenge: &PasskeyChallenge,
+    passkey: &Passkey,
+    credential: &PasskeyCredential<AssertionResponse>
+) -> Result<u32, PasskeyError> {
+    if credential.credential_type != PUBLIC_KEY_CREDENTIAL_TYPE {
+        return Err(PasskeyError::InvalidClientData);
+    }
+    let response = &credential.response;
+    if let Some(user_handle_base64) = &response.user_handle {
+        if decode_base64url(user_handle_base64)? != user_handle(passkey.user_id) {
+            return Err(PasskeyError::InvalidSignature);
+        }
+    }
+    let client_data_json = decode_base64url(&response.client_data_json)?;
+    let raw_authenticator_data = decode_base64url(&response.authenticator_data)?;
+    let authenticator_data = AuthenticatorData::parse(&raw_authenticator_data)?;
+    check_response(passkeys_config, challenge, "webauthn.get", &client_data_json, &authenticator_data)?;
+
+    // The authenticator signs its data followed by the hash of the client data
+    let signed_data = [
+        raw_authenticator_data.as_slice(),
+        &Sha256::digest(&client_data_json)
+    ].concat();
+    CosePublicKey::from_bytes(&passkey.public_key)?.verify(
+        &signed_data,
+        &decode_base64url(&response.signature)?
+    )?;
+
+    check_sign_count(passkey.sign_count, authenticator_data.sign_count)?;
+    Ok(authenticator_data.sign_count)
+}
+
+/// Authenticators that count signatures have to report a higher count on every login. A count
+/// that didn't move means a copy of the credential was used, WebAuthn section 6.1.1.
+/// Authenticators that don't count report 0 every time.
+pub fn check_sign_count(
+    stored_sign_count: i64,
+    sign_count: u32
+) -> Result<(), PasskeyError> {
+    if (stored_sign_count != 0 || sign_count != 0) && sign_count as i64 <= stored_sign_count {
+        return Err(PasskeyError::CloneDetected);
+    }
+    Ok(())
+}
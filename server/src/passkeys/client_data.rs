@@ -0,0 +1,41 @@
+use serde::Deserialize;
+
+use super::{
+    decode_base64url,
+    PasskeyError
+};
+
+/// `clientDataJSON` the browser passes to the authenticator, WebAuthn section 5.8.1
+#[derive(Debug, Deserialize)]
+pub struct CollectedClientData {
+    #[serde(rename = "type")]
+    pub ceremony_type: String,
+    pub challenge: String,
+    pub origin: String,
+    #[serde(rename = "crossOrigin", default)]
+    pub cross_origin: bool,
+}
+
+impl CollectedClientData {
+    pub fn parse(client_data_json: &[u8]) -> Result<Self, PasskeyError> {
+        serde_json::from_slice(client_data_json).map_err(|_| PasskeyError::InvalidClientData)
+    }
+
+    /// The response has to answer our challenge and come from one of our pages, which is what
+    /// stops phishing sites from relaying it
+    pub fn check(
+        &self,
+        ceremony_type: &str,
+        challenge: &[u8],
+        allowed_origins: &[String]
+    ) -> Result<(), PasskeyError> {
+        if self.ceremony_type != ceremony_type
+            || self.cross_origin
+            || !allowed_origins.contains(&self.origin)
+            || decode_base64url(&self.challenge).ok().as_deref() != Some(challenge)
+        {
+            return Err(PasskeyError::InvalidClientData);
+        }
+        Ok(())
+    }
+}
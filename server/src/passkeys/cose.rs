@@ -0,0 +1,102 @@
+use ring::signature::{
+    RsaPublicKeyComponents,
+    UnparsedPublicKey,
+    ECDSA_P256_SHA256_ASN1,
+    ED25519,
+    RSA_PKCS1_2048_8192_SHA256
+};
+
+use super::{
+    cbor::CborValue,
+    PasskeyError
+};
+
+// COSE algorithm ids (RFC 9053), in the order the server prefers them
+pub const COSE_ALGORITHM_ES256: i128 = -7;
+pub const COSE_ALGORITHM_EDDSA: i128 = -8;
+pub const COSE_ALGORITHM_RS256: i128 = -257;
+pub const SUPPORTED_ALGORITHMS: [i128; 3] = [COSE_ALGORITHM_ES256, COSE_ALGORITHM_EDDSA, COSE_ALGORITHM_RS256];
+
+// COSE_Key labels
+const KEY_TYPE: i128 = 1;
+const ALGORITHM: i128 = 3;
+const CURVE: i128 = -1;
+const X: i128 = -2;
+const Y: i128 = -3;
+const RSA_N: i128 = -1;
+const RSA_E: i128 = -2;
+
+const KEY_TYPE_OKP: i128 = 1;
+const KEY_TYPE_EC2: i128 = 2;
+const KEY_TYPE_RSA: i128 = 3;
+const CURVE_P256: i128 = 1;
+const CURVE_ED25519: i128 = 6;
+
+/// Public key of a passkey, read from the COSE_Key of the registration
+#[derive(Debug, Clone, PartialEq)]
+pub enum CosePublicKey {
+    // Uncompressed SEC1 point
+    Es256(Vec<u8>),
+    EdDsa(Vec<u8>),
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+impl CosePublicKey {
+    pub fn from_cbor(key: &CborValue) -> Result<Self, PasskeyError> {
+        let integer = |label| key.map_get_integer(label).and_then(CborValue::as_integer);
+        let bytes = |label| key.map_get_integer(label)
+            .and_then(CborValue::as_bytes)
+            .ok_or(PasskeyError::InvalidPublicKey);
+
+        let algorithm = integer(ALGORITHM).ok_or(PasskeyError::InvalidPublicKey)?;
+        match (integer(KEY_TYPE), algorithm) {
+            (Some(KEY_TYPE_EC2), COSE_ALGORITHM_ES256) => {
+                let (x, y) = (bytes(X)?, bytes(Y)?);
+                if integer(CURVE) != Some(CURVE_P256) || x.len() != 32 || y.len() != 32 {
+                    return Err(PasskeyError::InvalidPublicKey);
+                }
+                Ok(CosePublicKey::Es256([&[0x04], x, y].concat()))
+            },
+            (Some(KEY_TYPE_OKP), COSE_ALGORITHM_EDDSA) => {
+                let x = bytes(X)?;
+                if integer(CURVE) != Some(CURVE_ED25519) || x.len() != 32 {
+                    return Err(PasskeyError::InvalidPublicKey);
+                }
+                Ok(CosePublicKey::EdDsa(x.to_vec()))
+            },
+            (Some(KEY_TYPE_RSA), COSE_ALGORITHM_RS256) => {
+                Ok(CosePublicKey::Rs256 { n: bytes(RSA_N)?.to_vec(), e: bytes(RSA_E)?.to_vec() })
+            },
+            (_, algorithm) if !SUPPORTED_ALGORITHMS.contains(&algorithm) => Err(PasskeyError::UnsupportedAlgorithm),
+            _ => Err(PasskeyError::InvalidPublicKey),
+        }
+    }
+
+    /// Parses a COSE_Key as stored with the passkey
+    pub fn from_bytes(key: &[u8]) -> Result<Self, PasskeyError> {
+        let (key, rest) = CborValue::decode(key)?;
+        if !rest.is_empty() {
+            return Err(PasskeyError::InvalidPublicKey);
+        }
+        Self::from_cbor(&key)
+    }
+
+    pub fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8]
+    ) -> Result<(), PasskeyError> {
+        let verification = match self {
+            CosePublicKey::Es256(point) => {
+                UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, point).verify(message, signature)
+            },
+            CosePublicKey::EdDsa(key) => {
+                UnparsedPublicKey::new(&ED25519, key).verify(message, signature)
+            },
+            CosePublicKey::Rs256 { n, e } => {
+                RsaPublicKeyComponents { n, e }.verify(&RSA_PKCS1_2048_8192_SHA256, message, signature)
+            },
+        };
+        verification.map_err(|_| PasskeyError::InvalidSignature)
+    }
+}
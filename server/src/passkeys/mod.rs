@@ -0,0 +1,118 @@
+mod cbor;
+mod cose;
+mod authenticator_data;
+mod client_data;
+mod ceremony;
+mod payload;
+mod access;
+
+pub(crate) mod tests;
+
+pub use ceremony::{
+    creation_options,
+    generate_challenge,
+    request_options,
+    verify_assertion,
+    verify_registration,
+    CreationOptions,
+    RequestOptions
+};
+pub use payload::{
+    AssertionResponse,
+    AttestationResponse,
+    PasskeyAuthenticationPayload,
+    PasskeyCredential,
+    PasskeyRegistrationPayload
+};
+pub use access::take_challenge;
+
+use data_encoding::BASE64URL_NOPAD;
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    auth::AuthError,
+    database::DatabaseError
+};
+
+#[derive(Debug, Error)]
+pub enum PasskeyError {
+    #[error("The challenge expired or was already answered")]
+    ChallengeNotFound,
+    #[error("Malformed CBOR")]
+    InvalidCbor,
+    #[error("Client data doesn't match the challenge, the ceremony or the allowed origins")]
+    InvalidClientData,
+    #[error("Malformed authenticator data, wrong RP id or the user wasn't verified")]
+    InvalidAuthenticatorData,
+    #[error("Malformed public key")]
+    InvalidPublicKey,
+    #[error("Unsupported public key algorithm")]
+    UnsupportedAlgorithm,
+    #[error("Only the none attestation format is supported")]
+    UnsupportedAttestation,
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Passkey not found")]
+    PasskeyNotFound,
+    #[error("Passkey is already registered")]
+    PasskeyAlreadyRegistered,
+    #[error("Signature counter didn't increase, the passkey was probably cloned")]
+    CloneDetected,
+    #[error("Malformed base64url")]
+    InvalidEncoding,
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error("Authentication error: {0:?}")]
+    AuthError(AuthError),
+}
+
+impl From<AuthError> for PasskeyError {
+    fn from(error: AuthError) -> Self {
+        PasskeyError::AuthError(error)
+    }
+}
+
+impl IntoResponse for PasskeyError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            PasskeyError::ChallengeNotFound => (StatusCode::BAD_REQUEST, "2300"),
+            PasskeyError::InvalidCbor => (StatusCode::BAD_REQUEST, "2301"),
+            PasskeyError::InvalidClientData => (StatusCode::BAD_REQUEST, "2302"),
+            PasskeyError::InvalidAuthenticatorData => (StatusCode::BAD_REQUEST, "2303"),
+            PasskeyError::InvalidPublicKey => (StatusCode::BAD_REQUEST, "2304"),
+            PasskeyError::UnsupportedAlgorithm => (StatusCode::BAD_REQUEST, "2305"),
+            PasskeyError::UnsupportedAttestation => (StatusCode::BAD_REQUEST, "2306"),
+            PasskeyError::InvalidSignature => (StatusCode::UNAUTHORIZED, "2307"),
+            PasskeyError::PasskeyNotFound => (StatusCode::NOT_FOUND, "2308"),
+            PasskeyError::PasskeyAlreadyRegistered => (StatusCode::CONFLICT, "2309"),
+            PasskeyError::CloneDetected => (StatusCode::UNAUTHORIZED, "2310"),
+            PasskeyError::InvalidEncoding => (StatusCode::BAD_REQUEST, "2311"),
+            PasskeyError::DatabaseError(e) => return e.into_response(),
+            PasskeyError::AuthError(e) => return e.into_response(),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
+
+/// WebAuthn encodes binary fields as base64url, browsers leave the padding out
+pub fn encode_base64url(data: &[u8]) -> String {
+    BASE64URL_NOPAD.encode(data)
+}
+
+pub fn decode_base64url(data: &str) -> Result<Vec<u8>, PasskeyError> {
+    BASE64URL_NOPAD
+        .decode(data.trim_end_matches('=').as_bytes())
+        .map_err(|_| PasskeyError::InvalidEncoding)
+}
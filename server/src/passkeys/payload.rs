@@ -0,0 +1,47 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// `PublicKeyCredential` as serialized by `toJSON()` in the browser, binary fields are base64url
+#[derive(Debug, Deserialize)]
+pub struct PasskeyCredential<R> {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub response: R,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "attestationObject")]
+    pub attestation_object: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    #[serde(rename = "authenticatorData")]
+    pub authenticator_data: String,
+    pub signature: String,
+    #[serde(rename = "userHandle", default)]
+    pub user_handle: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasskeyRegistrationPayload {
+    pub challenge_id: Uuid,
+    // Shown in the passkey list, defaults to "Passkey"
+    #[serde(default)]
+    pub label: Option<String>,
+    pub credential: PasskeyCredential<AttestationResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasskeyAuthenticationPayload {
+    pub challenge_id: Uuid,
+    pub credential: PasskeyCredential<AssertionResponse>,
+    #[serde(default)]
+    pub device_label: Option<String>,
+}
@@ -0,0 +1,386 @@
+#[cfg(test)]
+pub(crate) mod tests {
+    use ring::{
+        rand::SystemRandom,
+        signature::{
+            EcdsaKeyPair,
+            Ed25519KeyPair,
+            KeyPair,
+            ECDSA_P256_SHA256_ASN1_SIGNING
+        }
+    };
+    use sha2::{
+        Digest,
+        Sha256
+    };
+
+    use crate::{
+        app_objects::{
+            Passkey,
+            PasskeyCeremony,
+            PasskeyChallenge
+        },
+        configuration::PasskeysConfig,
+        passkeys::{
+            cbor::CborValue,
+            ceremony::check_sign_count,
+            cose::CosePublicKey,
+            encode_base64url,
+            verify_assertion,
+            verify_registration,
+            PasskeyError
+        }
+    };
+
+    use pretty_assertions::assert_eq;
+
+    fn cbor_head(major_type: u8, value: u64) -> Vec<u8> {
+        let major_type = major_type << 5;
+        match value {
+            0..=23 => vec![major_type | value as u8],
+            24..=0xff => vec![major_type | 24, value as u8],
+            0x100..=0xffff => [&[major_type | 25][..], &(value as u16).to_be_bytes()].concat(),
+            _ => [&[major_type | 26][..], &(value as u32).to_be_bytes()].concat(),
+        }
+    }
+
+    fn cbor_integer(value: i64) -> Vec<u8> {
+        match value {
+            0.. => cbor_head(0, value as u64),
+            _ => cbor_head(1, (-1 - value) as u64),
+        }
+    }
+
+    fn cbor_bytes(value: &[u8]) -> Vec<u8> {
+        [cbor_head(2, value.len() as u64), value.to_vec()].concat()
+    }
+
+    fn cbor_text(value: &str) -> Vec<u8> {
+        [cbor_head(3, value.len() as u64), value.as_bytes().to_vec()].concat()
+    }
+
+    fn cbor_map(entries: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+        let mut map = cbor_head(5, entries.len() as u64);
+        for (key, value) in entries {
+            map.extend_from_slice(key);
+            map.extend_from_slice(value);
+        }
+        map
+    }
+
+    enum AuthenticatorKey {
+        Es256(EcdsaKeyPair),
+        EdDsa(Ed25519KeyPair),
+    }
+
+    /// Stand-in for a platform authenticator or security key, produces the JSON a browser
+    /// would send for `navigator.credentials.create()` and `navigator.credentials.get()`
+    pub struct SoftwareAuthenticator {
+        key: AuthenticatorKey,
+        rng: SystemRandom,
+        pub credential_id: Vec<u8>,
+        pub rp_id: String,
+        pub origin: String,
+        // Authenticators that don't count signatures keep it at 0
+        pub sign_count: u32,
+        pub counting: bool,
+        pub user_verified: bool,
+        pub attestation_format: &'static str,
+    }
+
+    impl SoftwareAuthenticator {
+        pub fn new_es256(rp_id: &str, origin: &str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+            let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+            Self::new(AuthenticatorKey::Es256(key), rng, rp_id, origin)
+        }
+
+        pub fn new_ed25519(rp_id: &str, origin: &str) -> Self {
+            let rng = SystemRandom::new();
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+            let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+            Self::new(AuthenticatorKey::EdDsa(key), rng, rp_id, origin)
+        }
+
+        fn new(key: AuthenticatorKey, rng: SystemRandom, rp_id: &str, origin: &str) -> Self {
+            Self {
+                key,
+                rng,
+                credential_id: uuid::Uuid::new_v4().as_bytes().to_vec(),
+                rp_id: rp_id.to_string(),
+                origin: origin.to_string(),
+                sign_count: 0,
+                counting: true,
+                user_verified: true,
+                attestation_format: "none",
+            }
+        }
+
+        pub fn cose_key(&self) -> Vec<u8> {
+            match &self.key {
+                AuthenticatorKey::Es256(key) => {
+                    let point = key.public_key().as_ref();
+                    cbor_map(&[
+                        (cbor_integer(1), cbor_integer(2)),
+                        (cbor_integer(3), cbor_integer(-7)),
+                        (cbor_integer(-1), cbor_integer(1)),
+                        (cbor_integer(-2), cbor_bytes(&point[1..33])),
+                        (cbor_integer(-3), cbor_bytes(&point[33..])),
+                    ])
+                },
+                AuthenticatorKey::EdDsa(key) => cbor_map(&[
+                    (cbor_integer(1), cbor_integer(1)),
+                    (cbor_integer(3), cbor_integer(-8)),
+                    (cbor_integer(-1), cbor_integer(6)),
+                    (cbor_integer(-2), cbor_bytes(key.public_key().as_ref())),
+                ]),
+            }
+        }
+
+        fn client_data(&self, ceremony_type: &str, challenge: &[u8]) -> Vec<u8> {
+            serde_json::json!({
+                "type": ceremony_type,
+                "challenge": encode_base64url(challenge),
+                "origin": self.origin,
+                "crossOrigin": false
+            }).to_string().into_bytes()
+        }
+
+        fn authenticator_data(&self, attested: bool) -> Vec<u8> {
+            let mut flags = 0x01;
+            if self.user_verified {
+                flags |= 0x04;
+            }
+            if attested {
+                flags |= 0x40;
+            }
+            let mut data = Sha256::digest(self.rp_id.as_bytes()).to_vec();
+            data.push(flags);
+            data.extend_from_slice(&self.sign_count.to_be_bytes());
+            if attested {
+                data.extend_from_slice(&[0; 16]);
+                data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+                data.extend_from_slice(&self.credential_id);
+                data.extend_from_slice(&self.cose_key());
+            }
+            data
+        }
+
+        /// Credential JSON for the registration challenge
+        pub fn register(&self, challenge: &[u8]) -> serde_json::Value {
+            let attestation_object = cbor_map(&[
+                (cbor_text("fmt"), cbor_text(self.attestation_format)),
+                (cbor_text("attStmt"), cbor_map(&[])),
+                (cbor_text("authData"), cbor_bytes(&self.authenticator_data(true))),
+            ]);
+            serde_json::json!({
+                "id": encode_base64url(&self.credential_id),
+                "rawId": encode_base64url(&self.credential_id),
+                "type": "public-key",
+                "response": {
+                    "clientDataJSON": encode_base64url(&self.client_data("webauthn.create", challenge)),
+                    "attestationObject": encode_base64url(&attestation_object)
+                }
+            })
+        }
+
+        /// Assertion JSON for the login challenge, signed by the passkey of the user
+        pub fn authenticate(&mut self, challenge: &[u8], user_id: i64) -> serde_json::Value {
+            if self.counting {
+                self.sign_count += 1;
+            }
+            let authenticator_data = self.authenticator_data(false);
+            let client_data = self.client_data("webauthn.get", challenge);
+            let signed_data = [authenticator_data.as_slice(), &Sha256::digest(&client_data)].concat();
+            let signature = match &self.key {
+                AuthenticatorKey::Es256(key) => key.sign(&self.rng, &signed_data).unwrap().as_ref().to_vec(),
+                AuthenticatorKey::EdDsa(key) => key.sign(&signed_data).as_ref().to_vec(),
+            };
+            serde_json::json!({
+                "id": encode_base64url(&self.credential_id),
+                "rawId": encode_base64url(&self.credential_id),
+                "type": "public-key",
+                "response": {
+                    "clientDataJSON": encode_base64url(&client_data),
+                    "authenticatorData": encode_base64url(&authenticator_data),
+                    "signature": encode_base64url(&signature),
+                    "userHandle": encode_base64url(&user_id.to_be_bytes())
+                }
+            })
+        }
+    }
+
+    fn get_passkeys_config() -> PasskeysConfig {
+        PasskeysConfig {
+            rp_id: "example.com".to_string(),
+            rp_name: "Example".to_string(),
+            allowed_origins: vec!["https://example.com".to_string()],
+            challenge_ttl_s: 60,
+            timeout_ms: 60000,
+            max_label_length: 64,
+        }
+    }
+
+    fn challenge(ceremony: PasskeyCeremony) -> PasskeyChallenge {
+        PasskeyChallenge::new(ceremony, vec![7; 32], None, 0)
+    }
+
+    /// Registers the authenticator for user 1
+    fn register(authenticator: &SoftwareAuthenticator) -> Passkey {
+        let challenge = challenge(PasskeyCeremony::Registration);
+        let credential = serde_json::from_value(authenticator.register(&challenge.challenge)).unwrap();
+        let registration = verify_registration(&get_passkeys_config(), &challenge, &credential).unwrap();
+        assert_eq!(registration.credential_id, authenticator.credential_id);
+        assert_eq!(registration.public_key, authenticator.cose_key());
+        Passkey::new(registration.credential_id, 1, registration.public_key, registration.sign_count as i64, "key".to_string(), 0)
+    }
+
+    fn assert(authenticator: &mut SoftwareAuthenticator, passkey: &Passkey) -> Result<u32, PasskeyError> {
+        let challenge = challenge(PasskeyCeremony::Authentication);
+        let credential = serde_json::from_value(authenticator.authenticate(&challenge.challenge, passkey.user_id)).unwrap();
+        verify_assertion(&get_passkeys_config(), &challenge, passkey, &credential)
+    }
+
+    #[test]
+    fn test_cbor_decode() {
+        let data = [
+            cbor_map(&[
+                (cbor_integer(-257), cbor_bytes(&[1, 2])),
+                (cbor_text("list"), [cbor_head(4, 3), cbor_integer(500), vec![0xf5], vec![0xf6]].concat()),
+            ]),
+            vec![0xff]
+        ].concat();
+        let (value, rest) = CborValue::decode(&data).unwrap();
+        assert_eq!(rest, &[0xff]);
+        assert_eq!(value.map_get_integer(-257).and_then(CborValue::as_bytes), Some(&[1u8, 2][..]));
+        assert_eq!(
+            value.map_get_text("list"),
+            Some(&CborValue::Array(vec![CborValue::Integer(500), CborValue::Bool(true), CborValue::Null]))
+        );
+
+        // Truncated items, indefinite lengths and lengths past the end of the data
+        assert!(matches!(CborValue::decode(&cbor_bytes(&[1, 2, 3])[..3]), Err(PasskeyError::InvalidCbor)));
+        assert!(matches!(CborValue::decode(&[0x5f, 0x41, 0x00, 0xff]), Err(PasskeyError::InvalidCbor)));
+        assert!(matches!(CborValue::decode(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]), Err(PasskeyError::InvalidCbor)));
+        assert!(matches!(CborValue::decode(&[0x81; 32]), Err(PasskeyError::InvalidCbor)));
+    }
+
+    #[test]
+    fn test_register_and_authenticate() {
+        for mut authenticator in [
+            SoftwareAuthenticator::new_es256("example.com", "https://example.com"),
+            SoftwareAuthenticator::new_ed25519("example.com", "https://example.com"),
+        ] {
+            let mut passkey = register(&authenticator);
+            assert_eq!(assert(&mut authenticator, &passkey).unwrap(), 1);
+            passkey.sign_count = 1;
+            assert_eq!(assert(&mut authenticator, &passkey).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn test_registration_is_bound_to_the_relying_party() {
+        let config = get_passkeys_config();
+        let registration_challenge = challenge(PasskeyCeremony::Registration);
+        let verify = |authenticator: &SoftwareAuthenticator, challenge: &[u8]| {
+            let credential = serde_json::from_value(authenticator.register(challenge)).unwrap();
+            verify_registration(&config, &registration_challenge, &credential)
+        };
+
+        let phishing = SoftwareAuthenticator::new_es256("example.com", "https://examp1e.com");
+        assert!(matches!(verify(&phishing, &registration_challenge.challenge), Err(PasskeyError::InvalidClientData)));
+        let authenticator = SoftwareAuthenticator::new_es256("example.com", "https://example.com");
+        assert!(matches!(verify(&authenticator, &[8; 32]), Err(PasskeyError::InvalidClientData)));
+        let other_rp = SoftwareAuthenticator::new_es256("examp1e.com", "https://example.com");
+        assert!(matches!(verify(&other_rp, &registration_challenge.challenge), Err(PasskeyError::InvalidAuthenticatorData)));
+
+        let mut unverified = SoftwareAuthenticator::new_es256("example.com", "https://example.com");
+        unverified.user_verified = false;
+        assert!(matches!(verify(&unverified, &registration_challenge.challenge), Err(PasskeyError::InvalidAuthenticatorData)));
+        let mut attested = SoftwareAuthenticator::new_es256("example.com", "https://example.com");
+        attested.attestation_format = "packed";
+        assert!(matches!(verify(&attested, &registration_challenge.challenge), Err(PasskeyError::UnsupportedAttestation)));
+
+        // An assertion can't be used as a registration
+        let mut credential = authenticator.register(&registration_challenge.challenge);
+        credential["response"]["clientDataJSON"] = serde_json::Value::from(encode_base64url(
+            &authenticator.client_data("webauthn.get", &registration_challenge.challenge)
+        ));
+        let credential = serde_json::from_value(credential).unwrap();
+        assert!(matches!(verify_registration(&config, &registration_challenge, &credential), Err(PasskeyError::InvalidClientData)));
+    }
+
+    #[test]
+    fn test_assertion_checks() {
+        let mut authenticator = SoftwareAuthenticator::new_es256("example.com", "https://example.com");
+        let passkey = register(&authenticator);
+
+        // Signed by another key
+        let mut other = SoftwareAuthenticator::new_es256("example.com", "https://example.com");
+        other.credential_id = authenticator.credential_id.clone();
+        assert!(matches!(assert(&mut other, &passkey), Err(PasskeyError::InvalidSignature)));
+
+        // Passkey of another user
+        let mut other_user_passkey = passkey.clone();
+        other_user_passkey.user_id = 2;
+        let challenge = challenge(PasskeyCeremony::Authentication);
+        let credential = serde_json::from_value(authenticator.authenticate(&challenge.challenge, 1)).unwrap();
+        assert!(matches!(
+            verify_assertion(&get_passkeys_config(), &challenge, &other_user_passkey, &credential),
+            Err(PasskeyError::InvalidSignature)
+        ));
+
+        // The counter of a copy lags behind the stored one
+        let mut cloned_passkey = passkey.clone();
+        cloned_passkey.sign_count = 10;
+        assert!(matches!(assert(&mut authenticator, &cloned_passkey), Err(PasskeyError::CloneDetected)));
+
+        let mut not_counting = SoftwareAuthenticator::new_ed25519("example.com", "https://example.com");
+        not_counting.counting = false;
+        let passkey = register(&not_counting);
+        assert_eq!(assert(&mut not_counting, &passkey).unwrap(), 0);
+        assert_eq!(assert(&mut not_counting, &passkey).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_check_sign_count() {
+        assert!(check_sign_count(0, 0).is_ok());
+        assert!(check_sign_count(0, 1).is_ok());
+        assert!(check_sign_count(5, 6).is_ok());
+        assert!(matches!(check_sign_count(5, 5), Err(PasskeyError::CloneDetected)));
+        assert!(matches!(check_sign_count(5, 0), Err(PasskeyError::CloneDetected)));
+    }
+
+    #[test]
+    fn test_cose_keys() {
+        let authenticator = SoftwareAuthenticator::new_ed25519("example.com", "https://example.com");
+        assert!(matches!(CosePublicKey::from_bytes(&authenticator.cose_key()), Ok(CosePublicKey::EdDsa(_))));
+
+        let rsa_key = cbor_map(&[
+            (cbor_integer(1), cbor_integer(3)),
+            (cbor_integer(3), cbor_integer(-257)),
+            (cbor_integer(-1), cbor_bytes(&[0xab; 256])),
+            (cbor_integer(-2), cbor_bytes(&[1, 0, 1])),
+        ]);
+        assert_eq!(
+            CosePublicKey::from_bytes(&rsa_key).unwrap(),
+            CosePublicKey::Rs256 { n: vec![0xab; 256], e: vec![1, 0, 1] }
+        );
+
+        // ES384
+        let unsupported = cbor_map(&[
+            (cbor_integer(1), cbor_integer(2)),
+            (cbor_integer(3), cbor_integer(-35)),
+        ]);
+        assert!(matches!(CosePublicKey::from_bytes(&unsupported), Err(PasskeyError::UnsupportedAlgorithm)));
+        let wrong_curve = cbor_map(&[
+            (cbor_integer(1), cbor_integer(2)),
+            (cbor_integer(3), cbor_integer(-7)),
+            (cbor_integer(-1), cbor_integer(2)),
+            (cbor_integer(-2), cbor_bytes(&[0; 32])),
+            (cbor_integer(-3), cbor_bytes(&[0; 32])),
+        ]);
+        assert!(matches!(CosePublicKey::from_bytes(&wrong_curve), Err(PasskeyError::InvalidPublicKey)));
+    }
+}
@@ -0,0 +1,80 @@
+mod payload;
+mod token;
+
+mod tests;
+
+pub use payload::{
+    PasswordResetConfirmPayload,
+    PasswordResetRequestPayload
+};
+pub use token::{
+    create_password_reset_token,
+    take_password_reset_token
+};
+
+use serde_json::json;
+use axum::{
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use thiserror::Error;
+
+use crate::{
+    auth::VerificationError,
+    cloudflare::TurnstileError,
+    credentials::PasswordError,
+    database::DatabaseError,
+    email::EmailHandlerError
+};
+
+#[derive(Debug, Error)]
+pub enum PasswordResetError {
+    #[error("The password reset link is invalid, expired or was already used")]
+    InvalidToken,
+    #[error("Turnstile verification failed")]
+    TurnstileDenied,
+    #[error(transparent)]
+    PasswordError(#[from] PasswordError),
+    #[error(transparent)]
+    TurnstileError(#[from] TurnstileError),
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    EmailError(#[from] EmailHandlerError),
+    #[error("Failed to encode the password reset token: {0}")]
+    EncodingError(#[from] jsonwebtoken::errors::Error),
+}
+
+impl From<VerificationError> for PasswordResetError {
+    fn from(_: VerificationError) -> Self {
+        PasswordResetError::InvalidToken
+    }
+}
+
+impl IntoResponse for PasswordResetError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            PasswordResetError::InvalidToken => (StatusCode::BAD_REQUEST, "2400"),
+            PasswordResetError::TurnstileDenied => (StatusCode::FORBIDDEN, "2401"),
+            PasswordResetError::PasswordError(e) => {
+                let status = match e {
+                    PasswordError::HashError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                    _ => StatusCode::BAD_REQUEST,
+                };
+                (status, e.into_internal_error_code())
+            },
+            PasswordResetError::TurnstileError(e) => return e.into_response(),
+            PasswordResetError::DatabaseError(e) => return e.into_response(),
+            PasswordResetError::EmailError(e) => return e.into_response(),
+            PasswordResetError::EncodingError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "2402"),
+        };
+        let body = Json(json!({
+            "error": error_message,
+        }));
+        (status, body).into_response()
+    }
+}
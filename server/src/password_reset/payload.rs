@@ -0,0 +1,28 @@
+use axum::Form;
+use serde::{
+    Deserialize,
+    Serialize
+};
+
+use crate::cloudflare::GetTurnstileCode;
+
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PasswordResetRequestPayload {
+    pub email: String,
+    #[serde(rename = "cf-turnstile-response")]
+    cf_turnstile_response: String,
+}
+
+impl GetTurnstileCode for Form<PasswordResetRequestPayload> {
+    fn get_turnstile_code(&self) -> String {
+        self.cf_turnstile_response.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PasswordResetConfirmPayload {
+    // Token from the emailed link
+    pub token: String,
+    pub password: String,
+}
@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use crate::auth::{
+        AuthClaims,
+        JWTKeys
+    };
+    use crate::password_reset::{
+        create_password_reset_token,
+        take_password_reset_token,
+        PasswordResetError
+    };
+    use crate::routes::tests::preparation::{
+        get_config,
+        TestContext
+    };
+
+    #[tokio::test]
+    async fn test_password_reset_token_works_once() {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let db_client = TestContext::new().db_client();
+
+        let token = create_password_reset_token(&db_client, &jwt_keys, 60, 420).await.unwrap();
+        assert_eq!(take_password_reset_token(&db_client, &jwt_keys, &token).await.unwrap(), 420);
+
+        let res = take_password_reset_token(&db_client, &jwt_keys, &token).await;
+        assert!(matches!(res, Err(PasswordResetError::InvalidToken)));
+    }
+
+    #[tokio::test]
+    async fn test_other_tokens_are_not_password_reset_tokens() {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let db_client = TestContext::new().db_client();
+
+        let access_token = jwt_keys.encode(&AuthClaims::new_access(60, 420, None)).unwrap();
+        let res = take_password_reset_token(&db_client, &jwt_keys, &access_token).await;
+        assert!(matches!(res, Err(PasswordResetError::InvalidToken)));
+
+        let res = take_password_reset_token(&db_client, &jwt_keys, "not a token").await;
+        assert!(matches!(res, Err(PasswordResetError::InvalidToken)));
+    }
+}
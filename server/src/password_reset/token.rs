@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::{
+    auth::{
+        AuthClaims,
+        ClaimType,
+        JWTKeys
+    },
+    database::{
+        DatabaseClient,
+        PasswordResetStore
+    }
+};
+
+use super::PasswordResetError;
+
+
+/// Signs the token of a reset link. The link works once, its `jti` is kept in Redis until it's
+/// used or the token expires.
+pub async fn create_password_reset_token(
+    db_client: &DatabaseClient,
+    jwt_keys: &JWTKeys,
+    lifetime_s: i64,
+    user_id: i64
+) -> Result<String, PasswordResetError> {
+    let jti = Uuid::new_v4();
+    let claims = AuthClaims::new_password_reset(lifetime_s, user_id, jti);
+    let token = jwt_keys.encode(&claims)?;
+    db_client.create_password_reset(jti, user_id, lifetime_s.max(1) as u64).await?;
+    Ok(token)
+}
+
+/// Checks the token of a reset link and marks it as used, returns the user whose password is reset
+pub async fn take_password_reset_token(
+    db_client: &DatabaseClient,
+    jwt_keys: &JWTKeys,
+    token: &str
+) -> Result<i64, PasswordResetError> {
+    let claims: AuthClaims = jwt_keys.verify_token_and_return_claims(token).await?;
+    if claims.claim_type != ClaimType::PasswordReset {
+        return Err(PasswordResetError::InvalidToken);
+    }
+    let jti = claims.jti.ok_or(PasswordResetError::InvalidToken)?;
+
+    match db_client.take_password_reset(jti).await? {
+        Some(user_id) if user_id == claims.user_id => Ok(user_id),
+        _ => Err(PasswordResetError::InvalidToken),
+    }
+}
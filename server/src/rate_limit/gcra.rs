@@ -0,0 +1,68 @@
+/// Result of checking a request against a policy, carries everything the `RateLimit-*` headers need
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u64,
+    pub remaining: u64,
+    // Until the whole limit is available again
+    pub reset_ms: i64,
+    // Until the next request is allowed, 0 for allowed requests
+    pub retry_after_ms: i64,
+}
+
+impl RateLimitDecision {
+    /// `offset_ms` is the distance of the theoretical arrival time from now,
+    /// after the request for allowed ones and before it for rejected ones
+    pub fn from_offset(
+        allowed: bool,
+        offset_ms: i64,
+        limit: u64,
+        period_s: u64
+    ) -> Self {
+        let period_ms = period_s as i64 * 1000;
+        let emission_interval_ms = emission_interval_ms(limit, period_s);
+        if allowed {
+            RateLimitDecision {
+                allowed,
+                limit,
+                remaining: ((period_ms - offset_ms).max(0) / emission_interval_ms) as u64,
+                reset_ms: offset_ms,
+                retry_after_ms: 0,
+            }
+        } else {
+            RateLimitDecision {
+                allowed,
+                limit,
+                remaining: 0,
+                reset_ms: offset_ms,
+                retry_after_ms: (offset_ms + emission_interval_ms - period_ms).max(0),
+            }
+        }
+    }
+}
+
+/// Time one request occupies, `limit` of them fill the period
+pub fn emission_interval_ms(
+    limit: u64,
+    period_s: u64
+) -> i64 {
+    (period_s as i64 * 1000 / limit.max(1) as i64).max(1)
+}
+
+/// Generic cell rate algorithm, a token bucket that only has to remember the theoretical arrival time (TAT)
+/// of the next request. Returns the decision and the TAT to store, which is `None` for rejected requests.
+/// Mirrored by the Lua script of the Redis backend, both have to stay in sync.
+pub fn gcra(
+    tat_ms: Option<i64>,
+    now_ms: i64,
+    limit: u64,
+    period_s: u64
+) -> (RateLimitDecision, Option<i64>) {
+    let period_ms = period_s as i64 * 1000;
+    let tat_ms = tat_ms.unwrap_or(now_ms).max(now_ms);
+    let new_tat_ms = tat_ms + emission_interval_ms(limit, period_s);
+    if new_tat_ms - now_ms > period_ms {
+        return (RateLimitDecision::from_offset(false, tat_ms - now_ms, limit, period_s), None);
+    }
+    (RateLimitDecision::from_offset(true, new_tat_ms - now_ms, limit, period_s), Some(new_tat_ms))
+}
@@ -0,0 +1,42 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex
+    }
+};
+
+use super::{
+    gcra,
+    RateLimitDecision
+};
+
+// Past this many buckets the ones that are full again get dropped
+const CLEANUP_THRESHOLD: usize = 10_000;
+
+/// Keeps the TAT of every bucket in memory, only sees the requests of this server instance
+#[derive(Clone, Debug, Default)]
+pub struct InProcessRateLimiter {
+    buckets: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl InProcessRateLimiter {
+    pub fn check(
+        &self,
+        key: &str,
+        limit: u64,
+        period_s: u64
+    ) -> RateLimitDecision {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= CLEANUP_THRESHOLD {
+            buckets.retain(|_, tat_ms| *tat_ms > now_ms);
+        }
+
+        let (decision, new_tat_ms) = gcra(buckets.get(key).copied(), now_ms, limit, period_s);
+        if let Some(new_tat_ms) = new_tat_ms {
+            buckets.insert(key.to_string(), new_tat_ms);
+        }
+        decision
+    }
+}
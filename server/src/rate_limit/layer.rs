@@ -0,0 +1,225 @@
+use std::{
+    convert::Infallible,
+    task::{
+        Context,
+        Poll
+    }
+};
+
+use axum::{
+    extract::{
+        FromRequestParts,
+        MatchedPath,
+        Request
+    },
+    http::{
+        header::RETRY_AFTER,
+        request::Parts,
+        HeaderMap,
+        HeaderValue,
+        StatusCode
+    },
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use axum_client_ip::SecureClientIp;
+use futures_util::future::BoxFuture;
+use headers::{
+    Cookie,
+    HeaderMapExt
+};
+use tower::{
+    Layer,
+    Service
+};
+use tracing::{
+    error,
+    warn
+};
+
+use crate::{
+    auth::{
+        AuthClaims,
+        ClaimType,
+        JWTKeys
+    },
+    configuration::{
+        RateLimitKey,
+        RateLimitPolicyConfig
+    }
+};
+
+use super::{
+    RateLimitDecision,
+    RateLimiter
+};
+
+/// Applies the configured policies to the routes it wraps, has to be added with `Router::route_layer`
+/// so the matched route is known. Routes without a policy pass through untouched.
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter,
+    jwt_keys: JWTKeys,
+}
+
+impl RateLimitLayer {
+    pub fn new(
+        limiter: RateLimiter,
+        jwt_keys: JWTKeys
+    ) -> Self {
+        RateLimitLayer {
+            limiter,
+            jwt_keys,
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+            jwt_keys: self.jwt_keys.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: RateLimiter,
+    jwt_keys: JWTKeys,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // The clone isn't ready yet, keep the one `poll_ready` was called on
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+        let jwt_keys = self.jwt_keys.clone();
+
+        Box::pin(async move {
+            let policy = request.extensions()
+                .get::<MatchedPath>()
+                .and_then(|route| limiter.policy_for(route.as_str(), request.method()))
+                .cloned();
+            let Some(policy) = policy else {
+                return inner.call(request).await;
+            };
+
+            let (mut parts, body) = request.into_parts();
+            let Some(subject) = get_subject(&mut parts, &policy, &jwt_keys).await else {
+                // Counting them in one shared bucket would let anyone lock the others out
+                warn!("Rejected a request to {} without a client IP", policy.name);
+                return Ok((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "error": "2602",
+                    }))
+                ).into_response());
+            };
+            let decision = match limiter.check(&policy, &subject).await {
+                Ok(decision) => decision,
+                Err(err) => {
+                    // Rather let requests through than take the API down with the limiter
+                    error!("Rate limiter failed for {}: {}", policy.name, err);
+                    return inner.call(Request::from_parts(parts, body)).await;
+                }
+            };
+
+            if !decision.allowed {
+                warn!("Rate limit {} hit by {}", policy.name, subject);
+                let mut response = (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(serde_json::json!({
+                        "error": "2600",
+                        "retry_after": seconds(decision.retry_after_ms),
+                    }))
+                ).into_response();
+                response.headers_mut().insert(RETRY_AFTER, seconds(decision.retry_after_ms).into());
+                insert_rate_limit_headers(response.headers_mut(), &policy, &decision);
+                return Ok(response);
+            }
+
+            let mut response = inner.call(Request::from_parts(parts, body)).await?;
+            insert_rate_limit_headers(response.headers_mut(), &policy, &decision);
+            Ok(response)
+        })
+    }
+}
+
+/// Whose bucket the request counts against, `None` when it has to be counted per IP but the client IP is unknown.
+/// While requests from outside Cloudflare are allowed the IP is the peer address, see `routes::client_ip_source`
+async fn get_subject(
+    parts: &mut Parts,
+    policy: &RateLimitPolicyConfig,
+    jwt_keys: &JWTKeys
+) -> Option<String> {
+    if policy.key == RateLimitKey::Route {
+        return Some("route".to_string());
+    }
+    if policy.key == RateLimitKey::User {
+        if let Some(user_id) = get_user_id(parts, jwt_keys).await {
+            return Some(format!("user:{}", user_id));
+        }
+    }
+    let SecureClientIp(ip) = SecureClientIp::from_request_parts(parts, &()).await.ok()?;
+    Some(format!("ip:{}", ip))
+}
+
+/// The user of the access token, or of the refresh token for routes like `/refresh_token`
+async fn get_user_id(
+    parts: &Parts,
+    jwt_keys: &JWTKeys
+) -> Option<i64> {
+    let cookies = parts.headers.typed_get::<Cookie>()?;
+    for claim_type in [ClaimType::Access, ClaimType::Refresh] {
+        let Some(token) = cookies.get(claim_type.as_str()) else {
+            continue;
+        };
+        let claims = jwt_keys.verify_token_and_return_claims::<AuthClaims>(
+            &token.replace("Bearer ", "")
+        ).await;
+        if let Ok(claims) = claims {
+            if claims.valid_type(claim_type) {
+                return Some(claims.user_id);
+            }
+        }
+    }
+    None
+}
+
+fn seconds(ms: i64) -> i64 {
+    (ms + 999) / 1000
+}
+
+fn insert_rate_limit_headers(
+    headers: &mut HeaderMap,
+    policy: &RateLimitPolicyConfig,
+    decision: &RateLimitDecision
+) {
+    headers.insert("ratelimit-limit", decision.limit.into());
+    headers.insert("ratelimit-remaining", decision.remaining.into());
+    headers.insert("ratelimit-reset", seconds(decision.reset_ms).into());
+    if let Ok(policy_header) = HeaderValue::from_str(&format!("{};w={}", policy.limit, policy.period_s)) {
+        headers.insert("ratelimit-policy", policy_header);
+    }
+}
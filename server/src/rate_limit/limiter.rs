@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use axum::http::Method;
+use tracing::info;
+
+use crate::{
+    configuration::{
+        RateLimitBackend,
+        RateLimitConfig,
+        RateLimitPolicyConfig,
+        RedisDatabaseConfig
+    }
+};
+
+use super::{
+    in_process_backend::InProcessRateLimiter,
+    redis_backend::RedisRateLimiter,
+    RateLimitDecision,
+    RateLimitError
+};
+
+#[derive(Clone, Debug)]
+enum RateLimitStore {
+    InProcess(InProcessRateLimiter),
+    Redis(RedisRateLimiter),
+}
+
+/// Holds the configured policies and the buckets they count in
+#[derive(Clone, Debug)]
+pub struct RateLimiter {
+    store: RateLimitStore,
+    policies: Arc<Vec<RateLimitPolicyConfig>>,
+}
+
+impl RateLimiter {
+    /// Only the Redis backend connects to anything
+    pub async fn new(
+        rate_limit_config: &RateLimitConfig,
+        redis_config: &RedisDatabaseConfig
+    ) -> Result<Self, RateLimitError> {
+        let store = match rate_limit_config.backend {
+            RateLimitBackend::InProcess => {
+                info!("Using in-process rate limiter");
+                RateLimitStore::InProcess(InProcessRateLimiter::default())
+            },
+            RateLimitBackend::Redis => {
+                info!("Using Redis rate limiter");
+                let client = redis::Client::open(
+                    format!("redis://{}:{}", redis_config.host, redis_config.port)
+                )?;
+                let redis_con = client.get_multiplexed_async_connection().await?;
+                RateLimitStore::Redis(RedisRateLimiter::new(redis_con))
+            },
+        };
+        Ok(RateLimiter {
+            store,
+            policies: Arc::new(rate_limit_config.policies.clone()),
+        })
+    }
+
+    /// The first policy declared for the route and method, `route` is the path as registered in the router
+    pub fn policy_for(
+        &self,
+        route: &str,
+        method: &Method
+    ) -> Option<&RateLimitPolicyConfig> {
+        self.policies.iter().find(|policy| {
+            policy.route == route && (
+                policy.methods.is_empty() ||
+                policy.methods.iter().any(|allowed| allowed.eq_ignore_ascii_case(method.as_str()))
+            )
+        })
+    }
+
+    /// Counts a request of `subject` against the policy
+    pub async fn check(
+        &self,
+        policy: &RateLimitPolicyConfig,
+        subject: &str
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let key = format!("rate_limit:{}:{}", policy.name, subject);
+        match &self.store {
+            RateLimitStore::InProcess(store) => Ok(store.check(&key, policy.limit, policy.period_s)),
+            RateLimitStore::Redis(store) => store.check(&key, policy.limit, policy.period_s).await,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+mod gcra;
+mod limiter;
+mod in_process_backend;
+mod redis_backend;
+mod layer;
+
+mod tests;
+
+pub use gcra::{
+    gcra,
+    RateLimitDecision
+};
+pub use limiter::RateLimiter;
+pub use layer::RateLimitLayer;
+
+use thiserror::Error;
+use axum::response::IntoResponse;
+
+#[derive(Error, Debug)]
+pub enum RateLimitError {
+    #[error("Redis error: {0}")]
+    RedisError(#[from] redis::RedisError),
+}
+
+impl IntoResponse for RateLimitError {
+    fn into_response(self) -> axum::response::Response {
+        let error_message = self.into_internal_error_code();
+
+        axum::http::Response::builder()
+            .status(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            .body(error_message.into())
+            .unwrap()
+    }
+}
+
+impl RateLimitError {
+    pub fn into_internal_error_code(&self) -> &'static str {
+        match self {
+            RateLimitError::RedisError(_) => "2601",
+        }
+    }
+}
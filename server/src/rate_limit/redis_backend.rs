@@ -0,0 +1,60 @@
+use redis::{
+    aio::MultiplexedConnection,
+    Script
+};
+
+use super::{
+    gcra::emission_interval_ms,
+    RateLimitDecision,
+    RateLimitError
+};
+
+// Same as `gcra`, the time comes from Redis so the clocks of the server instances don't matter
+const GCRA_SCRIPT: &str = r"
+local now = redis.call('TIME')
+local now_ms = tonumber(now[1]) * 1000 + math.floor(tonumber(now[2]) / 1000)
+local emission_interval_ms = tonumber(ARGV[1])
+local period_ms = tonumber(ARGV[2])
+local tat_ms = tonumber(redis.call('GET', KEYS[1])) or now_ms
+if tat_ms < now_ms then
+    tat_ms = now_ms
+end
+local new_tat_ms = tat_ms + emission_interval_ms
+if new_tat_ms - now_ms > period_ms then
+    return {0, tat_ms - now_ms}
+end
+redis.call('SET', KEYS[1], new_tat_ms, 'PX', new_tat_ms - now_ms)
+return {1, new_tat_ms - now_ms}
+";
+
+/// Keeps the TAT of every bucket in Redis, shared by all server instances
+#[derive(Clone, Debug)]
+pub struct RedisRateLimiter {
+    redis_con: MultiplexedConnection,
+    script: Script,
+}
+
+impl RedisRateLimiter {
+    pub fn new(redis_con: MultiplexedConnection) -> Self {
+        RedisRateLimiter {
+            redis_con,
+            script: Script::new(GCRA_SCRIPT),
+        }
+    }
+
+    pub async fn check(
+        &self,
+        key: &str,
+        limit: u64,
+        period_s: u64
+    ) -> Result<RateLimitDecision, RateLimitError> {
+        let mut con = self.redis_con.clone();
+        let (allowed, offset_ms): (i64, i64) = self.script
+            .key(key)
+            .arg(emission_interval_ms(limit, period_s))
+            .arg(period_s * 1000)
+            .invoke_async(&mut con)
+            .await?;
+        Ok(RateLimitDecision::from_offset(allowed == 1, offset_ms, limit, period_s))
+    }
+}
@@ -0,0 +1,137 @@
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        configuration::{
+            RateLimitBackend,
+            RateLimitConfig,
+            RateLimitKey,
+            RateLimitPolicyConfig
+        },
+        rate_limit::{
+            gcra,
+            RateLimitDecision,
+            RateLimiter
+        },
+        routes::tests::preparation::get_config
+    };
+
+    fn get_policy(
+        name: &str,
+        route: &str,
+        methods: &[&str]
+    ) -> RateLimitPolicyConfig {
+        RateLimitPolicyConfig {
+            name: name.to_string(),
+            route: route.to_string(),
+            methods: methods.iter().map(|method| method.to_string()).collect(),
+            key: RateLimitKey::Ip,
+            limit: 5,
+            period_s: 60,
+        }
+    }
+
+    async fn get_limiter(
+        backend: RateLimitBackend,
+        policies: Vec<RateLimitPolicyConfig>
+    ) -> RateLimiter {
+        let rate_limit_config = RateLimitConfig {
+            backend,
+            policies,
+        };
+        RateLimiter::new(&rate_limit_config, &get_config().redis_database).await.unwrap()
+    }
+
+    #[test]
+    fn test_gcra_allows_a_burst_of_limit_requests() {
+        let now_ms = 1_000_000;
+        let mut tat_ms = None;
+        for remaining in (0..5).rev() {
+            let (decision, new_tat_ms) = gcra(tat_ms, now_ms, 5, 60);
+            assert_eq!(decision.allowed, true);
+            assert_eq!(decision.remaining, remaining);
+            tat_ms = new_tat_ms;
+        }
+        assert_eq!(tat_ms, Some(now_ms + 60_000));
+
+        let (decision, new_tat_ms) = gcra(tat_ms, now_ms, 5, 60);
+        assert_eq!(
+            decision,
+            RateLimitDecision {
+                allowed: false,
+                limit: 5,
+                remaining: 0,
+                reset_ms: 60_000,
+                retry_after_ms: 12_000,
+            }
+        );
+        assert_eq!(new_tat_ms, None);
+    }
+
+    #[test]
+    fn test_gcra_refills_one_request_per_emission_interval() {
+        let now_ms = 1_000_000;
+        let tat_ms = Some(now_ms + 60_000);
+        assert_eq!(gcra(tat_ms, now_ms + 11_999, 5, 60).0.allowed, false);
+
+        let (decision, new_tat_ms) = gcra(tat_ms, now_ms + 12_000, 5, 60);
+        assert_eq!(decision.allowed, true);
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(new_tat_ms, Some(now_ms + 72_000));
+
+        // A bucket that sat idle is full again
+        let (decision, _) = gcra(tat_ms, now_ms + 120_000, 5, 60);
+        assert_eq!(decision.remaining, 4);
+        assert_eq!(decision.reset_ms, 12_000);
+    }
+
+    #[tokio::test]
+    async fn test_policy_for() {
+        let limiter = get_limiter(
+            RateLimitBackend::InProcess,
+            vec![
+                get_policy("email_post", "/me/email", &["post"]),
+                get_policy("email", "/me/email", &[]),
+                get_policy("sessions", "/sessions/:session_id", &[]),
+            ]
+        ).await;
+
+        assert_eq!(limiter.policy_for("/me/email", &Method::POST).unwrap().name, "email_post");
+        assert_eq!(limiter.policy_for("/me/email", &Method::GET).unwrap().name, "email");
+        assert_eq!(limiter.policy_for("/sessions/:session_id", &Method::DELETE).unwrap().name, "sessions");
+        assert!(limiter.policy_for("/sessions/1", &Method::DELETE).is_none());
+        assert!(limiter.policy_for("/guilds", &Method::GET).is_none());
+    }
+
+    async fn check_limiter(backend: RateLimitBackend) {
+        let policy = get_policy("test_limiter", "/test", &[]);
+        let limiter = get_limiter(backend, vec![policy.clone()]).await;
+        let subject = format!("ip:{}", uuid::Uuid::new_v4());
+
+        for remaining in (0..5).rev() {
+            let decision = limiter.check(&policy, &subject).await.unwrap();
+            assert_eq!(decision.allowed, true);
+            assert_eq!(decision.remaining, remaining);
+        }
+        let decision = limiter.check(&policy, &subject).await.unwrap();
+        assert_eq!(decision.allowed, false);
+        assert!(decision.retry_after_ms > 11_000 && decision.retry_after_ms <= 12_000);
+
+        // Other subjects have their own bucket
+        let other_subject = format!("ip:{}", uuid::Uuid::new_v4());
+        let decision = limiter.check(&policy, &other_subject).await.unwrap();
+        assert_eq!(decision.allowed, true);
+    }
+
+    #[tokio::test]
+    async fn test_in_process_limiter() {
+        check_limiter(RateLimitBackend::InProcess).await;
+    }
+
+    #[tokio::test]
+    async fn test_redis_limiter() {
+        check_limiter(RateLimitBackend::Redis).await;
+    }
+}
@@ -1,5 +1,8 @@
 use axum::{
-    http::StatusCode,
+    http::{
+        header::RETRY_AFTER,
+        StatusCode
+    },
     response::{
         IntoResponse,
         Response
@@ -7,26 +10,75 @@ use axum::{
     Json
 };
 use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    auth::VerificationError,
+    cloudflare::TurnstileError,
+    database::DatabaseError,
+    email::EmailHandlerError
+};
 
 mod payload;
 mod user_form_for_jwt;
 mod extractor;
+mod verification;
 
-pub use payload::CredentialBasedRegistrationPayload;
+pub use payload::{
+    CredentialBasedRegistrationPayload,
+    ResendVerificationEmailPayload
+};
 pub use user_form_for_jwt::UserRegistrationFormJWT;
+pub use verification::{
+    enqueue_verification_email,
+    take_verification_token
+};
 
 
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Error)]
 pub enum RegistrationError {
+    #[error("Invalid body, cf-turnstile-response is required")]
     InvalidBody,
+    #[error("The verification link is invalid, expired or was already used")]
+    InvalidToken,
+    #[error("Turnstile verification failed")]
+    TurnstileDenied,
+    #[error("A verification email can be requested again in {0}s")]
+    ResendCooldown(i64),
+    #[error(transparent)]
+    TurnstileError(#[from] TurnstileError),
+    #[error(transparent)]
+    DatabaseError(#[from] DatabaseError),
+    #[error(transparent)]
+    EmailError(#[from] EmailHandlerError),
+    #[error("Failed to encode the verification token: {0}")]
+    EncodingError(#[from] jsonwebtoken::errors::Error),
+}
+
+impl From<VerificationError> for RegistrationError {
+    fn from(_: VerificationError) -> Self {
+        RegistrationError::InvalidToken
+    }
 }
 
 impl IntoResponse for RegistrationError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             RegistrationError::InvalidBody => (StatusCode::BAD_REQUEST, "Invalid body, cf-turnstile-response is required"),
-
+            RegistrationError::InvalidToken => (StatusCode::BAD_REQUEST, "2900"),
+            RegistrationError::TurnstileDenied => (StatusCode::FORBIDDEN, "2901"),
+            RegistrationError::ResendCooldown(retry_after) => {
+                let body = Json(json!({
+                    "error": "2902",
+                    "retry_after": retry_after,
+                }));
+                return (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, retry_after.to_string())], body).into_response();
+            },
+            RegistrationError::TurnstileError(e) => return e.into_response(),
+            RegistrationError::DatabaseError(e) => return e.into_response(),
+            RegistrationError::EmailError(e) => return e.into_response(),
+            RegistrationError::EncodingError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "2903"),
         };
         let body = Json(json!({
             "error": error_message,
@@ -8,8 +8,8 @@ use time::serde::rfc3339;
 use axum::Form;
 use crate::cloudflare::{GetTurnstileCode, TurnstileRequest};
 use crate::credentials::{Password, PasswordRequirements, SaltMode};
-use crate::registration::UserRegistrationFormJWT;
-// TODO - Implement OTP 2fa and add date of birth field to the db
+use crate::app_objects::PendingRegistration;
+// TODO - add date of birth field to the db
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct CredentialBasedRegistrationPayload {
@@ -49,10 +49,10 @@ impl IntoResponse for CredentialBasedRegistrationPayloadError {
 }
 
 impl CredentialBasedRegistrationPayload {
-    pub async fn into_jwt_form(
-        &self,
-        email_jwt_lifetime: i64
-    ) -> Result<UserRegistrationFormJWT, CredentialBasedRegistrationPayloadError> {
+    /// Hashes the password, nothing of the form but the hash is kept
+    pub async fn into_pending_registration(
+        &self
+    ) -> Result<PendingRegistration, CredentialBasedRegistrationPayloadError> {
         let password = Password::new(
             &self.password,
             &PasswordRequirements::no_requirements()
@@ -60,13 +60,25 @@ impl CredentialBasedRegistrationPayload {
         let date_of_birth: NaiveDate = self.date_of_birth.parse().map_err(
             |_| CredentialBasedRegistrationPayloadError::InvalidBody
         )?;
-        Ok(UserRegistrationFormJWT::new(
+        Ok(PendingRegistration::new(
             self.email.clone(),
             self.username.clone(),
             password.password_hash,
             password.salt,
-            date_of_birth,
-            chrono::Utc::now().timestamp() + email_jwt_lifetime
+            date_of_birth
         ))
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ResendVerificationEmailPayload {
+    pub email: String,
+    #[serde(rename = "cf-turnstile-response")]
+    cf_turnstile_response: String,
+}
+
+impl GetTurnstileCode for Form<ResendVerificationEmailPayload> {
+    fn get_turnstile_code(&self) -> String {
+        self.cf_turnstile_response.clone()
+    }
+}
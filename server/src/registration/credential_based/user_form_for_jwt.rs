@@ -1,15 +1,12 @@
-use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use crate::{app_objects::User, auth::{JWTKeys, VerificationError}};
+use crate::auth::{JWTKeys, VerificationError};
 
+/// Claims of a verification link, the registration itself waits server side until the link is used
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct UserRegistrationFormJWT {
-    pub email: String,
-    pub username: String,
-    pub password_hash: String,
-    pub password_salt: String,
-    pub date_of_birth: NaiveDate,
+    pub registration_id: Uuid,
 
     pub exp: i64,
 }
@@ -17,55 +14,24 @@ pub struct UserRegistrationFormJWT {
 
 impl UserRegistrationFormJWT {
     pub fn new(
-        email: String,
-        username: String,
-        password_hash: String,
-        password_salt: String,
-        date_of_birth: NaiveDate,
+        registration_id: Uuid,
         lifetime_s: i64,
     ) -> Self {
         Self {
-            email,
-            username,
-            password_hash,
-            password_salt,
-            date_of_birth,
+            registration_id,
             exp: chrono::Utc::now().timestamp() + lifetime_s,
         }
     }
 
-    pub fn into_user(
-        &self,
-    ) -> User {
-        User {
-            email: self.email.clone(),
-            username: self.username.clone(),
-            password_hash: self.password_hash.clone(),
-            salt: self.password_salt.clone(),
-            date_of_birth: self.date_of_birth,
-            verified: false,
-            banned: false,
-            created_at: chrono::Utc::now().timestamp(),
-            valid_refresh_token: None,
-            id: {
-                0
-            },
-        }
-    }
-
     pub fn from_jwt_token(
         token: &str,
         keys: &JWTKeys,
     ) -> Result<Self, VerificationError> {
-        let data = jsonwebtoken::decode::<Self>(
-            token,
-            &keys.decoding,
-            &jsonwebtoken::Validation::default(),
-        )?;
-        if data.claims.exp < chrono::Utc::now().timestamp() {
+        let claims = keys.decode::<Self>(token)?;
+        if claims.exp < chrono::Utc::now().timestamp() {
             return Err(VerificationError::ExpiredToken);
         }
-        Ok(data.claims)
+        Ok(claims)
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,52 @@
+use lettre::message::Mailbox;
+
+use crate::{
+    app_objects::PendingRegistration,
+    auth::JWTKeys,
+    database::{
+        DatabaseClient,
+        PendingRegistrationStore
+    },
+    email::{
+        idempotency_key,
+        EmailHandler
+    }
+};
+
+use super::{
+    RegistrationError,
+    UserRegistrationFormJWT
+};
+
+
+/// Signs a new verification link for the stored registration and enqueues it. Any number of links can be
+/// issued for one registration, the first one used creates the user.
+pub async fn enqueue_verification_email(
+    email_handler: &EmailHandler,
+    jwt_keys: &JWTKeys,
+    pending_registration: &PendingRegistration
+) -> Result<(), RegistrationError> {
+    let Ok(recipient) = pending_registration.email.parse::<Mailbox>() else {
+        return Ok(());
+    };
+    let claims = UserRegistrationFormJWT::new(
+        pending_registration.id,
+        email_handler.state.verification_email_state.email_verification_jwt_lifetime_s
+    );
+    let token = jwt_keys.encode(&claims)?;
+    let idempotency_key = idempotency_key("verification", &token);
+    let email = email_handler.create_email_verification_email(recipient, token)?;
+    email_handler.enqueue_email(idempotency_key, email).await?;
+    Ok(())
+}
+
+/// Checks the token of a verification link and takes its registration, so it's only verified once
+pub async fn take_verification_token(
+    db_client: &DatabaseClient,
+    jwt_keys: &JWTKeys,
+    token: &str
+) -> Result<PendingRegistration, RegistrationError> {
+    let claims = UserRegistrationFormJWT::from_jwt_token(token, jwt_keys)?;
+    db_client.take_pending_registration(claims.registration_id).await?
+        .ok_or(RegistrationError::InvalidToken)
+}
@@ -1,6 +1,10 @@
 mod credential_based;
 
 pub use credential_based::{
+    enqueue_verification_email,
+    take_verification_token,
     CredentialBasedRegistrationPayload,
+    RegistrationError,
+    ResendVerificationEmailPayload,
     UserRegistrationFormJWT
-};
\ No newline at end of file
+};
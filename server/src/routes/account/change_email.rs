@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use axum_client_ip::SecureClientIp;
+use email_address::EmailAddress;
+use lettre::message::Mailbox;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    account::{
+        create_email_change_token,
+        AccountError,
+        ChangeEmailPayload
+    },
+    app_objects::PendingEmailChange,
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        UserStore
+    },
+    email::idempotency_key,
+    state::{
+        AccountState,
+        AuthenticationState
+    }
+};
+
+use super::current_password::check_current_password_like_login;
+
+
+/// Sends a confirmation link to the new address and a notice to the old one, the email
+/// only changes once the link is used
+pub async fn change_email(
+    State(account_state): State<Arc<AccountState>>,
+    State(authentication_state): State<Arc<AuthenticationState>>,
+    claims: AuthClaims,
+    client_ip: Option<SecureClientIp>,
+    Json(payload): Json<ChangeEmailPayload>,
+) -> Result<Response, AccountError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &account_state.db_client;
+    let email_handler = &account_state.email_handler;
+    let user_id = claims.user_id;
+
+    check_current_password_like_login(
+        &authentication_state,
+        &account_state,
+        user_id,
+        &payload.password,
+        client_ip,
+        &request_id.to_string()
+    ).await?;
+
+    let new_email = payload.new_email.trim().to_string();
+    if !EmailAddress::is_valid(&new_email) {
+        return Err(AccountError::InvalidEmail);
+    }
+    let new_mailbox: Mailbox = new_email.parse().map_err(|_| AccountError::InvalidEmail)?;
+
+    let user = db_client.get_user_by_id(user_id).await?
+        .ok_or(DatabaseError::UserNotFound(user_id))?;
+    if user.email == new_email {
+        return Err(AccountError::InvalidEmail);
+    }
+    // Checked again when the change is confirmed, the address could be taken in the meantime
+    if db_client.get_user_id_by_email(&new_email).await?.is_some() {
+        return Err(AccountError::EmailAlreadyExists);
+    }
+
+    let email_change = PendingEmailChange::new(user_id, user.email.clone(), new_email.clone());
+    let token = create_email_change_token(
+        db_client,
+        &account_state.jwt_keys,
+        email_handler.state.email_change_email_state.email_change_jwt_lifetime_s,
+        &email_change
+    ).await?;
+    // Both emails belong to this change, the token tells them apart from the ones of other changes
+    let confirmation_key = idempotency_key("email_change_confirmation", &token);
+    let notice_key = idempotency_key("email_change_notice", &token);
+    let confirmation_email = email_handler.create_email_change_confirmation_email(new_mailbox, token)?;
+    email_handler.enqueue_email(confirmation_key, confirmation_email).await.map_err(
+        |e| {
+            error!("|{}| Error enqueueing the email change confirmation: {:?}", request_id, e);
+            e
+        }
+    )?;
+    info!("|{}| Email change of user {} requested", request_id, user_id);
+
+    // Addresses from before the validation was added may not parse
+    let Ok(old_mailbox) = user.email.parse::<Mailbox>() else {
+        return Ok(StatusCode::ACCEPTED.into_response());
+    };
+    let notice_email = email_handler.create_email_change_notice_email(old_mailbox, &new_email)?;
+    if let Err(e) = email_handler.enqueue_email(notice_key, notice_email).await {
+        error!("|{}| Error enqueueing the email change notice: {:?}", request_id, e);
+    }
+
+    Ok(StatusCode::ACCEPTED.into_response())
+}
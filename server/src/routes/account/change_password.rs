@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Json
+};
+use axum_client_ip::SecureClientIp;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    account::{
+        AccountError,
+        ChangePasswordPayload
+    },
+    auth::AuthClaims,
+    credentials::{
+        Password,
+        PasswordError,
+        SaltMode
+    },
+    database::{
+        CredentialStore,
+        SessionStore
+    },
+    event_bus::BusEvent,
+    state::{
+        AccountState,
+        AuthenticationState
+    }
+};
+
+use super::current_password::check_current_password_like_login;
+
+
+/// Replaces the password of the caller, every other device is logged out
+pub async fn change_password(
+    State(account_state): State<Arc<AccountState>>,
+    State(authentication_state): State<Arc<AuthenticationState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    client_ip: Option<SecureClientIp>,
+    Json(payload): Json<ChangePasswordPayload>,
+) -> Result<Response, AccountError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &account_state.db_client;
+    let user_id = claims.user_id;
+
+    check_current_password_like_login(
+        &authentication_state,
+        &account_state,
+        user_id,
+        &payload.current_password,
+        client_ip,
+        &request_id.to_string()
+    ).await?;
+
+    let password = Password::new(
+        &payload.new_password,
+        &account_state.password_requirements
+    );
+    password.check_if_password_is_valid_based_on_requirements()?;
+    let prepared_password = password.hash_and_salt_password(&SaltMode::Generate).await.map_err(
+        |e| PasswordError::HashError(e.to_string())
+    )?;
+    db_client.update_password_hash_and_salt(
+        user_id,
+        &prepared_password.password_hash,
+        &prepared_password.salt
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error updating the password of user {}: {:?}", request_id, user_id, e);
+            e
+        }
+    )?;
+
+    // Tokens without a session can't be told apart from the other devices
+    let db_res = match claims.sid {
+        Some(session_id) => db_client.delete_other_user_sessions(user_id, session_id).await,
+        None => db_client.delete_user_sessions(user_id).await,
+    };
+    db_res.map_err(
+        |e| {
+            error!("|{}| Error revoking the sessions of user {}: {:?}", request_id, user_id, e);
+            e
+        }
+    )?;
+    info!("|{}| Password of user {} changed, other sessions revoked", request_id, user_id);
+    if let Err(e) = account_state.event_bus.publish(
+        BusEvent::SessionsRevoked { user_id, except_session_id: claims.sid }
+    ).await {
+        error!("|{}| Error publishing the revoked sessions of user {}: {:?}", request_id, user_id, e);
+    }
+
+    account_state.audit_log.record(
+        AuditEvent::new(AuditEventType::PasswordChange, Some(user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
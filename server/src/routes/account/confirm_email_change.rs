@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Form
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    account::{
+        take_email_change_token,
+        AccountError,
+        ConfirmEmailChangePayload
+    },
+    database::UserStore,
+    state::AccountState
+};
+
+
+/// Commits an email change from the link sent to the new address
+pub async fn confirm_email_change(
+    State(account_state): State<Arc<AccountState>>,
+    audit_client: AuditClient,
+    Form(payload): Form<ConfirmEmailChangePayload>,
+) -> Result<Response, AccountError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &account_state.db_client;
+
+    let email_change = take_email_change_token(
+        db_client,
+        &account_state.jwt_keys,
+        &payload.token
+    ).await?;
+    let user_id = email_change.user_id;
+
+    let updated = db_client.update_user_email(
+        user_id,
+        &email_change.old_email,
+        &email_change.new_email
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error changing the email of user {}: {:?}", request_id, user_id, e);
+            e
+        }
+    )?;
+    // The email was changed again or the user was deleted since the link was sent
+    if !updated {
+        return Err(AccountError::InvalidToken);
+    }
+    info!("|{}| Email of user {} changed", request_id, user_id);
+
+    account_state.audit_log.record(
+        AuditEvent::new(AuditEventType::EmailChange, Some(user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+            .with_metadata(json!({
+                "old_email": email_change.old_email,
+                "new_email": email_change.new_email,
+            }))
+    );
+    Ok(
+        (StatusCode::OK, "Email changed").into_response()
+    )
+}
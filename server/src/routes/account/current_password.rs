@@ -0,0 +1,68 @@
+use axum_client_ip::SecureClientIp;
+
+use crate::{
+    account::{
+        check_current_password,
+        AccountError
+    },
+    auth::AuthError,
+    database::{
+        DatabaseError,
+        UserStore
+    },
+    login_protection::LoginSubject,
+    routes::authenticate::{
+        check_lockout,
+        login_failed
+    },
+    state::{
+        AccountState,
+        AuthenticationState
+    }
+};
+
+
+/// Checks the current password like a login, wrong ones count for the email and IP of the caller and
+/// a lockout rejects the request, so a stolen access token can't be used to guess the password
+pub(super) async fn check_current_password_like_login(
+    authentication_state: &AuthenticationState,
+    account_state: &AccountState,
+    user_id: i64,
+    password: &str,
+    client_ip: Option<SecureClientIp>,
+    request_id: &str
+) -> Result<(), AccountError> {
+    let user = account_state.db_client.get_user_by_id(user_id).await?
+        .ok_or(DatabaseError::UserNotFound(user_id))?;
+    let login_subjects = LoginSubject::for_login(
+        &user.email,
+        client_ip.map(|SecureClientIp(ip)| ip),
+        authentication_state.client_ip_is_trusted
+    );
+    check_lockout(authentication_state, &login_subjects, request_id).await
+        .map_err(AccountError::LoginProtection)?;
+
+    match check_current_password(
+        &account_state.db_client,
+        &account_state.password_requirements,
+        user_id,
+        password
+    ).await {
+        Err(AccountError::WrongPassword) => {
+            let now = chrono::Utc::now().timestamp();
+            let failure = login_failed(
+                authentication_state,
+                &login_subjects,
+                Some(&user.email),
+                now,
+                request_id,
+                AuthError::WrongCredentials
+            ).await;
+            match failure {
+                AuthError::WrongCredentials => Err(AccountError::WrongPassword),
+                e => Err(AccountError::LoginProtection(e)),
+            }
+        },
+        res => res,
+    }
+}
@@ -0,0 +1,10 @@
+mod current_password;
+mod change_password;
+mod change_email;
+mod confirm_email_change;
+mod security_log;
+
+pub use change_password::change_password;
+pub use change_email::change_email;
+pub use confirm_email_change::confirm_email_change;
+pub use security_log::get_security_log;
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Query,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::AuditEvent,
+    audit::{
+        AuditError,
+        SecurityLogQuery
+    },
+    auth::AuthClaims,
+    database::AuditEventStore,
+    state::AccountState
+};
+
+
+/// Pages through the security events of the caller, newest first. Events are written in the
+/// background, so the last few seconds may be missing.
+pub async fn get_security_log(
+    State(account_state): State<Arc<AccountState>>,
+    claims: AuthClaims,
+    Query(query): Query<SecurityLogQuery>,
+) -> Result<Json<Vec<AuditEvent>>, AuditError> {
+    let limit = query.validated_limit(
+        account_state.audit_config.default_page_size,
+        account_state.audit_config.max_page_size
+    )?;
+    let audit_events = account_state.db_client.get_user_audit_events(
+        claims.user_id,
+        query.before,
+        limit
+    ).await.map_err(
+        |e| {
+            error!("Error fetching the security log of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+    Ok(Json(audit_events))
+}
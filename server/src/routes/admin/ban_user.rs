@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode,
+    Json
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError,
+        BanPayload
+    },
+    app_objects::{
+        AuditEvent,
+        AuditEventType,
+        UserBan
+    },
+    auth::AdminClaims,
+    database::BanStore,
+    event_bus::BusEvent,
+    state::AdminState
+};
+
+
+/// Bans a user, a second ban replaces the reason and the expiry of the first one.
+/// The user's gateway connections are closed, access tokens stop working through the denylist.
+pub async fn ban_user(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+    Json(payload): Json<BanPayload>,
+) -> Result<StatusCode, AdminError> {
+    if user_id == claims.user_id {
+        return Err(AdminError::CannotModerateSelf);
+    }
+    let now = chrono::Utc::now().timestamp();
+    let (reason, expires_at) = payload.validated(
+        now,
+        admin_state.admin_config.max_ban_reason_length
+    )?;
+
+    let user_ban = UserBan::new(user_id, reason, now, expires_at);
+    admin_state.db_client.ban_user(&user_ban).await.map_err(
+        |e| {
+            error!("Error banning user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    info!("User {} banned by admin {}", user_id, claims.user_id);
+
+    record_admin_action(
+        &admin_state.db_client,
+        claims.user_id,
+        AdminAction::BanUser,
+        Some(user_id),
+        json!({
+            "reason": user_ban.reason,
+            "expires_at": user_ban.expires_at,
+        })
+    ).await?;
+
+    admin_state.audit_log.record(
+        AuditEvent::new(AuditEventType::UserBanned, Some(user_id))
+            .with_actor(Some(claims.user_id))
+            .with_metadata(json!({
+                "reason": user_ban.reason,
+                "expires_at": user_ban.expires_at,
+            }))
+    );
+
+    // The ban is already stored, sessions that miss the event are rejected at their next refresh
+    if let Err(e) = admin_state.event_bus.publish(BusEvent::UserBanned { user_id }).await {
+        error!("Error publishing the ban of user {}: {:?}", user_id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Lifts the ban of a user, unbanning a user that isn't banned does nothing
+pub async fn unban_user(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, AdminError> {
+    let unbanned = admin_state.db_client.unban_user(user_id).await.map_err(
+        |e| {
+            error!("Error unbanning user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    if unbanned {
+        info!("User {} unbanned by admin {}", user_id, claims.user_id);
+        admin_state.audit_log.record(
+            AuditEvent::new(AuditEventType::UserUnbanned, Some(user_id))
+                .with_actor(Some(claims.user_id))
+        );
+    }
+
+    record_admin_action(
+        &admin_state.db_client,
+        claims.user_id,
+        AdminAction::UnbanUser,
+        Some(user_id),
+        json!({
+            "was_banned": unbanned,
+        })
+    ).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError
+    },
+    auth::AdminClaims,
+    database::{
+        DatabaseError,
+        UserStore
+    },
+    event_bus::BusEvent,
+    state::AdminState
+};
+
+
+/// Deletes a user with everything that references them, their tokens stop working right away
+pub async fn delete_user(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, AdminError> {
+    if user_id == claims.user_id {
+        return Err(AdminError::CannotModerateSelf);
+    }
+    let db_client = &admin_state.db_client;
+    // Kept in the audit log, the row is gone afterwards
+    let user = db_client.get_user_by_id(user_id).await?
+        .ok_or(DatabaseError::UserNotFound(user_id))?;
+
+    db_client.delete_user(user_id).await.map_err(
+        |e| {
+            error!("Error deleting user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    info!("User {} deleted by admin {}", user_id, claims.user_id);
+
+    record_admin_action(
+        db_client,
+        claims.user_id,
+        AdminAction::DeleteUser,
+        Some(user_id),
+        json!({
+            "email": user.email,
+            "username": user.username,
+        })
+    ).await?;
+
+    if let Err(e) = admin_state.event_bus.publish(
+        BusEvent::SessionsRevoked { user_id, except_session_id: None }
+    ).await {
+        error!("Error publishing the revoked sessions of user {}: {:?}", user_id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
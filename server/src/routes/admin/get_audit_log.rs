@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Query,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    admin::{
+        AdminError,
+        AuditLogQuery
+    },
+    app_objects::AdminAuditEntry,
+    auth::AdminClaims,
+    database::AdminAuditStore,
+    state::AdminState
+};
+
+
+/// Pages through the audit log, newest entries first
+pub async fn get_audit_log(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Json<Vec<AdminAuditEntry>>, AdminError> {
+    let limit = query.validated_limit(
+        admin_state.admin_config.default_page_size,
+        admin_state.admin_config.max_page_size
+    )?;
+    let entries = admin_state.db_client.get_admin_audit_entries(
+        query.target_user_id,
+        query.before,
+        limit
+    ).await.map_err(
+        |e| {
+            error!("Error fetching the audit log for admin {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+    Ok(Json(entries))
+}
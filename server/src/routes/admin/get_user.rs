@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use serde_json::json;
+use tracing::error;
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError,
+        AdminUserBody
+    },
+    auth::AdminClaims,
+    database::{
+        BanStore,
+        DatabaseError,
+        UserStore
+    },
+    state::AdminState
+};
+
+
+/// Returns a user together with the details of their ban
+pub async fn get_user(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<Json<AdminUserBody>, AdminError> {
+    let db_client = &admin_state.db_client;
+    let user = db_client.get_user_by_id(user_id).await.map_err(
+        |e| {
+            error!("Error fetching user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?.ok_or(DatabaseError::UserNotFound(user_id))?;
+    let ban = match user.banned {
+        true => db_client.get_user_ban(user_id).await?,
+        false => None,
+    };
+
+    record_admin_action(
+        db_client,
+        claims.user_id,
+        AdminAction::ViewUser,
+        Some(user_id),
+        json!({})
+    ).await?;
+
+    Ok(Json(AdminUserBody::new(user, ban)))
+}
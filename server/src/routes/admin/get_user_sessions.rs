@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use serde_json::json;
+use tracing::error;
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError
+    },
+    app_objects::Session,
+    auth::AdminClaims,
+    database::{
+        DatabaseError,
+        SessionStore,
+        UserStore
+    },
+    state::AdminState
+};
+
+
+/// Lists the devices a user is logged in on
+pub async fn get_user_sessions(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<Json<Vec<Session>>, AdminError> {
+    let db_client = &admin_state.db_client;
+    // An unknown user would look like one without sessions
+    db_client.get_user_by_id(user_id).await?
+        .ok_or(DatabaseError::UserNotFound(user_id))?;
+
+    let now = chrono::Utc::now().timestamp();
+    let sessions = db_client.get_user_sessions(user_id, now).await.map_err(
+        |e| {
+            error!("Error fetching sessions of user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+
+    record_admin_action(
+        db_client,
+        claims.user_id,
+        AdminAction::ViewSessions,
+        Some(user_id),
+        json!({})
+    ).await?;
+
+    Ok(Json(sessions))
+}
@@ -0,0 +1,20 @@
+mod search_users;
+mod get_user;
+mod get_user_sessions;
+mod ban_user;
+mod verify_user;
+mod reset_password;
+mod delete_user;
+mod get_audit_log;
+
+pub use search_users::search_users;
+pub use get_user::get_user;
+pub use get_user_sessions::get_user_sessions;
+pub use ban_user::{
+    ban_user,
+    unban_user
+};
+pub use verify_user::verify_user;
+pub use reset_password::reset_password;
+pub use delete_user::delete_user;
+pub use get_audit_log::get_audit_log;
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use rand::{
+    distributions::Alphanumeric,
+    Rng
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError
+    },
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    auth::AdminClaims,
+    credentials::{
+        Password,
+        PasswordError,
+        SaltMode
+    },
+    database::{
+        CredentialStore,
+        DatabaseError,
+        SessionStore,
+        UserStore
+    },
+    email::idempotency_key,
+    event_bus::BusEvent,
+    password_reset::create_password_reset_token,
+    state::AdminState
+};
+
+const SCRAMBLED_PASSWORD_LENGTH: usize = 64;
+
+/// Replaces the password of a user with one nobody knows and logs them out everywhere,
+/// then emails them a reset link. The email is sent in the background.
+pub async fn reset_password(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, AdminError> {
+    if user_id == claims.user_id {
+        return Err(AdminError::CannotModerateSelf);
+    }
+    let db_client = &admin_state.db_client;
+    let user = db_client.get_user_by_id(user_id).await?
+        .ok_or(DatabaseError::UserNotFound(user_id))?;
+
+    let scrambled_password: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(SCRAMBLED_PASSWORD_LENGTH)
+        .map(char::from)
+        .collect();
+    let prepared_password = Password::new(
+        &scrambled_password,
+        &admin_state.password_requirements
+    ).hash_and_salt_password(&SaltMode::Generate).await.map_err(
+        |e| PasswordError::HashError(e.to_string())
+    )?;
+    db_client.update_password_hash_and_salt(
+        user_id,
+        &prepared_password.password_hash,
+        &prepared_password.salt
+    ).await.map_err(
+        |e| {
+            error!("Error scrambling the password of user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    db_client.delete_user_sessions(user_id).await.map_err(
+        |e| {
+            error!("Error revoking the sessions of user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    info!("Password of user {} reset by admin {}", user_id, claims.user_id);
+
+    record_admin_action(
+        db_client,
+        claims.user_id,
+        AdminAction::ResetPassword,
+        Some(user_id),
+        json!({})
+    ).await?;
+
+    admin_state.audit_log.record(
+        AuditEvent::new(AuditEventType::PasswordReset, Some(user_id))
+            .with_actor(Some(claims.user_id))
+    );
+
+    if let Err(e) = admin_state.event_bus.publish(
+        BusEvent::SessionsRevoked { user_id, except_session_id: None }
+    ).await {
+        error!("Error publishing the revoked sessions of user {}: {:?}", user_id, e);
+    }
+
+    let admin_state = admin_state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = send_password_reset_email(&admin_state, user_id, &user.email).await {
+            error!("Error sending the password reset email to user {}: {:?}", user_id, e);
+        }
+    });
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn send_password_reset_email(
+    admin_state: &AdminState,
+    user_id: i64,
+    email: &str
+) -> Result<(), AdminError> {
+    // Addresses from before the validation was added may not parse
+    let Ok(recipient) = email.parse() else {
+        return Ok(());
+    };
+    let email_handler = &admin_state.email_handler;
+    let token = create_password_reset_token(
+        &admin_state.db_client,
+        &admin_state.jwt_keys,
+        email_handler.state.password_reset_email_state.password_reset_jwt_lifetime_s,
+        user_id
+    ).await?;
+    let idempotency_key = idempotency_key("password_reset", &token);
+    let message = email_handler.create_password_reset_email(recipient, token)?;
+    email_handler.enqueue_email(idempotency_key, message).await?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Query,
+        State
+    },
+    Json
+};
+use serde_json::json;
+use tracing::error;
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError,
+        AdminUserBody,
+        SearchUsersQuery
+    },
+    auth::AdminClaims,
+    database::UserStore,
+    state::AdminState
+};
+
+
+/// Finds users by id, email prefix or username prefix, ordered by id
+pub async fn search_users(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Query(query): Query<SearchUsersQuery>,
+) -> Result<Json<Vec<AdminUserBody>>, AdminError> {
+    let (email_pattern, username_pattern) = query.validated_patterns()?;
+    let limit = query.validated_limit(
+        admin_state.admin_config.default_page_size,
+        admin_state.admin_config.max_page_size
+    )?;
+
+    let users = admin_state.db_client.search_users(
+        query.id,
+        email_pattern.as_deref(),
+        username_pattern.as_deref(),
+        limit
+    ).await.map_err(
+        |e| {
+            error!("Error searching users for admin {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    record_admin_action(
+        &admin_state.db_client,
+        claims.user_id,
+        AdminAction::SearchUsers,
+        None,
+        json!({
+            "id": query.id,
+            "email": query.email,
+            "username": query.username,
+            "results": users.len(),
+        })
+    ).await?;
+
+    let users = users
+        .into_iter()
+        .map(|user| AdminUserBody::new(user, None))
+        .collect();
+    Ok(Json(users))
+}
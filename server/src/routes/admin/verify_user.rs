@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+
+use crate::{
+    admin::{
+        record_admin_action,
+        AdminAction,
+        AdminError
+    },
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    auth::AdminClaims,
+    database::UserStore,
+    state::AdminState
+};
+
+
+/// Marks the email of a user as verified
+pub async fn verify_user(
+    State(admin_state): State<Arc<AdminState>>,
+    AdminClaims(claims): AdminClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, AdminError> {
+    admin_state.db_client.set_user_verified(user_id).await.map_err(
+        |e| {
+            error!("Error verifying user {} for admin {}: {:?}", user_id, claims.user_id, e);
+            e
+        }
+    )?;
+    info!("User {} verified by admin {}", user_id, claims.user_id);
+
+    record_admin_action(
+        &admin_state.db_client,
+        claims.user_id,
+        AdminAction::VerifyUser,
+        Some(user_id),
+        json!({})
+    ).await?;
+
+    admin_state.audit_log.record(
+        AuditEvent::new(AuditEventType::EmailVerified, Some(user_id))
+            .with_actor(Some(claims.user_id))
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
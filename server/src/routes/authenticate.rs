@@ -1,13 +1,39 @@
 // TODO: https://github.com/tokio-rs/axum/blob/main/examples/jwt/src/main.rs
 
 use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType,
+        RefreshToken,
+        Session
+    },
+    audit::AuditClient,
     auth::{
+        check_account_status,
         AuthError,
         AuthenticationBody,
         AuthenticationPayload,
         ClaimType,
         AuthClaims,
-    }, credentials::Password, state::AuthenticationState
+        MfaChallengeBody,
+    },
+    cloudflare::TurnstileResult,
+    credentials::Password,
+    database::{
+        CredentialStore,
+        LoginAttemptStore,
+        MfaStore,
+        SessionStore,
+        UserStore
+    },
+    email::idempotency_key,
+    login_protection::{
+        get_lockout,
+        record_login_failure,
+        requires_turnstile,
+        LoginSubject
+    },
+    state::AuthenticationState
 };
 
 use axum::{
@@ -16,17 +42,21 @@ use axum::{
         HeaderMap,
         HeaderValue
     },
-    response::IntoResponse,
+    response::{
+        IntoResponse,
+        Response
+    },
     Json
 };
-use jsonwebtoken::{
-    encode,
-    Header
-};
+use axum_client_ip::SecureClientIp;
+use axum_extra::TypedHeader;
+use headers::UserAgent;
 use reqwest::header::SET_COOKIE;
+use serde_json::json;
 use tracing::{
     error,
-    info
+    info,
+    warn
 };
 use uuid::Uuid;
 
@@ -36,13 +66,24 @@ use std::sync::Arc;
 
 pub async fn authenticate(
     State(authentication_state): State<Arc<AuthenticationState>>,
+    client_ip: Option<SecureClientIp>,
+    user_agent: Option<TypedHeader<UserAgent>>,
     Json(payload): Json<AuthenticationPayload>,
-) -> Result<impl IntoResponse, AuthError> {
+) -> Result<Response, AuthError> {
     let request_id = Uuid::new_v4().to_string();
     info!("request_id: {}, authenticating user", request_id);
 
+    // Failed logins are counted per email and per IP, every attempt runs argon2 so they have to be limited
+    let now = chrono::Utc::now().timestamp();
+    let login_subjects = LoginSubject::for_login(
+        &payload.email,
+        client_ip.as_ref().map(|SecureClientIp(ip)| *ip),
+        authentication_state.client_ip_is_trusted
+    );
+    check_login_protection(&authentication_state, &payload, &login_subjects, now, &request_id).await?;
+
     // Check if email exists in the db
-    let db_res = authentication_state.db_client.cached_get_user_id_by_email(&payload.email).await;
+    let db_res = authentication_state.db_client.get_user_id_by_email(&payload.email).await;
     //info!("user_id: {:?}", user_id);
     if db_res.is_err() {
         let db_error = db_res.unwrap_err();
@@ -51,14 +92,23 @@ pub async fn authenticate(
         return Err(error);
     }
 
+    let audit_client = AuditClient::new(client_ip.as_ref(), user_agent.as_ref());
     let user_id = db_res.unwrap();
     if user_id.is_none() {
-        return Err(AuthError::WrongCredentials);
+        record_login_failure_event(&authentication_state, None, &audit_client, "unknown_email");
+        return Err(login_failed(
+            &authentication_state,
+            &login_subjects,
+            None,
+            now,
+            &request_id,
+            AuthError::WrongCredentials
+        ).await);
     }
     info!("request_id: {}, user with id: {:?} found", request_id, user_id);
 
     let user_id = user_id.unwrap();
-    let db_res = authentication_state.db_client.cached_get_password_hash_and_salt_by_user_id(user_id).await;
+    let db_res = authentication_state.db_client.get_password_hash_and_salt_by_user_id(user_id).await;
     if db_res.is_err() {
         let db_error = db_res.unwrap_err();
         error!("request_id: {}, db_error: {:?}", request_id, db_error);
@@ -87,23 +137,280 @@ pub async fn authenticate(
     let valid = match_result.unwrap();
 
     if !valid {
-        return Err(AuthError::WrongCredentials);
+        record_login_failure_event(&authentication_state, Some(user_id), &audit_client, "wrong_password");
+        return Err(login_failed(
+            &authentication_state,
+            &login_subjects,
+            Some(&payload.email),
+            now,
+            &request_id,
+            AuthError::WrongCredentials
+        ).await);
     }
     info!("request_id: {}, password matches hash", request_id);
 
-    // Get password hash from the db
+    // Users with a second factor get a short lived token instead, /authenticate/mfa exchanges it for a session
+    let db_res = authentication_state.db_client.get_user_mfa(user_id).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        let error = db_error.to_auth_error();
+        return Err(error);
+    }
+    if db_res.unwrap().is_some_and(|user_mfa| user_mfa.is_enabled()) {
+        let claims = AuthClaims::new_mfa_pending(
+            authentication_state.mfa_config.pending_token_lifetime_s,
+            user_id,
+            Uuid::new_v4()
+        );
+        let mfa_token = authentication_state.jwt_keys.encode(&claims);
+        if mfa_token.is_err() {
+            let error = mfa_token.unwrap_err();
+            error!("request_id: {}, jwt error: {:?}", request_id, error);
+            return Err(AuthError::TokenCreation);
+        }
+        // The failures of the email are only cleared once the second factor is checked too,
+        // otherwise knowing the password would reset the lockout of /authenticate/mfa
+        info!("request_id: {}, second factor required", request_id);
+        return Ok(Json(MfaChallengeBody::new(mfa_token.unwrap())).into_response());
+    }
+    login_succeeded(&authentication_state, &payload.email, &request_id).await?;
+
+    start_session(
+        &authentication_state,
+        user_id,
+        payload.device_label.as_deref(),
+        "password",
+        client_ip,
+        user_agent,
+        &request_id
+    ).await
+}
+
+/// Rejects locked out emails and IPs, and asks for a Turnstile token after repeated failures
+async fn check_login_protection(
+    authentication_state: &AuthenticationState,
+    payload: &AuthenticationPayload,
+    login_subjects: &[LoginSubject],
+    now: i64,
+    request_id: &str
+) -> Result<(), AuthError> {
+    let db_client = &authentication_state.db_client;
+    let login_protection_config = &authentication_state.login_protection_config;
+
+    check_lockout(authentication_state, login_subjects, request_id).await?;
+
+    let turnstile_required = requires_turnstile(
+        db_client,
+        login_protection_config,
+        login_subjects,
+        now
+    ).await.map_err(
+        |e| {
+            error!("request_id: {}, db_error: {:?}", request_id, e);
+            e.to_auth_error()
+        }
+    )?;
+    if !turnstile_required {
+        return Ok(());
+    }
+    if payload.cf_turnstile_response.is_none() {
+        return Err(AuthError::TurnstileRequired);
+    }
+    let turnstile_result = authentication_state.turnstile_state.verify_turnstile_from_request(
+        payload
+    ).await.map_err(
+        |e| {
+            error!("request_id: {}, error verifying turnstile: {:?}", request_id, e);
+            e.to_auth_error()
+        }
+    )?;
+    if turnstile_result == TurnstileResult::Denied {
+        return Err(AuthError::TurnstileRequired);
+    }
+    Ok(())
+}
+
+/// Both steps of a login are rejected while the email or IP is locked out
+pub(super) async fn check_lockout(
+    authentication_state: &AuthenticationState,
+    login_subjects: &[LoginSubject],
+    request_id: &str
+) -> Result<(), AuthError> {
+    let lockout = get_lockout(&authentication_state.db_client, login_subjects).await.map_err(
+        |e| {
+            error!("request_id: {}, db_error: {:?}", request_id, e);
+            e.to_auth_error()
+        }
+    )?;
+    if let Some(retry_after_s) = lockout {
+        info!("request_id: {}, login locked for {}s", request_id, retry_after_s);
+        return Err(AuthError::TooManyAttempts(retry_after_s));
+    }
+    Ok(())
+}
+
+/// Counts a failed login, the owner of the account is told when it gets locked.
+/// Returns `failure`, or the error that kept the failure from being counted
+pub(super) async fn login_failed(
+    authentication_state: &AuthenticationState,
+    login_subjects: &[LoginSubject],
+    user_email: Option<&str>,
+    now: i64,
+    request_id: &str,
+    failure: AuthError
+) -> AuthError {
+    let login_protection_config = &authentication_state.login_protection_config;
+    let db_res = record_login_failure(
+        &authentication_state.db_client,
+        login_protection_config,
+        login_subjects,
+        now
+    ).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        return db_error.to_auth_error();
+    }
+
+    for (subject, lockout_s) in db_res.unwrap() {
+        warn!("request_id: {}, {} locked out for {}s after failed logins", request_id, subject.key(), lockout_s);
+        let LoginSubject::Email(_) = subject else {
+            continue;
+        };
+        let Some(Ok(recipient)) = user_email.map(str::parse) else {
+            continue;
+        };
+        if !login_protection_config.notify_on_lockout {
+            continue;
+        }
+        // One email per lockout, however many requests run into it
+        let idempotency_key = idempotency_key("account_locked", &format!("{}:{}", subject.key(), now + lockout_s));
+        let email_handler = &authentication_state.email_handler;
+        let email = email_handler.create_account_locked_email(recipient, lockout_s);
+        let email_res = match email {
+            Ok(email) => email_handler.enqueue_email(idempotency_key, email).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = email_res {
+            error!("request_id: {}, error enqueueing the account locked email: {:?}", request_id, e);
+        }
+    }
+    failure
+}
+
+/// The owner got in, failures of the email don't count against them anymore
+pub(super) async fn login_succeeded(
+    authentication_state: &AuthenticationState,
+    user_email: &str,
+    request_id: &str
+) -> Result<(), AuthError> {
+    let email_subject = LoginSubject::email(user_email);
+    authentication_state.db_client.reset_login_failures(&email_subject.key()).await.map_err(
+        |e| {
+            error!("request_id: {}, db_error: {:?}", request_id, e);
+            e.to_auth_error()
+        }
+    )
+}
+
+/// A login is new when none of the other live sessions of the user has its IP or its user agent.
+/// The first session of a user isn't new, there's nothing to compare it with
+fn is_new_login(
+    known_sessions: &[Session],
+    session: &Session
+) -> bool {
+    if known_sessions.is_empty() {
+        return false;
+    }
+    let known_ip = known_sessions.iter().any(|known| known.ip == session.ip);
+    let known_user_agent = known_sessions.iter().any(|known| known.user_agent == session.user_agent);
+    !known_ip || !known_user_agent
+}
+
+/// Emails the user about the new session, the login goes through whether or not it works
+async fn notify_new_login(
+    authentication_state: &AuthenticationState,
+    session: &Session,
+    request_id: &str
+) {
+    let user = match authentication_state.db_client.get_user_by_id(session.user_id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return,
+        Err(e) => {
+            error!("request_id: {}, db_error: {:?}", request_id, e);
+            return;
+        },
+    };
+    let Ok(recipient) = user.email.parse() else {
+        return;
+    };
+    let email_handler = &authentication_state.email_handler;
+    let email = email_handler.create_new_login_email(recipient, &session.device_label, session.ip.as_deref());
+    let email_res = match email {
+        Ok(email) => email_handler.enqueue_email(idempotency_key("new_login", &session.id.to_string()), email).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = email_res {
+        error!("request_id: {}, error enqueueing the new login email: {:?}", request_id, e);
+    }
+}
+
+/// Shows up in the security log of the user, if the login can be tied to one
+pub(super) fn record_login_failure_event(
+    authentication_state: &AuthenticationState,
+    user_id: Option<i64>,
+    audit_client: &AuditClient,
+    reason: &str
+) {
+    authentication_state.audit_log.record(
+        AuditEvent::new(AuditEventType::LoginFailure, user_id)
+            .with_client(audit_client.ip.clone(), audit_client.user_agent.clone())
+            .with_metadata(json!({
+                "reason": reason,
+            }))
+    );
+}
+
+/// Last step of every login, stores a new session and sets its first refresh token cookie.
+/// `method` is how the user proved who they are, it's kept in the security log.
+pub(super) async fn start_session(
+    authentication_state: &AuthenticationState,
+    user_id: i64,
+    device_label: Option<&str>,
+    method: &str,
+    client_ip: Option<SecureClientIp>,
+    user_agent: Option<TypedHeader<UserAgent>>,
+    request_id: &str
+) -> Result<Response, AuthError> {
+    let audit_client = AuditClient::new(client_ip.as_ref(), user_agent.as_ref());
 
+    // Banned and unverified users are only told so once they proved who they are
+    let account_status = check_account_status(&authentication_state.db_client, user_id).await;
+    match account_status {
+        Ok(_) => {},
+        Err(e @ AuthError::Banned { .. }) => {
+            record_login_failure_event(authentication_state, Some(user_id), &audit_client, "banned");
+            return Err(e);
+        },
+        Err(e @ AuthError::UnverifiedEmail) => {
+            record_login_failure_event(authentication_state, Some(user_id), &audit_client, "unverified_email");
+            return Err(e);
+        },
+        Err(e) => return Err(e),
+    }
 
+    // Every login starts a new session with its own token family, so other devices stay logged in
+    let session_id = Uuid::new_v4();
+    let jti = Uuid::new_v4();
     let claims: AuthClaims = AuthClaims::new_refresh(
         authentication_state.jwt_config.refresh_key_lifetime_s,
-        user_id
+        user_id,
+        jti,
+        session_id
     );
     // Create the authorization token
-    let refresh_token = encode(
-        &Header::default(),
-        &claims,
-        &authentication_state.jwt_keys.encoding
-    );
+    let refresh_token = authentication_state.jwt_keys.encode(&claims);
     if refresh_token.is_err() {
         let error = refresh_token.unwrap_err();
         error!("request_id: {}, jwt error: {:?}", request_id, error);
@@ -124,10 +431,41 @@ pub async fn authenticate(
     let mut headers = HeaderMap::new();
     headers.insert(SET_COOKIE, HeaderValue::from_str(&cookie.to_string()).unwrap());
 
-    // Update the refresh token in the db
-    let db_res = authentication_state.db_client.cached_update_user_refresh_token(
+    // Store the session together with the refresh token in the db
+    let user_agent = user_agent.map(|TypedHeader(user_agent)| user_agent.to_string());
+    let sessions_config = &authentication_state.sessions_config;
+    let known_sessions = match sessions_config.notify_on_new_login {
+        true => authentication_state.db_client.get_user_sessions(user_id, claims.iat).await.map_err(
+            |e| {
+                error!("request_id: {}, db_error: {:?}", request_id, e);
+                e.to_auth_error()
+            }
+        )?,
+        false => Vec::new(),
+    };
+    let session = Session::new(
+        session_id,
         user_id,
-        &refresh_token
+        Session::device_label(
+            device_label,
+            user_agent.as_deref(),
+            sessions_config.max_device_label_length
+        ),
+        client_ip.map(|SecureClientIp(ip)| ip.to_string()),
+        user_agent,
+        claims.iat,
+        claims.exp
+    );
+    let db_res = authentication_state.db_client.create_session(
+        &session,
+        &RefreshToken::new_family(
+            jti,
+            session_id,
+            user_id,
+            claims.iat,
+            claims.exp
+        ),
+        sessions_config.cache_ttl_s
     ).await;
     if db_res.is_err() {
         let db_error = db_res.unwrap_err();
@@ -136,10 +474,21 @@ pub async fn authenticate(
         return Err(error);
     }
 
-    info!("request_id: {}, user refresh token updated", request_id);
+    info!("request_id: {}, session {} stored", request_id, session_id);
+    if is_new_login(&known_sessions, &session) {
+        notify_new_login(authentication_state, &session, request_id).await;
+    }
+    authentication_state.audit_log.record(
+        AuditEvent::new(AuditEventType::LoginSuccess, Some(user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+            .with_metadata(json!({
+                "method": method,
+                "session_id": session_id,
+            }))
+    );
 
     // Send the authorized token
-    Ok((headers, Json(AuthenticationBody::new(refresh_token))))
+    Ok((headers, Json(AuthenticationBody::new(refresh_token))).into_response())
 }
 
 
@@ -0,0 +1,192 @@
+use crate::{
+    audit::AuditClient,
+    auth::{
+        AuthClaims,
+        AuthError,
+        ClaimType,
+        MfaAuthenticationPayload
+    },
+    database::{
+        LoginAttemptStore,
+        MfaStore,
+        UserStore
+    },
+    login_protection::LoginSubject,
+    mfa::{
+        verify_second_factor,
+        SecondFactor
+    },
+    state::AuthenticationState
+};
+
+use super::authenticate::{
+    check_lockout,
+    login_failed,
+    login_succeeded,
+    record_login_failure_event,
+    start_session
+};
+
+use axum::{
+    extract::State,
+    response::Response,
+    Json
+};
+use axum_client_ip::SecureClientIp;
+use axum_extra::TypedHeader;
+use headers::UserAgent;
+use tracing::{
+    error,
+    info
+};
+use uuid::Uuid;
+
+use std::sync::Arc;
+
+
+/// Attempts are counted in the login attempt store, under a key of their own
+fn mfa_attempts_key(jti: Uuid) -> String {
+    format!("mfa_token:{}", jti)
+}
+
+/// Second step of a login with a second factor, exchanges the token from /authenticate and
+/// a TOTP or recovery code for a session
+pub async fn authenticate_mfa(
+    State(authentication_state): State<Arc<AuthenticationState>>,
+    client_ip: Option<SecureClientIp>,
+    user_agent: Option<TypedHeader<UserAgent>>,
+    Json(payload): Json<MfaAuthenticationPayload>,
+) -> Result<Response, AuthError> {
+    let request_id = Uuid::new_v4().to_string();
+    info!("request_id: {}, checking second factor", request_id);
+
+    let claims: AuthClaims = authentication_state.jwt_keys.verify_token_and_return_claims(
+        &payload.mfa_token
+    ).await.map_err(Into::<AuthError>::into)?;
+    if claims.claim_type != ClaimType::MfaPending {
+        return Err(AuthError::InvalidToken);
+    }
+    let user_id = claims.user_id;
+    let jti = claims.jti.ok_or(AuthError::InvalidToken)?;
+
+    let second_factor = payload.second_factor.second_factor().ok_or(AuthError::MissingCredentials)?;
+    let method = match second_factor {
+        SecondFactor::Totp(_) => "totp",
+        SecondFactor::RecoveryCode(_) => "recovery_code",
+    };
+
+    let db_client = &authentication_state.db_client;
+    let mfa_config = &authentication_state.mfa_config;
+
+    // Wrong codes count like wrong passwords, so a lockout covers both steps of the login
+    let db_res = db_client.get_user_by_id(user_id).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        return Err(db_error.to_auth_error());
+    }
+    let user_email = match db_res.unwrap() {
+        Some(user) => user.email,
+        None => return Err(AuthError::InvalidToken),
+    };
+    let login_subjects = LoginSubject::for_login(
+        &user_email,
+        client_ip.as_ref().map(|SecureClientIp(ip)| *ip),
+        authentication_state.client_ip_is_trusted
+    );
+    check_lockout(&authentication_state, &login_subjects, &request_id).await?;
+
+    // Every code tried counts against the token, the count outlives it, so once the token is used up
+    // the password has to be entered again for a new one
+    let now = chrono::Utc::now().timestamp();
+    let db_res = db_client.add_login_failure(
+        &mfa_attempts_key(jti),
+        now,
+        mfa_config.pending_token_lifetime_s
+    ).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        return Err(db_error.to_auth_error());
+    }
+    if db_res.unwrap() > mfa_config.max_attempts {
+        info!("request_id: {}, mfa token of user {} has no attempts left", request_id, user_id);
+        return Err(AuthError::InvalidToken);
+    }
+
+    let db_res = db_client.get_user_mfa(user_id).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        return Err(db_error.to_auth_error());
+    }
+    // The second factor was disabled since the password step
+    let user_mfa = match db_res.unwrap() {
+        Some(user_mfa) if user_mfa.is_enabled() => user_mfa,
+        _ => return Err(AuthError::InvalidToken),
+    };
+
+    // The token is claimed before the code is checked, so a request losing the race doesn't use up
+    // the recovery code or TOTP step it was sent with
+    let db_res = db_client.use_mfa_pending_token(jti, mfa_config.pending_token_lifetime_s).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        return Err(db_error.to_auth_error());
+    }
+    if !db_res.unwrap() {
+        info!("request_id: {}, mfa token of user {} was already used", request_id, user_id);
+        return Err(AuthError::InvalidToken);
+    }
+
+    let db_res = verify_second_factor(
+        db_client,
+        mfa_config,
+        &user_mfa,
+        second_factor
+    ).await;
+    if db_res.is_err() {
+        let db_error = db_res.unwrap_err();
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+        release_mfa_pending_token(&authentication_state, jti, &request_id).await;
+        return Err(db_error.to_auth_error());
+    }
+    if !db_res.unwrap() {
+        // A wrong code doesn't use up the token, the attempts left on it are counted above
+        release_mfa_pending_token(&authentication_state, jti, &request_id).await;
+        let audit_client = AuditClient::new(client_ip.as_ref(), user_agent.as_ref());
+        record_login_failure_event(&authentication_state, Some(user_id), &audit_client, "invalid_mfa_code");
+        return Err(login_failed(
+            &authentication_state,
+            &login_subjects,
+            Some(&user_email),
+            now,
+            &request_id,
+            AuthError::InvalidMfaCode
+        ).await);
+    }
+    info!("request_id: {}, second factor of user {} accepted", request_id, user_id);
+    login_succeeded(&authentication_state, &user_email, &request_id).await?;
+
+    start_session(
+        &authentication_state,
+        user_id,
+        payload.device_label.as_deref(),
+        method,
+        client_ip,
+        user_agent,
+        &request_id
+    ).await
+}
+
+async fn release_mfa_pending_token(
+    authentication_state: &AuthenticationState,
+    jti: Uuid,
+    request_id: &str
+) {
+    let db_res = authentication_state.db_client.release_mfa_pending_token(jti).await;
+    if let Err(db_error) = db_res {
+        // The token can't be retried then, the password step hands out a new one
+        error!("request_id: {}, db_error: {:?}", request_id, db_error);
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        DmChannel,
+        DmKind
+    },
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        DmStore
+    },
+    dms::{
+        check_not_blocked,
+        check_user_exists,
+        get_recipient_dm_channel,
+        DmError
+    },
+    state::DmsState
+};
+
+
+/// Any recipient of a group DM can add users they haven't blocked and aren't blocked by
+pub async fn add_dm_recipient(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+    Path((channel_id, user_id)): Path<(i64, i64)>,
+) -> Result<Json<DmChannel>, DmError> {
+    let request_id = uuid::Uuid::new_v4();
+    let dms_config = &dms_state.dms_config;
+    let db_client = &dms_state.db_client;
+
+    let channel = get_recipient_dm_channel(
+        db_client,
+        channel_id,
+        claims.user_id,
+        dms_config.cache_ttl_s
+    ).await?;
+    if channel.kind != DmKind::Group {
+        return Err(DmError::NotGroupDm);
+    }
+    if channel.is_recipient(user_id) {
+        return Ok(Json(channel));
+    }
+    if channel.recipient_ids.len() >= dms_config.max_group_size {
+        return Err(DmError::GroupTooLarge(dms_config.max_group_size));
+    }
+    check_user_exists(db_client, user_id).await?;
+    check_not_blocked(db_client, claims.user_id, user_id).await?;
+
+    db_client.add_dm_recipient(
+        channel_id,
+        user_id,
+        chrono::Utc::now().timestamp()
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error adding DM recipient: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let channel = db_client.get_dm_channel_by_id(
+        channel_id,
+        dms_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error fetching DM channel: {:?}", request_id, e);
+            e
+        }
+    )?;
+    // The last recipient could have left in the meantime
+    let channel = channel.ok_or(DatabaseError::ChannelNotFound(channel_id))?;
+
+    Ok(Json(channel))
+}
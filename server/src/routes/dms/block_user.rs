@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::AuthClaims,
+    database::DmStore,
+    dms::{
+        check_user_exists,
+        DmError
+    },
+    state::DmsState
+};
+
+
+/// Blocked users can't open DMs with the caller, add them to group DMs or post into their direct DM
+pub async fn block_user(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, DmError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &dms_state.db_client;
+
+    if user_id == claims.user_id {
+        return Err(DmError::BlockSelf);
+    }
+    check_user_exists(db_client, user_id).await?;
+
+    db_client.block_user(
+        claims.user_id,
+        user_id,
+        chrono::Utc::now().timestamp()
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error blocking user: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    auth::AuthClaims,
+    database::DmStore,
+    dms::DmError,
+    state::DmsState
+};
+
+
+/// Lists the ids of the users the caller blocked
+pub async fn get_blocks(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+) -> Result<Json<Vec<i64>>, DmError> {
+    let blocked_ids = dms_state.db_client.get_user_blocks(
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error fetching blocks of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(blocked_ids))
+}
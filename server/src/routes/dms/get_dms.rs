@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::DmChannel,
+    auth::AuthClaims,
+    database::DmStore,
+    dms::DmError,
+    state::DmsState
+};
+
+
+/// Lists the DM channels the caller is a recipient of
+pub async fn get_dms(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+) -> Result<Json<Vec<DmChannel>>, DmError> {
+    let channels = dms_state.db_client.get_user_dm_channels(
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error fetching DM channels of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(channels))
+}
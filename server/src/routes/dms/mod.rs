@@ -0,0 +1,15 @@
+mod open_dm;
+mod get_dms;
+mod add_dm_recipient;
+mod remove_dm_recipient;
+mod get_blocks;
+mod block_user;
+mod unblock_user;
+
+pub use open_dm::open_dm;
+pub use get_dms::get_dms;
+pub use add_dm_recipient::add_dm_recipient;
+pub use remove_dm_recipient::remove_dm_recipient;
+pub use get_blocks::get_blocks;
+pub use block_user::block_user;
+pub use unblock_user::unblock_user;
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::DmChannel,
+    auth::AuthClaims,
+    database::DmStore,
+    dms::{
+        check_not_blocked,
+        check_user_exists,
+        DmError,
+        OpenDmPayload
+    },
+    state::DmsState
+};
+
+
+/// Opening a direct DM that already exists returns it instead of creating a new one
+pub async fn open_dm(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+    Json(payload): Json<OpenDmPayload>,
+) -> Result<impl IntoResponse, DmError> {
+    let request_id = uuid::Uuid::new_v4();
+    let dms_config = &dms_state.dms_config;
+    let db_client = &dms_state.db_client;
+
+    let recipient_ids = payload.validated_recipients(
+        claims.user_id,
+        dms_config.max_group_size
+    )?;
+    for recipient_id in &recipient_ids {
+        check_user_exists(db_client, *recipient_id).await?;
+        check_not_blocked(db_client, claims.user_id, *recipient_id).await?;
+    }
+
+    let channel_id = dms_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating channel id: {:?}", request_id, e);
+            e
+        }
+    )?;
+    let now = chrono::Utc::now().timestamp();
+    let channel = match recipient_ids.as_slice() {
+        [recipient_id] => DmChannel::new_direct(channel_id, claims.user_id, *recipient_id, now),
+        _ => DmChannel::new_group(channel_id, claims.user_id, &recipient_ids, now),
+    };
+
+    let channel = db_client.open_dm_channel(
+        &channel,
+        dms_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error opening DM channel: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let status_code = match channel.id == channel_id {
+        true => StatusCode::CREATED,
+        false => StatusCode::OK,
+    };
+    Ok((status_code, Json(channel)))
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    app_objects::DmKind,
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        DmStore
+    },
+    dms::{
+        get_recipient_dm_channel,
+        DmError
+    },
+    event_bus::BusEvent,
+    state::DmsState
+};
+
+
+/// Recipients can leave a group DM, only the owner can remove others
+pub async fn remove_dm_recipient(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+    Path((channel_id, user_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, DmError> {
+    let request_id = uuid::Uuid::new_v4();
+    let dms_config = &dms_state.dms_config;
+    let db_client = &dms_state.db_client;
+
+    let channel = get_recipient_dm_channel(
+        db_client,
+        channel_id,
+        claims.user_id,
+        dms_config.cache_ttl_s
+    ).await?;
+    if channel.kind != DmKind::Group {
+        return Err(DmError::NotGroupDm);
+    }
+    if user_id != claims.user_id && channel.owner_id != Some(claims.user_id) {
+        return Err(DmError::NotGroupOwner);
+    }
+
+    db_client.remove_dm_recipient(channel_id, user_id).await.map_err(
+        |e| {
+            if !matches!(e, DatabaseError::RecipientNotFound(_)) {
+                error!("|{}| Error removing DM recipient: {:?}", request_id, e);
+            }
+            e
+        }
+    )?;
+
+    // The removed user's sessions are still subscribed, the recheck drops the channel from them
+    if let Err(e) = dms_state.event_bus.publish(BusEvent::ChannelAccessChanged { channel_id }).await {
+        error!("|{}| Error publishing channel access event: {:?}", request_id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,35 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::AuthClaims,
+    database::DmStore,
+    dms::DmError,
+    state::DmsState
+};
+
+
+pub async fn unblock_user(
+    State(dms_state): State<Arc<DmsState>>,
+    claims: AuthClaims,
+    Path(user_id): Path<i64>,
+) -> Result<StatusCode, DmError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    dms_state.db_client.unblock_user(claims.user_id, user_id).await.map_err(
+        |e| {
+            error!("|{}| Error unblocking user: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
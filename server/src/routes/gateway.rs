@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        ws::WebSocketUpgrade,
+        State
+    },
+    response::Response
+};
+
+use crate::{
+    auth::AuthClaims,
+    gateway::handle_gateway_socket,
+    state::GatewayState
+};
+
+// The access token cookie is checked by the AuthClaims extractor before the upgrade
+pub async fn gateway(
+    State(gateway_state): State<Arc<GatewayState>>,
+    claims: AuthClaims,
+    ws: WebSocketUpgrade,
+) -> Response {
+    ws.on_upgrade(move |socket| {
+        handle_gateway_socket(socket, claims, gateway_state)
+    })
+}
\ No newline at end of file
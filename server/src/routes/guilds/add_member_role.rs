@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::{
+        DatabaseError,
+        RoleStore
+    },
+    guilds::{
+        recheck_user_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+pub async fn add_member_role(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Path((guild_id, user_id, role_id)): Path<(i64, i64, i64)>,
+) -> Result<StatusCode, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    guild_permissions.require(Permissions::MANAGE_ROLES)?;
+
+    let role = guild_permissions.roles.iter()
+        .find(|role| role.id == role_id)
+        .ok_or(DatabaseError::RoleNotFound(role_id))?;
+    if role.is_everyone() {
+        return Err(GuildError::EveryoneRole);
+    }
+    if !guild_permissions.can_manage_role(role.position) {
+        return Err(GuildError::RoleHierarchy);
+    }
+
+    guilds_state.db_client.add_member_role(guild_id, user_id, role_id).await.map_err(
+        |e| {
+            error!("|{}| Error adding role to member: {:?}", request_id, e);
+            e
+        }
+    )?;
+    recheck_user_access(&guilds_state.event_bus, user_id, request_id).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        Channel,
+        ChannelKind
+    },
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::GuildStore,
+    guilds::{
+        CreateChannelPayload,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// New channels are placed at the end of the list
+pub async fn create_channel(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Json(payload): Json<CreateChannelPayload>,
+) -> Result<impl IntoResponse, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guilds_config = &guilds_state.guilds_config;
+    let db_client = &guilds_state.db_client;
+    let guild_id = guild_permissions.guild.id;
+
+    guild_permissions.require(Permissions::MANAGE_CHANNELS)?;
+
+    let name = payload.validated_name(
+        guilds_config.max_name_length
+    )?;
+
+    let channels = db_client.get_guild_channels(guild_id).await.map_err(
+        |e| {
+            error!("|{}| Error fetching channels: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if channels.len() >= guilds_config.max_channels_per_guild {
+        return Err(GuildError::TooManyChannels(guilds_config.max_channels_per_guild));
+    }
+
+    // Only one level of nesting, a category can't be placed in another category
+    if let Some(parent_id) = payload.parent_id {
+        let parent_is_category = channels.iter().any(
+            |channel| channel.id == parent_id && channel.kind == ChannelKind::Category
+        );
+        if !parent_is_category || payload.kind == ChannelKind::Category {
+            return Err(GuildError::InvalidParent(parent_id));
+        }
+    }
+
+    let position = channels.iter()
+        .map(|channel| channel.position + 1)
+        .max()
+        .unwrap_or(0);
+
+    let channel_id = guilds_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating channel id: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let channel = Channel::new(
+        channel_id,
+        guild_id,
+        name,
+        payload.kind,
+        position,
+        payload.parent_id,
+        chrono::Utc::now().timestamp()
+    );
+
+    db_client.insert_channel(
+        &channel,
+        guilds_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error inserting channel into db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok((StatusCode::CREATED, Json(channel)))
+}
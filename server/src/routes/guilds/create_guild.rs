@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        Channel,
+        ChannelKind,
+        Guild,
+        GuildMember,
+        Role
+    },
+    auth::AuthClaims,
+    database::GuildStore,
+    guilds::{
+        GuildError,
+        NamePayload
+    },
+    state::GuildsState
+};
+
+
+/// Creates the guild with the caller as its owner, the @everyone role and a single `general` text channel
+pub async fn create_guild(
+    State(guilds_state): State<Arc<GuildsState>>,
+    claims: AuthClaims,
+    Json(payload): Json<NamePayload>,
+) -> Result<impl IntoResponse, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guilds_config = &guilds_state.guilds_config;
+
+    let name = payload.validated_name(
+        guilds_config.max_name_length
+    )?;
+
+    let guild_id = guilds_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating guild id: {:?}", request_id, e);
+            e
+        }
+    )?;
+    let channel_id = guilds_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating channel id: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let now = chrono::Utc::now().timestamp();
+    let guild = Guild::new(guild_id, name, claims.user_id, now);
+    let owner = GuildMember::new(guild_id, claims.user_id, now);
+    let everyone_role = Role::everyone(guild_id, now);
+    let general_channel = Channel::new(
+        channel_id,
+        guild_id,
+        "general".to_string(),
+        ChannelKind::Text,
+        0,
+        None,
+        now
+    );
+
+    guilds_state.db_client.create_guild(
+        &guild,
+        &owner,
+        &everyone_role,
+        &[general_channel],
+        guilds_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error creating guild: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok((StatusCode::CREATED, Json(guild)))
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use rand::{
+    distributions::Alphanumeric,
+    Rng
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Invite,
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::GuildStore,
+    guilds::{
+        CreateInvitePayload,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+fn generate_invite_code(length: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+pub async fn create_invite(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    payload: Option<Json<CreateInvitePayload>>,
+) -> Result<impl IntoResponse, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guilds_config = &guilds_state.guilds_config;
+    let now = chrono::Utc::now().timestamp();
+
+    guild_permissions.require(Permissions::CREATE_INVITE)?;
+
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let (expires_at, max_uses) = payload.validated_limits(
+        now,
+        guilds_config.default_invite_max_age_s
+    )?;
+
+    let invite = Invite {
+        code: generate_invite_code(guilds_config.invite_code_length),
+        guild_id: guild_permissions.guild.id,
+        creator_id: guild_permissions.member.user_id,
+        created_at: now,
+        expires_at,
+        max_uses,
+        uses: 0,
+    };
+
+    guilds_state.db_client.insert_invite(&invite).await.map_err(
+        |e| {
+            error!("|{}| Error inserting invite into db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok((StatusCode::CREATED, Json(invite)))
+}
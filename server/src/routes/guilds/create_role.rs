@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Role,
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::RoleStore,
+    guilds::{
+        CreateRolePayload,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Roles can only be created below the caller's highest role and with permissions the caller has
+pub async fn create_role(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Json(payload): Json<CreateRolePayload>,
+) -> Result<impl IntoResponse, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    guild_permissions.require(Permissions::MANAGE_ROLES)?;
+
+    let (name, permissions, position) = payload.validated(
+        guilds_state.guilds_config.max_name_length
+    )?;
+    if !guild_permissions.can_manage_role(position) {
+        return Err(GuildError::RoleHierarchy);
+    }
+    guild_permissions.require(permissions)?;
+
+    let role_id = guilds_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating role id: {:?}", request_id, e);
+            e
+        }
+    )?;
+    let role = Role::new(
+        role_id,
+        guild_permissions.guild.id,
+        name,
+        permissions,
+        position,
+        chrono::Utc::now().timestamp()
+    );
+
+    guilds_state.db_client.insert_role(&role).await.map_err(
+        |e| {
+            error!("|{}| Error inserting role into db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok((StatusCode::CREATED, Json(role)))
+}
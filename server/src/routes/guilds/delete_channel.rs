@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::GuildStore,
+    guilds::{
+        recheck_channel_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Deletes the channel together with its messages
+pub async fn delete_channel(
+    State(guilds_state): State<Arc<GuildsState>>,
+    channel_permissions: ChannelPermissions,
+) -> Result<StatusCode, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    channel_permissions.require(Permissions::MANAGE_CHANNELS)?;
+
+    guilds_state.db_client.delete_channel(
+        channel_permissions.channel_id
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error deleting channel: {:?}", request_id, e);
+            e
+        }
+    )?;
+    // Sessions can't view a deleted channel, the recheck drops their subscriptions
+    recheck_channel_access(
+        &guilds_state.event_bus,
+        [channel_permissions.channel_id],
+        request_id
+    ).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
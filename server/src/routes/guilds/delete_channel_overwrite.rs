@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::RoleStore,
+    guilds::{
+        recheck_channel_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Deleting an overwrite that doesn't exist does nothing
+pub async fn delete_channel_overwrite(
+    State(guilds_state): State<Arc<GuildsState>>,
+    channel_permissions: ChannelPermissions,
+    Path((_, channel_id, target_id)): Path<(i64, i64, i64)>,
+) -> Result<StatusCode, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    channel_permissions.require(Permissions::MANAGE_ROLES)?;
+
+    guilds_state.db_client.delete_channel_overwrite(channel_id, target_id).await.map_err(
+        |e| {
+            error!("|{}| Error deleting channel overwrite: {:?}", request_id, e);
+            e
+        }
+    )?;
+    recheck_channel_access(&guilds_state.event_bus, [channel_id], request_id).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
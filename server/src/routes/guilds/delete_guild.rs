@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::GuildPermissions,
+    database::GuildStore,
+    guilds::{
+        recheck_channel_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Only the owner can delete the guild, no permission allows it
+pub async fn delete_guild(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+) -> Result<StatusCode, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guild = &guild_permissions.guild;
+
+    if guild.owner_id != guild_permissions.member.user_id {
+        return Err(GuildError::NotGuildOwner);
+    }
+
+    // The channels are gone after the delete, load them first to recheck them
+    let channels = guilds_state.db_client.get_guild_channels(guild.id).await?;
+    guilds_state.db_client.delete_guild(guild.id).await.map_err(
+        |e| {
+            error!("|{}| Error deleting guild: {:?}", request_id, e);
+            e
+        }
+    )?;
+    recheck_channel_access(
+        &guilds_state.event_bus,
+        channels.iter().map(|channel| channel.id),
+        request_id
+    ).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::{
+        DatabaseError,
+        RoleStore
+    },
+    guilds::{
+        recheck_guild_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Removes the role from its members and drops its channel overwrites
+pub async fn delete_role(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Path((guild_id, role_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    guild_permissions.require(Permissions::MANAGE_ROLES)?;
+
+    let role = guild_permissions.roles.iter()
+        .find(|role| role.id == role_id)
+        .ok_or(DatabaseError::RoleNotFound(role_id))?;
+    if role.is_everyone() {
+        return Err(GuildError::EveryoneRole);
+    }
+    if !guild_permissions.can_manage_role(role.position) {
+        return Err(GuildError::RoleHierarchy);
+    }
+
+    guilds_state.db_client.delete_role(guild_id, role_id).await.map_err(
+        |e| {
+            error!("|{}| Error deleting role: {:?}", request_id, e);
+            e
+        }
+    )?;
+    recheck_guild_access(
+        &guilds_state.db_client,
+        &guilds_state.event_bus,
+        guild_id,
+        request_id
+    ).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Channel,
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::GuildStore,
+    guilds::{
+        GuildError,
+        NamePayload
+    },
+    state::GuildsState
+};
+
+
+/// Renames the channel, positions are changed in bulk by `reorder_channels`
+pub async fn edit_channel(
+    State(guilds_state): State<Arc<GuildsState>>,
+    channel_permissions: ChannelPermissions,
+    Json(payload): Json<NamePayload>,
+) -> Result<Json<Channel>, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guilds_config = &guilds_state.guilds_config;
+
+    channel_permissions.require(Permissions::MANAGE_CHANNELS)?;
+
+    let name = payload.validated_name(
+        guilds_config.max_name_length
+    )?;
+
+    let channel = guilds_state.db_client.rename_channel(
+        channel_permissions.channel_id,
+        &name,
+        guilds_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error renaming channel: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(channel))
+}
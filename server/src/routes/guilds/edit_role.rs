@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Role,
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::{
+        DatabaseError,
+        RoleStore
+    },
+    guilds::{
+        EditRolePayload,
+        recheck_guild_access,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// The role has to stay below the caller's highest role, only permissions the caller has can be added to it
+pub async fn edit_role(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Path((_, role_id)): Path<(i64, i64)>,
+    Json(payload): Json<EditRolePayload>,
+) -> Result<Json<Role>, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    guild_permissions.require(Permissions::MANAGE_ROLES)?;
+
+    let role = guild_permissions.roles.iter()
+        .find(|role| role.id == role_id)
+        .ok_or(DatabaseError::RoleNotFound(role_id))?;
+    if !guild_permissions.can_manage_role(role.position) {
+        return Err(GuildError::RoleHierarchy);
+    }
+
+    let edited = payload.applied_to(
+        role,
+        guilds_state.guilds_config.max_name_length
+    )?;
+    if !guild_permissions.can_manage_role(edited.position) {
+        return Err(GuildError::RoleHierarchy);
+    }
+    guild_permissions.require(edited.permissions - role.permissions)?;
+
+    guilds_state.db_client.update_role(&edited).await.map_err(
+        |e| {
+            error!("|{}| Error updating role: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if edited.permissions != role.permissions {
+        recheck_guild_access(
+            &guilds_state.db_client,
+            &guilds_state.event_bus,
+            edited.guild_id,
+            request_id
+        ).await;
+    }
+
+    Ok(Json(edited))
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        Channel,
+        PermissionOverwrite
+    },
+    auth::{
+        compute_channel_permissions,
+        GuildPermissions,
+        Permissions
+    },
+    database::{
+        GuildStore,
+        RoleStore
+    },
+    guilds::GuildError,
+    state::GuildsState
+};
+
+
+/// Lists the channels of the guild the caller can view
+pub async fn get_channels(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+) -> Result<Json<Vec<Channel>>, GuildError> {
+    let db_client = &guilds_state.db_client;
+    let guild_id = guild_permissions.guild.id;
+
+    let channels = db_client.get_guild_channels(guild_id).await.map_err(
+        |e| {
+            error!("Error fetching channels of guild {}: {:?}", guild_id, e);
+            e
+        }
+    )?;
+    let overwrites = db_client.get_guild_overwrites(guild_id).await.map_err(
+        |e| {
+            error!("Error fetching overwrites of guild {}: {:?}", guild_id, e);
+            e
+        }
+    )?;
+
+    let channels = channels.into_iter()
+        .filter(|channel| {
+            let channel_overwrites: Vec<PermissionOverwrite> = overwrites.iter()
+                .filter(|overwrite| overwrite.channel_id == channel.id)
+                .cloned()
+                .collect();
+            compute_channel_permissions(
+                guild_permissions.permissions,
+                guild_id,
+                &guild_permissions.member,
+                &channel_overwrites
+            ).contains(Permissions::VIEW_CHANNEL)
+        })
+        .collect();
+
+    Ok(Json(channels))
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Guild,
+    auth::AuthClaims,
+    database::GuildStore,
+    guilds::GuildError,
+    state::GuildsState
+};
+
+
+/// Lists the guilds the caller is a member of
+pub async fn get_guilds(
+    State(guilds_state): State<Arc<GuildsState>>,
+    claims: AuthClaims,
+) -> Result<Json<Vec<Guild>>, GuildError> {
+    let guilds = guilds_state.db_client.get_user_guilds(
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error fetching guilds of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(guilds))
+}
@@ -0,0 +1,15 @@
+use axum::Json;
+
+use crate::{
+    app_objects::Role,
+    auth::GuildPermissions,
+    guilds::GuildError
+};
+
+
+/// Lists the roles of the guild from the lowest to the highest, @everyone included
+pub async fn get_roles(
+    guild_permissions: GuildPermissions,
+) -> Result<Json<Vec<Role>>, GuildError> {
+    Ok(Json(guild_permissions.roles))
+}
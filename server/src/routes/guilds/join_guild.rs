@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::GuildMember,
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        GuildStore
+    },
+    guilds::GuildError,
+    state::GuildsState
+};
+
+
+pub async fn join_guild(
+    State(guilds_state): State<Arc<GuildsState>>,
+    claims: AuthClaims,
+    Path(code): Path<String>,
+) -> Result<Json<GuildMember>, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+
+    let member = guilds_state.db_client.join_guild_with_invite(
+        &code,
+        claims.user_id,
+        guilds_state.guilds_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            match e {
+                DatabaseError::InviteNotFound(_) | DatabaseError::MemberAlreadyExists(_) => {},
+                ref e => error!("|{}| Error joining guild: {:?}", request_id, e),
+            }
+            e
+        }
+    )?;
+
+    Ok(Json(member))
+}
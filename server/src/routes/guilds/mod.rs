@@ -0,0 +1,37 @@
+mod create_guild;
+mod get_guilds;
+mod delete_guild;
+mod get_channels;
+mod create_channel;
+mod edit_channel;
+mod reorder_channels;
+mod delete_channel;
+mod create_invite;
+mod join_guild;
+mod get_roles;
+mod create_role;
+mod edit_role;
+mod delete_role;
+mod add_member_role;
+mod remove_member_role;
+mod set_channel_overwrite;
+mod delete_channel_overwrite;
+
+pub use create_guild::create_guild;
+pub use get_guilds::get_guilds;
+pub use delete_guild::delete_guild;
+pub use get_channels::get_channels;
+pub use create_channel::create_channel;
+pub use edit_channel::edit_channel;
+pub use reorder_channels::reorder_channels;
+pub use delete_channel::delete_channel;
+pub use create_invite::create_invite;
+pub use join_guild::join_guild;
+pub use get_roles::get_roles;
+pub use create_role::create_role;
+pub use edit_role::edit_role;
+pub use delete_role::delete_role;
+pub use add_member_role::add_member_role;
+pub use remove_member_role::remove_member_role;
+pub use set_channel_overwrite::set_channel_overwrite;
+pub use delete_channel_overwrite::delete_channel_overwrite;
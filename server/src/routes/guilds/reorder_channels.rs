@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Channel,
+    auth::{
+        GuildPermissions,
+        Permissions
+    },
+    database::GuildStore,
+    guilds::{
+        ChannelPositionPayload,
+        GuildError
+    },
+    state::GuildsState
+};
+
+
+/// Moves the listed channels to their new positions and returns the whole reordered list
+pub async fn reorder_channels(
+    State(guilds_state): State<Arc<GuildsState>>,
+    guild_permissions: GuildPermissions,
+    Json(payload): Json<Vec<ChannelPositionPayload>>,
+) -> Result<Json<Vec<Channel>>, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let guilds_config = &guilds_state.guilds_config;
+
+    guild_permissions.require(Permissions::MANAGE_CHANNELS)?;
+
+    let positions = ChannelPositionPayload::validated_positions(&payload)?;
+
+    let channels = guilds_state.db_client.update_channel_positions(
+        guild_permissions.guild.id,
+        &positions,
+        guilds_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error reordering channels: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(channels))
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        OverwriteKind,
+        PermissionOverwrite
+    },
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::{
+        GuildStore,
+        RoleStore
+    },
+    guilds::{
+        recheck_channel_access,
+        GuildError,
+        OverwritePayload
+    },
+    state::GuildsState
+};
+
+
+/// Creates or replaces the overwrite of a role or member, only permissions the caller has in the channel can be changed
+pub async fn set_channel_overwrite(
+    State(guilds_state): State<Arc<GuildsState>>,
+    channel_permissions: ChannelPermissions,
+    Path((guild_id, channel_id, target_id)): Path<(i64, i64, i64)>,
+    Json(payload): Json<OverwritePayload>,
+) -> Result<Json<PermissionOverwrite>, GuildError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &guilds_state.db_client;
+    let cache_ttl_s = guilds_state.guilds_config.cache_ttl_s;
+
+    channel_permissions.require(Permissions::MANAGE_ROLES)?;
+    channel_permissions.require(payload.allow | payload.deny)?;
+
+    let target_exists = match payload.kind {
+        OverwriteKind::Role => db_client.get_guild_roles(guild_id, cache_ttl_s).await?
+            .iter()
+            .any(|role| role.id == target_id),
+        OverwriteKind::Member => db_client.get_guild_member(guild_id, target_id, cache_ttl_s).await?
+            .is_some(),
+    };
+    if !target_exists {
+        return Err(GuildError::InvalidOverwriteTarget(target_id));
+    }
+
+    let overwrite = PermissionOverwrite {
+        channel_id,
+        target_id,
+        kind: payload.kind,
+        allow: payload.allow,
+        deny: payload.deny,
+    };
+    db_client.set_channel_overwrite(&overwrite).await.map_err(
+        |e| {
+            error!("|{}| Error setting channel overwrite: {:?}", request_id, e);
+            e
+        }
+    )?;
+    recheck_channel_access(&guilds_state.event_bus, [channel_id], request_id).await;
+
+    Ok(Json(overwrite))
+}
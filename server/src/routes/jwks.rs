@@ -0,0 +1,19 @@
+use axum::{
+    extract::State,
+    http::header::CACHE_CONTROL,
+    response::IntoResponse,
+    Json
+};
+
+use crate::auth::JWTKeys;
+
+
+/// Public keys other services can verify our tokens with, picked by the `kid` header of the token
+pub async fn jwks(
+    State(jwt_keys): State<JWTKeys>,
+) -> impl IntoResponse {
+    (
+        [(CACHE_CONTROL, "public, max-age=300")],
+        Json(jwt_keys.jwks().clone())
+    )
+}
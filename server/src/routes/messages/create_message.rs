@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode,
+    response::IntoResponse,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Message,
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::MessageStore,
+    event_bus::BusEvent,
+    gateway::GatewayEvent,
+    messaging::{
+        MessageContentPayload,
+        MessagingError
+    },
+    state::MessagesState
+};
+
+
+pub async fn create_message(
+    State(messages_state): State<Arc<MessagesState>>,
+    channel_permissions: ChannelPermissions,
+    Path(channel_id): Path<i64>,
+    Json(payload): Json<MessageContentPayload>,
+) -> Result<impl IntoResponse, MessagingError> {
+    let request_id = uuid::Uuid::new_v4();
+    let messages_config = &messages_state.messages_config;
+
+    channel_permissions.require_text_channel()?;
+    channel_permissions.require(Permissions::SEND_MESSAGES)?;
+
+    let content = payload.validated_content(
+        messages_config.max_content_length
+    )?;
+
+    let message_id = messages_state.id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating message id: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let message = Message::new(
+        message_id,
+        content,
+        channel_permissions.user_id,
+        chrono::Utc::now().timestamp(),
+        None,
+        channel_id
+    );
+
+    messages_state.db_client.insert_message(
+        &message,
+        messages_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error inserting message into db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    // The change is already stored, clients that miss the event see it on their next fetch
+    if let Err(e) = messages_state.event_bus.publish(
+        BusEvent::Dispatch(GatewayEvent::MessageCreate(message.clone()))
+    ).await {
+        error!("|{}| Error publishing message event: {:?}", request_id, e);
+    }
+
+    Ok((StatusCode::CREATED, Json(message)))
+}
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    auth::{
+        ChannelPermissions,
+        Permissions
+    },
+    database::MessageStore,
+    event_bus::BusEvent,
+    gateway::GatewayEvent,
+    messaging::MessagingError,
+    state::MessagesState
+};
+
+
+pub async fn delete_message(
+    State(messages_state): State<Arc<MessagesState>>,
+    channel_permissions: ChannelPermissions,
+    Path((channel_id, message_id)): Path<(i64, i64)>,
+) -> Result<StatusCode, MessagingError> {
+    let request_id = uuid::Uuid::new_v4();
+    let messages_config = &messages_state.messages_config;
+    let db_client = &messages_state.db_client;
+
+    channel_permissions.require_text_channel()?;
+
+    let message = db_client.get_message_by_id(
+        message_id,
+        messages_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error getting message from db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let message = match message {
+        Some(message) if message.channel_id == channel_id => message,
+        _ => return Err(MessagingError::MessageNotFound(message_id)),
+    };
+
+    // Moderators can delete any message, editing stays limited to the author
+    if message.author_id != channel_permissions.user_id
+        && !channel_permissions.permissions.contains(Permissions::MANAGE_MESSAGES) {
+        return Err(MessagingError::NotMessageAuthor);
+    }
+
+    db_client.delete_message(message_id).await.map_err(
+        |e| {
+            error!("|{}| Error deleting message: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    if let Err(e) = messages_state.event_bus.publish(
+        BusEvent::Dispatch(GatewayEvent::MessageDelete {
+            id: message_id,
+            channel_id,
+        })
+    ).await {
+        error!("|{}| Error publishing message event: {:?}", request_id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
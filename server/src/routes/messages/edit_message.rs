@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Message,
+    auth::ChannelPermissions,
+    database::MessageStore,
+    event_bus::BusEvent,
+    gateway::GatewayEvent,
+    messaging::{
+        MessageContentPayload,
+        MessagingError
+    },
+    state::MessagesState
+};
+
+
+pub async fn edit_message(
+    State(messages_state): State<Arc<MessagesState>>,
+    channel_permissions: ChannelPermissions,
+    Path((channel_id, message_id)): Path<(i64, i64)>,
+    Json(payload): Json<MessageContentPayload>,
+) -> Result<Json<Message>, MessagingError> {
+    let request_id = uuid::Uuid::new_v4();
+    let messages_config = &messages_state.messages_config;
+    let db_client = &messages_state.db_client;
+
+    let content = payload.validated_content(
+        messages_config.max_content_length
+    )?;
+
+    channel_permissions.require_text_channel()?;
+
+    let message = db_client.get_message_by_id(
+        message_id,
+        messages_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error getting message from db: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    let message = match message {
+        Some(message) if message.channel_id == channel_id => message,
+        _ => return Err(MessagingError::MessageNotFound(message_id)),
+    };
+
+    if message.author_id != channel_permissions.user_id {
+        return Err(MessagingError::NotMessageAuthor);
+    }
+
+    let message = db_client.edit_message(
+        message_id,
+        &content,
+        messages_config.cache_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error editing message: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    if let Err(e) = messages_state.event_bus.publish(
+        BusEvent::Dispatch(GatewayEvent::MessageUpdate(message.clone()))
+    ).await {
+        error!("|{}| Error publishing message event: {:?}", request_id, e);
+    }
+
+    Ok(Json(message))
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        Query,
+        State
+    },
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::Message,
+    auth::ChannelPermissions,
+    database::MessageStore,
+    messaging::{
+        MessagePageQuery,
+        MessagingError
+    },
+    state::MessagesState
+};
+
+
+pub async fn get_messages(
+    State(messages_state): State<Arc<MessagesState>>,
+    channel_permissions: ChannelPermissions,
+    Path(channel_id): Path<i64>,
+    Query(page_query): Query<MessagePageQuery>,
+) -> Result<Json<Vec<Message>>, MessagingError> {
+    let messages_config = &messages_state.messages_config;
+
+    let limit = page_query.validated_limit(
+        messages_config.default_page_size,
+        messages_config.max_page_size
+    )?;
+
+    channel_permissions.require_text_channel()?;
+
+    let messages = messages_state.db_client.get_channel_messages(
+        channel_id,
+        page_query.before,
+        page_query.after,
+        limit
+    ).await.map_err(
+        |e| {
+            error!("Error fetching messages for channel {}: {:?}", channel_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(messages))
+}
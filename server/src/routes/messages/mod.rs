@@ -0,0 +1,9 @@
+mod create_message;
+mod get_messages;
+mod edit_message;
+mod delete_message;
+
+pub use create_message::create_message;
+pub use get_messages::get_messages;
+pub use edit_message::edit_message;
+pub use delete_message::delete_message;
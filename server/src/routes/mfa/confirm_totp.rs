@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    auth::AuthClaims,
+    database::MfaStore,
+    mfa::{
+        generate_recovery_codes,
+        hash_recovery_code,
+        MfaConfirmPayload,
+        MfaError,
+        Totp
+    },
+    state::MfaState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesBody {
+    // Only shown once, the server keeps their hashes
+    pub recovery_codes: Vec<String>,
+}
+
+/// Enables the enrolled secret once the caller proves their authenticator app produces valid codes
+pub async fn confirm_totp(
+    State(mfa_state): State<Arc<MfaState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    Json(payload): Json<MfaConfirmPayload>,
+) -> Result<Json<RecoveryCodesBody>, MfaError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &mfa_state.db_client;
+    let mfa_config = &mfa_state.mfa_config;
+
+    let user_mfa = match db_client.get_user_mfa(claims.user_id).await? {
+        Some(user_mfa) if user_mfa.is_enabled() => return Err(MfaError::AlreadyEnabled),
+        Some(user_mfa) => user_mfa,
+        None => return Err(MfaError::NotEnrolled),
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    let totp = Totp::new(&user_mfa.totp_secret, mfa_config.digits, mfa_config.period_s);
+    let step = totp.verify(
+        &payload.code,
+        now,
+        mfa_config.allowed_skew_steps
+    ).ok_or(MfaError::InvalidCode)?;
+
+    let recovery_codes = generate_recovery_codes(mfa_config.recovery_code_count);
+    let recovery_code_hashes: Vec<String> = recovery_codes
+        .iter()
+        .map(|code| hash_recovery_code(code))
+        .collect();
+    let confirmed = db_client.confirm_mfa(
+        claims.user_id,
+        step,
+        now,
+        &recovery_code_hashes
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error confirming the second factor: {:?}", request_id, e);
+            e
+        }
+    )?;
+    // Another request confirmed or replaced the enrollment in the meantime
+    if !confirmed {
+        return Err(MfaError::NotEnrolled);
+    }
+
+    mfa_state.audit_log.record(
+        AuditEvent::new(AuditEventType::MfaEnabled, Some(claims.user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(Json(RecoveryCodesBody { recovery_codes }))
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    auth::AuthClaims,
+    database::MfaStore,
+    mfa::{
+        check_second_factor,
+        get_enabled_user_mfa,
+        MfaCodePayload,
+        MfaError
+    },
+    state::MfaState
+};
+
+
+/// Turns the second factor off and drops the recovery codes, takes a TOTP or a recovery code
+pub async fn disable_totp(
+    State(mfa_state): State<Arc<MfaState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    Json(payload): Json<MfaCodePayload>,
+) -> Result<StatusCode, MfaError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &mfa_state.db_client;
+
+    let user_mfa = get_enabled_user_mfa(db_client, claims.user_id).await?;
+    check_second_factor(db_client, &mfa_state.mfa_config, &user_mfa, &payload).await?;
+
+    db_client.delete_user_mfa(claims.user_id).await.map_err(
+        |e| {
+            error!("|{}| Error deleting the second factor: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    mfa_state.audit_log.record(
+        AuditEvent::new(AuditEventType::MfaDisabled, Some(claims.user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,67 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    app_objects::UserMfa,
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        MfaStore,
+        UserStore
+    },
+    mfa::{
+        MfaError,
+        Totp
+    },
+    state::MfaState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct TotpEnrollmentBody {
+    // Base32, for typing it into the authenticator app
+    pub secret: String,
+    // otpauth:// URI, usually shown as a QR code
+    pub provisioning_uri: String,
+}
+
+/// Generates a new TOTP secret for the caller. Logins don't ask for codes until the
+/// enrollment is confirmed, starting over replaces an unconfirmed secret.
+pub async fn enroll_totp(
+    State(mfa_state): State<Arc<MfaState>>,
+    claims: AuthClaims,
+) -> Result<Json<TotpEnrollmentBody>, MfaError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &mfa_state.db_client;
+    let mfa_config = &mfa_state.mfa_config;
+
+    let user = db_client.get_user_by_id(claims.user_id).await?
+        .ok_or(DatabaseError::UserNotFound(claims.user_id))?;
+
+    let user_mfa = UserMfa::new(
+        claims.user_id,
+        Totp::generate_secret(),
+        chrono::Utc::now().timestamp()
+    );
+    let started = db_client.start_mfa_enrollment(&user_mfa).await.map_err(
+        |e| {
+            error!("|{}| Error storing the TOTP secret: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if !started {
+        return Err(MfaError::AlreadyEnabled);
+    }
+
+    let totp = Totp::new(&user_mfa.totp_secret, mfa_config.digits, mfa_config.period_s);
+    Ok(Json(TotpEnrollmentBody {
+        secret: totp.encoded_secret(),
+        provisioning_uri: totp.provisioning_uri(&mfa_config.issuer, &user.email),
+    }))
+}
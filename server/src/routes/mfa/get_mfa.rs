@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    auth::AuthClaims,
+    database::MfaStore,
+    mfa::MfaError,
+    state::MfaState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct MfaStatusBody {
+    pub enabled: bool,
+    pub recovery_codes_left: i64,
+}
+
+/// Tells the caller whether logins need a second factor and how many recovery codes are left
+pub async fn get_mfa(
+    State(mfa_state): State<Arc<MfaState>>,
+    claims: AuthClaims,
+) -> Result<Json<MfaStatusBody>, MfaError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &mfa_state.db_client;
+
+    let user_mfa = db_client.get_user_mfa(claims.user_id).await.map_err(
+        |e| {
+            error!("|{}| Error fetching the second factor: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if !user_mfa.is_some_and(|user_mfa| user_mfa.is_enabled()) {
+        return Ok(Json(MfaStatusBody { enabled: false, recovery_codes_left: 0 }));
+    }
+
+    let recovery_codes_left = db_client.count_unused_recovery_codes(claims.user_id).await.map_err(
+        |e| {
+            error!("|{}| Error counting recovery codes: {:?}", request_id, e);
+            e
+        }
+    )?;
+    Ok(Json(MfaStatusBody { enabled: true, recovery_codes_left }))
+}
@@ -0,0 +1,11 @@
+mod get_mfa;
+mod enroll_totp;
+mod confirm_totp;
+mod disable_totp;
+mod regenerate_recovery_codes;
+
+pub use get_mfa::get_mfa;
+pub use enroll_totp::enroll_totp;
+pub use confirm_totp::confirm_totp;
+pub use disable_totp::disable_totp;
+pub use regenerate_recovery_codes::regenerate_recovery_codes;
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    auth::AuthClaims,
+    database::MfaStore,
+    mfa::{
+        check_second_factor,
+        generate_recovery_codes,
+        get_enabled_user_mfa,
+        hash_recovery_code,
+        MfaCodePayload,
+        MfaError
+    },
+    state::MfaState
+};
+
+use super::confirm_totp::RecoveryCodesBody;
+
+
+/// Replaces every recovery code of the caller, the old ones stop working
+pub async fn regenerate_recovery_codes(
+    State(mfa_state): State<Arc<MfaState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    Json(payload): Json<MfaCodePayload>,
+) -> Result<Json<RecoveryCodesBody>, MfaError> {
+    let request_id = uuid::Uuid::new_v4();
+    let db_client = &mfa_state.db_client;
+    let mfa_config = &mfa_state.mfa_config;
+
+    let user_mfa = get_enabled_user_mfa(db_client, claims.user_id).await?;
+    check_second_factor(db_client, mfa_config, &user_mfa, &payload).await?;
+
+    let recovery_codes = generate_recovery_codes(mfa_config.recovery_code_count);
+    let recovery_code_hashes: Vec<String> = recovery_codes
+        .iter()
+        .map(|code| hash_recovery_code(code))
+        .collect();
+    db_client.replace_recovery_codes(
+        claims.user_id,
+        &recovery_code_hashes
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error replacing recovery codes: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    mfa_state.audit_log.record(
+        AuditEvent::new(AuditEventType::RecoveryCodesRegenerated, Some(claims.user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(Json(RecoveryCodesBody { recovery_codes }))
+}
@@ -1,17 +1,33 @@
 mod hello_world;
 mod secured;
 mod authenticate;
+mod authenticate_mfa;
 mod refresh_token;
 mod registration;
+mod messages;
+mod gateway;
+mod guilds;
+mod dms;
+mod sessions;
+mod jwks;
+mod mfa;
+mod passkeys;
+mod password_reset;
+mod account;
+mod admin;
 
 pub mod tests;
 
 use std::sync::Arc;
 
+use axum_client_ip::SecureClientIpSource;
 use axum::{
     routing::{
+        delete,
         get,
-        post
+        patch,
+        post,
+        put
     }, Router
 };
 
@@ -20,41 +36,75 @@ use secured::secured;
 use authenticate::authenticate;
 
 use crate::{
+    audit::AuditLog,
     auth::JWTKeys,
     cloudflare::TurnstileState,
     configuration::Config,
     credentials::PasswordRequirements,
-    database::DatabaseClientWithCaching,
+    database::DatabaseClient,
     email::EmailHandler,
+    event_bus::EventBus,
+    gateway::GatewayHub,
+    rate_limit::{
+        RateLimitLayer,
+        RateLimiter
+    },
+    snowflake::SnowflakeGenerator,
     state::{
+        AccountState,
+        AdminState,
         AddUserFromJWTTokenState,
         ApiState,
         AuthenticationState,
+        DmsState,
+        GatewayState,
+        GuildsState,
+        MessagesState,
+        MfaState,
+        PasskeysState,
+        PasswordResetState,
         RefreshState,
-        RegisterUserCredentialBasedState
+        RegisterUserCredentialBasedState,
+        SessionsState
     }
 };
 
 pub async fn configure_routes(
     jwt_keys: &JWTKeys,
-    db_client: DatabaseClientWithCaching,
+    db_client: DatabaseClient,
     password_requirements: PasswordRequirements,
     turnstile_state: &TurnstileState,
     email_handler: &EmailHandler,
+    id_generator: &SnowflakeGenerator,
+    gateway_hub: &GatewayHub,
+    event_bus: &EventBus,
+    rate_limiter: &RateLimiter,
     config: &Config
 ) -> Router {
+    let audit_log = AuditLog::spawn(db_client.clone(), config.audit.channel_capacity);
+
     let authentication_state = AuthenticationState {
         jwt_keys: jwt_keys.clone(),
         jwt_config: config.jwt_config.clone(),
         db_client: db_client.clone(),
         password_requirements: password_requirements.clone(),
+        sessions_config: config.sessions.clone(),
+        mfa_config: config.mfa.clone(),
+        passkeys_config: config.passkeys.clone(),
+        login_protection_config: config.login_protection.clone(),
+        turnstile_state: turnstile_state.clone(),
+        email_handler: email_handler.clone(),
+        audit_log: audit_log.clone(),
+        client_ip_is_trusted: client_ip_is_trusted(config),
     };
     let refresh_state = RefreshState {
         jwt_keys: jwt_keys.clone(),
         jwt_config: config.jwt_config.clone(),
         db_client: db_client.clone(),
+        audit_log: audit_log.clone(),
     };
     let register_user_credential_based_state = RegisterUserCredentialBasedState {
+        db_client: db_client.clone(),
         email_handler: email_handler.clone(),
         turnstile_state: turnstile_state.clone(),
         jwt_keys: jwt_keys.clone(),
@@ -64,6 +114,84 @@ pub async fn configure_routes(
     let add_user_from_jwt_token_state = AddUserFromJWTTokenState {
         db_client: db_client.clone(),
         jwt_keys: jwt_keys.clone(),
+        id_generator: id_generator.clone(),
+        audit_log: audit_log.clone(),
+    };
+
+    let messages_state = MessagesState {
+        db_client: db_client.clone(),
+        id_generator: id_generator.clone(),
+        messages_config: config.messages.clone(),
+        event_bus: event_bus.clone(),
+    };
+
+    let gateway_state = GatewayState {
+        hub: gateway_hub.clone(),
+        gateway_config: config.gateway.clone(),
+        db_client: db_client.clone(),
+        guilds_config: config.guilds.clone(),
+    };
+
+    let guilds_state = GuildsState {
+        db_client: db_client.clone(),
+        id_generator: id_generator.clone(),
+        guilds_config: config.guilds.clone(),
+        event_bus: event_bus.clone(),
+    };
+
+    let dms_state = DmsState {
+        db_client: db_client.clone(),
+        id_generator: id_generator.clone(),
+        dms_config: config.dms.clone(),
+        event_bus: event_bus.clone(),
+    };
+
+    let sessions_state = SessionsState {
+        db_client: db_client.clone(),
+        event_bus: event_bus.clone(),
+        sessions_config: config.sessions.clone(),
+    };
+
+    let mfa_state = MfaState {
+        db_client: db_client.clone(),
+        mfa_config: config.mfa.clone(),
+        audit_log: audit_log.clone(),
+    };
+
+    let passkeys_state = PasskeysState {
+        db_client: db_client.clone(),
+        passkeys_config: config.passkeys.clone(),
+        audit_log: audit_log.clone(),
+    };
+
+    let password_reset_state = PasswordResetState {
+        db_client: db_client.clone(),
+        email_handler: email_handler.clone(),
+        turnstile_state: turnstile_state.clone(),
+        jwt_keys: jwt_keys.clone(),
+        event_bus: event_bus.clone(),
+        password_requirements: password_requirements.clone(),
+        audit_log: audit_log.clone(),
+    };
+
+    let account_state = AccountState {
+        db_client: db_client.clone(),
+        email_handler: email_handler.clone(),
+        jwt_keys: jwt_keys.clone(),
+        event_bus: event_bus.clone(),
+        password_requirements: password_requirements.clone(),
+        audit_log: audit_log.clone(),
+        audit_config: config.audit.clone(),
+    };
+
+    let admin_state = AdminState {
+        db_client: db_client.clone(),
+        email_handler: email_handler.clone(),
+        jwt_keys: jwt_keys.clone(),
+        event_bus: event_bus.clone(),
+        password_requirements: password_requirements.clone(),
+        admin_config: config.admin.clone(),
+        audit_log: audit_log.clone(),
     };
 
     let api_state = ApiState {
@@ -71,19 +199,171 @@ pub async fn configure_routes(
         refresh: Arc::new(refresh_state),
         register_user_credential_based: Arc::new(register_user_credential_based_state),
         add_user_from_jwt: Arc::new(add_user_from_jwt_token_state),
+        messages: Arc::new(messages_state),
+        gateway: Arc::new(gateway_state),
+        guilds: Arc::new(guilds_state),
+        dms: Arc::new(dms_state),
+        sessions: Arc::new(sessions_state),
+        mfa: Arc::new(mfa_state),
+        passkeys: Arc::new(passkeys_state),
+        password_reset: Arc::new(password_reset_state),
+        account: Arc::new(account_state),
+        admin: Arc::new(admin_state),
+        jwt_keys: jwt_keys.clone(),
+        db_client: db_client.clone(),
     };
 
+    let rate_limit_layer = RateLimitLayer::new(
+        rate_limiter.clone(),
+        jwt_keys.clone()
+    );
+
     Router::new()
         .route("/", get(hello_world))
         .route("/authenticate", post(authenticate))
             .with_state(api_state.clone())
+        .route("/authenticate/mfa", post(authenticate_mfa::authenticate_mfa))
+            .with_state(api_state.clone())
+        .route("/authenticate/passkey/challenge", post(passkeys::authentication_challenge))
+            .with_state(api_state.clone())
+        .route("/authenticate/passkey", post(passkeys::authenticate_passkey))
+            .with_state(api_state.clone())
         .route("/refresh_token", post(refresh_token::refresh_token))
             .with_state(api_state.clone())
+        .route("/logout", post(sessions::logout))
+            .with_state(api_state.clone())
+        .route("/logout_all", post(sessions::logout_all))
+            .with_state(api_state.clone())
+        .route("/sessions", get(sessions::get_sessions))
+            .with_state(api_state.clone())
+        .route("/sessions/:session_id", delete(sessions::delete_session))
+            .with_state(api_state.clone())
+        .route("/me/password", post(account::change_password))
+            .with_state(api_state.clone())
+        .route("/me/email", post(account::change_email))
+            .with_state(api_state.clone())
+        .route("/email_change/confirm", post(account::confirm_email_change))
+            .with_state(api_state.clone())
+        .route("/me/security_log", get(account::get_security_log))
+            .with_state(api_state.clone())
+        .route("/me/mfa", get(mfa::get_mfa))
+            .with_state(api_state.clone())
+        .route("/me/mfa/totp", post(mfa::enroll_totp).delete(mfa::disable_totp))
+            .with_state(api_state.clone())
+        .route("/me/mfa/totp/confirm", post(mfa::confirm_totp))
+            .with_state(api_state.clone())
+        .route("/me/mfa/recovery_codes", post(mfa::regenerate_recovery_codes))
+            .with_state(api_state.clone())
+        .route("/me/passkeys", get(passkeys::get_passkeys).post(passkeys::register_passkey))
+            .with_state(api_state.clone())
+        .route("/me/passkeys/challenge", post(passkeys::registration_challenge))
+            .with_state(api_state.clone())
+        .route("/me/passkeys/:credential_id", delete(passkeys::delete_passkey))
+            .with_state(api_state.clone())
         .route("/secured", get(secured))
+            .with_state(api_state.clone())
+        .route("/.well-known/jwks.json", get(jwks::jwks))
             .with_state(jwt_keys.clone())
         .route("/register_user", post(registration::register_user))
             .with_state(api_state.clone())
         .route("/verify_email", get(registration::add_user_from_jwt_token))
             .with_state(api_state.clone())
+        .route("/verify_email/resend", post(registration::resend_verification_email))
+            .with_state(api_state.clone())
+        .route("/password_reset/request", post(password_reset::request_password_reset))
+            .with_state(api_state.clone())
+        .route("/password_reset/confirm", post(password_reset::confirm_password_reset))
+            .with_state(api_state.clone())
+        .route(
+            "/channels/:channel_id/messages",
+            post(messages::create_message).get(messages::get_messages)
+        )
+            .with_state(api_state.clone())
+        .route(
+            "/channels/:channel_id/messages/:message_id",
+            patch(messages::edit_message).delete(messages::delete_message)
+        )
+            .with_state(api_state.clone())
+        .route("/gateway", get(gateway::gateway))
+            .with_state(api_state.clone())
+        .route("/guilds", post(guilds::create_guild).get(guilds::get_guilds))
+            .with_state(api_state.clone())
+        .route("/guilds/:guild_id", delete(guilds::delete_guild))
+            .with_state(api_state.clone())
+        .route(
+            "/guilds/:guild_id/channels",
+            get(guilds::get_channels)
+                .post(guilds::create_channel)
+                .patch(guilds::reorder_channels)
+        )
+            .with_state(api_state.clone())
+        .route(
+            "/guilds/:guild_id/channels/:channel_id",
+            patch(guilds::edit_channel).delete(guilds::delete_channel)
+        )
+            .with_state(api_state.clone())
+        .route("/guilds/:guild_id/invites", post(guilds::create_invite))
+            .with_state(api_state.clone())
+        .route(
+            "/guilds/:guild_id/channels/:channel_id/permissions/:target_id",
+            put(guilds::set_channel_overwrite).delete(guilds::delete_channel_overwrite)
+        )
+            .with_state(api_state.clone())
+        .route("/guilds/:guild_id/roles", get(guilds::get_roles).post(guilds::create_role))
+            .with_state(api_state.clone())
+        .route(
+            "/guilds/:guild_id/roles/:role_id",
+            patch(guilds::edit_role).delete(guilds::delete_role)
+        )
+            .with_state(api_state.clone())
+        .route(
+            "/guilds/:guild_id/members/:user_id/roles/:role_id",
+            put(guilds::add_member_role).delete(guilds::remove_member_role)
+        )
+            .with_state(api_state.clone())
+        .route("/invites/:code", post(guilds::join_guild))
+            .with_state(api_state.clone())
+        .route("/dms", post(dms::open_dm).get(dms::get_dms))
+            .with_state(api_state.clone())
+        .route(
+            "/dms/:channel_id/recipients/:user_id",
+            put(dms::add_dm_recipient).delete(dms::remove_dm_recipient)
+        )
+            .with_state(api_state.clone())
+        .route("/blocks", get(dms::get_blocks))
+            .with_state(api_state.clone())
+        .route("/blocks/:user_id", put(dms::block_user).delete(dms::unblock_user))
+            .with_state(api_state.clone())
+        .route("/admin/users", get(admin::search_users))
+            .with_state(api_state.clone())
+        .route("/admin/users/:user_id", get(admin::get_user).delete(admin::delete_user))
+            .with_state(api_state.clone())
+        .route("/admin/users/:user_id/sessions", get(admin::get_user_sessions))
+            .with_state(api_state.clone())
+        .route("/admin/users/:user_id/ban", put(admin::ban_user).delete(admin::unban_user))
+            .with_state(api_state.clone())
+        .route("/admin/users/:user_id/verify", post(admin::verify_user))
+            .with_state(api_state.clone())
+        .route("/admin/users/:user_id/password_reset", post(admin::reset_password))
+            .with_state(api_state.clone())
+        .route("/admin/audit_log", get(admin::get_audit_log))
+            .with_state(api_state.clone())
+        .route_layer(rate_limit_layer)
+        .layer(client_ip_source(config).into_extension())
+}
+
+/// Behind Cloudflare the peer is one of its edge servers and the client is in `CF-Connecting-IP`, the header can only
+/// be trusted when requests from anywhere else are refused
+pub fn client_ip_source(config: &Config) -> SecureClientIpSource {
+    if config.cloudflare.allow_non_cloudflare_ips {
+        SecureClientIpSource::ConnectInfo
+    } else {
+        SecureClientIpSource::CfConnectingIp
+    }
 }
 
+/// Only `CF-Connecting-IP` is known to be the client, while other traffic is allowed the peer may still be
+/// a Cloudflare edge server shared by many clients
+pub fn client_ip_is_trusted(config: &Config) -> bool {
+    !config.cloudflare.allow_non_cloudflare_ips
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    response::Response,
+    Json
+};
+use axum_client_ip::SecureClientIp;
+use axum_extra::TypedHeader;
+use headers::UserAgent;
+use tracing::{
+    error,
+    info,
+    warn
+};
+use uuid::Uuid;
+
+use crate::{
+    app_objects::PasskeyCeremony,
+    audit::AuditClient,
+    database::PasskeyStore,
+    passkeys::{
+        decode_base64url,
+        take_challenge,
+        verify_assertion,
+        PasskeyAuthenticationPayload,
+        PasskeyError
+    },
+    routes::authenticate::{
+        record_login_failure_event,
+        start_session
+    },
+    state::AuthenticationState
+};
+
+
+/// Logs the owner of the passkey in with the assertion `navigator.credentials.get()` returned.
+/// Passkeys verify the user on the device, so no TOTP code is asked for.
+pub async fn authenticate_passkey(
+    State(authentication_state): State<Arc<AuthenticationState>>,
+    client_ip: Option<SecureClientIp>,
+    user_agent: Option<TypedHeader<UserAgent>>,
+    Json(payload): Json<PasskeyAuthenticationPayload>,
+) -> Result<Response, PasskeyError> {
+    let request_id = Uuid::new_v4().to_string();
+    info!("request_id: {}, authenticating user with a passkey", request_id);
+    let db_client = &authentication_state.db_client;
+
+    let challenge = take_challenge(
+        db_client,
+        payload.challenge_id,
+        PasskeyCeremony::Authentication,
+        None
+    ).await?;
+    let credential_id = decode_base64url(&payload.credential.id)?;
+    let passkey = db_client.get_passkey(&credential_id).await.map_err(
+        |e| {
+            error!("request_id: {}, db_error: {:?}", request_id, e);
+            e
+        }
+    )?.ok_or(PasskeyError::PasskeyNotFound)?;
+
+    let sign_count = verify_assertion(
+        &authentication_state.passkeys_config,
+        &challenge,
+        &passkey,
+        &payload.credential
+    ).map_err(
+        |e| {
+            let audit_client = AuditClient::new(client_ip.as_ref(), user_agent.as_ref());
+            record_login_failure_event(&authentication_state, Some(passkey.user_id), &audit_client, "invalid_passkey");
+            match e {
+                PasskeyError::CloneDetected => warn!(
+                    "request_id: {}, signature counter of a passkey of user {} didn't increase", request_id, passkey.user_id
+                ),
+                _ => info!("request_id: {}, rejected passkey assertion: {:?}", request_id, e),
+            }
+            e
+        }
+    )?;
+
+    // A concurrent login with the same counter value is a clone as well
+    let updated = db_client.update_passkey_sign_count(
+        &credential_id,
+        passkey.sign_count,
+        sign_count as i64,
+        chrono::Utc::now().timestamp()
+    ).await?;
+    if !updated {
+        return Err(PasskeyError::CloneDetected);
+    }
+    info!("request_id: {}, passkey of user {} accepted", request_id, passkey.user_id);
+
+    Ok(start_session(
+        &authentication_state,
+        passkey.user_id,
+        payload.device_label.as_deref(),
+        "passkey",
+        client_ip,
+        user_agent,
+        &request_id
+    ).await?)
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        PasskeyCeremony,
+        PasskeyChallenge
+    },
+    database::PasskeyStore,
+    passkeys::{
+        generate_challenge,
+        request_options,
+        PasskeyError,
+        RequestOptions
+    },
+    state::AuthenticationState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct AuthenticationChallengeBody {
+    // Sent back with the assertion
+    pub challenge_id: Uuid,
+    pub public_key: RequestOptions,
+}
+
+/// First step of a passkey login, returns the options for `navigator.credentials.get()`.
+/// The authenticator picks the account, so nothing about the user is needed yet.
+pub async fn authentication_challenge(
+    State(authentication_state): State<Arc<AuthenticationState>>,
+) -> Result<Json<AuthenticationChallengeBody>, PasskeyError> {
+    let request_id = Uuid::new_v4();
+    let passkeys_config = &authentication_state.passkeys_config;
+
+    let challenge = PasskeyChallenge::new(
+        PasskeyCeremony::Authentication,
+        generate_challenge(),
+        None,
+        chrono::Utc::now().timestamp()
+    );
+    authentication_state.db_client.create_passkey_challenge(
+        &challenge,
+        passkeys_config.challenge_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error storing the passkey challenge: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(AuthenticationChallengeBody {
+        challenge_id: challenge.id,
+        public_key: request_options(passkeys_config, &challenge),
+    }))
+}
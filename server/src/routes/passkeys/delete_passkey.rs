@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    auth::AuthClaims,
+    database::PasskeyStore,
+    passkeys::{
+        decode_base64url,
+        PasskeyError
+    },
+    state::PasskeysState
+};
+
+
+/// `credential_id` is the base64url id from the passkey list
+pub async fn delete_passkey(
+    State(passkeys_state): State<Arc<PasskeysState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    Path(credential_id): Path<String>,
+) -> Result<StatusCode, PasskeyError> {
+    let credential_id = decode_base64url(&credential_id)?;
+    let deleted = passkeys_state.db_client.delete_passkey(
+        &credential_id,
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error deleting a passkey of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+    if !deleted {
+        return Err(PasskeyError::PasskeyNotFound);
+    }
+
+    passkeys_state.audit_log.record(
+        AuditEvent::new(AuditEventType::PasskeyRemoved, Some(claims.user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
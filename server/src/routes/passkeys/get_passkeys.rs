@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    app_objects::Passkey,
+    auth::AuthClaims,
+    database::PasskeyStore,
+    passkeys::{
+        encode_base64url,
+        PasskeyError
+    },
+    state::PasskeysState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct PasskeyBody {
+    // Base64url credential id, used to delete the passkey
+    pub id: String,
+    pub label: String,
+    pub created_at: i64,
+    pub last_used_at: Option<i64>,
+}
+
+impl From<&Passkey> for PasskeyBody {
+    fn from(passkey: &Passkey) -> Self {
+        Self {
+            id: encode_base64url(&passkey.credential_id),
+            label: passkey.label.clone(),
+            created_at: passkey.created_at,
+            last_used_at: passkey.last_used_at,
+        }
+    }
+}
+
+pub async fn get_passkeys(
+    State(passkeys_state): State<Arc<PasskeysState>>,
+    claims: AuthClaims,
+) -> Result<Json<Vec<PasskeyBody>>, PasskeyError> {
+    let passkeys = passkeys_state.db_client.get_user_passkeys(
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error fetching passkeys of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(passkeys.iter().map(PasskeyBody::from).collect()))
+}
@@ -0,0 +1,13 @@
+mod registration_challenge;
+mod register_passkey;
+mod get_passkeys;
+mod delete_passkey;
+mod authentication_challenge;
+mod authenticate_passkey;
+
+pub use registration_challenge::registration_challenge;
+pub use register_passkey::register_passkey;
+pub use get_passkeys::get_passkeys;
+pub use delete_passkey::delete_passkey;
+pub use authentication_challenge::authentication_challenge;
+pub use authenticate_passkey::authenticate_passkey;
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json
+};
+use serde_json::json;
+use tracing::{
+    error,
+    info
+};
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType,
+        Passkey,
+        PasskeyCeremony
+    },
+    audit::AuditClient,
+    auth::AuthClaims,
+    database::PasskeyStore,
+    passkeys::{
+        take_challenge,
+        verify_registration,
+        PasskeyError,
+        PasskeyRegistrationPayload
+    },
+    state::PasskeysState
+};
+
+use super::get_passkeys::PasskeyBody;
+
+
+/// Stores the credential `navigator.credentials.create()` returned for the challenge
+pub async fn register_passkey(
+    State(passkeys_state): State<Arc<PasskeysState>>,
+    claims: AuthClaims,
+    audit_client: AuditClient,
+    Json(payload): Json<PasskeyRegistrationPayload>,
+) -> Result<(StatusCode, Json<PasskeyBody>), PasskeyError> {
+    let request_id = Uuid::new_v4();
+    let db_client = &passkeys_state.db_client;
+    let passkeys_config = &passkeys_state.passkeys_config;
+
+    let challenge = take_challenge(
+        db_client,
+        payload.challenge_id,
+        PasskeyCeremony::Registration,
+        Some(claims.user_id)
+    ).await?;
+    let registration = verify_registration(
+        passkeys_config,
+        &challenge,
+        &payload.credential
+    ).map_err(
+        |e| {
+            info!("|{}| Rejected passkey registration of user {}: {:?}", request_id, claims.user_id, e);
+            e
+        }
+    )?;
+
+    let label = payload.label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .unwrap_or("Passkey")
+        .chars()
+        .take(passkeys_config.max_label_length)
+        .collect();
+    let passkey = Passkey::new(
+        registration.credential_id,
+        claims.user_id,
+        registration.public_key,
+        registration.sign_count as i64,
+        label,
+        chrono::Utc::now().timestamp()
+    );
+    let inserted = db_client.insert_passkey(&passkey).await.map_err(
+        |e| {
+            error!("|{}| Error storing the passkey: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if !inserted {
+        return Err(PasskeyError::PasskeyAlreadyRegistered);
+    }
+
+    passkeys_state.audit_log.record(
+        AuditEvent::new(AuditEventType::PasskeyAdded, Some(claims.user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+            .with_metadata(json!({
+                "label": passkey.label,
+            }))
+    );
+    Ok((StatusCode::CREATED, Json(PasskeyBody::from(&passkey))))
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        PasskeyCeremony,
+        PasskeyChallenge
+    },
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        PasskeyStore,
+        UserStore
+    },
+    passkeys::{
+        creation_options,
+        generate_challenge,
+        CreationOptions,
+        PasskeyError
+    },
+    state::PasskeysState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct RegistrationChallengeBody {
+    // Sent back with the new credential
+    pub challenge_id: Uuid,
+    pub public_key: CreationOptions,
+}
+
+/// First step of adding a passkey, returns the options for `navigator.credentials.create()`
+pub async fn registration_challenge(
+    State(passkeys_state): State<Arc<PasskeysState>>,
+    claims: AuthClaims,
+) -> Result<Json<RegistrationChallengeBody>, PasskeyError> {
+    let request_id = Uuid::new_v4();
+    let db_client = &passkeys_state.db_client;
+    let passkeys_config = &passkeys_state.passkeys_config;
+
+    let user = db_client.get_user_by_id(claims.user_id).await?
+        .ok_or(DatabaseError::UserNotFound(claims.user_id))?;
+    let existing_passkeys = db_client.get_user_passkeys(claims.user_id).await?;
+
+    let challenge = PasskeyChallenge::new(
+        PasskeyCeremony::Registration,
+        generate_challenge(),
+        Some(claims.user_id),
+        chrono::Utc::now().timestamp()
+    );
+    db_client.create_passkey_challenge(
+        &challenge,
+        passkeys_config.challenge_ttl_s
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error storing the passkey challenge: {:?}", request_id, e);
+            e
+        }
+    )?;
+
+    Ok(Json(RegistrationChallengeBody {
+        challenge_id: challenge.id,
+        public_key: creation_options(
+            passkeys_config,
+            &challenge,
+            user.id,
+            &user.email,
+            &user.username,
+            &existing_passkeys
+        ),
+    }))
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Form
+};
+use tracing::{
+    error,
+    info
+};
+use uuid::Uuid;
+
+use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType
+    },
+    audit::AuditClient,
+    credentials::{
+        Password,
+        PasswordError,
+        SaltMode
+    },
+    database::{
+        CredentialStore,
+        DatabaseError,
+        SessionStore
+    },
+    event_bus::BusEvent,
+    password_reset::{
+        take_password_reset_token,
+        PasswordResetConfirmPayload,
+        PasswordResetError
+    },
+    state::PasswordResetState
+};
+
+
+/// Sets the new password from a reset link and logs the user out on every device
+pub async fn confirm_password_reset(
+    State(password_reset_state): State<Arc<PasswordResetState>>,
+    audit_client: AuditClient,
+    Form(payload): Form<PasswordResetConfirmPayload>,
+) -> Result<Response, PasswordResetError> {
+    let request_id = Uuid::new_v4();
+    let db_client = &password_reset_state.db_client;
+
+    // Checked before the link is used up, so a rejected password can be corrected
+    let password = Password::new(
+        &payload.password,
+        &password_reset_state.password_requirements
+    );
+    password.check_if_password_is_valid_based_on_requirements()?;
+
+    let user_id = take_password_reset_token(
+        db_client,
+        &password_reset_state.jwt_keys,
+        &payload.token
+    ).await?;
+
+    let prepared_password = password.hash_and_salt_password(&SaltMode::Generate).await.map_err(
+        |e| PasswordError::HashError(e.to_string())
+    )?;
+    let db_res = db_client.update_password_hash_and_salt(
+        user_id,
+        &prepared_password.password_hash,
+        &prepared_password.salt
+    ).await;
+    match db_res {
+        Ok(()) => {},
+        // The account was deleted after the link was sent
+        Err(DatabaseError::UserNotFound(_)) => return Err(PasswordResetError::InvalidToken),
+        Err(e) => {
+            error!("|{}| Error updating the password of user {}: {:?}", request_id, user_id, e);
+            return Err(e.into());
+        },
+    }
+
+    db_client.delete_user_sessions(user_id).await.map_err(
+        |e| {
+            error!("|{}| Error revoking the sessions of user {}: {:?}", request_id, user_id, e);
+            e
+        }
+    )?;
+    info!("|{}| Password of user {} reset, all sessions revoked", request_id, user_id);
+    if let Err(e) = password_reset_state.event_bus.publish(
+        BusEvent::SessionsRevoked { user_id, except_session_id: None }
+    ).await {
+        error!("|{}| Error publishing the revoked sessions of user {}: {:?}", request_id, user_id, e);
+    }
+
+    password_reset_state.audit_log.record(
+        AuditEvent::new(AuditEventType::PasswordReset, Some(user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
+    Ok(
+        (StatusCode::OK, "Password reset").into_response()
+    )
+}
@@ -0,0 +1,5 @@
+mod request_password_reset;
+mod confirm_password_reset;
+
+pub use request_password_reset::request_password_reset;
+pub use confirm_password_reset::confirm_password_reset;
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Form
+};
+use email_address::EmailAddress;
+use tracing::{
+    error,
+    info
+};
+use uuid::Uuid;
+
+use crate::{
+    cloudflare::TurnstileResult,
+    database::UserStore,
+    email::idempotency_key,
+    password_reset::{
+        create_password_reset_token,
+        PasswordResetError,
+        PasswordResetRequestPayload
+    },
+    state::PasswordResetState
+};
+
+
+/// Emails a reset link to the address if it belongs to a user. The response doesn't depend on
+/// the address, so it can't be used to find out who has an account.
+pub async fn request_password_reset(
+    State(password_reset_state): State<Arc<PasswordResetState>>,
+    password_reset_form: Form<PasswordResetRequestPayload>,
+) -> Result<Response, PasswordResetError> {
+    let request_id = Uuid::new_v4();
+
+    let turnstile_result = password_reset_state.turnstile_state.verify_turnstile_from_request(
+        &password_reset_form
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error verifying turnstile: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if turnstile_result == TurnstileResult::Denied {
+        return Err(PasswordResetError::TurnstileDenied);
+    }
+
+    // Looking the user up and sending the email would make known addresses answer slower
+    let email = password_reset_form.0.email;
+    tokio::spawn(async move {
+        let res = send_password_reset_email(&password_reset_state, &email, request_id).await;
+        if let Err(e) = res {
+            error!("|{}| Error sending password reset email: {:?}", request_id, e);
+        }
+    });
+
+    Ok(
+        (StatusCode::OK, "Password reset requested").into_response()
+    )
+}
+
+async fn send_password_reset_email(
+    password_reset_state: &PasswordResetState,
+    email: &str,
+    request_id: Uuid
+) -> Result<(), PasswordResetError> {
+    if !EmailAddress::is_valid(email) {
+        return Ok(());
+    }
+    let db_client = &password_reset_state.db_client;
+    let email_handler = &password_reset_state.email_handler;
+
+    let Some(user_id) = db_client.get_user_id_by_email(email).await? else {
+        info!("|{}| Password reset requested for an unknown email", request_id);
+        return Ok(());
+    };
+    let Ok(recipient) = email.parse() else {
+        return Ok(());
+    };
+
+    let token = create_password_reset_token(
+        db_client,
+        &password_reset_state.jwt_keys,
+        email_handler.state.password_reset_email_state.password_reset_jwt_lifetime_s,
+        user_id
+    ).await?;
+    let idempotency_key = idempotency_key("password_reset", &token);
+    let message = email_handler.create_password_reset_email(recipient, token)?;
+    email_handler.enqueue_email(idempotency_key, message).await?;
+
+    info!("|{}| Password reset email enqueued for user {}", request_id, user_id);
+    Ok(())
+}
@@ -11,18 +11,28 @@ use axum::http::header::SET_COOKIE;
 use axum_extra::TypedHeader;
 
 use headers::Cookie;
-use jsonwebtoken::{
-    encode, Header
+use serde_json::json;
+use tracing::{
+    error,
+    warn
 };
-use tracing::error;
+use uuid::Uuid;
 
 use crate::{
+    app_objects::{
+        AuditEvent,
+        AuditEventType,
+        RefreshTokenRotation
+    },
+    audit::AuditClient,
     auth::{
+        check_account_status,
         extract_token_from_cookie,
         AuthError,
         ClaimType,
         AuthClaims,
     },
+    database::RefreshTokenStore,
     state::RefreshState
 };
 
@@ -31,6 +41,7 @@ use anyhow::Result;
 
 pub async fn refresh_token(
     State(refresh_state): State<Arc<RefreshState>>,
+    audit_client: AuditClient,
     cookies: TypedHeader<Cookie>,
 ) -> Result<HeaderMap, AuthError> {
     //let cookie = headers.typed_get::<Cookie>().ok_or(AuthError::MissingCredentials)?;
@@ -55,10 +66,55 @@ pub async fn refresh_token(
     }
 
     let user_id = claims.user_id;
+    let (jti, session_id) = match (claims.jti, claims.sid) {
+        (Some(jti), Some(session_id)) => (jti, session_id),
+        _ => return Err(AuthError::InvalidToken),
+    };
+
+    // The token isn't rotated for banned users, it works again once the ban is lifted
+    let user = check_account_status(&refresh_state.db_client, user_id).await?;
+
+    // The presented token is exchanged for a new one of the same family
+    let new_jti = Uuid::new_v4();
+    let refresh_claims = AuthClaims::new_refresh(
+        refresh_state.jwt_config.refresh_key_lifetime_s,
+        user_id,
+        new_jti,
+        session_id
+    );
+    // Both tokens are signed before the rotation, once it is stored the old token can't be presented again
+    let claims = AuthClaims::new_access(
+        refresh_state.jwt_config.access_key_lifetime_s,
+        user_id,
+        Some(session_id)
+    ).with_admin(user.admin);
+
+    let encoding_res = refresh_state.jwt_keys.encode(&claims);
+    if encoding_res.is_err() {
+        let encoding_error = encoding_res.unwrap_err();
+        error!("encoding error: {:?}", encoding_error);
+        return Err(AuthError::TokenCreation);
+    }
+
+    let token = encoding_res.unwrap();
+
+    let encoding_res = refresh_state.jwt_keys.encode(&refresh_claims);
+    if encoding_res.is_err() {
+        let encoding_error = encoding_res.unwrap_err();
+        error!("encoding error: {:?}", encoding_error);
+        return Err(AuthError::TokenCreation);
+    }
+
+    let refresh_token = encoding_res.unwrap();
 
     let db_res = refresh_state
-        .db_client.cached_get_user_refresh_token(
-            user_id
+        .db_client.rotate_refresh_token(
+            jti,
+            session_id,
+            user_id,
+            new_jti,
+            refresh_claims.iat,
+            refresh_claims.exp
         )
         .await;
     if db_res.is_err() {
@@ -67,34 +123,25 @@ pub async fn refresh_token(
         let error = db_error.to_auth_error();
         return Err(error);
     }
-    let real_refresh_token = db_res.unwrap();
-
-    if real_refresh_token.is_none() {
-        return Err(AuthError::InvalidToken);
-    }
-
-    if real_refresh_token.unwrap() != bearer_token {
-        return Err(AuthError::InvalidToken);
-    }
-
-    let claims = AuthClaims::new_access(
-        refresh_state.jwt_config.access_key_lifetime_s,
-        user_id
-    );
-
-    let encoding_res = encode(
-        &Header::default(),
-        &claims,
-        &refresh_state.jwt_keys.encoding
-    );
-    if encoding_res.is_err() {
-        let encoding_error = encoding_res.unwrap_err();
-        error!("encoding error: {:?}", encoding_error);
-        return Err(AuthError::TokenCreation);
+    let audit_event = |event_type| {
+        AuditEvent::new(event_type, Some(user_id))
+            .with_client(audit_client.ip.clone(), audit_client.user_agent.clone())
+            .with_metadata(json!({
+                "session_id": session_id,
+            }))
+    };
+    match db_res.unwrap() {
+        RefreshTokenRotation::Rotated => {
+            refresh_state.audit_log.record(audit_event(AuditEventType::TokenRefresh));
+        },
+        RefreshTokenRotation::Reused => {
+            warn!("refresh token {} of user {} was reused, session {} got revoked", jti, user_id, session_id);
+            refresh_state.audit_log.record(audit_event(AuditEventType::RefreshTokenReused));
+            return Err(AuthError::InvalidToken);
+        },
+        RefreshTokenRotation::Invalid => return Err(AuthError::InvalidToken),
     }
 
-    let token = encoding_res.unwrap();
-
     let cookie = cookie::Cookie::build(
         (ClaimType::Access.as_str(), format!("Bearer {}", token))
     )
@@ -104,12 +151,24 @@ pub async fn refresh_token(
         //.secure(true)
         .http_only(false)
         .build();
+    let refresh_cookie = cookie::Cookie::build(
+        (ClaimType::Refresh.as_str(), format!("Bearer {}", refresh_token))
+    )
+        .max_age(cookie::time::Duration::seconds(
+            refresh_state.jwt_config.refresh_key_lifetime_s
+        ))
+        //.secure(true)
+        .http_only(false)
+        .build();
 
     let mut headers = HeaderMap::new();
-    headers.insert(
+    headers.append(
         SET_COOKIE, HeaderValue::from_str(&cookie.to_string()).unwrap()
     );
+    headers.append(
+        SET_COOKIE, HeaderValue::from_str(&refresh_cookie.to_string()).unwrap()
+    );
 
     Ok(headers)
 
-}
\ No newline at end of file
+}
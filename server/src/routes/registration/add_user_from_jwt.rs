@@ -6,7 +6,7 @@ use axum::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
-use crate::{registration::{CredentialBasedRegistrationPayload, UserRegistrationFormJWT}, state::AddUserFromJWTTokenState};
+use crate::{app_objects::{AuditEvent, AuditEventType}, audit::AuditClient, database::{DatabaseError, UserStore}, registration::take_verification_token, state::AddUserFromJWTTokenState};
 
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -14,27 +14,31 @@ pub struct AddUserFromJWTToken {
     token: String,
 }
 
-// work in progress
-
 pub async fn add_user_from_jwt_token(
     State(add_user_from_jwt_token_state): State<Arc<AddUserFromJWTTokenState>>,
+    audit_client: AuditClient,
     Form(jwt_token): Form<AddUserFromJWTToken>,
 ) -> Result<Response, Response> {
     let request_id = uuid::Uuid::new_v4();
 
     let db_client = &add_user_from_jwt_token_state.db_client;
     let jwt_keys = &add_user_from_jwt_token_state.jwt_keys;
+    let id_generator = &add_user_from_jwt_token_state.id_generator;
 
-    let registration_payload: UserRegistrationFormJWT = jwt_keys.verify_token_and_return_claims(
-        &jwt_token.token,
+    // Taken before anything else, of two requests with the same link only one gets the registration
+    let pending_registration = take_verification_token(
+        db_client,
+        jwt_keys,
+        &jwt_token.token
     ).await.map_err(
         |e| {
             error!("|{}| Error verifying token: {:?}", request_id, e);
             e.into_response()
         }
     )?;
+    let email = pending_registration.email.clone();
 
-    let user_id_from_db = db_client.cached_get_user_id_by_email(&registration_payload.email).await
+    let user_id_from_db = db_client.get_user_id_by_email(&email).await
         .map_err(
             |e| {
                 error!("|{}| Error getting user_id from db: {:?}", request_id, e);
@@ -47,18 +51,35 @@ pub async fn add_user_from_jwt_token(
         );
     }
     
-    let user = registration_payload.into_user();
-    todo!("id handling");
-    db_client.cached_insert_user(&user).await
-        .map_err(
-            |e| {
-                error!("|{}| Error inserting user into db: {:?}", request_id, e);
-                e.into_response()
-            }
-        )?;
+    let user_id = id_generator.generate().map_err(
+        |e| {
+            error!("|{}| Error generating user id: {:?}", request_id, e);
+            e.into_response()
+        }
+    )?;
+
+    let user = pending_registration.into_user(user_id);
+    let db_res = db_client.insert_user(&user).await;
+    if let Err(DatabaseError::EmailAlreadyExists(_)) = db_res {
+        // The address was verified through another registration in the meantime
+        return Ok(
+            (StatusCode::BAD_REQUEST, "User already exists").into_response()
+        );
+    }
+    db_res.map_err(
+        |e| {
+            error!("|{}| Error inserting user into db: {:?}", request_id, e);
+            e.into_response()
+        }
+    )?;
+
+    add_user_from_jwt_token_state.audit_log.record(
+        AuditEvent::new(AuditEventType::EmailVerified, Some(user_id))
+            .with_client(audit_client.ip, audit_client.user_agent)
+    );
 
     Ok(format!(
         "User with email {} added to the database",
-        registration_payload.email
+        email
     ).into_response())
 }
@@ -1,5 +1,7 @@
 mod register_user_credential_based;
 mod add_user_from_jwt;
+mod resend_verification_email;
 
 pub use register_user_credential_based::register_user;
-pub use add_user_from_jwt::add_user_from_jwt_token;
\ No newline at end of file
+pub use add_user_from_jwt::add_user_from_jwt_token;
+pub use resend_verification_email::resend_verification_email;
@@ -3,7 +3,11 @@ use std::sync::Arc;
 use crate::{
     cloudflare::TurnstileResult,
     credentials::Password,
-    registration::CredentialBasedRegistrationPayload,
+    database::PendingRegistrationStore,
+    registration::{
+        enqueue_verification_email,
+        CredentialBasedRegistrationPayload
+    },
     state::RegisterUserCredentialBasedState
 };
 use axum::{
@@ -74,33 +78,44 @@ pub async fn register_user(
     };
 
 
-    let jwt_encoded_registration_form = registration_form.into_jwt_form(
-        email_handler.state.verification_email_state.email_verification_jwt_lifetime_s
-        ).await.map_err(
-            |e| {
-                error!("Error creating jwt form: {:?}", e);
-                e.into_response()
-            }
-        )?.into_jwt_token(
-            &register_user_credential_based_state.jwt_keys
-        ).map_err(
-            |e| {
-                error!("Error encoding jwt: {:?}", e);
-                e.into_response()
-            }
-        )?;
-
-    let email = email_handler.create_email_verification_email(
-        user_email.parse().unwrap(),
-        jwt_encoded_registration_form
-    ).map_err(
+    let db_client = &register_user_credential_based_state.db_client;
+    let verification_email_state = &email_handler.state.verification_email_state;
+    let pending_registration = registration_form.into_pending_registration().await.map_err(
         |e| {
-            error!("Error creating email: {:?}", e);
+            error!("|{}| Error creating pending registration: {:?}", request_id, e);
+            e.into_response()
+        }
+    )?;
+    db_client.create_pending_registration(
+        &pending_registration,
+        verification_email_state.pending_registration_lifetime_s.max(1) as u64
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error storing pending registration: {:?}", request_id, e);
+            e.into_response()
+        }
+    )?;
+    // The email sent here counts as the first one, a resend right after it is refused
+    db_client.start_verification_email_cooldown(
+        &user_email,
+        verification_email_state.resend_cooldown_s.max(1) as u64
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error starting verification email cooldown: {:?}", request_id, e);
             e.into_response()
         }
     )?;
 
-    email_handler.send_email(email).await.unwrap();
+    enqueue_verification_email(
+        email_handler,
+        &register_user_credential_based_state.jwt_keys,
+        &pending_registration
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error enqueueing verification email: {:?}", request_id, e);
+            e.into_response()
+        }
+    )?;
 
 
     return Ok(
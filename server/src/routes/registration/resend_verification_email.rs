@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{
+        IntoResponse,
+        Response
+    },
+    Form
+};
+use email_address::EmailAddress;
+use tracing::{
+    error,
+    info
+};
+use uuid::Uuid;
+
+use crate::{
+    cloudflare::TurnstileResult,
+    database::PendingRegistrationStore,
+    registration::{
+        enqueue_verification_email,
+        RegistrationError,
+        ResendVerificationEmailPayload
+    },
+    state::RegisterUserCredentialBasedState
+};
+
+
+/// Emails a new verification link if the address has a pending registration. The cooldown applies to
+/// every address and the response doesn't depend on the registration, so neither tells who registered.
+pub async fn resend_verification_email(
+    State(register_user_credential_based_state): State<Arc<RegisterUserCredentialBasedState>>,
+    resend_form: Form<ResendVerificationEmailPayload>,
+) -> Result<Response, RegistrationError> {
+    let request_id = Uuid::new_v4();
+
+    let turnstile_result = register_user_credential_based_state.turnstile_state.verify_turnstile_from_request(
+        &resend_form
+    ).await.map_err(
+        |e| {
+            error!("|{}| Error verifying turnstile: {:?}", request_id, e);
+            e
+        }
+    )?;
+    if turnstile_result == TurnstileResult::Denied {
+        return Err(RegistrationError::TurnstileDenied);
+    }
+
+    let email = resend_form.0.email;
+    if !EmailAddress::is_valid(&email) {
+        return Ok(
+            (StatusCode::OK, "Verification email requested").into_response()
+        );
+    }
+    let db_client = &register_user_credential_based_state.db_client;
+    let resend_cooldown_s = register_user_credential_based_state.email_handler.state
+        .verification_email_state.resend_cooldown_s;
+    if let Some(retry_after) = db_client.start_verification_email_cooldown(&email, resend_cooldown_s.max(1) as u64).await? {
+        return Err(RegistrationError::ResendCooldown(retry_after));
+    }
+
+    // Looking the registration up and signing the link would make pending addresses answer slower
+    tokio::spawn(async move {
+        let res = resend_to_pending_registration(&register_user_credential_based_state, &email, request_id).await;
+        if let Err(e) = res {
+            error!("|{}| Error resending verification email: {:?}", request_id, e);
+        }
+    });
+
+    Ok(
+        (StatusCode::OK, "Verification email requested").into_response()
+    )
+}
+
+async fn resend_to_pending_registration(
+    register_user_credential_based_state: &RegisterUserCredentialBasedState,
+    email: &str,
+    request_id: Uuid
+) -> Result<(), RegistrationError> {
+    let db_client = &register_user_credential_based_state.db_client;
+    let Some(pending_registration) = db_client.get_pending_registration_by_email(email).await? else {
+        info!("|{}| Verification email requested for an address without a pending registration", request_id);
+        return Ok(());
+    };
+    enqueue_verification_email(
+        &register_user_credential_based_state.email_handler,
+        &register_user_credential_based_state.jwt_keys,
+        &pending_registration
+    ).await?;
+    info!("|{}| Verification email enqueued for {}", request_id, pending_registration.id);
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{
+        Path,
+        State
+    },
+    http::StatusCode
+};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        SessionStore
+    },
+    event_bus::BusEvent,
+    state::SessionsState
+};
+
+
+/// Logs one of the caller's devices out, its refresh token stops working right away and its gateway
+/// connections are closed. Access tokens already issued for the session stay valid until they expire.
+pub async fn delete_session(
+    State(sessions_state): State<Arc<SessionsState>>,
+    claims: AuthClaims,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, DatabaseError> {
+    sessions_state.db_client.delete_session(
+        session_id,
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            if !matches!(e, DatabaseError::SessionNotFound(_)) {
+                error!("Error deleting session {} of user {}: {:?}", session_id, claims.user_id, e);
+            }
+            e
+        }
+    )?;
+
+    let user_id = claims.user_id;
+    if let Err(e) = sessions_state.event_bus.publish(
+        BusEvent::SessionRevoked { user_id, session_id }
+    ).await {
+        error!("Error publishing the revoked session {} of user {}: {:?}", session_id, user_id, e);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    Json
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    app_objects::Session,
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        SessionStore
+    },
+    state::SessionsState
+};
+
+
+#[derive(Debug, Serialize)]
+pub struct SessionBody {
+    #[serde(flatten)]
+    pub session: Session,
+    // The session of the access token used for the request
+    pub current: bool,
+}
+
+/// Lists the devices the caller is logged in on
+pub async fn get_sessions(
+    State(sessions_state): State<Arc<SessionsState>>,
+    claims: AuthClaims,
+) -> Result<Json<Vec<SessionBody>>, DatabaseError> {
+    let now = chrono::Utc::now().timestamp();
+    let sessions = sessions_state.db_client.get_user_sessions(
+        claims.user_id,
+        now
+    ).await.map_err(
+        |e| {
+            error!("Error fetching sessions of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    let sessions = sessions
+        .into_iter()
+        .map(|session| SessionBody {
+            current: claims.sid == Some(session.id),
+            session,
+        })
+        .collect();
+    Ok(Json(sessions))
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{
+        HeaderMap,
+        HeaderValue,
+        StatusCode
+    }
+};
+use reqwest::header::SET_COOKIE;
+use tracing::error;
+
+use crate::{
+    auth::{
+        AuthClaims,
+        ClaimType
+    },
+    database::{
+        DatabaseError,
+        SessionStore
+    },
+    event_bus::BusEvent,
+    state::SessionsState
+};
+
+
+/// Tells the browser to drop both token cookies
+pub(super) fn remove_token_cookies() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for claim_type in [ClaimType::Access, ClaimType::Refresh] {
+        let cookie = cookie::Cookie::build((claim_type.as_str(), ""))
+            .max_age(cookie::time::Duration::ZERO)
+            .build();
+        headers.append(
+            SET_COOKIE, HeaderValue::from_str(&cookie.to_string()).unwrap()
+        );
+    }
+    headers
+}
+
+/// Ends the session of the access token used for the request
+pub async fn logout(
+    State(sessions_state): State<Arc<SessionsState>>,
+    claims: AuthClaims,
+) -> Result<(StatusCode, HeaderMap), DatabaseError> {
+    // Tokens issued before sessions existed have nothing to revoke
+    if let Some(session_id) = claims.sid {
+        let db_res = sessions_state.db_client.delete_session(
+            session_id,
+            claims.user_id
+        ).await;
+        match db_res {
+            Ok(()) | Err(DatabaseError::SessionNotFound(_)) => {},
+            Err(e) => {
+                error!("Error deleting session {} of user {}: {:?}", session_id, claims.user_id, e);
+                return Err(e);
+            }
+        }
+
+        let user_id = claims.user_id;
+        if let Err(e) = sessions_state.event_bus.publish(
+            BusEvent::SessionRevoked { user_id, session_id }
+        ).await {
+            error!("Error publishing the revoked session {} of user {}: {:?}", session_id, user_id, e);
+        }
+    }
+
+    Ok((StatusCode::NO_CONTENT, remove_token_cookies()))
+}
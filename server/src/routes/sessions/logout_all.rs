@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::State,
+    http::{
+        HeaderMap,
+        StatusCode
+    }
+};
+use tracing::error;
+
+use crate::{
+    auth::AuthClaims,
+    database::{
+        DatabaseError,
+        SessionStore
+    },
+    event_bus::BusEvent,
+    state::SessionsState
+};
+
+use super::logout::remove_token_cookies;
+
+
+/// Ends every session of the caller, the current one included
+pub async fn logout_all(
+    State(sessions_state): State<Arc<SessionsState>>,
+    claims: AuthClaims,
+) -> Result<(StatusCode, HeaderMap), DatabaseError> {
+    sessions_state.db_client.delete_user_sessions(
+        claims.user_id
+    ).await.map_err(
+        |e| {
+            error!("Error deleting sessions of user {}: {:?}", claims.user_id, e);
+            e
+        }
+    )?;
+
+    let user_id = claims.user_id;
+    if let Err(e) = sessions_state.event_bus.publish(
+        BusEvent::SessionsRevoked { user_id, except_session_id: None }
+    ).await {
+        error!("Error publishing the revoked sessions of user {}: {:?}", user_id, e);
+    }
+
+    Ok((StatusCode::NO_CONTENT, remove_token_cookies()))
+}
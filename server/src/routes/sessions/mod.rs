@@ -0,0 +1,9 @@
+mod get_sessions;
+mod delete_session;
+mod logout;
+mod logout_all;
+
+pub use get_sessions::get_sessions;
+pub use delete_session::delete_session;
+pub use logout::logout;
+pub use logout_all::logout_all;
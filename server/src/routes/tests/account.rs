@@ -0,0 +1,244 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        account::create_email_change_token,
+        app_objects::{
+            PendingEmailChange,
+            User
+        },
+        auth::{
+            AuthClaims,
+            JWTKeys
+        },
+        database::{
+            SessionStore,
+            UserStore
+        },
+        routes::tests::{
+            authenticate::tests::get_authenticate_endpoint_response_and_status_code,
+            preparation::{
+                get_access_token_cookie,
+                get_axum_app,
+                get_config,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    const PASSWORD: &str = "test_password123*&@#ABC";
+
+    async fn send_request(
+        app: Router,
+        uri: &str,
+        cookie: &str,
+        body: serde_json::Value
+    ) -> (String, u16) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("Cookie", cookie)
+                    .body(Body::from(body.to_string()))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (String::from_utf8(body_bytes.to_vec()).unwrap(), status_code)
+    }
+
+    /// Logs user 420 in again, returns an access cookie of the new session
+    async fn login(app: Router) -> String {
+        let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
+            PASSWORD,
+            "test_email",
+            app
+        ).await;
+        assert_eq!(status_code, 200);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+
+        let jwt_keys = JWTKeys::new(&get_config()).unwrap();
+        let refresh_claims: AuthClaims = jwt_keys.verify_token_and_return_claims(
+            response["refresh_token"].as_str().unwrap()
+        ).await.unwrap();
+        let access_claims = AuthClaims::new_access(60, 420, refresh_claims.sid);
+        format!("authorization_token=Bearer {}", jwt_keys.encode(&access_claims).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let _ = db_client.delete_user_sessions(420).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = login(app.clone()).await;
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(db_client.get_user_sessions(420, now).await.unwrap().len(), 2);
+
+        let new_password = "new_password456*&@#XYZ";
+        let (response, status_code) = send_request(app.clone(), "/me/password", &access_cookie, serde_json::json!({
+            "current_password": "wrong_password123*&@#ABC",
+            "new_password": new_password
+        })).await;
+        assert_eq!(status_code, 403);
+        assert!(response.contains("2500"));
+
+        let (response, status_code) = send_request(app.clone(), "/me/password", &access_cookie, serde_json::json!({
+            "current_password": PASSWORD,
+            "new_password": "short"
+        })).await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("1100"));
+
+        let (_, status_code) = send_request(app.clone(), "/me/password", &access_cookie, serde_json::json!({
+            "current_password": PASSWORD,
+            "new_password": new_password
+        })).await;
+        assert_eq!(status_code, 204);
+
+        // Only the session that changed the password is left
+        let sessions = db_client.get_user_sessions(420, now).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(PASSWORD, "test_email", app.clone()).await;
+        assert_ne!(status_code, 200);
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(new_password, "test_email", app.clone()).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wrong_current_passwords_lock_the_account() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.login_protection.max_failures_per_email = 3;
+        config.login_protection.notify_on_lockout = false;
+        let app = get_axum_app(&context, Some(config)).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+
+        for _ in 0..3 {
+            let (response, status_code) = send_request(app.clone(), "/me/password", &access_cookie, serde_json::json!({
+                "current_password": "wrong_password123*&@#ABC",
+                "new_password": "new_password456*&@#XYZ"
+            })).await;
+            assert_eq!(status_code, 403);
+            assert!(response.contains("2500"));
+        }
+
+        // The right password doesn't help anymore, on either route or at the login
+        let (response, status_code) = send_request(app.clone(), "/me/password", &access_cookie, serde_json::json!({
+            "current_password": PASSWORD,
+            "new_password": "new_password456*&@#XYZ"
+        })).await;
+        assert_eq!(status_code, 429);
+        assert!(response.contains("1303"));
+        let (response, status_code) = send_request(app.clone(), "/me/email", &access_cookie, serde_json::json!({
+            "password": PASSWORD,
+            "new_email": "new@example.com"
+        })).await;
+        assert_eq!(status_code, 429);
+        assert!(response.contains("1303"));
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(PASSWORD, "test_email", app).await;
+        assert_eq!(status_code, 429);
+    }
+
+    #[tokio::test]
+    async fn test_change_email_is_rejected() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let _ = db_client.delete_user(421).await;
+        let other_user = User {
+            id: 421,
+            email: "taken@example.com".to_string(),
+            ..User::default()
+        };
+        db_client.insert_user(&other_user).await.unwrap();
+        let access_cookie = get_access_token_cookie(420);
+
+        let cases = [
+            ("wrong_password123*&@#ABC", "new@example.com", 403, "2500"),
+            (PASSWORD, "not an email", 400, "2502"),
+            (PASSWORD, "taken@example.com", 409, "2501"),
+        ];
+        for (password, new_email, expected_status_code, expected_error) in cases {
+            let (response, status_code) = send_request(app.clone(), "/me/email", &access_cookie, serde_json::json!({
+                "password": password,
+                "new_email": new_email
+            })).await;
+            assert_eq!(status_code, expected_status_code);
+            assert!(response.contains(expected_error));
+        }
+
+        db_client.delete_user(420).await.unwrap();
+        db_client.delete_user(421).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_confirm_email_change() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+
+        let jwt_keys = JWTKeys::new(&get_config()).unwrap();
+        let email_change = PendingEmailChange::new(420, "test_email".to_string(), "new@example.com".to_string());
+        let token = create_email_change_token(&db_client, &jwt_keys, 60, &email_change).await.unwrap();
+
+        let confirm = |token: String| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method(Method::POST)
+                            .uri("/email_change/confirm")
+                            .header("content-type", "application/x-www-form-urlencoded")
+                            .body(Body::from(format!("token={}", token)))
+                            .unwrap()
+                    )
+                    .await
+                    .unwrap();
+                let status_code = response.status().as_u16();
+                let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (String::from_utf8(body_bytes.to_vec()).unwrap(), status_code)
+            }
+        };
+
+        let (response, status_code) = confirm(token.clone()).await;
+        assert_eq!(status_code, 200, "{}", response);
+        let (response, status_code) = confirm(token).await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("2503"));
+
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(PASSWORD, "test_email", app.clone()).await;
+        assert_ne!(status_code, 200);
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(PASSWORD, "new@example.com", app.clone()).await;
+        assert_eq!(status_code, 200);
+
+        // A change that was requested before the email changed again is dropped
+        let token = create_email_change_token(&db_client, &jwt_keys, 60, &email_change).await.unwrap();
+        let (_, status_code) = confirm(token).await;
+        assert_eq!(status_code, 400);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+}
@@ -9,59 +9,76 @@ pub(super) mod tests {
     use chrono::NaiveDate;
     use pretty_assertions::assert_eq;
     use reqwest::blocking::multipart::Form;
-    use serial_test::serial;
     use tower::util::ServiceExt;
     use tower_http::trace::{DefaultMakeSpan, TraceLayer};
     use axum::body::to_bytes;
     use crate::{
-        app_objects::User, auth::JWTKeys, credentials::{
+        app_objects::{PendingRegistration, User}, auth::JWTKeys, credentials::{
             Password,
             SaltMode
         }, logs, registration::UserRegistrationFormJWT, routes::tests::{authenticate::tests::get_authenticate_endpoint_response_and_status_code, preparation::{
             get_axum_app,
             get_config,
-            get_db_client
-        }}
+            TestContext
+        }},
+        database::{PendingRegistrationStore, UserStore}
     };
 
     use urlencoding;
 
+    async fn delete_user_by_email(context: &TestContext, email: &str) {
+        let db_client = context.db_client();
+        let user_id = db_client.get_user_id_by_email(email).await.unwrap();
+        if let Some(user_id) = user_id {
+            db_client.delete_user(user_id).await.unwrap();
+        }
+    }
+
+    fn get_verification_request(jwt: &str) -> Request<Body> {
+        let url = format!("/verify_email?token={}", jwt);
+        Request::builder()
+            .method(Method::GET)
+            .uri(url)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn get_registration_jwt(context: &TestContext, email: &str, jwt_keys: &JWTKeys) -> String {
+        let pending_registration = PendingRegistration::new(
+            email.to_string(),
+            "test_username".to_string(),
+            "test_password_hash".to_string(),
+            "test_password_salt".to_string(),
+            NaiveDate::from_ymd(2024, 10, 27)
+        );
+        context.db_client().create_pending_registration(&pending_registration, 60).await.unwrap();
+        let user_registration_form_jwt = UserRegistrationFormJWT {
+            registration_id: pending_registration.id,
+            exp: u32::MAX as i64,
+        };
+
+        jwt_keys.encode(&user_registration_form_jwt).unwrap()
+    }
+
     #[tokio::test]
-    #[serial]
     async fn test_add_user_from_jwt_success() {
+        let context = TestContext::new();
         //logs::setup_logging().unwrap();
         let mut config = get_config();
         let jwt_keys = JWTKeys::new(&config).unwrap();
         config.cloudflare.allow_invalid_turnstile = true;
-        let app = get_axum_app(Some(config)).await;
+        let app = get_axum_app(&context, Some(config)).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
             trace_layer.clone()
         );
-        let user_registration_form_jwt = UserRegistrationFormJWT {
-            email: "test_email1".to_string(),
-            username: "test_username".to_string(),
-            password_hash: "test_password_hash".to_string(),
-            password_salt: "test_password_salt".to_string(),
-            date_of_birth: NaiveDate::from_ymd(2024, 10, 27),
-            exp: u32::MAX as i64,
-        };
+        delete_user_by_email(&context, "test_email1").await;
 
-        let jwt = user_registration_form_jwt.into_jwt_token(
-            &jwt_keys
-        ).unwrap();
+        let jwt = get_registration_jwt(&context, "test_email1", &jwt_keys).await;
 
-        let url = format!("/verify_email?token={}", jwt);
-        todo!("consult database to get user id and check if user is in db");
-        let request = Request::builder()
-            .method(Method::GET)
-            .uri(url)
-            .body(Body::empty())
-            .unwrap();
-        
         let response = app
-            .oneshot(request)
+            .oneshot(get_verification_request(&jwt))
             .await
             .unwrap();
         let status_code = response.status();
@@ -71,12 +88,43 @@ pub(super) mod tests {
 
         println!("response: {}", body_str);
 
-        assert_eq!("User added", body_str);
+        assert_eq!("User with email test_email1 added to the database", body_str);
         assert_eq!(status_code.as_u16(), 200);
 
+        let db_client = context.db_client();
+        let user_id = db_client.get_user_id_by_email("test_email1").await.unwrap();
+        assert!(user_id.is_some());
+        let user = db_client.get_user_by_id(user_id.unwrap()).await.unwrap().unwrap();
+        assert_eq!(user.username, "test_username");
+        assert_ne!(user.id, 0);
 
-        //logs::setup_logging().unwrap();
+        delete_user_by_email(&context, "test_email1").await;
+    }
+
+    #[tokio::test]
+    async fn test_add_user_from_jwt_concurrent_verification() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let app = get_axum_app(&context, Some(config)).await;
+        delete_user_by_email(&context, "test_email2").await;
+
+        let jwt = get_registration_jwt(&context, "test_email2", &jwt_keys).await;
+
+        let (first_response, second_response) = tokio::join!(
+            app.clone().oneshot(get_verification_request(&jwt)),
+            app.clone().oneshot(get_verification_request(&jwt))
+        );
+        let mut status_codes = vec![
+            first_response.unwrap().status().as_u16(),
+            second_response.unwrap().status().as_u16(),
+        ];
+        status_codes.sort();
+
+        assert_eq!(status_codes, vec![200, 400]);
 
+        delete_user_by_email(&context, "test_email2").await;
     }
 
 }
\ No newline at end of file
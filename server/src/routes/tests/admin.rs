@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tower::util::ServiceExt;
+    use crate::{
+        app_objects::{
+            DmChannel,
+            User
+        },
+        database::{
+            DmStore,
+            GuildStore,
+            UserStore
+        },
+        routes::tests::{
+            authenticate::tests::get_authenticate_endpoint_response_and_status_code,
+            preparation::{
+                create_test_guild,
+                get_access_token_cookie,
+                get_admin_access_token_cookie,
+                get_axum_app,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    const PASSWORD: &str = "test_password123*&@#ABC";
+    const ADMIN_ID: i64 = 1;
+
+    async fn send(
+        app: Router,
+        method: Method,
+        uri: &str,
+        cookie: String,
+        body: Option<serde_json::Value>
+    ) -> (serde_json::Value, u16) {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("cookie", cookie)
+            .header("content-type", "application/json");
+        let body = match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        };
+        let response = app
+            .oneshot(request.body(body).unwrap())
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code)
+    }
+
+    fn actions(entries: &serde_json::Value) -> Vec<&str> {
+        entries.as_array().unwrap().iter().map(|entry| entry["action"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_admin_routes_need_the_admin_claim() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let (response, status_code) = send(
+            app.clone(), Method::GET, "/admin/users?id=420", get_access_token_cookie(420), None
+        ).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1307");
+
+        let (_, status_code) = send(
+            app.clone(), Method::GET, "/admin/audit_log", get_access_token_cookie(420), None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let admin_cookie = get_admin_access_token_cookie(ADMIN_ID);
+        let (_, status_code) = send(app.clone(), Method::GET, "/admin/users?id=420", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 200);
+        let (response, status_code) = send(app.clone(), Method::GET, "/admin/users", admin_cookie, None).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2700");
+    }
+
+    #[tokio::test]
+    async fn test_admin_moderation() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let refresh_token = get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let refresh_cookie = format!("refresh_token=Bearer {}", refresh_token);
+        let admin_cookie = get_admin_access_token_cookie(ADMIN_ID);
+
+        let (response, status_code) = send(
+            app.clone(), Method::GET, "/admin/users?email=TEST_EM", admin_cookie.clone(), None
+        ).await;
+        assert_eq!(status_code, 200);
+        assert!(response.as_array().unwrap().iter().any(|user| user["id"] == 420));
+
+        let (response, status_code) = send(
+            app.clone(), Method::GET, "/admin/users/420", admin_cookie.clone(), None
+        ).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response["email"], "test_email");
+        assert!(response.get("password_hash").is_none());
+
+        let (response, status_code) = send(
+            app.clone(), Method::GET, "/admin/users/420/sessions", admin_cookie.clone(), None
+        ).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response.as_array().unwrap().len(), 1);
+
+        // Bans
+        let (response, status_code) = send(
+            app.clone(), Method::PUT, "/admin/users/420/ban", admin_cookie.clone(), Some(json!({ "reason": "  " }))
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2702");
+        let (response, status_code) = send(
+            app.clone(), Method::PUT, &format!("/admin/users/{}/ban", ADMIN_ID), admin_cookie.clone(), Some(json!({ "reason": "spam" }))
+        ).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "2704");
+
+        let (_, status_code) = send(
+            app.clone(), Method::PUT, "/admin/users/420/ban", admin_cookie.clone(), Some(json!({ "reason": "spam", "duration_s": 3600 }))
+        ).await;
+        assert_eq!(status_code, 204);
+        let (response, status_code) = send(app.clone(), Method::GET, "/secured", get_access_token_cookie(420), None).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1305");
+        let (response, _) = send(app.clone(), Method::GET, "/admin/users/420", admin_cookie.clone(), None).await;
+        assert_eq!(response["banned"], true);
+        assert_eq!(response["ban"]["reason"], "spam");
+
+        let (_, status_code) = send(app.clone(), Method::DELETE, "/admin/users/420/ban", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = send(app.clone(), Method::GET, "/secured", get_access_token_cookie(420), None).await;
+        assert_eq!(status_code, 200);
+
+        // Forced password reset
+        let (_, status_code) = send(
+            app.clone(), Method::POST, "/admin/users/420/password_reset", admin_cookie.clone(), None
+        ).await;
+        assert_eq!(status_code, 202);
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(PASSWORD, "test_email", app.clone()).await;
+        assert_eq!(status_code, 401);
+        let (_, status_code) = send(app.clone(), Method::POST, "/refresh_token", refresh_cookie, None).await;
+        assert_eq!(status_code, 400);
+
+        let (response, status_code) = send(
+            app.clone(), Method::GET, "/admin/audit_log?target_user_id=420&limit=5", admin_cookie.clone(), None
+        ).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(
+            actions(&response),
+            vec!["reset_password", "unban_user", "view_user", "ban_user", "view_sessions"]
+        );
+        assert_eq!(response[3]["details"]["reason"], "spam");
+        assert_eq!(response[3]["admin_id"], ADMIN_ID);
+
+        // Deletion
+        let (_, status_code) = send(app.clone(), Method::DELETE, "/admin/users/420", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 204);
+        assert_eq!(db_client.get_user_by_id(420).await.unwrap(), None);
+        assert_eq!(db_client.get_user_id_by_email("test_email").await.unwrap(), None);
+        let (_, status_code) = send(app.clone(), Method::GET, "/admin/users/420", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 404);
+
+        let (response, _) = send(
+            app.clone(), Method::GET, "/admin/audit_log?target_user_id=420&limit=1", admin_cookie, None
+        ).await;
+        assert_eq!(actions(&response), vec!["delete_user"]);
+        assert_eq!(response[0]["details"]["email"], "test_email");
+    }
+
+    #[tokio::test]
+    async fn test_admin_verify_user() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let admin_cookie = get_admin_access_token_cookie(ADMIN_ID);
+        let _ = db_client.delete_user(480).await;
+        let user = User {
+            id: 480,
+            email: "unverified@example.com".to_string(),
+            verified: false,
+            created_at: chrono::Utc::now().timestamp(),
+            ..User::default()
+        };
+        db_client.insert_user(&user).await.unwrap();
+
+        let (_, status_code) = send(app.clone(), Method::POST, "/admin/users/480/verify", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 204);
+        assert!(db_client.get_user_by_id(480).await.unwrap().unwrap().verified);
+
+        let (_, status_code) = send(app.clone(), Method::POST, "/admin/users/481/verify", admin_cookie, None).await;
+        assert_eq!(status_code, 404);
+
+        db_client.delete_user(480).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_delete_guild_owner() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let admin_cookie = get_admin_access_token_cookie(ADMIN_ID);
+        let now = chrono::Utc::now().timestamp();
+        for user_id in [490, 491, 492] {
+            let user = User {
+                id: user_id,
+                email: format!("user{}@example.com", user_id),
+                created_at: now,
+                ..User::default()
+            };
+            db_client.insert_user(&user).await.unwrap();
+        }
+        create_test_guild(&db_client, 4900, 4901, 490, &[491]).await;
+        create_test_guild(&db_client, 4910, 4911, 491, &[490]).await;
+        let group = DmChannel::new_group(4920, 490, &[491, 492], now);
+        db_client.open_dm_channel(&group, 60).await.unwrap();
+        db_client.block_user(492, 490, now).await.unwrap();
+
+        // The guild has to be deleted or handed over first
+        let (response, status_code) = send(app.clone(), Method::DELETE, "/admin/users/490", admin_cookie.clone(), None).await;
+        assert_eq!(status_code, 409);
+        assert_eq!(response["error"], "1217");
+        assert!(db_client.get_user_by_id(490).await.unwrap().is_some());
+        assert!(db_client.get_dm_channel_by_id(4920, 60).await.unwrap().unwrap().is_recipient(490));
+
+        db_client.delete_guild(4900).await.unwrap();
+        let (_, status_code) = send(app.clone(), Method::DELETE, "/admin/users/490", admin_cookie, None).await;
+        assert_eq!(status_code, 204);
+        assert_eq!(db_client.get_guild_member(4910, 490, 60).await.unwrap(), None);
+        assert!(!db_client.get_user_guilds(490).await.unwrap().iter().any(|guild| guild.id == 4910));
+        // The recipient that joined first takes the group over
+        let group = db_client.get_dm_channel_by_id(4920, 60).await.unwrap().unwrap();
+        assert_eq!(group.owner_id, Some(491));
+        assert_eq!(group.recipient_ids, vec![491, 492]);
+        assert!(db_client.get_user_blocks(492).await.unwrap().is_empty());
+    }
+}
@@ -9,7 +9,6 @@ pub(super) mod tests {
         }, Router
     };
     use pretty_assertions::assert_eq;
-    use serial_test::serial;
     use tower::util::ServiceExt;
     use tower_http::trace::{DefaultMakeSpan, TraceLayer};
     use axum::body::to_bytes;
@@ -19,11 +18,12 @@ pub(super) mod tests {
             Password,
             SaltMode
         },
+        database::UserStore,
         logs,
         routes::tests::preparation::{
             get_axum_app,
             get_config,
-            get_db_client
+            TestContext
         }
     };
 
@@ -64,12 +64,11 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_authenticate() {
-        let db_client = get_db_client().await;
-        db_client.redis_delete_password_hash_by_user_id(420).await.unwrap();
+        let context = TestContext::new();
+        let db_client = context.db_client();
 
-        let app = get_axum_app(None).await;
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -95,14 +94,14 @@ pub(super) mod tests {
             salt: salt_string.to_string(),
             ..User::default()
         };
-        let res = db_client.postgres_delete_user_by_id(420).await;
+        let res = db_client.delete_user(420).await;
         if res.is_err() {
             match res.err().unwrap() {
                 crate::database::DatabaseError::UserNotFound(_) => {},
                 _ => panic!("Error deleting user")
             }
         }
-        db_client.postgres_insert_user(&user).await.unwrap();
+        db_client.insert_user(&user).await.unwrap();
 
         let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
             test_password.get_password(),
@@ -111,19 +110,18 @@ pub(super) mod tests {
         ).await;
         println!("Response: {}", response);
 
-        db_client.postgres_delete_user_by_id(420).await.unwrap();
+        db_client.delete_user(420).await.unwrap();
 
         assert_eq!(status_code, 200);
     }
 
 
     #[tokio::test]
-    #[serial]
     async fn test_authenticate_invalid_password() {
-        let db_client = get_db_client().await;
-        db_client.redis_delete_password_hash_by_user_id(420).await.unwrap();
+        let context = TestContext::new();
+        let db_client = context.db_client();
 
-        let app = get_axum_app(None).await;
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -149,14 +147,14 @@ pub(super) mod tests {
             salt: salt_string.to_string(),
             ..User::default()
         };
-        let res = db_client.postgres_delete_user_by_id(420).await;
+        let res = db_client.delete_user(420).await;
         if res.is_err() {
             match res.err().unwrap() {
                 crate::database::DatabaseError::UserNotFound(_) => {},
                 _ => panic!("Error deleting user")
             }
         }
-        db_client.postgres_insert_user(&user).await.unwrap();
+        db_client.insert_user(&user).await.unwrap();
 
         let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
             format!("{}INVALID", test_password.get_password()).as_str(),
@@ -165,7 +163,7 @@ pub(super) mod tests {
         ).await;
         println!("Response: {}", response);
 
-        db_client.postgres_delete_user_by_id(420).await.unwrap();
+        db_client.delete_user(420).await.unwrap();
 
         let response: serde_json::Value = serde_json::from_str(&response).unwrap();
         assert_eq!(response["error"], "Wrong credentials");
@@ -173,12 +171,11 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_authenticate_invalid_email() {
-        let db_client = get_db_client().await;
-        db_client.redis_delete_password_hash_by_user_id(420).await.unwrap();
+        let context = TestContext::new();
+        let db_client = context.db_client();
 
-        let app = get_axum_app(None).await;
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -204,14 +201,14 @@ pub(super) mod tests {
             salt: salt_string.to_string(),
             ..User::default()
         };
-        let res = db_client.postgres_delete_user_by_id(420).await;
+        let res = db_client.delete_user(420).await;
         if res.is_err() {
             match res.err().unwrap() {
                 crate::database::DatabaseError::UserNotFound(_) => {},
                 _ => panic!("Error deleting user")
             }
         }
-        db_client.postgres_insert_user(&user).await.unwrap();
+        db_client.insert_user(&user).await.unwrap();
 
         let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
             test_password.get_password(),
@@ -220,7 +217,7 @@ pub(super) mod tests {
         ).await;
         println!("Response: {}", response);
 
-        db_client.postgres_delete_user_by_id(420).await.unwrap();
+        db_client.delete_user(420).await.unwrap();
         let response: serde_json::Value = serde_json::from_str(&response).unwrap();
         assert_eq!(response["error"], "Wrong credentials");
 
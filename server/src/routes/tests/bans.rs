@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        app_objects::{
+            User,
+            UserBan
+        },
+        database::{
+            BanStore,
+            UserStore
+        },
+        routes::tests::{
+            authenticate::tests::get_authenticate_endpoint_response_and_status_code,
+            preparation::{
+                get_access_token_cookie,
+                get_axum_app,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    const PASSWORD: &str = "test_password123*&@#ABC";
+
+    async fn send(
+        app: Router,
+        uri: &str,
+        cookie: String
+    ) -> (serde_json::Value, u16) {
+        let method = if uri == "/refresh_token" { Method::POST } else { Method::GET };
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("cookie", cookie)
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code)
+    }
+
+    #[tokio::test]
+    async fn test_banned_user_is_rejected_everywhere() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let refresh_token = get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let refresh_cookie = format!("refresh_token=Bearer {}", refresh_token);
+
+        let now = chrono::Utc::now().timestamp();
+        db_client.ban_user(&UserBan::new(420, "spam".to_string(), now, Some(now + 3600))).await.unwrap();
+
+        // Access tokens issued before the ban stop working right away
+        let (response, status_code) = send(app.clone(), "/secured", get_access_token_cookie(420)).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1305");
+        assert_eq!(response["reason"], "spam");
+        assert_eq!(response["expires_at"], now + 3600);
+
+        let (response, status_code) = send(app.clone(), "/refresh_token", refresh_cookie.clone()).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1305");
+
+        let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
+            PASSWORD,
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 403);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "1305");
+
+        // The ban doesn't leak to someone without the password
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(
+            "wrong_password",
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 401);
+
+        db_client.unban_user(420).await.unwrap();
+        let (_, status_code) = send(app.clone(), "/secured", get_access_token_cookie(420)).await;
+        assert_eq!(status_code, 200);
+        // The refresh token wasn't rotated while the user was banned
+        let (_, status_code) = send(app.clone(), "/refresh_token", refresh_cookie).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unverified_user_cant_log_in() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let user = db_client.get_user_by_id(420).await.unwrap().unwrap();
+        db_client.delete_user(420).await.unwrap();
+        db_client.insert_user(&User {
+            verified: false,
+            created_at: chrono::Utc::now().timestamp(),
+            ..user
+        }).await.unwrap();
+
+        let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
+            PASSWORD,
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 403);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "1306");
+
+        db_client.delete_user(420).await.unwrap();
+    }
+}
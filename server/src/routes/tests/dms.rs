@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::Method,
+        Router
+    };
+    use pretty_assertions::assert_eq;
+    use crate::{
+        app_objects::{
+            DmChannel,
+            DmKind,
+            Message,
+            User
+        },
+        database::{
+            DmStore,
+            MessageStore,
+            UserStore
+        },
+        routes::tests::preparation::{
+            get_axum_app,
+            get_config,
+            send_request,
+            TestContext
+        }
+    };
+
+    const TEST_USER_IDS: [i64; 4] = [7310, 7311, 7312, 7313];
+
+    /// DMs can only be opened with users that exist, blocks left over from earlier runs are dropped
+    async fn create_test_users(context: &TestContext) {
+        let db_client = context.db_client();
+        for user_id in TEST_USER_IDS {
+            let _ = db_client.delete_user(user_id).await;
+            let user = User {
+                id: user_id,
+                username: format!("dm_user_{}", user_id),
+                email: format!("dm_user_{}@example.com", user_id),
+                ..User::default()
+            };
+            db_client.insert_user(&user).await.unwrap();
+            for blocked_id in TEST_USER_IDS {
+                db_client.unblock_user(user_id, blocked_id).await.unwrap();
+            }
+        }
+    }
+
+    async fn delete_test_users(context: &TestContext) {
+        let db_client = context.db_client();
+        for user_id in TEST_USER_IDS {
+            let _ = db_client.delete_user(user_id).await;
+        }
+    }
+
+    async fn open_dm(app: Router, user_id: i64, recipient_ids: &[i64]) -> (String, u16) {
+        send_request(
+            app,
+            Method::POST,
+            "/dms",
+            Some(user_id),
+            Some(serde_json::json!({ "recipient_ids": recipient_ids }))
+        ).await
+    }
+
+    async fn post_message(app: Router, channel_id: i64, user_id: i64) -> (String, u16) {
+        send_request(
+            app,
+            Method::POST,
+            &format!("/channels/{}/messages", channel_id),
+            Some(user_id),
+            Some(serde_json::json!({ "content": "hello" }))
+        ).await
+    }
+
+    fn get_error_code(response: &str) -> String {
+        let response: serde_json::Value = serde_json::from_str(response).unwrap();
+        response["error"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_direct_dm_with_blocks() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        create_test_users(&context).await;
+        let db_client = context.db_client();
+
+        // The DM outlives the test users, so it can already exist from an earlier run
+        let (response, status_code) = open_dm(app.clone(), 7310, &[7311]).await;
+        assert!(status_code == 201 || status_code == 200);
+        let channel: DmChannel = serde_json::from_str(&response).unwrap();
+        assert_eq!(channel.kind, DmKind::Direct);
+        let (response, status_code) = open_dm(app.clone(), 7311, &[7310]).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(serde_json::from_str::<DmChannel>(&response).unwrap().id, channel.id);
+
+        let (response, status_code) = send_request(app.clone(), Method::GET, "/dms", Some(7311), None).await;
+        assert_eq!(status_code, 200);
+        let channels: Vec<DmChannel> = serde_json::from_str(&response).unwrap();
+        assert!(channels.iter().any(|dm| dm.id == channel.id));
+
+        let (response, status_code) = post_message(app.clone(), channel.id, 7311).await;
+        assert_eq!(status_code, 201);
+        let message = Message::from_json(&response).unwrap();
+
+        let messages_uri = format!("/channels/{}/messages", channel.id);
+        let (_, status_code) = send_request(app.clone(), Method::GET, &messages_uri, Some(7312), None).await;
+        assert_eq!(status_code, 403);
+        let (_, status_code) = post_message(app.clone(), channel.id, 7312).await;
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(app.clone(), Method::PUT, "/blocks/7310", Some(7311), None).await;
+        assert_eq!(status_code, 204);
+        let (response, status_code) = send_request(app.clone(), Method::GET, "/blocks", Some(7311), None).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(serde_json::from_str::<Vec<i64>>(&response).unwrap(), vec![7310]);
+
+        // Neither side can post or open the DM again, but the history stays readable
+        let (response, status_code) = open_dm(app.clone(), 7310, &[7311]).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(get_error_code(&response), "2102");
+        let (response, status_code) = open_dm(app.clone(), 7310, &[7311, 7312]).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(get_error_code(&response), "2102");
+        for user_id in [7310, 7311] {
+            let (_, status_code) = post_message(app.clone(), channel.id, user_id).await;
+            assert_eq!(status_code, 403);
+        }
+        let (response, status_code) = send_request(app.clone(), Method::GET, &messages_uri, Some(7310), None).await;
+        assert_eq!(status_code, 200);
+        assert!(serde_json::from_str::<Vec<Message>>(&response).unwrap().contains(&message));
+
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, "/blocks/7310", Some(7311), None).await;
+        assert_eq!(status_code, 204);
+        let (response, status_code) = post_message(app.clone(), channel.id, 7310).await;
+        assert_eq!(status_code, 201);
+        let reply = Message::from_json(&response).unwrap();
+
+        let (response, status_code) = send_request(app.clone(), Method::PUT, "/blocks/7311", Some(7311), None).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(get_error_code(&response), "2106");
+        let (_, status_code) = open_dm(app.clone(), 7310, &[7399]).await;
+        assert_eq!(status_code, 404);
+
+        for message in [message, reply] {
+            let _ = db_client.delete_message(message.id).await;
+        }
+        delete_test_users(&context).await;
+    }
+
+    #[tokio::test]
+    async fn test_group_dm_recipients() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.dms.max_group_size = 3;
+        let app = get_axum_app(&context, Some(config)).await;
+        create_test_users(&context).await;
+
+        let (response, status_code) = open_dm(app.clone(), 7310, &[7311, 7312, 7313]).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(get_error_code(&response), "2101");
+
+        let (response, status_code) = open_dm(app.clone(), 7310, &[7311, 7312]).await;
+        assert_eq!(status_code, 201);
+        let channel: DmChannel = serde_json::from_str(&response).unwrap();
+        assert_eq!(channel.kind, DmKind::Group);
+        assert_eq!(channel.owner_id, Some(7310));
+        assert_eq!(channel.recipient_ids, vec![7310, 7311, 7312]);
+        let recipient_uri = |user_id: i64| format!("/dms/{}/recipients/{}", channel.id, user_id);
+
+        let (_, status_code) = post_message(app.clone(), channel.id, 7312).await;
+        assert_eq!(status_code, 201);
+
+        let uri = recipient_uri(7313);
+        let (response, status_code) = send_request(app.clone(), Method::PUT, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(get_error_code(&response), "2101");
+
+        // Only the owner can remove others, everyone can leave
+        let uri = recipient_uri(7312);
+        let (response, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(get_error_code(&response), "2104");
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7312), None).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = post_message(app.clone(), channel.id, 7312).await;
+        assert_eq!(status_code, 403);
+
+        let uri = recipient_uri(7313);
+        let (response, status_code) = send_request(app.clone(), Method::PUT, &uri, Some(7312), None).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(get_error_code(&response), "2103");
+        let (response, status_code) = send_request(app.clone(), Method::PUT, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 200);
+        let updated: DmChannel = serde_json::from_str(&response).unwrap();
+        assert_eq!(updated.recipient_ids, vec![7310, 7311, 7313]);
+
+        // Leaving hands the group to the recipient that joined first
+        let uri = recipient_uri(7310);
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7310), None).await;
+        assert_eq!(status_code, 204);
+        let uri = recipient_uri(7313);
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 204);
+
+        // The channel is deleted together with its messages once the last recipient leaves
+        let uri = recipient_uri(7311);
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &uri, Some(7311), None).await;
+        assert_eq!(status_code, 404);
+        let db_client = context.db_client();
+        assert!(db_client.get_channel_messages(channel.id, None, None, 10).await.unwrap().is_empty());
+
+        delete_test_users(&context).await;
+    }
+
+    #[tokio::test]
+    async fn test_direct_dm_recipients_are_fixed() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        create_test_users(&context).await;
+
+        let (response, status_code) = open_dm(app.clone(), 7312, &[7313]).await;
+        assert!(status_code == 201 || status_code == 200);
+        let channel: DmChannel = serde_json::from_str(&response).unwrap();
+
+        let uri = format!("/dms/{}/recipients/7310", channel.id);
+        let (response, status_code) = send_request(app.clone(), Method::PUT, &uri, Some(7312), None).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(get_error_code(&response), "2105");
+
+        for recipient_ids in [vec![], vec![7312], vec![7313, 7313]] {
+            let (response, status_code) = open_dm(app.clone(), 7312, &recipient_ids).await;
+            assert_eq!(status_code, 400);
+            assert_eq!(get_error_code(&response), "2100");
+        }
+
+        delete_test_users(&context).await;
+    }
+}
@@ -0,0 +1,273 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::Method,
+        Router
+    };
+    use pretty_assertions::assert_eq;
+    use crate::{
+        app_objects::{
+            Channel,
+            ChannelKind,
+            Guild,
+            GuildMember,
+            Invite
+        },
+        database::GuildStore,
+        routes::tests::preparation::{
+            get_axum_app,
+            send_request,
+            TestContext
+        }
+    };
+
+    async fn create_guild(app: Router, owner_id: i64) -> Guild {
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            "/guilds",
+            Some(owner_id),
+            Some(serde_json::json!({ "name": "  test guild  " }))
+        ).await;
+        assert_eq!(status_code, 201);
+        serde_json::from_str(&response).unwrap()
+    }
+
+    async fn get_channels(app: Router, guild_id: i64, user_id: i64) -> (Vec<Channel>, u16) {
+        let (response, status_code) = send_request(
+            app,
+            Method::GET,
+            &format!("/guilds/{}/channels", guild_id),
+            Some(user_id),
+            None
+        ).await;
+        (serde_json::from_str(&response).unwrap_or_default(), status_code)
+    }
+
+    #[tokio::test]
+    async fn test_create_guild() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let guild = create_guild(app.clone(), 420).await;
+        assert_eq!(guild.name, "test guild");
+        assert_eq!(guild.owner_id, 420);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            "/guilds",
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 200);
+        let guilds: Vec<Guild> = serde_json::from_str(&response).unwrap();
+        assert!(guilds.contains(&guild));
+
+        let (channels, status_code) = get_channels(app.clone(), guild.id, 420).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].name, "general");
+        assert_eq!(channels[0].kind, ChannelKind::Text);
+
+        let (_, status_code) = get_channels(app.clone(), guild.id, 421).await;
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            "/guilds",
+            Some(420),
+            Some(serde_json::json!({ "name": "" }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "2000");
+        assert_eq!(status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_manage_channels() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let guild = create_guild(app.clone(), 420).await;
+        let (channels, _) = get_channels(app.clone(), guild.id, 420).await;
+        let general = &channels[0];
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/channels", guild.id),
+            Some(420),
+            Some(serde_json::json!({ "name": "voice", "kind": "category" }))
+        ).await;
+        assert_eq!(status_code, 201);
+        let category: Channel = serde_json::from_str(&response).unwrap();
+        assert_eq!(category.position, 1);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/channels", guild.id),
+            Some(420),
+            Some(serde_json::json!({ "name": "lounge", "kind": "voice", "parent_id": category.id }))
+        ).await;
+        assert_eq!(status_code, 201);
+        let voice: Channel = serde_json::from_str(&response).unwrap();
+        assert_eq!(voice.parent_id, Some(category.id));
+
+        // Only categories can be parents
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/channels", guild.id),
+            Some(420),
+            Some(serde_json::json!({ "name": "nested", "kind": "text", "parent_id": voice.id }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "2003");
+        assert_eq!(status_code, 400);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &format!("/guilds/{}/channels/{}", guild.id, general.id),
+            Some(420),
+            Some(serde_json::json!({ "name": "renamed" }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let renamed: Channel = serde_json::from_str(&response).unwrap();
+        assert_eq!(renamed.name, "renamed");
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &format!("/guilds/{}/channels", guild.id),
+            Some(420),
+            Some(serde_json::json!([
+                { "id": category.id, "position": 0 },
+                { "id": general.id, "position": 3 }
+            ]))
+        ).await;
+        assert_eq!(status_code, 200);
+        let reordered: Vec<Channel> = serde_json::from_str(&response).unwrap();
+        let order: Vec<i64> = reordered.iter().map(|channel| channel.id).collect();
+        assert_eq!(order, vec![category.id, voice.id, general.id]);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}/channels/{}", guild.id, category.id),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 204);
+        let (channels, _) = get_channels(app.clone(), guild.id, 420).await;
+        assert_eq!(channels.len(), 2);
+        assert!(channels.iter().all(|channel| channel.parent_id.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_managing_channels_requires_permission() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let guild = create_guild(app.clone(), 420).await;
+        let db_client = context.db_client();
+        db_client.insert_guild_member(&GuildMember::new(guild.id, 421, 0), 60).await.unwrap();
+        let (channels, _) = get_channels(app.clone(), guild.id, 421).await;
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/channels", guild.id),
+            Some(421),
+            Some(serde_json::json!({ "name": "mine", "kind": "text" }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "1301");
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}/channels/{}", guild.id, channels[0].id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}", guild.id),
+            Some(421),
+            None
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "2002");
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}", guild.id),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = get_channels(app.clone(), guild.id, 420).await;
+        assert_eq!(status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_join_guild_with_invite() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let guild = create_guild(app.clone(), 420).await;
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/invites", guild.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/guilds/{}/invites", guild.id),
+            Some(420),
+            Some(serde_json::json!({ "max_uses": 1 }))
+        ).await;
+        assert_eq!(status_code, 201);
+        let invite: Invite = serde_json::from_str(&response).unwrap();
+        assert_eq!(invite.guild_id, guild.id);
+        assert!(invite.expires_at.is_some());
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/invites/{}", invite.code),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 200);
+        let member: GuildMember = serde_json::from_str(&response).unwrap();
+        assert_eq!(member.user_id, 421);
+
+        let (channels, status_code) = get_channels(app.clone(), guild.id, 421).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(channels.len(), 1);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/invites/{}", invite.code),
+            Some(422),
+            None
+        ).await;
+        // Used up
+        assert_eq!(response, "1211");
+        assert_eq!(status_code, 404);
+    }
+}
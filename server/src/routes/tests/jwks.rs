@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        }
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::routes::tests::preparation::{
+        get_axum_app,
+        get_config,
+        TestContext
+    };
+
+    #[tokio::test]
+    async fn test_jwks_endpoint() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let config = get_config();
+
+        let req = Request::builder()
+            .method(Method::GET)
+            .uri("/.well-known/jwks.json")
+            .body(Body::empty())
+            .unwrap();
+        let response = app
+            .oneshot(req)
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let jwks: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0]["kid"], config.jwt_config.signing_key.kid);
+        assert_eq!(keys[0]["kty"], "OKP");
+        assert_eq!(keys[0]["crv"], "Ed25519");
+        assert_eq!(keys[0]["use"], "sig");
+        // Only the public part is published
+        assert!(keys[0].get("d").is_none());
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            header::RETRY_AFTER,
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        database::{
+            LoginAttemptStore,
+            UserStore
+        },
+        login_protection::LoginSubject,
+        routes::tests::{
+            preparation::{
+                get_axum_app,
+                get_config,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    const PASSWORD: &str = "test_password123*&@#ABC";
+
+    /// Returns the body, the status code and the Retry-After header
+    async fn login(
+        app: Router,
+        email: &str,
+        password: &str,
+        turnstile_response: Option<&str>
+    ) -> (serde_json::Value, u16, Option<String>) {
+        let mut payload = serde_json::json!({
+            "email": email,
+            "password": password
+        });
+        if let Some(turnstile_response) = turnstile_response {
+            payload["cf-turnstile-response"] = serde_json::Value::from(turnstile_response);
+        }
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/authenticate")
+                    .header("content-type", "application/json")
+                    .body(Body::from(payload.to_string()))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let retry_after = response.headers()
+            .get(RETRY_AFTER)
+            .map(|retry_after| retry_after.to_str().unwrap().to_string());
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code, retry_after)
+    }
+
+    async fn get_protected_app(context: &TestContext) -> Router {
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        config.login_protection.max_failures_per_email = 3;
+        config.login_protection.turnstile_after_failures = 2;
+        config.login_protection.base_lockout_s = 60;
+        config.login_protection.notify_on_lockout = false;
+        get_axum_app(context, Some(config)).await
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_lock_the_account() {
+        let context = TestContext::new();
+        let app = get_protected_app(&context).await;
+        let db_client = context.db_client();
+        let email_key = LoginSubject::email("test_email").key();
+        db_client.reset_login_failures(&email_key).await.unwrap();
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+
+        for _ in 0..2 {
+            let (_, status_code, _) = login(app.clone(), "test_email", "wrong_password", None).await;
+            assert_eq!(status_code, 401);
+        }
+        // Even the right password needs a Turnstile token now
+        let (response, status_code, _) = login(app.clone(), "test_email", PASSWORD, None).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1304");
+
+        // Emails differing in case count for the same account
+        let (_, status_code, _) = login(app.clone(), "TEST_EMAIL", "wrong_password", Some("token")).await;
+        assert_eq!(status_code, 401);
+        let (response, status_code, retry_after) = login(app.clone(), "test_email", PASSWORD, Some("token")).await;
+        assert_eq!(status_code, 429);
+        assert_eq!(response["error"], "1303");
+        let retry_after: i64 = retry_after.unwrap().parse().unwrap();
+        assert!(retry_after > 55 && retry_after <= 60);
+
+        db_client.reset_login_failures(&email_key).await.unwrap();
+        let (_, status_code, _) = login(app.clone(), "test_email", PASSWORD, None).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_resets_the_failures() {
+        let context = TestContext::new();
+        let app = get_protected_app(&context).await;
+        let db_client = context.db_client();
+        let email_key = LoginSubject::email("test_email").key();
+        db_client.reset_login_failures(&email_key).await.unwrap();
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+
+        let (_, status_code, _) = login(app.clone(), "test_email", "wrong_password", None).await;
+        assert_eq!(status_code, 401);
+        let (_, status_code, _) = login(app.clone(), "test_email", PASSWORD, None).await;
+        assert_eq!(status_code, 200);
+        let (_, status_code, _) = login(app.clone(), "test_email", "wrong_password", None).await;
+        assert_eq!(status_code, 401);
+        let (_, status_code, _) = login(app.clone(), "test_email", PASSWORD, None).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unknown_emails_are_counted() {
+        let context = TestContext::new();
+        let app = get_protected_app(&context).await;
+        let db_client = context.db_client();
+        let email_key = LoginSubject::email("unknown@example.com").key();
+        db_client.reset_login_failures(&email_key).await.unwrap();
+
+        for _ in 0..2 {
+            let (_, status_code, _) = login(app.clone(), "unknown@example.com", PASSWORD, None).await;
+            assert_eq!(status_code, 401);
+        }
+        let (response, status_code, _) = login(app.clone(), "unknown@example.com", PASSWORD, None).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "1304");
+
+        db_client.reset_login_failures(&email_key).await.unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+pub(super) mod tests {
+    use axum::{
+        http::Method,
+        Router
+    };
+    use pretty_assertions::assert_eq;
+    use crate::{
+        app_objects::Message,
+        routes::tests::preparation::{
+            create_test_guild,
+            get_axum_app,
+            send_request,
+            TestContext
+        }
+    };
+
+    const TEST_GUILD_ID: i64 = 4240;
+    const TEST_CHANNEL_ID: i64 = 4242;
+
+    /// User 420 owns the test guild, 421 is a member and 422 is not
+    async fn prepare_test_guild(context: &TestContext) {
+        create_test_guild(&context.db_client(), TEST_GUILD_ID, TEST_CHANNEL_ID, 420, &[421]).await;
+    }
+
+    async fn post_message(app: Router, user_id: i64, content: &str) -> Message {
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID),
+            Some(user_id),
+            Some(serde_json::json!({ "content": content }))
+        ).await;
+        assert_eq!(status_code, 201);
+        Message::from_json(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get_messages() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        prepare_test_guild(&context).await;
+
+        let first = post_message(app.clone(), 420, "first").await;
+        let second = post_message(app.clone(), 420, "second").await;
+        assert_eq!(first.author_id, 420);
+        assert_eq!(first.channel_id, TEST_CHANNEL_ID);
+        assert!(second.id > first.id);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages?after={}&limit=10", TEST_CHANNEL_ID, first.id - 1),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 200);
+        let messages: Vec<Message> = serde_json::from_str(&response).unwrap();
+        assert_eq!(messages, vec![second.clone(), first.clone()]);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages?before={}", TEST_CHANNEL_ID, second.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 200);
+        let messages: Vec<Message> = serde_json::from_str(&response).unwrap();
+        assert_eq!(messages[0], first);
+    }
+
+    #[tokio::test]
+    async fn test_create_message_invalid_content() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        prepare_test_guild(&context).await;
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID),
+            Some(420),
+            Some(serde_json::json!({ "content": "   " }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "1800");
+        assert_eq!(status_code, 400);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages?limit=0", TEST_CHANNEL_ID),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_messages_require_authentication() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID),
+            None,
+            Some(serde_json::json!({ "content": "hello" }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "No token");
+        assert_eq!(status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_edit_message() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        prepare_test_guild(&context).await;
+        let message = post_message(app.clone(), 420, "before edit").await;
+        let uri = format!("/channels/{}/messages/{}", TEST_CHANNEL_ID, message.id);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &uri,
+            Some(421),
+            Some(serde_json::json!({ "content": "not my message" }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "1804");
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &uri,
+            Some(420),
+            Some(serde_json::json!({ "content": "after edit" }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let edited = Message::from_json(&response).unwrap();
+        assert_eq!(edited.content, "after edit");
+        assert!(edited.updated_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_delete_message() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        prepare_test_guild(&context).await;
+        let message = post_message(app.clone(), 420, "to be deleted").await;
+        let uri = format!("/channels/{}/messages/{}", TEST_CHANNEL_ID, message.id);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &uri,
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &uri,
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 204);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &uri,
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 404);
+    }
+
+    #[tokio::test]
+    async fn test_messages_require_guild_membership() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        prepare_test_guild(&context).await;
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID),
+            Some(422),
+            Some(serde_json::json!({ "content": "let me in" }))
+        ).await;
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "2001");
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID),
+            Some(422),
+            None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages", TEST_CHANNEL_ID + 1),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 404);
+    }
+}
@@ -0,0 +1,329 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use data_encoding::BASE32_NOPAD;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        mfa::Totp,
+        routes::tests::{
+            preparation::{
+                get_access_token_cookie,
+                get_axum_app,
+                get_config,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    async fn send_request(
+        app: Router,
+        method: Method,
+        uri: &str,
+        cookie: &str,
+        body: Option<serde_json::Value>
+    ) -> (serde_json::Value, u16) {
+        let body = match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        };
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("Cookie", cookie)
+                    .body(body)
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code)
+    }
+
+    /// Password step of a login of user 420
+    async fn login(app: Router) -> serde_json::Value {
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            "/authenticate",
+            "",
+            Some(serde_json::json!({
+                "email": "test_email",
+                "password": "test_password123*&@#ABC"
+            }))
+        ).await;
+        assert_eq!(status_code, 200);
+        response
+    }
+
+    async fn login_mfa(app: Router, mfa_token: &str, second_factor: serde_json::Value) -> (serde_json::Value, u16) {
+        let mut payload = second_factor;
+        payload["mfa_token"] = serde_json::Value::from(mfa_token);
+        send_request(app, Method::POST, "/authenticate/mfa", "", Some(payload)).await
+    }
+
+    /// Enrolls and confirms a second factor for user 420, returns the secret and the recovery codes
+    async fn enable_mfa(app: Router, access_cookie: &str) -> (Vec<u8>, Vec<String>) {
+        let (response, status_code) = send_request(app.clone(), Method::POST, "/me/mfa/totp", access_cookie, None).await;
+        assert_eq!(status_code, 200);
+        let secret = BASE32_NOPAD.decode(response["secret"].as_str().unwrap().as_bytes()).unwrap();
+        let provisioning_uri = response["provisioning_uri"].as_str().unwrap();
+        assert!(provisioning_uri.starts_with("otpauth://totp/Discord%20Sucks:test_email?secret="));
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            "/me/mfa/totp/confirm",
+            access_cookie,
+            Some(serde_json::json!({ "code": "abcdef" }))
+        ).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "2203");
+
+        let totp = Totp::new(&secret, 6, 30);
+        let code = totp.code_at_step(totp.step_at(chrono::Utc::now().timestamp()));
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            "/me/mfa/totp/confirm",
+            access_cookie,
+            Some(serde_json::json!({ "code": code }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let recovery_codes: Vec<String> = serde_json::from_value(response["recovery_codes"].clone()).unwrap();
+        assert_eq!(recovery_codes.len(), 10);
+        (secret, recovery_codes)
+    }
+
+    #[tokio::test]
+    async fn test_login_with_totp() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+
+        let (response, status_code) = send_request(app.clone(), Method::GET, "/me/mfa", &access_cookie, None).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response, serde_json::json!({ "enabled": false, "recovery_codes_left": 0 }));
+
+        let (secret, recovery_codes) = enable_mfa(app.clone(), &access_cookie).await;
+        let (response, _) = send_request(app.clone(), Method::GET, "/me/mfa", &access_cookie, None).await;
+        assert_eq!(response, serde_json::json!({ "enabled": true, "recovery_codes_left": 10 }));
+
+        // The password alone only gets a token for the second step
+        let response = login(app.clone()).await;
+        assert!(response.get("refresh_token").is_none());
+        assert_eq!(response["token_type"], "mfa_token");
+        let mfa_token = response["mfa_token"].as_str().unwrap().to_string();
+
+        // The code that confirmed the enrollment was spent
+        let totp = Totp::new(&secret, 6, 30);
+        let step = totp.step_at(chrono::Utc::now().timestamp());
+        let (response, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "code": totp.code_at_step(step) })
+        ).await;
+        assert_eq!(status_code, 401);
+        assert_eq!(response["error"], "1302");
+
+        let (response, status_code) = login_mfa(app.clone(), &mfa_token, serde_json::json!({})).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "Missing credentials");
+
+        let next_code = totp.code_at_step(step + 1);
+        let (response, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "code": next_code })
+        ).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response["token_type"], "refresh_token");
+        assert!(response["refresh_token"].as_str().is_some());
+
+        let (_, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "code": next_code })
+        ).await;
+        assert_eq!(status_code, 400);
+
+        // A used token can't start a second session, even with another valid code, which isn't used up by trying
+        let (response, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": recovery_codes[0] })
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "Invalid token");
+        let (response, _) = send_request(app.clone(), Method::GET, "/me/mfa", &access_cookie, None).await;
+        assert_eq!(response["recovery_codes_left"], 10);
+
+        // The token of the second step is no access token and the other way around
+        let (_, status_code) = send_request(
+            app.clone(), Method::GET, "/me/mfa", &format!("authorization_token=Bearer {}", mfa_token), None
+        ).await;
+        assert_eq!(status_code, 400);
+        let access_token = access_cookie.trim_start_matches("authorization_token=Bearer ");
+        let (_, status_code) = login_mfa(
+            app.clone(), access_token, serde_json::json!({ "code": totp.code_at_step(step + 1) })
+        ).await;
+        assert_eq!(status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_codes_and_disable() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+
+        // Nothing to confirm or disable yet
+        let (response, status_code) = send_request(
+            app.clone(), Method::POST, "/me/mfa/totp/confirm", &access_cookie, Some(serde_json::json!({ "code": "123456" }))
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2202");
+        let (response, status_code) = send_request(
+            app.clone(), Method::DELETE, "/me/mfa/totp", &access_cookie, Some(serde_json::json!({ "code": "123456" }))
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2200");
+
+        let (_, recovery_codes) = enable_mfa(app.clone(), &access_cookie).await;
+        let (response, status_code) = send_request(app.clone(), Method::POST, "/me/mfa/totp", &access_cookie, None).await;
+        assert_eq!(status_code, 409);
+        assert_eq!(response["error"], "2201");
+
+        // Recovery codes work once, regardless of how they are typed
+        let mfa_token = login(app.clone()).await["mfa_token"].as_str().unwrap().to_string();
+        let recovery_code = recovery_codes[0].to_uppercase().replace('-', " ");
+        let (_, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": recovery_code })
+        ).await;
+        assert_eq!(status_code, 200);
+        let mfa_token = login(app.clone()).await["mfa_token"].as_str().unwrap().to_string();
+        let (_, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": recovery_codes[0] })
+        ).await;
+        assert_eq!(status_code, 401);
+
+        // Regenerating invalidates the old codes
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            "/me/mfa/recovery_codes",
+            &access_cookie,
+            Some(serde_json::json!({ "recovery_code": recovery_codes[1] }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let new_recovery_codes: Vec<String> = serde_json::from_value(response["recovery_codes"].clone()).unwrap();
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            "/me/mfa/totp",
+            &access_cookie,
+            Some(serde_json::json!({ "recovery_code": recovery_codes[2] }))
+        ).await;
+        assert_eq!(status_code, 403);
+        assert_eq!(response["error"], "2203");
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            "/me/mfa/totp",
+            &access_cookie,
+            Some(serde_json::json!({ "recovery_code": new_recovery_codes[0] }))
+        ).await;
+        assert_eq!(status_code, 204);
+        let (response, _) = send_request(app.clone(), Method::GET, "/me/mfa", &access_cookie, None).await;
+        assert_eq!(response["enabled"], false);
+
+        // Pending logins can't be finished once the second factor is gone
+        let (_, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": new_recovery_codes[1] })
+        ).await;
+        assert_eq!(status_code, 400);
+        assert!(login(app).await["refresh_token"].as_str().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_mfa_token_is_used_up_after_max_attempts() {
+        let context = TestContext::new();
+        // Wrong codes count as login failures, they must not lock the account before the token is used up
+        let mut config = get_config();
+        config.login_protection.turnstile_after_failures = 10;
+        let app = get_axum_app(&context, Some(config)).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+        let (_, recovery_codes) = enable_mfa(app.clone(), &access_cookie).await;
+
+        let mfa_token = login(app.clone()).await["mfa_token"].as_str().unwrap().to_string();
+        for _ in 0..5 {
+            let (response, status_code) = login_mfa(
+                app.clone(), &mfa_token, serde_json::json!({ "recovery_code": "aaaaa-bbbbb" })
+            ).await;
+            assert_eq!(status_code, 401);
+            assert_eq!(response["error"], "1302");
+        }
+
+        // The right code doesn't help anymore, the password has to be entered again
+        let (response, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": recovery_codes[0] })
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "Invalid token");
+
+        let mfa_token = login(app.clone()).await["mfa_token"].as_str().unwrap().to_string();
+        let (_, status_code) = login_mfa(
+            app, &mfa_token, serde_json::json!({ "recovery_code": recovery_codes[0] })
+        ).await;
+        assert_eq!(status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_codes_lock_both_login_steps() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.login_protection.max_failures_per_email = 3;
+        config.login_protection.turnstile_after_failures = 10;
+        config.login_protection.notify_on_lockout = false;
+        let app = get_axum_app(&context, Some(config)).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+        let (_, recovery_codes) = enable_mfa(app.clone(), &access_cookie).await;
+
+        let mfa_token = login(app.clone()).await["mfa_token"].as_str().unwrap().to_string();
+        for _ in 0..3 {
+            let (response, status_code) = login_mfa(
+                app.clone(), &mfa_token, serde_json::json!({ "recovery_code": "aaaaa-bbbbb" })
+            ).await;
+            assert_eq!(status_code, 401);
+            assert_eq!(response["error"], "1302");
+        }
+
+        let (response, status_code) = login_mfa(
+            app.clone(), &mfa_token, serde_json::json!({ "recovery_code": recovery_codes[0] })
+        ).await;
+        assert_eq!(status_code, 429);
+        assert_eq!(response["error"], "1303");
+        let (response, status_code) = send_request(
+            app,
+            Method::POST,
+            "/authenticate",
+            "",
+            Some(serde_json::json!({
+                "email": "test_email",
+                "password": "test_password123*&@#ABC"
+            }))
+        ).await;
+        assert_eq!(status_code, 429);
+        assert_eq!(response["error"], "1303");
+    }
+}
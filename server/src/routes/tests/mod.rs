@@ -4,4 +4,20 @@ mod authenticate;
 mod refresh_token;
 mod secured;
 mod register_user_credential_based;
-mod add_user_from_jwt;
\ No newline at end of file
+mod add_user_from_jwt;
+mod resend_verification_email;
+mod messages;
+mod guilds;
+mod roles;
+mod dms;
+mod sessions;
+mod jwks;
+mod mfa;
+mod passkeys;
+mod password_reset;
+mod account;
+mod login_protection;
+mod rate_limit;
+mod bans;
+mod admin;
+mod security_log;
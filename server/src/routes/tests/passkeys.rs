@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            HeaderMap,
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        passkeys::{
+            decode_base64url,
+            encode_base64url,
+            tests::tests::SoftwareAuthenticator
+        },
+        routes::tests::{
+            preparation::{
+                get_access_token_cookie,
+                get_axum_app,
+                get_config,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    async fn send_request(
+        app: Router,
+        method: Method,
+        uri: &str,
+        cookie: &str,
+        body: Option<serde_json::Value>
+    ) -> (serde_json::Value, u16, HeaderMap) {
+        let body = match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        };
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .header("Cookie", cookie)
+                    .body(body)
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code, headers)
+    }
+
+    fn get_authenticator() -> SoftwareAuthenticator {
+        let config = get_config();
+        SoftwareAuthenticator::new_es256(&config.passkeys.rp_id, &config.passkeys.allowed_origins[0])
+    }
+
+    /// Returns the challenge id and the challenge bytes of the options
+    async fn get_challenge(app: Router, uri: &str, cookie: &str) -> (serde_json::Value, serde_json::Value, Vec<u8>) {
+        let (response, status_code, _) = send_request(app, Method::POST, uri, cookie, None).await;
+        assert_eq!(status_code, 200);
+        let challenge = decode_base64url(response["public_key"]["challenge"].as_str().unwrap()).unwrap();
+        (response["challenge_id"].clone(), response["public_key"].clone(), challenge)
+    }
+
+    async fn register(
+        app: Router,
+        access_cookie: &str,
+        authenticator: &SoftwareAuthenticator
+    ) -> (serde_json::Value, u16) {
+        let (challenge_id, _, challenge) = get_challenge(app.clone(), "/me/passkeys/challenge", access_cookie).await;
+        let (response, status_code, _) = send_request(
+            app,
+            Method::POST,
+            "/me/passkeys",
+            access_cookie,
+            Some(serde_json::json!({
+                "challenge_id": challenge_id,
+                "label": "  security key  ",
+                "credential": authenticator.register(&challenge)
+            }))
+        ).await;
+        (response, status_code)
+    }
+
+    async fn login(app: Router, authenticator: &mut SoftwareAuthenticator) -> (serde_json::Value, u16, HeaderMap) {
+        let (challenge_id, options, challenge) = get_challenge(app.clone(), "/authenticate/passkey/challenge", "").await;
+        assert_eq!(options["rpId"], get_config().passkeys.rp_id);
+        assert_eq!(options["userVerification"], "required");
+        send_request(
+            app,
+            Method::POST,
+            "/authenticate/passkey",
+            "",
+            Some(serde_json::json!({
+                "challenge_id": challenge_id,
+                "credential": authenticator.authenticate(&challenge, 420),
+                "device_label": "passkey device"
+            }))
+        ).await
+    }
+
+    #[tokio::test]
+    async fn test_register_passkey() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+        let authenticator = get_authenticator();
+
+        let (_, options, _) = get_challenge(app.clone(), "/me/passkeys/challenge", &access_cookie).await;
+        assert_eq!(options["rp"]["id"], get_config().passkeys.rp_id);
+        assert_eq!(options["user"]["id"], encode_base64url(&420i64.to_be_bytes()));
+        assert_eq!(options["user"]["name"], "test_email");
+        assert_eq!(options["authenticatorSelection"]["residentKey"], "required");
+        assert_eq!(options["excludeCredentials"], serde_json::json!([]));
+
+        let (response, status_code) = register(app.clone(), &access_cookie, &authenticator).await;
+        assert_eq!(status_code, 201);
+        let credential_id = encode_base64url(&authenticator.credential_id);
+        assert_eq!(response["id"], credential_id);
+        assert_eq!(response["label"], "security key");
+
+        // The authenticator is told about registered credentials, registering one twice fails
+        let (_, options, _) = get_challenge(app.clone(), "/me/passkeys/challenge", &access_cookie).await;
+        assert_eq!(options["excludeCredentials"][0]["id"], credential_id);
+        let (response, status_code) = register(app.clone(), &access_cookie, &authenticator).await;
+        assert_eq!(status_code, 409);
+        assert_eq!(response["error"], "2309");
+
+        // Challenges are answered once and only by the user they were issued to
+        let (challenge_id, _, challenge) = get_challenge(app.clone(), "/me/passkeys/challenge", &access_cookie).await;
+        let payload = serde_json::json!({
+            "challenge_id": challenge_id,
+            "credential": get_authenticator().register(&challenge)
+        });
+        let (response, status_code, _) = send_request(
+            app.clone(), Method::POST, "/me/passkeys", &get_access_token_cookie(421), Some(payload.clone())
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2300");
+        let (_, status_code, _) = send_request(
+            app.clone(), Method::POST, "/me/passkeys", &access_cookie, Some(payload)
+        ).await;
+        assert_eq!(status_code, 400);
+
+        let (response, status_code, _) = send_request(app.clone(), Method::GET, "/me/passkeys", &access_cookie, None).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response.as_array().unwrap().len(), 1);
+        assert_eq!(response[0]["last_used_at"], serde_json::Value::Null);
+
+        let uri = format!("/me/passkeys/{}", credential_id);
+        let (_, status_code, _) = send_request(app.clone(), Method::DELETE, &uri, &get_access_token_cookie(421), None).await;
+        assert_eq!(status_code, 404);
+        let (_, status_code, _) = send_request(app.clone(), Method::DELETE, &uri, &access_cookie, None).await;
+        assert_eq!(status_code, 204);
+        let (response, status_code, _) = send_request(app, Method::DELETE, &uri, &access_cookie, None).await;
+        assert_eq!(status_code, 404);
+        assert_eq!(response["error"], "2308");
+    }
+
+    #[tokio::test]
+    async fn test_login_with_passkey() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let access_cookie = get_access_token_cookie(420);
+        let mut authenticator = get_authenticator();
+        let (_, status_code) = register(app.clone(), &access_cookie, &authenticator).await;
+        assert_eq!(status_code, 201);
+
+        let (response, status_code, headers) = login(app.clone(), &mut authenticator).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response["token_type"], "refresh_token");
+        let refresh_token = response["refresh_token"].as_str().unwrap();
+        let cookie = headers.get("set-cookie").unwrap().to_str().unwrap();
+        assert!(cookie.starts_with(&format!("refresh_token=Bearer {}", refresh_token)));
+
+        let (response, _, _) = send_request(app.clone(), Method::GET, "/me/passkeys", &access_cookie, None).await;
+        assert!(response[0]["last_used_at"].as_i64().is_some());
+
+        // A copy of the credential reports a counter the server has already seen
+        let (_, status_code, _) = login(app.clone(), &mut authenticator).await;
+        assert_eq!(status_code, 200);
+        authenticator.sign_count = 0;
+        let (response, status_code, _) = login(app.clone(), &mut authenticator).await;
+        assert_eq!(status_code, 401);
+        assert_eq!(response["error"], "2310");
+
+        // Registration challenges can't be used to log in
+        let (challenge_id, _, challenge) = get_challenge(app.clone(), "/me/passkeys/challenge", &access_cookie).await;
+        authenticator.sign_count = 10;
+        let (response, status_code, _) = send_request(
+            app.clone(),
+            Method::POST,
+            "/authenticate/passkey",
+            "",
+            Some(serde_json::json!({
+                "challenge_id": challenge_id,
+                "credential": authenticator.authenticate(&challenge, 420)
+            }))
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(response["error"], "2300");
+
+        let (response, status_code, _) = login(app, &mut get_authenticator()).await;
+        assert_eq!(status_code, 404);
+        assert_eq!(response["error"], "2308");
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        auth::JWTKeys,
+        database::{
+            SessionStore,
+            UserStore
+        },
+        password_reset::create_password_reset_token,
+        routes::tests::{
+            authenticate::tests::get_authenticate_endpoint_response_and_status_code,
+            preparation::{
+                get_axum_app,
+                get_config,
+                TestContext
+            },
+            refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+        }
+    };
+
+    async fn send_form(
+        app: Router,
+        uri: &str,
+        body: String
+    ) -> (String, u16) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(uri)
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(body))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (String::from_utf8(body_bytes.to_vec()).unwrap(), status_code)
+    }
+
+    async fn confirm(app: Router, token: &str, password: &str) -> (String, u16) {
+        let body = format!(
+            "token={}&password={}",
+            urlencoding::encode(token),
+            urlencoding::encode(password)
+        );
+        send_form(app, "/password_reset/confirm", body).await
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_request_does_not_reveal_accounts() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let app = get_axum_app(&context, Some(config)).await;
+
+        for email in ["nobody%40example.com", "not-an-email"] {
+            let body = format!("email={}&cf-turnstile-response=1222", email);
+            let (response, status_code) = send_form(app.clone(), "/password_reset/request", body).await;
+            assert_eq!(status_code, 200);
+            assert_eq!(response, "Password reset requested");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_confirm() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let _ = db_client.delete_user_sessions(420).await;
+        let refresh_token = get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let token = create_password_reset_token(&db_client, &jwt_keys, 60, 420).await.unwrap();
+
+        // A password that doesn't meet the requirements doesn't use up the link
+        let (response, status_code) = confirm(app.clone(), &token, "short").await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("1100"));
+
+        let new_password = "new_password456*&@#XYZ";
+        let (response, status_code) = confirm(app.clone(), &token, new_password).await;
+        assert_eq!(status_code, 200, "{}", response);
+
+        let (response, status_code) = confirm(app.clone(), &token, new_password).await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("2400"));
+
+        // Every session was logged out
+        assert!(db_client.get_user_sessions(420, chrono::Utc::now().timestamp()).await.unwrap().is_empty());
+        let response = app.clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/refresh_token")
+                    .header("Cookie", format!("refresh_token={}", refresh_token))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 400);
+
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(
+            "test_password123*&@#ABC",
+            "test_email",
+            app.clone()
+        ).await;
+        assert_ne!(status_code, 200);
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(
+            new_password,
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_confirm_invalid_token() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+
+        let (response, status_code) = confirm(app.clone(), "not a token", "new_password456*&@#XYZ").await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("2400"));
+    }
+}
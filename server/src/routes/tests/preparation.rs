@@ -3,12 +3,55 @@ pub use preparation::*;
 
 #[cfg(test)]
 mod preparation {
-    use std::path::PathBuf;
+    use std::{
+        path::PathBuf,
+        sync::Arc
+    };
+    use axum::{
+        body::{
+            to_bytes,
+            Body
+        },
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use tower::util::ServiceExt;
+    use crate::app_objects::{
+        Channel,
+        ChannelKind,
+        Guild,
+        GuildMember,
+        Role
+    };
+    use crate::auth::{
+        AuthClaims,
+        JWTKeys
+    };
     use crate::cloudflare::TurnstileState;
-    use crate::configuration::Config;
-    use crate::database::DatabaseClientWithCaching;
-    use crate::email::EmailHandler;
+    use crate::configuration::{
+        Config,
+        EmailTransportBackend,
+        RateLimitBackend
+    };
+    use crate::database::{
+        DatabaseClient,
+        GuildStore,
+        InMemoryDatabase
+    };
+    use crate::email::{
+        EmailHandler,
+        EmailTransport,
+        MemoryTransport,
+        SentEmail
+    };
+    use crate::event_bus::EventBus;
+    use crate::gateway::GatewayHub;
+    use crate::rate_limit::RateLimiter;
     use crate::routes::configure_routes;
+    use crate::snowflake::SnowflakeGenerator;
 
     pub fn get_config() -> Config {
         let mut cfg_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -18,30 +61,155 @@ mod preparation {
         turnstile_path.push("..");
         turnstile_path.push(&cfg.cloudflare.turnstile_secret_key_path);
 
-        let mut smtp_password_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        smtp_password_path.push("..");
-        smtp_password_path.push(&cfg.smtp.smtp_password_path);
-
-        let mut jwt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        jwt_path.push("../configuration/server/jwt_secret.txt");
+        let jwt_key_path = |path: &str| {
+            let mut jwt_key_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            jwt_key_path.push("..");
+            jwt_key_path.push(path);
+            jwt_key_path.to_str().unwrap().to_string()
+        };
+        let signing_key = &mut cfg.jwt_config.signing_key;
+        signing_key.private_key_path = jwt_key_path(&signing_key.private_key_path);
+        signing_key.public_key_path = jwt_key_path(&signing_key.public_key_path);
+        for key in &mut cfg.jwt_config.previous_keys {
+            key.public_key_path = jwt_key_path(&key.public_key_path);
+        }
 
+        let mut email_templates_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        email_templates_path.push("..");
+        email_templates_path.push(&cfg.email_templates.dir);
 
-        cfg.jwt_config.jwt_secret_path = jwt_path.to_str().unwrap().to_string();
-        cfg.smtp.smtp_password_path = smtp_password_path.to_str().unwrap().to_string();
+        cfg.smtp.smtp_password_path = cfg.smtp.smtp_password_path.as_ref().map(|path| {
+            let mut smtp_password_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            smtp_password_path.push("..");
+            smtp_password_path.push(path);
+            smtp_password_path.to_str().unwrap().to_string()
+        });
+        cfg.email_templates.dir = email_templates_path.to_str().unwrap().to_string();
         cfg.cloudflare.turnstile_secret_key_path = turnstile_path.to_str().unwrap().to_string();
+        // Every test gets fresh buckets instead of sharing them over Redis
+        cfg.rate_limit.backend = RateLimitBackend::InProcess;
+        // Requests sent with `oneshot` have no client IP, the rate limit tests set the policies they check
+        cfg.rate_limit.policies.clear();
+        // Tests never send real emails
+        cfg.smtp.transport = EmailTransportBackend::Memory;
 
         cfg
     }
 
-    pub async fn get_db_client() -> DatabaseClientWithCaching {
+    /// The database and the sent emails of a single test. Every test creates its own and passes it to
+    /// the apps it builds, so they stay apart however the runtime schedules the requests
+    #[derive(Debug, Clone, Default)]
+    pub struct TestContext {
+        pub db: InMemoryDatabase,
+        pub emails: MemoryTransport,
+    }
+
+    impl TestContext {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn db_client(&self) -> DatabaseClient {
+            Arc::new(self.db.clone())
+        }
+
+        /// Emails sent by the apps of the test, oldest first. The queued ones that are due are sent
+        /// first, tests don't run the background workers
+        pub async fn sent_emails(&self) -> Vec<SentEmail> {
+            let config = get_config();
+            let email_handler = EmailHandler::with_transport(
+                &config,
+                EmailTransport::Memory(self.emails.clone()),
+                self.db_client()
+            ).unwrap();
+            let email_worker = email_handler.worker(&config.email_queue);
+            while email_worker.run_due_jobs().await.unwrap() > 0 {}
+            self.emails.sent_emails()
+        }
+    }
+
+    /// Creates a guild with a single text channel, `member_ids` are added next to the owner
+    pub async fn create_test_guild(
+        db_client: &DatabaseClient,
+        guild_id: i64,
+        channel_id: i64,
+        owner_id: i64,
+        member_ids: &[i64]
+    ) {
+        let now = chrono::Utc::now().timestamp();
+        let guild = Guild::new(guild_id, "test guild".to_string(), owner_id, now);
+        let owner = GuildMember::new(guild_id, owner_id, now);
+        let channel = Channel::new(channel_id, guild_id, "general".to_string(), ChannelKind::Text, 0, None, now);
+        let everyone_role = Role::everyone(guild_id, now);
+        db_client.create_guild(&guild, &owner, &everyone_role, &[channel], 60).await.unwrap();
+        for member_id in member_ids {
+            db_client.insert_guild_member(&GuildMember::new(guild_id, *member_id, now), 60).await.unwrap();
+        }
+    }
+
+    /// Creates a valid access token cookie for the user without going through /authenticate
+    pub fn get_access_token_cookie(
+        user_id: i64
+    ) -> String {
         let config = get_config();
-        let db_client = DatabaseClientWithCaching::new(
-            &config.redis_database,
-            &config.postgres_database
-        ).await.unwrap();
-        db_client
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let claims = AuthClaims::new_access(
+            config.jwt_config.access_key_lifetime_s,
+            user_id,
+            None
+        );
+        let token = jwt_keys.encode(&claims).unwrap();
+        format!("authorization_token=Bearer {}", token)
     }
+
+    /// Sends a JSON request as the user, or without a cookie when `user_id` is `None`
+    pub async fn send_request(
+        app: Router,
+        method: Method,
+        uri: &str,
+        user_id: Option<i64>,
+        body: Option<serde_json::Value>
+    ) -> (String, u16) {
+        let mut req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(user_id) = user_id {
+            req = req.header("Cookie", get_access_token_cookie(user_id));
+        }
+        let body = match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        };
+        let response = app
+            .oneshot(req.body(body).unwrap())
+            .await
+            .unwrap();
+        let status_code = response.status();
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body_str = String::from_utf8(body_bytes.to_vec())
+            .expect("Failed to convert body to string");
+
+        (body_str, status_code.as_u16())
+    }
+
+    pub fn get_admin_access_token_cookie(
+        user_id: i64
+    ) -> String {
+        let config = get_config();
+        let jwt_keys = JWTKeys::new(&config).unwrap();
+        let claims = AuthClaims::new_access(
+            config.jwt_config.access_key_lifetime_s,
+            user_id,
+            None
+        ).with_admin(true);
+        let token = jwt_keys.encode(&claims).unwrap();
+        format!("authorization_token=Bearer {}", token)
+    }
+
     pub async fn get_axum_app(
+        context: &TestContext,
         custom_config: Option<Config>
     ) -> axum::Router {
         let mut config = get_config();
@@ -49,26 +217,36 @@ mod preparation {
             config = custom_config;
         }
 
-        // jwt_secret_path = "configuration/server/jwt_secret.txt"
-        let mut jwt_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        jwt_path.push("../configuration/server/jwt_secret.txt");
-        config.jwt_config.jwt_secret_path = jwt_path.to_str().unwrap().to_string();
         let jwt_keys = crate::auth::JWTKeys::new(&config).unwrap();
-        let db_client = get_db_client().await;
+        let db_client = context.db_client();
         let password_requirements = config.password_requirements.clone();
         let turnstile_state = TurnstileState::new(
             &config
         ).unwrap();
     
-        let email_handler = EmailHandler::new(
-            &config
+        let email_handler = EmailHandler::with_transport(
+            &config,
+            EmailTransport::Memory(context.emails.clone()),
+            db_client.clone()
+        ).unwrap();
+        let id_generator = SnowflakeGenerator::new(
+            &config.snowflake
         ).unwrap();
+        let gateway_hub = GatewayHub::new();
+        let rate_limiter = RateLimiter::new(
+            &config.rate_limit,
+            &config.redis_database
+        ).await.unwrap();
         let app = configure_routes(
             &jwt_keys,
             db_client.clone(),
             password_requirements,
             &turnstile_state,
             &email_handler,
+            &id_generator,
+            &gateway_hub,
+            &EventBus::in_process(&gateway_hub),
+            &rate_limiter,
             &config
         ).await;
 
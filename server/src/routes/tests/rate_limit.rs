@@ -0,0 +1,218 @@
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use axum::{
+        body::Body,
+        extract::ConnectInfo,
+        http::{
+            header::RETRY_AFTER,
+            HeaderMap,
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        configuration::{
+            Config,
+            RateLimitKey,
+            RateLimitPolicyConfig
+        },
+        routes::tests::preparation::{
+            get_access_token_cookie,
+            get_axum_app,
+            get_config,
+            TestContext
+        }
+    };
+
+    /// Trusts the client IP in `CF-Connecting-IP`, IPs are only counted when they are trusted
+    async fn get_rate_limited_app(
+        route: &str,
+        key: RateLimitKey
+    ) -> Router {
+        let mut config = get_config();
+        config.cloudflare.allow_non_cloudflare_ips = false;
+        get_rate_limited_app_with_config(route, key, config).await
+    }
+
+    async fn get_rate_limited_app_with_config(
+        route: &str,
+        key: RateLimitKey,
+        mut config: Config
+    ) -> Router {
+        config.rate_limit.policies = vec![
+            RateLimitPolicyConfig {
+                name: "test".to_string(),
+                route: route.to_string(),
+                methods: vec![],
+                key,
+                limit: 2,
+                period_s: 60,
+            }
+        ];
+        get_axum_app(&TestContext::new(), Some(config)).await
+    }
+
+    /// Returns the status code, the headers and the body
+    async fn send(
+        app: Router,
+        uri: &str,
+        ip: &str,
+        cookie: Option<String>
+    ) -> (u16, HeaderMap, serde_json::Value) {
+        let mut request = Request::builder()
+            .method(Method::GET)
+            .uri(uri)
+            .header("cf-connecting-ip", ip)
+            .extension(ConnectInfo("173.245.48.1:4000".parse::<SocketAddr>().unwrap()));
+        if let Some(cookie) = cookie {
+            request = request.header("cookie", cookie);
+        }
+        send_built(app, request).await
+    }
+
+    async fn send_built(
+        app: Router,
+        request: axum::http::request::Builder
+    ) -> (u16, HeaderMap, serde_json::Value) {
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (status_code, headers, body)
+    }
+
+    fn header(headers: &HeaderMap, name: &str) -> String {
+        headers.get(name).unwrap().to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_ip() {
+        let app = get_rate_limited_app("/", RateLimitKey::Ip).await;
+
+        let (status_code, headers, _) = send(app.clone(), "/", "10.0.0.1", None).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(header(&headers, "ratelimit-limit"), "2");
+        assert_eq!(header(&headers, "ratelimit-remaining"), "1");
+        assert_eq!(header(&headers, "ratelimit-reset"), "30");
+        assert_eq!(header(&headers, "ratelimit-policy"), "2;w=60");
+
+        let (status_code, headers, _) = send(app.clone(), "/", "10.0.0.1", None).await;
+        assert_eq!(status_code, 200);
+        assert_eq!(header(&headers, "ratelimit-remaining"), "0");
+
+        let (status_code, headers, body) = send(app.clone(), "/", "10.0.0.1", None).await;
+        assert_eq!(status_code, 429);
+        assert_eq!(body["error"], "2600");
+        assert_eq!(body["retry_after"], 30);
+        assert_eq!(header(&headers, RETRY_AFTER.as_str()), "30");
+        assert_eq!(header(&headers, "ratelimit-remaining"), "0");
+
+        let (status_code, _, _) = send(app.clone(), "/", "10.0.0.2", None).await;
+        assert_eq!(status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_user() {
+        let app = get_rate_limited_app("/secured", RateLimitKey::User).await;
+
+        // Same IP, but every user has their own bucket
+        for _ in 0..2 {
+            let (status_code, _, _) = send(app.clone(), "/secured", "10.0.0.1", Some(get_access_token_cookie(1))).await;
+            assert_eq!(status_code, 200);
+        }
+        let (status_code, _, _) = send(app.clone(), "/secured", "10.0.0.1", Some(get_access_token_cookie(1))).await;
+        assert_eq!(status_code, 429);
+        let (status_code, _, _) = send(app.clone(), "/secured", "10.0.0.1", Some(get_access_token_cookie(2))).await;
+        assert_eq!(status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_route() {
+        let app = get_rate_limited_app("/", RateLimitKey::Route).await;
+
+        let (status_code, _, _) = send(app.clone(), "/", "10.0.0.1", None).await;
+        assert_eq!(status_code, 200);
+        let (status_code, _, _) = send(app.clone(), "/", "10.0.0.2", None).await;
+        assert_eq!(status_code, 200);
+        let (status_code, _, _) = send(app.clone(), "/", "10.0.0.3", None).await;
+        assert_eq!(status_code, 429);
+
+        // Routes without a policy aren't limited and get no headers
+        let (status_code, headers, _) = send(app.clone(), "/secured", "10.0.0.3", Some(get_access_token_cookie(1))).await;
+        assert_eq!(status_code, 200);
+        assert!(headers.get("ratelimit-limit").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_without_client_ip() {
+        let app = get_rate_limited_app("/", RateLimitKey::Ip).await;
+
+        let (status_code, _, body) = send_built(app.clone(), Request::builder().uri("/")).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(body["error"], "2602");
+
+        // Keyed by route the IP isn't needed
+        let app = get_rate_limited_app("/", RateLimitKey::Route).await;
+        let (status_code, _, _) = send_built(app.clone(), Request::builder().uri("/")).await;
+        assert_eq!(status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_peer_ip() {
+        // The shipped config allows requests from outside Cloudflare, they are counted by the peer address
+        let app = get_rate_limited_app_with_config("/", RateLimitKey::Ip, get_config()).await;
+
+        let request = |peer: &str| Request::builder()
+            .uri("/")
+            .header("cf-connecting-ip", "10.0.0.9")
+            .extension(ConnectInfo(format!("{}:4000", peer).parse::<SocketAddr>().unwrap()));
+        for _ in 0..2 {
+            let (status_code, _, _) = send_built(app.clone(), request("10.0.0.1")).await;
+            assert_eq!(status_code, 200);
+        }
+        // The header can't be trusted there and doesn't give a bucket of its own
+        let (status_code, _, body) = send_built(app.clone(), request("10.0.0.1")).await;
+        assert_eq!(status_code, 429);
+        assert_eq!(body["error"], "2600");
+        let (status_code, _, _) = send_built(app.clone(), request("10.0.0.2")).await;
+        assert_eq!(status_code, 200);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_by_cf_connecting_ip() {
+        let app = get_rate_limited_app("/", RateLimitKey::Ip).await;
+
+        // Every request comes from the same Cloudflare edge server
+        let request = |client_ip: &str| Request::builder()
+            .uri("/")
+            .header("cf-connecting-ip", client_ip)
+            .extension(ConnectInfo("173.245.48.1:4000".parse::<SocketAddr>().unwrap()));
+        for _ in 0..2 {
+            let (status_code, _, _) = send_built(app.clone(), request("10.0.0.1")).await;
+            assert_eq!(status_code, 200);
+        }
+        let (status_code, _, _) = send_built(app.clone(), request("10.0.0.1")).await;
+        assert_eq!(status_code, 429);
+        let (status_code, _, _) = send_built(app.clone(), request("10.0.0.2")).await;
+        assert_eq!(status_code, 200);
+
+        let (status_code, _, body) = send_built(
+            app.clone(),
+            Request::builder()
+                .uri("/")
+                .extension(ConnectInfo("173.245.48.1:4000".parse::<SocketAddr>().unwrap()))
+        ).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(body["error"], "2602");
+    }
+}
@@ -7,7 +7,6 @@ pub(super) mod tests {
         }, Router
     };
     use pretty_assertions::assert_eq;
-    use serial_test::serial;
     use tower::util::ServiceExt;
     use tower_http::trace::{DefaultMakeSpan, TraceLayer};
     use axum::body::to_bytes;
@@ -17,19 +16,23 @@ pub(super) mod tests {
             Password,
             SaltMode
         },
+        database::{
+            SessionStore,
+            UserStore
+        },
         logs,
         routes::tests::{authenticate::tests::get_authenticate_endpoint_response_and_status_code, preparation::{
             get_axum_app,
             get_config,
-            get_db_client
+            TestContext
         }}
     };
 
     pub async fn get_refresh_token_from_authenticate_endpoint(
+        context: &TestContext,
         app: Router
     ) -> String {
-        let db_client = get_db_client().await;
-        db_client.redis_delete_password_hash_by_user_id(420).await.unwrap();
+        let db_client = context.db_client();
         let config = get_config();
         let test_password = Password::new(
             "test_password123*&@#ABC",
@@ -48,14 +51,14 @@ pub(super) mod tests {
             salt: salt_string.to_string(),
             ..User::default()
         };
-        let res = db_client.postgres_delete_user_by_id(420).await;
+        let res = db_client.delete_user(420).await;
         if res.is_err() {
             match res.err().unwrap() {
                 crate::database::DatabaseError::UserNotFound(_) => {},
                 _ => panic!("Error deleting user")
             }
         }
-        db_client.postgres_insert_user(&user).await.unwrap();
+        db_client.insert_user(&user).await.unwrap();
 
         let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
             test_password.get_password(),
@@ -71,18 +74,19 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_refresh_token_endpoint() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
             trace_layer.clone()
         );
         //logs::setup_logging().unwrap();
-        let db_client = get_db_client().await;
-        let _ = db_client.cached_delete_user_refresh_token(420).await;
+        let db_client = context.db_client();
+        let _ = db_client.delete_user_sessions(420).await;
         let refresh_token = get_refresh_token_from_authenticate_endpoint(
+            &context,
             app.clone()
         ).await;
 
@@ -109,13 +113,13 @@ pub(super) mod tests {
         println!("Response: {}", body_str);
         assert_eq!(status_code.as_u16(), 200);
 
-        db_client.postgres_delete_user_by_id(420).await.unwrap();
+        db_client.delete_user(420).await.unwrap();
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_refresh_token_endpoint_no_token() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -147,9 +151,9 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_refresh_token_endpoint_invalid_token() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -181,4 +185,69 @@ pub(super) mod tests {
         assert_eq!(response["error"], "Invalid token");
 
     }
-}
\ No newline at end of file
+
+    /// Returns the status code and the new refresh token if there is one
+    async fn send_refresh_request(
+        app: Router,
+        refresh_token: &str
+    ) -> (u16, Option<String>) {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri("/refresh_token")
+            .header("Cookie", format!("refresh_token=Bearer {}", refresh_token))
+            .body(Body::empty())
+            .unwrap();
+        let response = app
+            .oneshot(req)
+            .await
+            .unwrap();
+        let new_refresh_token = response.headers()
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|cookie| cookie::Cookie::parse(cookie.to_str().unwrap().to_string()).ok())
+            .find(|cookie| cookie.name() == "refresh_token")
+            .map(|cookie| cookie.value().trim_start_matches("Bearer ").to_string());
+        (response.status().as_u16(), new_refresh_token)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_rotation_and_reuse() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let first_token = get_refresh_token_from_authenticate_endpoint(
+            &context,
+            app.clone()
+        ).await;
+
+        let (status_code, second_token) = send_refresh_request(app.clone(), &first_token).await;
+        assert_eq!(status_code, 200);
+        let second_token = second_token.unwrap();
+        assert_ne!(second_token, first_token);
+        let (status_code, third_token) = send_refresh_request(app.clone(), &second_token).await;
+        assert_eq!(status_code, 200);
+        let third_token = third_token.unwrap();
+
+        // A login on another device is a separate family
+        let (response, status_code) = get_authenticate_endpoint_response_and_status_code(
+            "test_password123*&@#ABC",
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 200);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        let other_login_token = response["refresh_token"].as_str().unwrap().to_string();
+
+        // Reusing a rotated token revokes the whole family, the newest token included
+        let (status_code, new_token) = send_refresh_request(app.clone(), &first_token).await;
+        assert_eq!(status_code, 400);
+        assert_eq!(new_token, None);
+        let (status_code, _) = send_refresh_request(app.clone(), &third_token).await;
+        assert_eq!(status_code, 400);
+
+        let (status_code, _) = send_refresh_request(app.clone(), &other_login_token).await;
+        assert_eq!(status_code, 200);
+
+        db_client.delete_user(420).await.unwrap();
+    }
+}
@@ -8,7 +8,6 @@ pub(super) mod tests {
     };
     use pretty_assertions::assert_eq;
     use reqwest::blocking::multipart::Form;
-    use serial_test::serial;
     use tower::util::ServiceExt;
     use tower_http::trace::{DefaultMakeSpan, TraceLayer};
     use axum::body::to_bytes;
@@ -22,19 +21,19 @@ pub(super) mod tests {
         routes::tests::{authenticate::tests::get_authenticate_endpoint_response_and_status_code, preparation::{
             get_axum_app,
             get_config,
-            get_db_client
+            TestContext
         }}
     };
 
 
 
     #[tokio::test]
-    #[serial]
     async fn test_register_user_credential_based_success() {
+        let context = TestContext::new();
         //logs::setup_logging().unwrap();
         let mut config = get_config();
         config.cloudflare.allow_invalid_turnstile = true;
-        let app = get_axum_app(Some(config)).await;
+        let app = get_axum_app(&context, Some(config)).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -67,15 +66,20 @@ pub(super) mod tests {
 
         assert_eq!("User registered", body_str);
         assert_eq!(status_code.as_u16(), 200);
+
+        let sent_emails = context.sent_emails().await;
+        assert_eq!(sent_emails.len(), 1);
+        assert_eq!(sent_emails[0].to, vec!["niadg@sjda.sd".parse().unwrap()]);
+        assert!(sent_emails[0].text().contains("/verify_email?token="));
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_register_user_credential_based_bad_password() {
+        let context = TestContext::new();
         //logs::setup_logging().unwrap();
         let mut config = get_config();
         config.cloudflare.allow_invalid_turnstile = true;
-        let app = get_axum_app(Some(config)).await;
+        let app = get_axum_app(&context, Some(config)).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -112,12 +116,12 @@ pub(super) mod tests {
 
     
     #[tokio::test]
-    #[serial]
     async fn test_register_user_credential_based_bad_email() {
+        let context = TestContext::new();
         //logs::setup_logging().unwrap();
         let mut config = get_config();
         config.cloudflare.allow_invalid_turnstile = true;
-        let app = get_axum_app(Some(config)).await;
+        let app = get_axum_app(&context, Some(config)).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
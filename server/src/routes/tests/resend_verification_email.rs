@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::{
+        app_objects::PendingRegistration,
+        database::{
+            PendingRegistrationStore,
+            UserStore
+        },
+        email::SentEmail,
+        routes::tests::preparation::{
+            get_axum_app,
+            get_config,
+            TestContext
+        }
+    };
+
+    async fn send_form(
+        app: Router,
+        method: Method,
+        uri: &str,
+        body: String
+    ) -> (String, u16) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from(body))
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (String::from_utf8(body_bytes.to_vec()).unwrap(), status_code)
+    }
+
+    async fn resend(app: Router, email: &str) -> (String, u16) {
+        let body = format!("email={}&cf-turnstile-response=1222", urlencoding::encode(email));
+        send_form(app, Method::POST, "/verify_email/resend", body).await
+    }
+
+    /// The route enqueues the email from a task of its own
+    async fn wait_for_sent_emails(context: &TestContext, count: usize) -> Vec<SentEmail> {
+        for _ in 0..100 {
+            let sent_emails = context.sent_emails().await;
+            if sent_emails.len() >= count {
+                return sent_emails;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        context.sent_emails().await
+    }
+
+    fn get_verification_token(sent_email: &SentEmail) -> String {
+        let text = sent_email.text();
+        let (_, token) = text.split_once("?token=").unwrap();
+        token.split_whitespace().next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_resend_verification_email() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let app = get_axum_app(&context, Some(config)).await;
+
+        let pending_registration = PendingRegistration::new(
+            "resend@example.com".to_string(),
+            "resend_username".to_string(),
+            "test_password_hash".to_string(),
+            "test_password_salt".to_string(),
+            NaiveDate::from_ymd_opt(2024, 10, 27).unwrap()
+        );
+        context.db_client().create_pending_registration(&pending_registration, 60).await.unwrap();
+
+        let (response, status_code) = resend(app.clone(), "resend@example.com").await;
+        assert_eq!(status_code, 200);
+        assert_eq!(response, "Verification email requested");
+
+        let sent_emails = wait_for_sent_emails(&context, 1).await;
+        assert_eq!(sent_emails.len(), 1);
+        assert_eq!(sent_emails[0].to, vec!["resend@example.com".parse().unwrap()]);
+
+        // The new link creates the user from the stored registration
+        let token = get_verification_token(&sent_emails[0]);
+        let (_, status_code) = send_form(app, Method::GET, &format!("/verify_email?token={}", token), String::new()).await;
+        assert_eq!(status_code, 200);
+        let db_client = context.db_client();
+        let user_id = db_client.get_user_id_by_email("resend@example.com").await.unwrap().unwrap();
+        let user = db_client.get_user_by_id(user_id).await.unwrap().unwrap();
+        assert_eq!(user.username, "resend_username");
+        assert_eq!(user.password_hash, "test_password_hash");
+    }
+
+    #[tokio::test]
+    async fn test_resend_verification_email_cooldown() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let resend_cooldown_s = config.verification_email.resend_cooldown_s;
+        let app = get_axum_app(&context, Some(config)).await;
+
+        let body = format!(
+            r#"email=cooldown%40example.com&username=sadas&password=Test123!x112d&date_of_birth=2024-10-27&cf-turnstile-response=1222"#
+        );
+        let (_, status_code) = send_form(app.clone(), Method::POST, "/register_user", body).await;
+        assert_eq!(status_code, 200);
+
+        // Registering sent the first email
+        let (response, status_code) = resend(app.clone(), "cooldown@example.com").await;
+        assert_eq!(status_code, 429);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert_eq!(response["error"], "2902");
+        let retry_after = response["retry_after"].as_i64().unwrap();
+        assert!(retry_after > 0 && retry_after <= resend_cooldown_s);
+
+        // Other addresses have cooldowns of their own
+        let (_, status_code) = resend(app, "other@example.com").await;
+        assert_eq!(status_code, 200);
+        assert_eq!(wait_for_sent_emails(&context, 2).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resend_verification_email_does_not_reveal_registrations() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let app = get_axum_app(&context, Some(config)).await;
+
+        for email in ["nobody@example.com", "not-an-email"] {
+            let (response, status_code) = resend(app.clone(), email).await;
+            assert_eq!(status_code, 200);
+            assert_eq!(response, "Verification email requested");
+        }
+        assert_eq!(wait_for_sent_emails(&context, 1).await.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replaced_registration_link_is_invalid() {
+        let context = TestContext::new();
+        let mut config = get_config();
+        config.cloudflare.allow_invalid_turnstile = true;
+        let app = get_axum_app(&context, Some(config)).await;
+
+        for username in ["first", "second"] {
+            let body = format!(
+                "email=replaced%40example.com&username={}&password=Test123!x112d&date_of_birth=2024-10-27&cf-turnstile-response=1222",
+                username
+            );
+            let (_, status_code) = send_form(app.clone(), Method::POST, "/register_user", body).await;
+            assert_eq!(status_code, 200);
+        }
+        let sent_emails = wait_for_sent_emails(&context, 2).await;
+        assert_eq!(sent_emails.len(), 2);
+
+        let first_token = get_verification_token(&sent_emails[0]);
+        let (response, status_code) = send_form(app.clone(), Method::GET, &format!("/verify_email?token={}", first_token), String::new()).await;
+        assert_eq!(status_code, 400);
+        assert!(response.contains("2900"));
+
+        let second_token = get_verification_token(&sent_emails[1]);
+        let (_, status_code) = send_form(app, Method::GET, &format!("/verify_email?token={}", second_token), String::new()).await;
+        assert_eq!(status_code, 200);
+        let db_client = context.db_client();
+        let user_id = db_client.get_user_id_by_email("replaced@example.com").await.unwrap().unwrap();
+        assert_eq!(db_client.get_user_by_id(user_id).await.unwrap().unwrap().username, "second");
+    }
+}
@@ -0,0 +1,314 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        http::Method,
+        Router
+    };
+    use pretty_assertions::assert_eq;
+    use crate::{
+        app_objects::{
+            Channel,
+            Guild,
+            GuildMember,
+            Message,
+            Role
+        },
+        auth::Permissions,
+        database::GuildStore,
+        routes::tests::preparation::{
+            get_axum_app,
+            send_request,
+            TestContext
+        }
+    };
+
+    /// Guild owned by user 420 with 421 as a member, returns the guild and its `general` channel
+    async fn create_guild(context: &TestContext, app: Router) -> (Guild, Channel) {
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            "/guilds",
+            Some(420),
+            Some(serde_json::json!({ "name": "roles test guild" }))
+        ).await;
+        assert_eq!(status_code, 201);
+        let guild: Guild = serde_json::from_str(&response).unwrap();
+        let db_client = context.db_client();
+        db_client.insert_guild_member(&GuildMember::new(guild.id, 421, 0), 60).await.unwrap();
+        let channel = db_client.get_guild_channels(guild.id).await.unwrap().remove(0);
+        (guild, channel)
+    }
+
+    async fn create_role(
+        app: Router,
+        guild_id: i64,
+        user_id: i64,
+        permissions: Permissions,
+        position: i32
+    ) -> (String, u16) {
+        send_request(
+            app,
+            Method::POST,
+            &format!("/guilds/{}/roles", guild_id),
+            Some(user_id),
+            Some(serde_json::json!({ "name": "role", "permissions": permissions, "position": position }))
+        ).await
+    }
+
+    fn get_error_code(response: &str) -> String {
+        let response: serde_json::Value = serde_json::from_str(response).unwrap();
+        response["error"].as_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_roles_grant_permissions() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let (guild, _) = create_guild(&context, app.clone()).await;
+        let channels_uri = format!("/guilds/{}/channels", guild.id);
+        let create_channel = |user_id: i64| send_request(
+            app.clone(),
+            Method::POST,
+            &channels_uri,
+            Some(user_id),
+            Some(serde_json::json!({ "name": "new", "kind": "text" }))
+        );
+
+        let (response, status_code) = create_role(app.clone(), guild.id, 421, Permissions::empty(), 1).await;
+        assert_eq!(get_error_code(&response), "1301");
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = create_role(
+            app.clone(),
+            guild.id,
+            420,
+            Permissions::MANAGE_CHANNELS | Permissions::MANAGE_ROLES,
+            2
+        ).await;
+        assert_eq!(status_code, 201);
+        let manager: Role = serde_json::from_str(&response).unwrap();
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/guilds/{}/roles", guild.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 200);
+        let roles: Vec<Role> = serde_json::from_str(&response).unwrap();
+        assert_eq!(roles.len(), 2);
+        assert!(roles[0].is_everyone());
+        assert_eq!(roles[1], manager);
+
+        let member_role_uri = format!("/guilds/{}/members/421/roles/{}", guild.id, manager.id);
+        let (_, status_code) = send_request(app.clone(), Method::PUT, &member_role_uri, Some(420), None).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = create_channel(421).await;
+        assert_eq!(status_code, 201);
+
+        // Roles can only be created below the member's highest role and with permissions the member has
+        let (response, status_code) = create_role(app.clone(), guild.id, 421, Permissions::empty(), 2).await;
+        assert_eq!(get_error_code(&response), "2008");
+        assert_eq!(status_code, 403);
+        let (response, _) = create_role(app.clone(), guild.id, 421, Permissions::BAN_MEMBERS, 1).await;
+        assert_eq!(get_error_code(&response), "1301");
+        let (_, status_code) = create_role(app.clone(), guild.id, 421, Permissions::MANAGE_CHANNELS, 1).await;
+        assert_eq!(status_code, 201);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}/roles/{}", guild.id, manager.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(get_error_code(&response), "2008");
+        assert_eq!(status_code, 403);
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &format!("/guilds/{}/roles/{}", guild.id, guild.id),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(get_error_code(&response), "2010");
+        assert_eq!(status_code, 400);
+
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &member_role_uri, Some(420), None).await;
+        assert_eq!(status_code, 204);
+        let (_, status_code) = create_channel(421).await;
+        assert_eq!(status_code, 403);
+    }
+
+    #[tokio::test]
+    async fn test_edit_everyone_role() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let (guild, channel) = create_guild(&context, app.clone()).await;
+        let everyone_uri = format!("/guilds/{}/roles/{}", guild.id, guild.id);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &everyone_uri,
+            Some(420),
+            Some(serde_json::json!({ "permissions": Permissions::VIEW_CHANNEL }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let everyone: Role = serde_json::from_str(&response).unwrap();
+        assert_eq!(everyone.permissions, Permissions::VIEW_CHANNEL);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/channels/{}/messages", channel.id),
+            Some(421),
+            Some(serde_json::json!({ "content": "read only" }))
+        ).await;
+        assert_eq!(get_error_code(&response), "1301");
+        assert_eq!(status_code, 403);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &everyone_uri,
+            Some(420),
+            Some(serde_json::json!({ "position": 3 }))
+        ).await;
+        assert_eq!(get_error_code(&response), "2010");
+        assert_eq!(status_code, 400);
+    }
+
+    #[tokio::test]
+    async fn test_channel_overwrites() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let (guild, channel) = create_guild(&context, app.clone()).await;
+        let overwrite_uri = |target_id: i64| format!(
+            "/guilds/{}/channels/{}/permissions/{}",
+            guild.id,
+            channel.id,
+            target_id
+        );
+        let channels_uri = format!("/guilds/{}/channels", guild.id);
+        let get_channels = || send_request(
+            app.clone(),
+            Method::GET,
+            &channels_uri,
+            Some(421),
+            None
+        );
+
+        // Hide the channel from everyone
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::PUT,
+            &overwrite_uri(guild.id),
+            Some(420),
+            Some(serde_json::json!({ "kind": "role", "allow": 0, "deny": Permissions::VIEW_CHANNEL }))
+        ).await;
+        assert_eq!(status_code, 200);
+
+        let (response, _) = get_channels().await;
+        let channels: Vec<Channel> = serde_json::from_str(&response).unwrap();
+        assert!(channels.is_empty());
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::GET,
+            &format!("/channels/{}/messages", channel.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(get_error_code(&response), "1301");
+        assert_eq!(status_code, 403);
+
+        // The member overwrite is applied last
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::PUT,
+            &overwrite_uri(421),
+            Some(420),
+            Some(serde_json::json!({ "kind": "member", "allow": Permissions::VIEW_CHANNEL, "deny": 0 }))
+        ).await;
+        assert_eq!(status_code, 200);
+        let (response, _) = get_channels().await;
+        let channels: Vec<Channel> = serde_json::from_str(&response).unwrap();
+        assert_eq!(channels, vec![channel.clone()]);
+
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::PUT,
+            &overwrite_uri(422),
+            Some(420),
+            Some(serde_json::json!({ "kind": "member", "allow": 0, "deny": 0 }))
+        ).await;
+        assert_eq!(get_error_code(&response), "2011");
+        assert_eq!(status_code, 400);
+
+        // Members without MANAGE_ROLES can't change overwrites
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &overwrite_uri(guild.id),
+            Some(421),
+            None
+        ).await;
+        assert_eq!(status_code, 403);
+
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::DELETE,
+            &overwrite_uri(421),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 204);
+        let (response, _) = get_channels().await;
+        let channels: Vec<Channel> = serde_json::from_str(&response).unwrap();
+        assert!(channels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_manage_messages_deletes_messages_of_others() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let (guild, channel) = create_guild(&context, app.clone()).await;
+        let (response, status_code) = send_request(
+            app.clone(),
+            Method::POST,
+            &format!("/channels/{}/messages", channel.id),
+            Some(420),
+            Some(serde_json::json!({ "content": "moderate me" }))
+        ).await;
+        assert_eq!(status_code, 201);
+        let message = Message::from_json(&response).unwrap();
+        let message_uri = format!("/channels/{}/messages/{}", channel.id, message.id);
+
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &message_uri, Some(421), None).await;
+        assert_eq!(status_code, 403);
+
+        let (response, _) = create_role(app.clone(), guild.id, 420, Permissions::MANAGE_MESSAGES, 1).await;
+        let moderator: Role = serde_json::from_str(&response).unwrap();
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::PUT,
+            &format!("/guilds/{}/members/421/roles/{}", guild.id, moderator.id),
+            Some(420),
+            None
+        ).await;
+        assert_eq!(status_code, 204);
+
+        // Editing stays limited to the author
+        let (_, status_code) = send_request(
+            app.clone(),
+            Method::PATCH,
+            &message_uri,
+            Some(421),
+            Some(serde_json::json!({ "content": "edited" }))
+        ).await;
+        assert_eq!(status_code, 403);
+        let (_, status_code) = send_request(app.clone(), Method::DELETE, &message_uri, Some(421), None).await;
+        assert_eq!(status_code, 204);
+    }
+}
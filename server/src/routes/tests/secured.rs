@@ -9,7 +9,6 @@ pub(super) mod tests {
         }, Router
     };
     use pretty_assertions::assert_eq;
-    use serial_test::serial;
     use tower::util::ServiceExt;
     use tower_http::trace::{DefaultMakeSpan, TraceLayer};
     use axum::body::to_bytes;
@@ -19,18 +18,24 @@ pub(super) mod tests {
             Password,
             SaltMode
         },
+        database::{
+            SessionStore,
+            UserStore
+        },
         logs,
         routes::tests::{authenticate::tests::get_authenticate_endpoint_response_and_status_code, preparation::{
             get_axum_app,
             get_config,
-            get_db_client
+            TestContext
         }, refresh_token::tests::get_refresh_token_from_authenticate_endpoint}
     };
 
     async fn get_authorization_token_from_refresh_token_endpoint(
+        context: &TestContext,
         app: Router,
     ) -> String {
         let refresh_token = get_refresh_token_from_authenticate_endpoint(
+            context,
             app.clone()
         ).await;
         println!("Refresh token: {}", refresh_token);
@@ -57,18 +62,19 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_secured_endpoint() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
             trace_layer.clone()
         );
         //logs::setup_logging().unwrap();
-        let db_client = get_db_client().await;
-        let _ = db_client.cached_delete_user_refresh_token(420).await;
+        let db_client = context.db_client();
+        let _ = db_client.delete_user_sessions(420).await;
         let access_token = get_authorization_token_from_refresh_token_endpoint(
+            &context,
             app.clone()
         ).await;
 
@@ -96,13 +102,13 @@ pub(super) mod tests {
         println!("Response: {}", body_str);
         assert_eq!(status_code.as_u16(), 200);
 
-        db_client.postgres_delete_user_by_id(420).await.unwrap();
+        db_client.delete_user(420).await.unwrap();
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_secured_endpoint_no_cookie() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
@@ -133,9 +139,9 @@ pub(super) mod tests {
     }
 
     #[tokio::test]
-    #[serial]
     async fn test_secured_endpoint_invalid_cookie() {
-        let app = get_axum_app(None).await;
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
         let trace_layer = TraceLayer::new_for_http()
             .make_span_with(DefaultMakeSpan::default().include_headers(true));
         let app = app.layer(
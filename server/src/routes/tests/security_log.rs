@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::{
+        body::Body,
+        http::{
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::database::{
+        AuditEventStore,
+        UserStore
+    };
+    use crate::routes::tests::{
+        authenticate::tests::get_authenticate_endpoint_response_and_status_code,
+        preparation::{
+            get_access_token_cookie,
+            get_axum_app,
+            TestContext
+        },
+        refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+    };
+
+    async fn send(
+        app: Router,
+        method: Method,
+        uri: &str,
+        cookie: String
+    ) -> (serde_json::Value, u16) {
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header("cookie", cookie)
+                    .header("user-agent", "security log test")
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body = serde_json::from_slice(&body_bytes).unwrap_or(serde_json::Value::Null);
+        (body, status_code)
+    }
+
+    /// Events are written in the background, waits until `count` events newer than `after` show up
+    async fn wait_for_events(
+        app: Router,
+        after: i64,
+        count: usize
+    ) -> Vec<serde_json::Value> {
+        for _ in 0..50 {
+            let (response, status_code) = send(
+                app.clone(),
+                Method::GET,
+                "/me/security_log?limit=10",
+                get_access_token_cookie(420)
+            ).await;
+            assert_eq!(status_code, 200);
+            let events: Vec<serde_json::Value> = response
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|event| event["id"].as_i64().unwrap() > after)
+                .cloned()
+                .collect();
+            if events.len() >= count {
+                return events;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("Audit events weren't written");
+    }
+
+    #[tokio::test]
+    async fn test_security_log_shows_logins_and_refreshes() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        // The table is append-only, older runs left their events behind
+        let after = db_client.get_user_audit_events(420, None, 1).await.unwrap()
+            .first()
+            .map_or(0, |event| event.id);
+
+        let refresh_token = get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let (_, status_code) = get_authenticate_endpoint_response_and_status_code(
+            "wrong_password",
+            "test_email",
+            app.clone()
+        ).await;
+        assert_eq!(status_code, 401);
+        let (_, status_code) = send(
+            app.clone(),
+            Method::POST,
+            "/refresh_token",
+            format!("refresh_token=Bearer {}", refresh_token)
+        ).await;
+        assert_eq!(status_code, 200);
+
+        let events = wait_for_events(app.clone(), after, 3).await;
+        let event_types: Vec<&str> = events.iter().map(|event| event["event_type"].as_str().unwrap()).collect();
+        assert_eq!(event_types, vec!["token_refresh", "login_failure", "login_success"]);
+        assert_eq!(events[0]["user_agent"], "security log test");
+        assert_eq!(events[1]["metadata"]["reason"], "wrong_password");
+        assert_eq!(events[2]["metadata"]["method"], "password");
+        assert_eq!(events[0]["metadata"]["session_id"], events[2]["metadata"]["session_id"]);
+
+        // Events of other users stay hidden
+        let (response, _) = send(app.clone(), Method::GET, "/me/security_log", get_access_token_cookie(421)).await;
+        assert!(response.as_array().unwrap().iter().all(|event| event["target_user_id"] == 421));
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_security_log_limit() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        for limit in [0, 101] {
+            let (response, status_code) = send(
+                app.clone(),
+                Method::GET,
+                &format!("/me/security_log?limit={}", limit),
+                get_access_token_cookie(420)
+            ).await;
+            assert_eq!(status_code, 400);
+            assert_eq!(response["error"], "2800");
+        }
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{
+            HeaderMap,
+            Method,
+            Request
+        },
+        Router
+    };
+    use axum::body::to_bytes;
+    use pretty_assertions::assert_eq;
+    use tower::util::ServiceExt;
+    use crate::app_objects::User;
+    use crate::credentials::{
+        Password,
+        SaltMode
+    };
+    use crate::database::{
+        SessionStore,
+        UserStore
+    };
+    use crate::routes::tests::{
+        preparation::{
+            get_axum_app,
+            get_config,
+            TestContext
+        },
+        refresh_token::tests::get_refresh_token_from_authenticate_endpoint
+    };
+
+    async fn send_request(
+        app: Router,
+        method: Method,
+        uri: &str,
+        cookie: &str,
+        body: Option<serde_json::Value>,
+        user_agent: Option<&str>
+    ) -> (String, u16, HeaderMap) {
+        let mut req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("Cookie", cookie);
+        if let Some(user_agent) = user_agent {
+            req = req.header("user-agent", user_agent);
+        }
+        let body = match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        };
+        let response = app
+            .oneshot(req.body(body).unwrap())
+            .await
+            .unwrap();
+        let status_code = response.status().as_u16();
+        let headers = response.headers().clone();
+
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body_str = String::from_utf8(body_bytes.to_vec())
+            .expect("Failed to convert body to string");
+
+        (body_str, status_code, headers)
+    }
+
+    fn get_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get_all("set-cookie")
+            .iter()
+            .filter_map(|cookie| cookie::Cookie::parse(cookie.to_str().unwrap().to_string()).ok())
+            .find(|cookie| cookie.name() == name)
+            .map(|cookie| cookie.value().to_string())
+    }
+
+    /// Logs user 420 in on another device, returns the refresh token
+    async fn login(app: Router, device_label: Option<&str>, user_agent: &str) -> String {
+        let (response, status_code, _) = send_request(
+            app,
+            Method::POST,
+            "/authenticate",
+            "",
+            Some(serde_json::json!({
+                "email": "test_email",
+                "password": "test_password123*&@#ABC",
+                "device_label": device_label
+            })),
+            Some(user_agent)
+        ).await;
+        assert_eq!(status_code, 200);
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        response["refresh_token"].as_str().unwrap().to_string()
+    }
+
+    /// Returns the status code, the new refresh token and the access cookie if the refresh worked
+    async fn refresh(app: Router, refresh_token: &str) -> (u16, Option<(String, String)>) {
+        let cookie = format!("refresh_token=Bearer {}", refresh_token);
+        let (_, status_code, headers) = send_request(app, Method::POST, "/refresh_token", &cookie, None, None).await;
+        let tokens = get_cookie(&headers, "refresh_token").zip(get_cookie(&headers, "authorization_token"))
+            .map(|(refresh_token, access_token)| (
+                refresh_token.trim_start_matches("Bearer ").to_string(),
+                format!("authorization_token={}", access_token)
+            ));
+        (status_code, tokens)
+    }
+
+    async fn get_sessions(app: Router, access_cookie: &str) -> Vec<serde_json::Value> {
+        let (response, status_code, _) = send_request(app, Method::GET, "/sessions", access_cookie, None, None).await;
+        assert_eq!(status_code, 200);
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_list_and_revoke_sessions() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let _ = db_client.delete_user_sessions(420).await;
+
+        let first_token = get_refresh_token_from_authenticate_endpoint(&context, app.clone()).await;
+        let phone_token = login(app.clone(), Some("  phone  "), "test agent").await;
+        let desktop_token = login(app.clone(), None, "desktop browser").await;
+        let (status_code, tokens) = refresh(app.clone(), &first_token).await;
+        assert_eq!(status_code, 200);
+        let (first_token, access_cookie) = tokens.unwrap();
+
+        let sessions = get_sessions(app.clone(), &access_cookie).await;
+        assert_eq!(sessions.len(), 3);
+        let find_session = |label: &str| sessions.iter().find(|session| session["device_label"] == label).unwrap().clone();
+        let current = find_session("Unknown device");
+        assert_eq!(current["current"], true);
+        let phone = find_session("phone");
+        assert_eq!(phone["current"], false);
+        assert_eq!(phone["user_agent"], "test agent");
+        let desktop = find_session("desktop browser");
+        assert_eq!(desktop["user_agent"], "desktop browser");
+
+        // Deleting a session logs the device out
+        let uri = format!("/sessions/{}", phone["id"].as_str().unwrap());
+        let (_, status_code, _) = send_request(app.clone(), Method::DELETE, &uri, &access_cookie, None, None).await;
+        assert_eq!(status_code, 204);
+        let (status_code, _) = refresh(app.clone(), &phone_token).await;
+        assert_eq!(status_code, 400);
+        let (_, status_code, _) = send_request(app.clone(), Method::DELETE, &uri, &access_cookie, None, None).await;
+        assert_eq!(status_code, 404);
+
+        let (_, status_code, headers) = send_request(app.clone(), Method::POST, "/logout", &access_cookie, None, None).await;
+        assert_eq!(status_code, 204);
+        assert_eq!(get_cookie(&headers, "refresh_token"), Some("".to_string()));
+        assert_eq!(get_cookie(&headers, "authorization_token"), Some("".to_string()));
+        let (status_code, _) = refresh(app.clone(), &first_token).await;
+        assert_eq!(status_code, 400);
+
+        // The access token stays valid until it expires
+        let sessions = get_sessions(app.clone(), &access_cookie).await;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0]["id"], desktop["id"]);
+        assert_eq!(sessions[0]["current"], false);
+
+        let other_token = login(app.clone(), Some("laptop"), "test agent").await;
+        let (_, status_code, _) = send_request(app.clone(), Method::POST, "/logout_all", &access_cookie, None, None).await;
+        assert_eq!(status_code, 204);
+        for token in [desktop_token, other_token] {
+            let (status_code, _) = refresh(app.clone(), &token).await;
+            assert_eq!(status_code, 400);
+        }
+        assert!(get_sessions(app.clone(), &access_cookie).await.is_empty());
+
+        db_client.delete_user(420).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_new_device_login_is_emailed() {
+        let context = TestContext::new();
+        let app = get_axum_app(&context, None).await;
+        let db_client = context.db_client();
+        let password = "test_password123*&@#ABC";
+        let salt_string = "ExampleSaltStringExampleSaltString";
+        let hash = Password::new(password, &get_config().password_requirements)
+            .hash_and_salt_password(&SaltMode::FromString(salt_string))
+            .await.unwrap().password_hash;
+        let user = User {
+            id: 425,
+            email: "new_login@example.com".to_string(),
+            password_hash: hash,
+            salt: salt_string.to_string(),
+            ..User::default()
+        };
+        db_client.insert_user(&user).await.unwrap();
+
+        let login_with = |user_agent: &'static str| {
+            let app = app.clone();
+            async move {
+                let (_, status_code, _) = send_request(
+                    app,
+                    Method::POST,
+                    "/authenticate",
+                    "",
+                    Some(serde_json::json!({ "email": "new_login@example.com", "password": password })),
+                    Some(user_agent)
+                ).await;
+                assert_eq!(status_code, 200);
+            }
+        };
+
+        // Nothing to compare the first session with, the second one comes from a known device
+        login_with("laptop browser").await;
+        login_with("laptop browser").await;
+        assert!(context.sent_emails().await.is_empty());
+
+        login_with("phone browser").await;
+        let sent_emails = context.sent_emails().await;
+        assert_eq!(sent_emails.len(), 1);
+        assert_eq!(sent_emails[0].to, vec!["new_login@example.com".parse().unwrap()]);
+        assert!(sent_emails[0].text().contains("phone browser"));
+
+        db_client.delete_user(425).await.unwrap();
+    }
+}
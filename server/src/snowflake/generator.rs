@@ -0,0 +1,101 @@
+use std::sync::{
+    Arc,
+    Mutex
+};
+
+use crate::configuration::SnowflakeConfig;
+
+use super::SnowflakeError;
+
+// 64 bit id layout (most significant bit is always 0 so ids fit in BIGINT):
+// | 41 bits ms since epoch | 10 bits worker id | 12 bits sequence |
+const WORKER_ID_BITS: u8 = 10;
+const SEQUENCE_BITS: u8 = 12;
+
+const MAX_WORKER_ID: u16 = (1 << WORKER_ID_BITS) - 1;
+const MAX_SEQUENCE: u16 = (1 << SEQUENCE_BITS) - 1;
+
+const WORKER_ID_SHIFT: u8 = SEQUENCE_BITS;
+const TIMESTAMP_SHIFT: u8 = SEQUENCE_BITS + WORKER_ID_BITS;
+
+#[derive(Debug)]
+struct SnowflakeSequence {
+    last_timestamp_ms: i64,
+    sequence: u16,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnowflakeGenerator {
+    epoch_ms: i64,
+    worker_id: u16,
+    sequence: Arc<Mutex<SnowflakeSequence>>,
+}
+
+impl SnowflakeGenerator {
+    pub fn new(
+        config: &SnowflakeConfig
+    ) -> Result<Self, SnowflakeError> {
+        if config.worker_id > MAX_WORKER_ID {
+            return Err(SnowflakeError::InvalidWorkerId(config.worker_id));
+        }
+        if config.epoch_ms > chrono::Utc::now().timestamp_millis() {
+            return Err(SnowflakeError::EpochInTheFuture(config.epoch_ms));
+        }
+        Ok(Self {
+            epoch_ms: config.epoch_ms,
+            worker_id: config.worker_id,
+            sequence: Arc::new(Mutex::new(SnowflakeSequence {
+                last_timestamp_ms: -1,
+                sequence: 0,
+            })),
+        })
+    }
+
+    /// Generates a new unique id, blocks for at most 1ms if the sequence for
+    /// the current millisecond is exhausted.
+    pub fn generate(&self) -> Result<i64, SnowflakeError> {
+        let mut state = self.sequence.lock().unwrap();
+        let mut timestamp_ms = self.current_timestamp_ms();
+
+        if timestamp_ms < state.last_timestamp_ms {
+            return Err(SnowflakeError::ClockMovedBackwards(
+                state.last_timestamp_ms - timestamp_ms
+            ));
+        }
+
+        if timestamp_ms == state.last_timestamp_ms {
+            state.sequence = (state.sequence + 1) & MAX_SEQUENCE;
+            if state.sequence == 0 {
+                // Sequence exhausted, wait for the next millisecond
+                while timestamp_ms <= state.last_timestamp_ms {
+                    std::hint::spin_loop();
+                    timestamp_ms = self.current_timestamp_ms();
+                }
+            }
+        } else {
+            state.sequence = 0;
+        }
+        state.last_timestamp_ms = timestamp_ms;
+
+        Ok(self.compose(timestamp_ms, state.sequence))
+    }
+
+    /// Unix timestamp in milliseconds at which the id was generated
+    pub fn timestamp_ms_from_id(&self, id: i64) -> i64 {
+        (id >> TIMESTAMP_SHIFT) + self.epoch_ms
+    }
+
+    pub fn worker_id_from_id(id: i64) -> u16 {
+        ((id >> WORKER_ID_SHIFT) as u16) & MAX_WORKER_ID
+    }
+
+    fn compose(&self, timestamp_ms: i64, sequence: u16) -> i64 {
+        (timestamp_ms << TIMESTAMP_SHIFT)
+            | ((self.worker_id as i64) << WORKER_ID_SHIFT)
+            | sequence as i64
+    }
+
+    fn current_timestamp_ms(&self) -> i64 {
+        chrono::Utc::now().timestamp_millis() - self.epoch_ms
+    }
+}
@@ -0,0 +1,39 @@
+mod generator;
+
+mod tests;
+
+pub use generator::SnowflakeGenerator;
+
+use thiserror::Error;
+use axum::response::IntoResponse;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SnowflakeError {
+    #[error("Worker id {0} does not fit in 10 bits")]
+    InvalidWorkerId(u16),
+    #[error("Snowflake epoch {0} is in the future")]
+    EpochInTheFuture(i64),
+    #[error("Clock moved backwards, refusing to generate id for {0}ms")]
+    ClockMovedBackwards(i64),
+}
+
+impl IntoResponse for SnowflakeError {
+    fn into_response(self) -> axum::response::Response {
+        let error_message = self.into_internal_error_code();
+
+        axum::http::Response::builder()
+            .status(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+            .body(error_message.into())
+            .unwrap()
+    }
+}
+
+impl SnowflakeError {
+    pub fn into_internal_error_code(&self) -> &'static str {
+        match self {
+            SnowflakeError::InvalidWorkerId(_) => "1700",
+            SnowflakeError::EpochInTheFuture(_) => "1701",
+            SnowflakeError::ClockMovedBackwards(_) => "1702",
+        }
+    }
+}
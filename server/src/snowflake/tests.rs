@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        configuration::SnowflakeConfig,
+        snowflake::{
+            SnowflakeError,
+            SnowflakeGenerator
+        }
+    };
+
+    fn get_generator(worker_id: u16) -> SnowflakeGenerator {
+        SnowflakeGenerator::new(&SnowflakeConfig {
+            epoch_ms: 1704067200000,
+            worker_id,
+        }).unwrap()
+    }
+
+    #[test]
+    fn test_generated_ids_are_unique_and_increasing() {
+        let generator = get_generator(1);
+        let mut ids = HashSet::new();
+        let mut last_id = 0;
+        for _ in 0..50_000 {
+            let id = generator.generate().unwrap();
+            assert!(id > last_id);
+            assert!(ids.insert(id));
+            last_id = id;
+        }
+    }
+
+    #[test]
+    fn test_id_contains_worker_id_and_timestamp() {
+        let generator = get_generator(42);
+        let before = chrono::Utc::now().timestamp_millis();
+        let id = generator.generate().unwrap();
+        let after = chrono::Utc::now().timestamp_millis();
+
+        assert_eq!(SnowflakeGenerator::worker_id_from_id(id), 42);
+        let timestamp = generator.timestamp_ms_from_id(id);
+        assert!(timestamp >= before && timestamp <= after);
+    }
+
+    #[test]
+    fn test_ids_are_unique_across_threads() {
+        let generator = get_generator(3);
+        let handles = (0..4).map(|_| {
+            let generator = generator.clone();
+            std::thread::spawn(move || {
+                (0..10_000).map(|_| generator.generate().unwrap()).collect::<Vec<i64>>()
+            })
+        }).collect::<Vec<_>>();
+
+        let mut ids = HashSet::new();
+        for handle in handles {
+            for id in handle.join().unwrap() {
+                assert!(ids.insert(id));
+            }
+        }
+        assert_eq!(ids.len(), 40_000);
+    }
+
+    #[test]
+    fn test_invalid_worker_id() {
+        let res = SnowflakeGenerator::new(&SnowflakeConfig {
+            epoch_ms: 1704067200000,
+            worker_id: 1024,
+        });
+        assert_eq!(res.unwrap_err(), SnowflakeError::InvalidWorkerId(1024));
+    }
+
+    #[test]
+    fn test_epoch_in_the_future() {
+        let epoch_ms = chrono::Utc::now().timestamp_millis() + 100_000;
+        let res = SnowflakeGenerator::new(&SnowflakeConfig {
+            epoch_ms,
+            worker_id: 0,
+        });
+        assert_eq!(res.unwrap_err(), SnowflakeError::EpochInTheFuture(epoch_ms));
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{auth::JWTKeys, database::DatabaseClientWithCaching};
+use crate::{audit::AuditLog, auth::JWTKeys, database::DatabaseClient, snowflake::SnowflakeGenerator};
 
 
 #[derive(Clone, Debug)]
 pub struct AddUserFromJWTTokenState {
-    pub db_client: DatabaseClientWithCaching,
+    pub db_client: DatabaseClient,
     pub jwt_keys: JWTKeys,
-}
\ No newline at end of file
+    pub id_generator: SnowflakeGenerator,
+    pub audit_log: AuditLog,
+}
@@ -0,0 +1,20 @@
+use crate::{
+    audit::AuditLog,
+    auth::JWTKeys,
+    configuration::AdminConfig,
+    credentials::PasswordRequirements,
+    database::DatabaseClient,
+    email::EmailHandler,
+    event_bus::EventBus
+};
+
+#[derive(Clone)]
+pub struct AdminState {
+    pub db_client: DatabaseClient,
+    pub email_handler: EmailHandler,
+    pub jwt_keys: JWTKeys,
+    pub event_bus: EventBus,
+    pub password_requirements: PasswordRequirements,
+    pub admin_config: AdminConfig,
+    pub audit_log: AuditLog,
+}
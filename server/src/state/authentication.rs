@@ -1,9 +1,18 @@
-use crate::{auth::JWTKeys, configuration::JWTConfig, credentials::PasswordRequirements, database::DatabaseClientWithCaching};
+use crate::{audit::AuditLog, auth::JWTKeys, cloudflare::TurnstileState, configuration::{JWTConfig, LoginProtectionConfig, MfaConfig, PasskeysConfig, SessionsConfig}, credentials::PasswordRequirements, database::DatabaseClient, email::EmailHandler};
 
 #[derive(Clone, Debug)]
 pub struct AuthenticationState {
     pub jwt_keys: JWTKeys,
     pub jwt_config: JWTConfig,
-    pub db_client: DatabaseClientWithCaching,
+    pub db_client: DatabaseClient,
     pub password_requirements: PasswordRequirements,
-}
\ No newline at end of file
+    pub sessions_config: SessionsConfig,
+    pub mfa_config: MfaConfig,
+    pub passkeys_config: PasskeysConfig,
+    pub login_protection_config: LoginProtectionConfig,
+    pub turnstile_state: TurnstileState,
+    pub email_handler: EmailHandler,
+    pub audit_log: AuditLog,
+    // Whether the client IP can be counted for login failures, see `routes::client_ip_is_trusted`
+    pub client_ip_is_trusted: bool,
+}
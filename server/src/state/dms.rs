@@ -0,0 +1,14 @@
+use crate::{
+    configuration::DmsConfig,
+    database::DatabaseClient,
+    event_bus::EventBus,
+    snowflake::SnowflakeGenerator
+};
+
+#[derive(Clone, Debug)]
+pub struct DmsState {
+    pub db_client: DatabaseClient,
+    pub id_generator: SnowflakeGenerator,
+    pub dms_config: DmsConfig,
+    pub event_bus: EventBus,
+}
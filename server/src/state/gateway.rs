@@ -0,0 +1,17 @@
+use crate::{
+    configuration::{
+        GatewayConfig,
+        GuildsConfig
+    },
+    database::DatabaseClient,
+    gateway::GatewayHub
+};
+
+#[derive(Clone, Debug)]
+pub struct GatewayState {
+    pub hub: GatewayHub,
+    pub gateway_config: GatewayConfig,
+    // Subscriptions are limited to channels the user can read
+    pub db_client: DatabaseClient,
+    pub guilds_config: GuildsConfig,
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use crate::{
+    configuration::GuildsConfig,
+    database::DatabaseClient,
+    event_bus::EventBus,
+    snowflake::SnowflakeGenerator
+};
+
+#[derive(Clone, Debug)]
+pub struct GuildsState {
+    pub db_client: DatabaseClient,
+    pub id_generator: SnowflakeGenerator,
+    pub guilds_config: GuildsConfig,
+    pub event_bus: EventBus,
+}
@@ -0,0 +1,14 @@
+use crate::{
+    configuration::MessagesConfig,
+    database::DatabaseClient,
+    event_bus::EventBus,
+    snowflake::SnowflakeGenerator
+};
+
+#[derive(Clone, Debug)]
+pub struct MessagesState {
+    pub db_client: DatabaseClient,
+    pub id_generator: SnowflakeGenerator,
+    pub messages_config: MessagesConfig,
+    pub event_bus: EventBus,
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use crate::{
+    audit::AuditLog,
+    configuration::MfaConfig,
+    database::DatabaseClient
+};
+
+#[derive(Clone, Debug)]
+pub struct MfaState {
+    pub db_client: DatabaseClient,
+    pub mfa_config: MfaConfig,
+    pub audit_log: AuditLog,
+}
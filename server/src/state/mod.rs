@@ -2,6 +2,16 @@ mod authentication;
 mod refresh;
 mod register_user_credential_based;
 mod add_user_from_jwt;
+mod messages;
+mod gateway;
+mod guilds;
+mod dms;
+mod sessions;
+mod mfa;
+mod passkeys;
+mod password_reset;
+mod account;
+mod admin;
 
 use std::sync::Arc;
 
@@ -9,10 +19,25 @@ pub use authentication::AuthenticationState;
 pub use refresh::RefreshState;
 pub use register_user_credential_based::RegisterUserCredentialBasedState;
 pub use add_user_from_jwt::AddUserFromJWTTokenState;
+pub use messages::MessagesState;
+pub use gateway::GatewayState;
+pub use guilds::GuildsState;
+pub use dms::DmsState;
+pub use sessions::SessionsState;
+pub use mfa::MfaState;
+pub use passkeys::PasskeysState;
+pub use password_reset::PasswordResetState;
+pub use account::AccountState;
+pub use admin::AdminState;
 
 
 use axum::extract::FromRef;
 
+use crate::{
+    auth::JWTKeys,
+    database::DatabaseClient
+};
+
 
 #[derive(Clone)]
 pub struct ApiState {
@@ -20,6 +45,18 @@ pub struct ApiState {
     pub refresh: Arc<RefreshState>,
     pub register_user_credential_based: Arc<RegisterUserCredentialBasedState>,
     pub add_user_from_jwt: Arc<AddUserFromJWTTokenState>,
+    pub messages: Arc<MessagesState>,
+    pub gateway: Arc<GatewayState>,
+    pub guilds: Arc<GuildsState>,
+    pub dms: Arc<DmsState>,
+    pub sessions: Arc<SessionsState>,
+    pub mfa: Arc<MfaState>,
+    pub passkeys: Arc<PasskeysState>,
+    pub password_reset: Arc<PasswordResetState>,
+    pub account: Arc<AccountState>,
+    pub admin: Arc<AdminState>,
+    pub jwt_keys: JWTKeys,
+    pub db_client: DatabaseClient,
 }
 
 impl FromRef<ApiState> for Arc<AuthenticationState> {
@@ -44,4 +81,76 @@ impl FromRef<ApiState> for Arc<AddUserFromJWTTokenState> {
     fn from_ref(api_state: &ApiState) -> Arc<AddUserFromJWTTokenState> {
         api_state.add_user_from_jwt.clone()
     }
+}
+
+impl FromRef<ApiState> for Arc<MessagesState> {
+    fn from_ref(api_state: &ApiState) -> Arc<MessagesState> {
+        api_state.messages.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<GatewayState> {
+    fn from_ref(api_state: &ApiState) -> Arc<GatewayState> {
+        api_state.gateway.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<GuildsState> {
+    fn from_ref(api_state: &ApiState) -> Arc<GuildsState> {
+        api_state.guilds.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<DmsState> {
+    fn from_ref(api_state: &ApiState) -> Arc<DmsState> {
+        api_state.dms.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<SessionsState> {
+    fn from_ref(api_state: &ApiState) -> Arc<SessionsState> {
+        api_state.sessions.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<MfaState> {
+    fn from_ref(api_state: &ApiState) -> Arc<MfaState> {
+        api_state.mfa.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<PasskeysState> {
+    fn from_ref(api_state: &ApiState) -> Arc<PasskeysState> {
+        api_state.passkeys.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<PasswordResetState> {
+    fn from_ref(api_state: &ApiState) -> Arc<PasswordResetState> {
+        api_state.password_reset.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<AccountState> {
+    fn from_ref(api_state: &ApiState) -> Arc<AccountState> {
+        api_state.account.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<AdminState> {
+    fn from_ref(api_state: &ApiState) -> Arc<AdminState> {
+        api_state.admin.clone()
+    }
+}
+
+impl FromRef<ApiState> for JWTKeys {
+    fn from_ref(api_state: &ApiState) -> JWTKeys {
+        api_state.jwt_keys.clone()
+    }
+}
+
+impl FromRef<ApiState> for DatabaseClient {
+    fn from_ref(api_state: &ApiState) -> DatabaseClient {
+        api_state.db_client.clone()
+    }
 }
\ No newline at end of file
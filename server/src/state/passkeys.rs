@@ -0,0 +1,12 @@
+use crate::{
+    audit::AuditLog,
+    configuration::PasskeysConfig,
+    database::DatabaseClient
+};
+
+#[derive(Clone, Debug)]
+pub struct PasskeysState {
+    pub db_client: DatabaseClient,
+    pub passkeys_config: PasskeysConfig,
+    pub audit_log: AuditLog,
+}
@@ -0,0 +1,20 @@
+use crate::{
+    audit::AuditLog,
+    auth::JWTKeys,
+    cloudflare::TurnstileState,
+    credentials::PasswordRequirements,
+    database::DatabaseClient,
+    email::EmailHandler,
+    event_bus::EventBus
+};
+
+#[derive(Clone)]
+pub struct PasswordResetState {
+    pub db_client: DatabaseClient,
+    pub email_handler: EmailHandler,
+    pub turnstile_state: TurnstileState,
+    pub jwt_keys: JWTKeys,
+    pub event_bus: EventBus,
+    pub password_requirements: PasswordRequirements,
+    pub audit_log: AuditLog,
+}
@@ -1,12 +1,14 @@
 use crate::{
+    audit::AuditLog,
     auth::JWTKeys,
     configuration::JWTConfig,
-    database::DatabaseClientWithCaching
+    database::DatabaseClient
 };
 
 #[derive(Clone)]
 pub struct RefreshState {
     pub jwt_keys: JWTKeys,
     pub jwt_config: JWTConfig,
-    pub db_client: DatabaseClientWithCaching,
-}
\ No newline at end of file
+    pub db_client: DatabaseClient,
+    pub audit_log: AuditLog,
+}
@@ -1,9 +1,10 @@
-use crate::{auth::JWTKeys, cloudflare::TurnstileState, credentials::PasswordRequirements, email::EmailHandler};
+use crate::{auth::JWTKeys, cloudflare::TurnstileState, credentials::PasswordRequirements, database::DatabaseClient, email::EmailHandler};
 
 
 
 #[derive(Clone)]
 pub struct RegisterUserCredentialBasedState {
+    pub db_client: DatabaseClient,
     pub email_handler: EmailHandler,
     pub turnstile_state: TurnstileState,
     pub jwt_keys: JWTKeys,
@@ -0,0 +1,12 @@
+use crate::{
+    configuration::SessionsConfig,
+    database::DatabaseClient,
+    event_bus::EventBus
+};
+
+#[derive(Clone, Debug)]
+pub struct SessionsState {
+    pub db_client: DatabaseClient,
+    pub event_bus: EventBus,
+    pub sessions_config: SessionsConfig,
+}